@@ -91,6 +91,12 @@ pub enum Feature {
         target_body: Option<Uuid>,
         /// Draft angle in radians (0 = no draft)
         draft_angle: f32,
+        /// Create one body per profile instead of merging them
+        ///
+        /// When off, every closed profile is unioned into one body; older
+        /// builds used only the first profile.
+        #[serde(default)]
+        keep_bodies_separate: bool,
         /// Whether the feature is suppressed
         #[serde(default)]
         suppressed: bool,
@@ -114,6 +120,12 @@ pub enum Feature {
         boolean_op: BooleanOp,
         /// Target body ID (for boolean operations)
         target_body: Option<Uuid>,
+        /// Create one body per profile instead of merging them
+        ///
+        /// When off, every closed profile is unioned into one body; older
+        /// builds used only the first profile.
+        #[serde(default)]
+        keep_bodies_separate: bool,
         /// Whether the feature is suppressed
         #[serde(default)]
         suppressed: bool,
@@ -227,6 +239,36 @@ impl Feature {
         }
     }
 
+    /// Whether each profile becomes its own body (sketch features only)
+    pub fn keeps_bodies_separate(&self) -> bool {
+        match self {
+            Feature::Extrude {
+                keep_bodies_separate,
+                ..
+            }
+            | Feature::Revolve {
+                keep_bodies_separate,
+                ..
+            } => *keep_bodies_separate,
+            _ => false,
+        }
+    }
+
+    /// Set whether each profile becomes its own body (ignored by non-sketch features)
+    pub fn set_keep_bodies_separate(&mut self, value: bool) {
+        if let Feature::Extrude {
+            keep_bodies_separate,
+            ..
+        }
+        | Feature::Revolve {
+            keep_bodies_separate,
+            ..
+        } = self
+        {
+            *keep_bodies_separate = value;
+        }
+    }
+
     /// Create a new extrude feature
     pub fn extrude(
         name: impl Into<String>,
@@ -243,6 +285,7 @@ impl Feature {
             boolean_op: BooleanOp::New,
             target_body: None,
             draft_angle: 0.0,
+            keep_bodies_separate: false,
             suppressed: false,
         }
     }
@@ -258,17 +301,24 @@ impl Feature {
             angle,
             boolean_op: BooleanOp::New,
             target_body: None,
+            keep_bodies_separate: false,
             suppressed: false,
         }
     }
 
-    /// Execute this feature to produce a solid
+    /// Execute this feature to produce its solids
+    ///
+    /// Sketch features return one solid per closed profile when
+    /// `keep_bodies_separate` is set, otherwise the profiles are merged into
+    /// one. A boolean with a target body always yields the single modified body.
+    /// Sketch curve endpoints within `gap_tolerance` close a profile.
     pub fn execute(
         &self,
         kernel: &dyn CadKernel,
         sketches: &std::collections::HashMap<Uuid, Sketch>,
         existing_bodies: &std::collections::HashMap<Uuid, Solid>,
-    ) -> FeatureResult<Solid> {
+        gap_tolerance: f32,
+    ) -> FeatureResult<Vec<Solid>> {
         if self.is_suppressed() {
             return Err(FeatureError::InvalidFeature("Feature is suppressed".into()));
         }
@@ -280,6 +330,7 @@ impl Feature {
                 direction,
                 boolean_op,
                 target_body,
+                keep_bodies_separate,
                 ..
             } => {
                 let sketch =
@@ -291,7 +342,7 @@ impl Feature {
                         )))?;
 
                 // Extract profiles from sketch
-                let profiles = sketch.extract_profiles()?;
+                let profiles = sketch.extract_profiles_with_tolerance(gap_tolerance)?;
 
                 if profiles.is_empty() {
                     return Err(FeatureError::InvalidFeature(
@@ -306,37 +357,37 @@ impl Feature {
                     ExtrudeDirection::Symmetric => (sketch.plane.normal, *distance / 2.0),
                 };
 
-                // Extrude the first profile (for now)
-                let profile = &profiles[0];
-                let mut solid = kernel.extrude(
-                    profile,
-                    sketch.plane.origin,
-                    sketch.plane.normal,
-                    extrude_dir,
-                    extrude_dist,
-                )?;
-
-                // For symmetric, extrude in the other direction and union
-                if matches!(direction, ExtrudeDirection::Symmetric) {
-                    let solid2 = kernel.extrude(
+                let mut solids = Vec::with_capacity(profiles.len());
+                for profile in &profiles {
+                    let mut solid = kernel.extrude(
                         profile,
                         sketch.plane.origin,
                         sketch.plane.normal,
-                        -extrude_dir,
+                        extrude_dir,
                         extrude_dist,
                     )?;
-                    solid = kernel.boolean(&solid, &solid2, BooleanType::Union)?;
-                }
 
-                // Apply boolean operation with target body
-                if let (Some(op), Some(target_id)) =
-                    (Option::<BooleanType>::from(*boolean_op), target_body)
-                    && let Some(target) = existing_bodies.get(target_id)
-                {
-                    solid = kernel.boolean(target, &solid, op)?;
+                    // For symmetric, extrude in the other direction and union
+                    if matches!(direction, ExtrudeDirection::Symmetric) {
+                        let solid2 = kernel.extrude(
+                            profile,
+                            sketch.plane.origin,
+                            sketch.plane.normal,
+                            -extrude_dir,
+                            extrude_dist,
+                        )?;
+                        solid = kernel.boolean(&solid, &solid2, BooleanType::Union)?;
+                    }
+                    solids.push(solid);
                 }
 
-                Ok(solid)
+                combine_profile_solids(
+                    kernel,
+                    solids,
+                    *boolean_op,
+                    target_body.and_then(|id| existing_bodies.get(&id)),
+                    *keep_bodies_separate,
+                )
             }
 
             Feature::Revolve {
@@ -346,6 +397,7 @@ impl Feature {
                 angle,
                 boolean_op,
                 target_body,
+                keep_bodies_separate,
                 ..
             } => {
                 let sketch =
@@ -356,7 +408,7 @@ impl Feature {
                             sketch_id
                         )))?;
 
-                let profiles = sketch.extract_profiles()?;
+                let profiles = sketch.extract_profiles_with_tolerance(gap_tolerance)?;
 
                 if profiles.is_empty() {
                     return Err(FeatureError::InvalidFeature(
@@ -365,25 +417,26 @@ impl Feature {
                 }
 
                 let axis = Axis3D::new(*axis_origin, *axis_direction);
-                let profile = &profiles[0];
-
-                let mut solid = kernel.revolve(
-                    profile,
-                    sketch.plane.origin,
-                    sketch.plane.normal,
-                    &axis,
-                    *angle,
-                )?;
-
-                // Apply boolean operation
-                if let (Some(op), Some(target_id)) =
-                    (Option::<BooleanType>::from(*boolean_op), target_body)
-                    && let Some(target) = existing_bodies.get(target_id)
-                {
-                    solid = kernel.boolean(target, &solid, op)?;
-                }
-
-                Ok(solid)
+                let solids = profiles
+                    .iter()
+                    .map(|profile| {
+                        kernel.revolve(
+                            profile,
+                            sketch.plane.origin,
+                            sketch.plane.normal,
+                            &axis,
+                            *angle,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                combine_profile_solids(
+                    kernel,
+                    solids,
+                    *boolean_op,
+                    target_body.and_then(|id| existing_bodies.get(&id)),
+                    *keep_bodies_separate,
+                )
             }
 
             Feature::Boolean {
@@ -404,7 +457,7 @@ impl Feature {
                     FeatureError::InvalidFeature("Invalid boolean operation".into()),
                 )?;
 
-                kernel.boolean(target, tool, op).map_err(|e| e.into())
+                Ok(vec![kernel.boolean(target, tool, op)?])
             }
 
             Feature::Fillet { .. } | Feature::Chamfer { .. } => Err(FeatureError::InvalidFeature(
@@ -414,6 +467,40 @@ impl Feature {
     }
 }
 
+/// Combine the per-profile solids of a sketch feature into its result bodies
+///
+/// With a boolean op and an existing target, every profile solid is applied to
+/// the target in turn. Otherwise the solids are returned as-is when kept
+/// separate, or unioned into one.
+fn combine_profile_solids(
+    kernel: &dyn CadKernel,
+    solids: Vec<Solid>,
+    boolean_op: BooleanOp,
+    target: Option<&Solid>,
+    keep_separate: bool,
+) -> FeatureResult<Vec<Solid>> {
+    if let (Some(op), Some(target)) = (Option::<BooleanType>::from(boolean_op), target) {
+        let mut result = target.clone();
+        for solid in &solids {
+            result = kernel.boolean(&result, solid, op)?;
+        }
+        return Ok(vec![result]);
+    }
+
+    if keep_separate {
+        return Ok(solids);
+    }
+
+    let mut solids = solids.into_iter();
+    let Some(mut merged) = solids.next() else {
+        return Ok(Vec::new());
+    };
+    for solid in solids {
+        merged = kernel.boolean(&merged, &solid, BooleanType::Union)?;
+    }
+    Ok(vec![merged])
+}
+
 /// A body produced by features
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CadBody {
@@ -9,7 +9,7 @@ use uuid::Uuid;
 
 use crate::feature::{CadBody, Feature, FeatureError, FeatureResult};
 use crate::kernel::{CadKernel, Solid};
-use crate::sketch::Sketch;
+use crate::sketch::{PROFILE_GAP_TOLERANCE, Sketch};
 
 /// An entry in the feature history
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +24,9 @@ pub struct HistoryEntry {
     pub modified_bodies: Vec<Uuid>,
     /// Bodies deleted by this feature
     pub deleted_bodies: Vec<Uuid>,
+    /// Error from the last rebuild, if this feature failed
+    #[serde(skip)]
+    pub error: Option<String>,
 }
 
 impl HistoryEntry {
@@ -35,12 +38,13 @@ impl HistoryEntry {
             created_bodies: Vec::new(),
             modified_bodies: Vec::new(),
             deleted_bodies: Vec::new(),
+            error: None,
         }
     }
 }
 
 /// Manages the parametric feature history
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureHistory {
     /// Ordered list of features
     entries: Vec<HistoryEntry>,
@@ -51,6 +55,25 @@ pub struct FeatureHistory {
     /// All bodies in the model
     #[serde(skip)]
     bodies: HashMap<Uuid, CadBody>,
+    /// Distance within which sketch curve endpoints close a profile
+    #[serde(default = "default_gap_tolerance")]
+    gap_tolerance: f32,
+}
+
+fn default_gap_tolerance() -> f32 {
+    PROFILE_GAP_TOLERANCE
+}
+
+impl Default for FeatureHistory {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            rollback_position: None,
+            sketches: HashMap::new(),
+            bodies: HashMap::new(),
+            gap_tolerance: PROFILE_GAP_TOLERANCE,
+        }
+    }
 }
 
 impl FeatureHistory {
@@ -213,6 +236,20 @@ impl FeatureHistory {
         self.rollback_position.unwrap_or(self.entries.len())
     }
 
+    /// Index of the first feature that failed in the last rebuild
+    pub fn first_failure(&self) -> Option<usize> {
+        self.entries[..self.effective_len()]
+            .iter()
+            .position(|e| e.error.is_some())
+    }
+
+    /// Position of the rollback indicator: after the first failed feature,
+    /// or at the rollback position if everything built
+    pub fn build_stop_position(&self) -> usize {
+        self.first_failure()
+            .map_or(self.effective_len(), |index| index + 1)
+    }
+
     /// Iterate over effective features (accounting for rollback)
     pub fn effective_features(&self) -> impl Iterator<Item = &Feature> {
         let end = self.effective_len();
@@ -221,10 +258,23 @@ impl FeatureHistory {
 
     // ============== Rebuild ==============
 
+    /// Distance within which sketch curve endpoints close a profile
+    pub fn gap_tolerance(&self) -> f32 {
+        self.gap_tolerance
+    }
+
+    /// Set the profile gap tolerance used by the next rebuild
+    pub fn set_gap_tolerance(&mut self, tolerance: f32) {
+        self.gap_tolerance = tolerance.max(0.0);
+    }
+
     /// Rebuild all geometry from features
     pub fn rebuild(&mut self, kernel: &dyn CadKernel) -> FeatureResult<()> {
-        // Clear existing bodies
+        // Clear existing bodies and errors from the previous rebuild
         self.bodies.clear();
+        for entry in &mut self.entries {
+            entry.error = None;
+        }
 
         // Convert bodies to solids for feature execution
         let mut solids: HashMap<Uuid, Solid> = HashMap::new();
@@ -233,26 +283,41 @@ impl FeatureHistory {
         let end = self.effective_len();
         for entry in &mut self.entries[..end] {
             if entry.feature.is_suppressed() {
+                entry.created_bodies.clear();
                 continue;
             }
 
-            match entry.feature.execute(kernel, &self.sketches, &solids) {
-                Ok(solid) => {
-                    // Create a new body for the result
-                    let mut body = CadBody::new(entry.feature.name());
-                    body.source_feature = Some(entry.feature.id());
-                    let body_id = body.id;
-
-                    // Store the solid
-                    solids.insert(body_id, solid.clone());
-                    body.solid = Some(solid);
-
-                    self.bodies.insert(body_id, body);
-                    entry.created_bodies = vec![body_id];
+            match entry
+                .feature
+                .execute(kernel, &self.sketches, &solids, self.gap_tolerance)
+            {
+                Ok(results) => {
+                    // Create a new body for each resulting solid
+                    let count = results.len();
+                    entry.created_bodies.clear();
+                    for (index, solid) in results.into_iter().enumerate() {
+                        let name = if count > 1 {
+                            format!("{} ({})", entry.feature.name(), index + 1)
+                        } else {
+                            entry.feature.name().to_string()
+                        };
+                        let mut body = CadBody::new(name);
+                        body.source_feature = Some(entry.feature.id());
+                        let body_id = body.id;
+
+                        // Store the solid
+                        solids.insert(body_id, solid.clone());
+                        body.solid = Some(solid);
+
+                        self.bodies.insert(body_id, body);
+                        entry.created_bodies.push(body_id);
+                    }
                 }
                 Err(e) => {
-                    // Log error but continue with other features
+                    // Record the error but continue with other features
                     tracing::warn!("Feature {} failed: {}", entry.feature.name(), e);
+                    entry.created_bodies.clear();
+                    entry.error = Some(e.to_string());
                 }
             }
         }
@@ -269,6 +334,32 @@ impl FeatureHistory {
         // TODO: Optimize to only rebuild affected features
         self.rebuild(kernel)
     }
+
+    /// Suppress or unsuppress a feature, then rebuild from it
+    pub fn set_suppressed(
+        &mut self,
+        id: Uuid,
+        suppressed: bool,
+        kernel: &dyn CadKernel,
+    ) -> FeatureResult<()> {
+        self.get_by_id_mut(id)
+            .ok_or(FeatureError::FeatureNotFound(id))?
+            .set_suppressed(suppressed);
+        self.rebuild_from(id, kernel)
+    }
+
+    /// Set whether a sketch feature keeps one body per profile, then rebuild from it
+    pub fn set_keep_bodies_separate(
+        &mut self,
+        id: Uuid,
+        keep_separate: bool,
+        kernel: &dyn CadKernel,
+    ) -> FeatureResult<()> {
+        self.get_by_id_mut(id)
+            .ok_or(FeatureError::FeatureNotFound(id))?
+            .set_keep_bodies_separate(keep_separate);
+        self.rebuild_from(id, kernel)
+    }
 }
 
 /// CAD data that can be stored in a project
@@ -294,6 +385,52 @@ impl CadData {
 mod tests {
     use super::*;
     use crate::feature::ExtrudeDirection;
+    use crate::kernel::{Axis3D, BooleanType, CadResult, NullKernel, TessellatedMesh, Wire2D};
+    use crate::sketch::SketchPlane;
+    use glam::{Vec2, Vec3};
+
+    /// Kernel that hands out empty solids for extrudes and booleans
+    ///
+    /// Every other operation fails like [`NullKernel`].
+    struct StubKernel;
+
+    impl CadKernel for StubKernel {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn extrude(&self, _: &Wire2D, _: Vec3, _: Vec3, _: Vec3, _: f32) -> CadResult<Solid> {
+            Ok(Solid::new(Uuid::new_v4()))
+        }
+
+        fn revolve(&self, p: &Wire2D, o: Vec3, n: Vec3, a: &Axis3D, t: f32) -> CadResult<Solid> {
+            NullKernel.revolve(p, o, n, a, t)
+        }
+
+        fn boolean(&self, _: &Solid, _: &Solid, _: BooleanType) -> CadResult<Solid> {
+            Ok(Solid::new(Uuid::new_v4()))
+        }
+
+        fn tessellate(&self, solid: &Solid, tolerance: f32) -> CadResult<TessellatedMesh> {
+            NullKernel.tessellate(solid, tolerance)
+        }
+
+        fn create_box(&self, center: Vec3, size: Vec3) -> CadResult<Solid> {
+            NullKernel.create_box(center, size)
+        }
+
+        fn create_cylinder(&self, c: Vec3, r: f32, h: f32, axis: Vec3) -> CadResult<Solid> {
+            NullKernel.create_cylinder(c, r, h, axis)
+        }
+
+        fn create_sphere(&self, center: Vec3, radius: f32) -> CadResult<Solid> {
+            NullKernel.create_sphere(center, radius)
+        }
+    }
 
     #[test]
     fn test_add_feature() {
@@ -333,4 +470,110 @@ mod tests {
         history.rollback_to_end();
         assert_eq!(history.effective_len(), 3);
     }
+
+    #[test]
+    fn test_missing_sketch_records_error() {
+        let mut history = FeatureHistory::new();
+        let missing_sketch = Uuid::new_v4();
+        let feature = Feature::extrude("Orphan", missing_sketch, 10.0, ExtrudeDirection::Positive);
+        let id = feature.id();
+        history.add_feature(feature);
+
+        history.rebuild_from(id, &NullKernel).unwrap();
+
+        let entry = &history.entries()[0];
+        let error = entry.error.as_deref().expect("failure should be recorded");
+        assert!(error.contains(&missing_sketch.to_string()));
+        assert!(entry.created_bodies.is_empty());
+        assert!(history.bodies().is_empty());
+        assert_eq!(history.first_failure(), Some(0));
+        assert_eq!(history.build_stop_position(), 1);
+
+        // Suppressing the feature clears the error on the next rebuild
+        history.get_by_id_mut(id).unwrap().set_suppressed(true);
+        history.rebuild(&NullKernel).unwrap();
+        assert!(history.entries()[0].error.is_none());
+        assert_eq!(history.first_failure(), None);
+    }
+
+    #[test]
+    fn test_suppress_extrude_removes_body() {
+        let mut history = FeatureHistory::new();
+        let mut sketch = Sketch::new("Sketch", SketchPlane::xy());
+        sketch.add_rectangle(Vec2::ZERO, Vec2::new(2.0, 1.0));
+        let sketch_id = history.add_sketch(sketch);
+        let feature = Feature::extrude("Pad", sketch_id, 5.0, ExtrudeDirection::Positive);
+        let id = feature.id();
+        history.add_feature(feature);
+
+        history.rebuild(&StubKernel).unwrap();
+        assert_eq!(history.bodies().len(), 1);
+        assert_eq!(history.entries()[0].created_bodies.len(), 1);
+
+        history.set_suppressed(id, true, &StubKernel).unwrap();
+        assert!(history.bodies().is_empty());
+        assert!(history.entries()[0].created_bodies.is_empty());
+
+        history.set_suppressed(id, false, &StubKernel).unwrap();
+        assert_eq!(history.bodies().len(), 1);
+        let body_id = history.entries()[0].created_bodies[0];
+        assert_eq!(history.get_body(body_id).unwrap().source_feature, Some(id));
+    }
+
+    #[test]
+    fn test_gap_tolerance_applies_to_rebuild() {
+        let mut history = FeatureHistory::new();
+        let mut sketch = Sketch::new("Sketch", SketchPlane::xy());
+        let p0 = sketch.add_point(Vec2::new(0.0, 0.0));
+        let p1 = sketch.add_point(Vec2::new(1.0, 0.0));
+        let p2 = sketch.add_point(Vec2::new(0.0, 1.0));
+        let near_p0 = sketch.add_point(Vec2::new(1e-4, 0.0));
+        sketch.add_line(p0, p1);
+        sketch.add_line(p1, p2);
+        sketch.add_line(p2, near_p0);
+        let sketch_id = history.add_sketch(sketch);
+        history.add_feature(Feature::extrude(
+            "Pad",
+            sketch_id,
+            5.0,
+            ExtrudeDirection::Positive,
+        ));
+
+        // The default tolerance bridges the small gap
+        history.rebuild(&StubKernel).unwrap();
+        assert_eq!(history.bodies().len(), 1);
+
+        history.set_gap_tolerance(0.0);
+        history.rebuild(&StubKernel).unwrap();
+        assert!(history.bodies().is_empty());
+        assert!(history.entries()[0].error.is_some());
+    }
+
+    #[test]
+    fn test_disjoint_profiles_kept_separate() {
+        let mut history = FeatureHistory::new();
+        let mut sketch = Sketch::new("Sketch", SketchPlane::xy());
+        sketch.add_rectangle(Vec2::ZERO, Vec2::new(1.0, 1.0));
+        sketch.add_rectangle(Vec2::new(3.0, 0.0), Vec2::new(4.0, 1.0));
+        let sketch_id = history.add_sketch(sketch);
+        let mut feature = Feature::extrude("Pads", sketch_id, 5.0, ExtrudeDirection::Positive);
+        feature.set_keep_bodies_separate(true);
+        let id = feature.id();
+        history.add_feature(feature);
+
+        history.rebuild(&StubKernel).unwrap();
+        let created = &history.entries()[0].created_bodies;
+        assert_eq!(created.len(), 2);
+        assert_eq!(history.bodies().len(), 2);
+        for body_id in created {
+            assert_eq!(history.get_body(*body_id).unwrap().source_feature, Some(id));
+        }
+
+        // Merged by default
+        history
+            .set_keep_bodies_separate(id, false, &StubKernel)
+            .unwrap();
+        assert_eq!(history.entries()[0].created_bodies.len(), 1);
+        assert_eq!(history.bodies().len(), 1);
+    }
 }
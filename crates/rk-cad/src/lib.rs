@@ -20,5 +20,6 @@ pub use kernel::{
     Wire2D, default_kernel,
 };
 pub use sketch::{
-    ConstraintSolver, Sketch, SketchConstraint, SketchEntity, SketchError, SketchPlane, SolveResult,
+    ArcDirection, ConstraintSolver, Sketch, SketchConstraint, SketchEntity, SketchError,
+    SketchPlane, SolveResult,
 };
@@ -6,6 +6,75 @@ use glam::Vec2;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Direction in which an arc sweeps from its start point to its end point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ArcDirection {
+    /// Counter-clockwise (positive angle) sweep
+    #[default]
+    CounterClockwise,
+    /// Clockwise (negative angle) sweep
+    Clockwise,
+}
+
+impl ArcDirection {
+    /// Get the opposite direction
+    pub fn reversed(self) -> Self {
+        match self {
+            ArcDirection::CounterClockwise => ArcDirection::Clockwise,
+            ArcDirection::Clockwise => ArcDirection::CounterClockwise,
+        }
+    }
+
+    /// Compute the start angle and signed sweep of an arc
+    ///
+    /// The sweep is in `(0, TAU]` for counter-clockwise arcs and `[-TAU, 0)`
+    /// for clockwise arcs; coincident start and end points give a full turn.
+    pub fn sweep_angles(self, center: Vec2, start: Vec2, end: Vec2) -> (f32, f32) {
+        use std::f32::consts::TAU;
+
+        let start_angle = (start - center).to_angle();
+        let end_angle = (end - center).to_angle();
+        let mut ccw_sweep = (end_angle - start_angle).rem_euclid(TAU);
+        if ccw_sweep <= f32::EPSILON {
+            ccw_sweep = TAU;
+        }
+
+        let sweep = match self {
+            ArcDirection::CounterClockwise => ccw_sweep,
+            ArcDirection::Clockwise if ccw_sweep >= TAU => -TAU,
+            ArcDirection::Clockwise => ccw_sweep - TAU,
+        };
+        (start_angle, sweep)
+    }
+}
+
+/// Sample points along an arc, including both end points
+///
+/// `max_segments` is the segment count for a full turn; shorter arcs use
+/// proportionally fewer segments (at least one).
+pub fn flatten_arc(
+    center: Vec2,
+    radius: f32,
+    start_angle: f32,
+    sweep: f32,
+    max_segments: u32,
+) -> Vec<Vec2> {
+    let segments = arc_segment_count(sweep, max_segments);
+    (0..=segments)
+        .map(|i| {
+            let angle = start_angle + sweep * (i as f32 / segments as f32);
+            center + Vec2::from_angle(angle) * radius
+        })
+        .collect()
+}
+
+/// Number of segments used to approximate an arc of the given sweep
+pub fn arc_segment_count(sweep: f32, max_segments: u32) -> u32 {
+    let fraction = (sweep.abs() / std::f32::consts::TAU).min(1.0);
+    // Small tolerance so exact fractions (e.g. a quarter turn) don't round up
+    ((fraction * max_segments as f32 - 1e-3).ceil() as u32).max(1)
+}
+
 /// A geometric entity in a sketch
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SketchEntity {
@@ -39,6 +108,9 @@ pub enum SketchEntity {
         end: Uuid,
         /// Radius of the arc
         radius: f32,
+        /// Sweep direction from start to end
+        #[serde(default)]
+        direction: ArcDirection,
     },
 
     /// A full circle
@@ -166,14 +238,36 @@ impl SketchEntity {
         }
     }
 
-    /// Create a new arc entity
+    /// Create a new counter-clockwise arc entity
     pub fn arc(center: Uuid, start: Uuid, end: Uuid, radius: f32) -> Self {
+        Self::arc_with_direction(center, start, end, radius, ArcDirection::CounterClockwise)
+    }
+
+    /// Create a new arc entity sweeping in the given direction
+    pub fn arc_with_direction(
+        center: Uuid,
+        start: Uuid,
+        end: Uuid,
+        radius: f32,
+        direction: ArcDirection,
+    ) -> Self {
         SketchEntity::Arc {
             id: Uuid::new_v4(),
             center,
             start,
             end,
             radius,
+            direction,
+        }
+    }
+
+    /// Get the start and end point IDs of an open curve (line or arc)
+    pub fn endpoints(&self) -> Option<(Uuid, Uuid)> {
+        match self {
+            SketchEntity::Line { start, end, .. } | SketchEntity::Arc { start, end, .. } => {
+                Some((*start, *end))
+            }
+            _ => None,
         }
     }
 
@@ -234,4 +328,38 @@ mod tests {
         assert!(refs.contains(&p1));
         assert!(refs.contains(&p2));
     }
+
+    #[test]
+    fn test_arc_sweep_direction() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        // From +X to -Y: 270° counter-clockwise, 90° clockwise
+        let (start, sweep) =
+            ArcDirection::CounterClockwise.sweep_angles(Vec2::ZERO, Vec2::X, Vec2::NEG_Y);
+        assert!(start.abs() < 1e-6);
+        assert!((sweep - 3.0 * FRAC_PI_2).abs() < 1e-5);
+
+        let (_, sweep) = ArcDirection::Clockwise.sweep_angles(Vec2::ZERO, Vec2::X, Vec2::NEG_Y);
+        assert!((sweep + FRAC_PI_2).abs() < 1e-5);
+
+        // Reflex arc crossing the ±π seam
+        let (_, sweep) = ArcDirection::CounterClockwise.sweep_angles(
+            Vec2::ZERO,
+            Vec2::from_angle(0.75 * PI),
+            Vec2::from_angle(0.5 * PI),
+        );
+        assert!((sweep - 1.75 * PI).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_flatten_arc() {
+        use std::f32::consts::FRAC_PI_2;
+
+        let points = flatten_arc(Vec2::ZERO, 2.0, 0.0, 3.0 * FRAC_PI_2, 64);
+        assert_eq!(points.len(), 49); // 48 segments
+        assert!((points[0] - Vec2::new(2.0, 0.0)).length() < 1e-5);
+        assert!((points[48] - Vec2::new(0.0, -2.0)).length() < 1e-4);
+        // Passes through +Y on the way round
+        assert!((points[16] - Vec2::new(0.0, 2.0)).length() < 1e-4);
+    }
 }
@@ -19,6 +19,16 @@ use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 use uuid::Uuid;
 
+/// Half-size of the bounds reported for a sketch without entities
+const EMPTY_SKETCH_HALF_EXTENT: f32 = 0.1;
+
+/// Segments per full turn used when flattening arcs into profiles
+const PROFILE_ARC_SEGMENTS: u32 = 32;
+
+/// Distance within which curve endpoints close a profile loop when features
+/// are built, so hand-drawn sketches with nearly touching ends still extrude
+pub const PROFILE_GAP_TOLERANCE: f32 = 1e-3;
+
 /// Sketch-related errors
 #[derive(Debug, Clone, Error)]
 pub enum SketchError {
@@ -97,6 +107,16 @@ impl SketchPlane {
         self.normal.cross(self.x_axis).normalize()
     }
 
+    /// Reverse the plane normal, swapping the X and Y axes to stay right-handed
+    ///
+    /// Sketch coordinates are kept, so the geometry is mirrored across the
+    /// plane's X = Y diagonal.
+    pub fn flip(&mut self) {
+        let y_axis = self.y_axis();
+        self.x_axis = y_axis;
+        self.normal = -self.normal;
+    }
+
     /// Convert a 2D point on the sketch to 3D world coordinates
     pub fn to_world(&self, point: Vec2) -> Vec3 {
         self.origin + self.x_axis * point.x + self.y_axis() * point.y
@@ -137,8 +157,8 @@ pub struct Sketch {
     pub plane: SketchPlane,
     /// Geometric entities (points, lines, arcs, etc.)
     entities: HashMap<Uuid, SketchEntity>,
-    /// Constraints between entities
-    constraints: HashMap<Uuid, SketchConstraint>,
+    /// Constraints between entities, in the order they were added
+    constraints: Vec<SketchConstraint>,
     /// Construction geometry (not used for profiles)
     construction: HashSet<Uuid>,
     /// Whether the sketch is fully constrained
@@ -163,7 +183,7 @@ impl Sketch {
             name: name.into(),
             plane,
             entities: HashMap::new(),
-            constraints: HashMap::new(),
+            constraints: Vec::new(),
             construction: HashSet::new(),
             is_solved: true,
             dof: 0,
@@ -177,7 +197,7 @@ impl Sketch {
             name: name.into(),
             plane,
             entities: HashMap::new(),
-            constraints: HashMap::new(),
+            constraints: Vec::new(),
             construction: HashSet::new(),
             is_solved: true,
             dof: 0,
@@ -208,16 +228,7 @@ impl Sketch {
     /// Remove an entity
     pub fn remove_entity(&mut self, id: Uuid) -> Option<SketchEntity> {
         // Also remove related constraints
-        let related_constraints: Vec<Uuid> = self
-            .constraints
-            .iter()
-            .filter(|(_, c)| c.references_entity(id))
-            .map(|(cid, _)| *cid)
-            .collect();
-
-        for cid in related_constraints {
-            self.constraints.remove(&cid);
-        }
+        self.constraints.retain(|c| !c.references_entity(id));
 
         self.construction.remove(&id);
         self.is_solved = false;
@@ -246,30 +257,71 @@ impl Sketch {
         }
 
         let id = constraint.id();
-        self.constraints.insert(id, constraint);
+        // Re-adding a constraint with the same ID replaces it in place
+        match self.constraints.iter_mut().find(|c| c.id() == id) {
+            Some(existing) => *existing = constraint,
+            None => self.constraints.push(constraint),
+        }
         self.is_solved = false;
         Ok(id)
     }
 
     /// Get a constraint by ID
     pub fn get_constraint(&self, id: Uuid) -> Option<&SketchConstraint> {
-        self.constraints.get(&id)
+        self.constraints.iter().find(|c| c.id() == id)
+    }
+
+    /// Get a mutable constraint by ID
+    ///
+    /// The sketch is marked unsolved only when the constraint exists.
+    pub fn get_constraint_mut(&mut self, id: Uuid) -> Option<&mut SketchConstraint> {
+        let constraint = self.constraints.iter_mut().find(|c| c.id() == id)?;
+        self.is_solved = false;
+        Some(constraint)
     }
 
     /// Remove a constraint
     pub fn remove_constraint(&mut self, id: Uuid) -> Option<SketchConstraint> {
+        let index = self.constraints.iter().position(|c| c.id() == id)?;
         self.is_solved = false;
-        self.constraints.remove(&id)
+        Some(self.constraints.remove(index))
     }
 
-    /// Get all constraints
-    pub fn constraints(&self) -> &HashMap<Uuid, SketchConstraint> {
+    /// Get all constraints, in the order they were added
+    pub fn constraints(&self) -> &[SketchConstraint] {
         &self.constraints
     }
 
     /// Iterate over constraints
     pub fn constraints_iter(&self) -> impl Iterator<Item = &SketchConstraint> {
-        self.constraints.values()
+        self.constraints.iter()
+    }
+
+    /// Get the ID of the fixed constraint anchoring a point, if any
+    pub fn fixed_constraint_for(&self, point: Uuid) -> Option<Uuid> {
+        self.constraints.iter().find_map(|c| match c {
+            SketchConstraint::Fixed { id, point: p, .. } if *p == point => Some(*id),
+            _ => None,
+        })
+    }
+
+    /// Check if a point is anchored by a fixed constraint
+    pub fn is_point_fixed(&self, point: Uuid) -> bool {
+        self.fixed_constraint_for(point).is_some()
+    }
+
+    /// Fix a point at its current position, or unfix it if already fixed
+    ///
+    /// Returns whether the point is fixed afterwards.
+    pub fn toggle_fixed(&mut self, point: Uuid) -> Result<bool, SketchError> {
+        if let Some(id) = self.fixed_constraint_for(point) {
+            self.remove_constraint(id);
+            return Ok(false);
+        }
+
+        let position = self.get_point_position(point)?;
+        self.add_constraint(SketchConstraint::fixed(point, position.x, position.y))?;
+        Ok(true)
     }
 
     // ============== Construction Geometry ==============
@@ -329,29 +381,86 @@ impl Sketch {
 
     /// Extract closed profiles from the sketch for extrusion
     ///
-    /// Returns a list of closed wire profiles (excluding construction geometry)
+    /// Returns a list of closed wire profiles (excluding construction geometry).
+    /// Curves only connect through shared point entities.
     pub fn extract_profiles(&self) -> Result<Vec<crate::kernel::Wire2D>, SketchError> {
-        // For now, implement a simple profile extraction
-        // that works with lines forming closed loops
+        self.extract_profiles_with_tolerance(0.0)
+    }
+
+    /// Extract closed profiles, treating curve endpoints within `tolerance`
+    /// of each other as connected
+    ///
+    /// A warning is logged when gaps had to be bridged; use
+    /// [`Sketch::close_gaps`] to make such connections permanent. Endpoints
+    /// already joined by a coincident constraint are only logged at debug level.
+    pub fn extract_profiles_with_tolerance(
+        &self,
+        tolerance: f32,
+    ) -> Result<Vec<crate::kernel::Wire2D>, SketchError> {
+        let gaps = self.endpoint_gaps(tolerance);
+        let constrained = gaps
+            .iter()
+            .filter(|(a, b)| {
+                self.constraints.iter().any(|c| match c {
+                    SketchConstraint::Coincident { point1, point2, .. } => {
+                        (point1, point2) == (a, b) || (point1, point2) == (b, a)
+                    }
+                    _ => false,
+                })
+            })
+            .count();
+        if constrained < gaps.len() {
+            tracing::warn!(
+                "Sketch '{}': bridging {} endpoint gap(s) within {} to close profiles",
+                self.name,
+                gaps.len() - constrained,
+                tolerance
+            );
+        }
+        if constrained > 0 {
+            tracing::debug!(
+                "Sketch '{}': joining {} coincident endpoint pair(s)",
+                self.name,
+                constrained
+            );
+        }
+        // Union the gapped endpoints so chains of near points form one
+        // cluster, then map every member onto its cluster's root
+        let mut parent: HashMap<Uuid, Uuid> = HashMap::new();
+        fn find(parent: &HashMap<Uuid, Uuid>, mut id: Uuid) -> Uuid {
+            while let Some(&next) = parent.get(&id) {
+                id = next;
+            }
+            id
+        }
+        for (a, b) in gaps {
+            let (root_a, root_b) = (find(&parent, a), find(&parent, b));
+            if root_a != root_b {
+                parent.insert(root_b, root_a);
+            }
+        }
+        let merged: HashMap<Uuid, Uuid> =
+            parent.keys().map(|&id| (id, find(&parent, id))).collect();
 
         let mut profiles = Vec::new();
         let mut used_entities: HashSet<Uuid> = HashSet::new();
 
-        // Find all line entities that are not construction
-        let lines: Vec<&SketchEntity> = self
+        // Find all open curves (lines and arcs) that are not construction
+        let curves: Vec<Uuid> = self
             .entities
             .values()
-            .filter(|e| matches!(e, SketchEntity::Line { .. }) && !self.is_construction(e.id()))
+            .filter(|e| e.endpoints().is_some() && !self.is_construction(e.id()))
+            .map(|e| e.id())
             .collect();
 
         // Try to form closed loops
-        for start_line in &lines {
-            if used_entities.contains(&start_line.id()) {
+        for start_id in &curves {
+            if used_entities.contains(start_id) {
                 continue;
             }
 
-            if let Some(profile) = self.trace_closed_loop(start_line.id(), &used_entities) {
-                for id in &profile {
+            if let Some(profile) = self.trace_closed_loop(*start_id, &used_entities, &merged) {
+                for (id, _) in &profile {
                     used_entities.insert(*id);
                 }
 
@@ -384,72 +493,227 @@ impl Sketch {
         Ok(profiles)
     }
 
-    /// Trace a closed loop starting from a line
-    fn trace_closed_loop(&self, start_id: Uuid, used: &HashSet<Uuid>) -> Option<Vec<Uuid>> {
-        let start = self.entities.get(&start_id)?;
-        let SketchEntity::Line {
-            start: start_point,
-            end: first_end,
-            ..
-        } = start
-        else {
-            return None;
-        };
+    /// Pairs of distinct curve endpoints lying within `tolerance` of each other
+    ///
+    /// Only endpoints of non-construction lines and arcs are considered. Each
+    /// pair is ordered so the first point is the one that sorts earlier.
+    pub fn endpoint_gaps(&self, tolerance: f32) -> Vec<(Uuid, Uuid)> {
+        if tolerance <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut endpoints: Vec<(Uuid, Vec2)> = self
+            .entities
+            .values()
+            .filter(|e| !self.is_construction(e.id()))
+            .filter_map(|e| e.endpoints())
+            .flat_map(|(start, end)| [start, end])
+            .filter_map(|id| Some((id, self.get_point_position(id).ok()?)))
+            .collect();
+        // Sorted for a stable result regardless of HashMap iteration
+        endpoints.sort_by_key(|(id, _)| *id);
+        endpoints.dedup_by_key(|(id, _)| *id);
+
+        let mut gaps = Vec::new();
+        for (i, (a, pa)) in endpoints.iter().enumerate() {
+            for (b, pb) in &endpoints[i + 1..] {
+                if pa.distance(*pb) <= tolerance {
+                    gaps.push((*a, *b));
+                }
+            }
+        }
+        gaps
+    }
 
-        let mut loop_entities = vec![start_id];
-        let mut current_end = *first_end;
-        let target = *start_point;
+    /// Add coincident constraints joining every endpoint gap within `tolerance`
+    ///
+    /// Returns the IDs of the added constraints.
+    pub fn close_gaps(&mut self, tolerance: f32) -> Result<Vec<Uuid>, SketchError> {
+        self.endpoint_gaps(tolerance)
+            .into_iter()
+            .map(|(a, b)| self.add_constraint(SketchConstraint::coincident(a, b)))
+            .collect()
+    }
 
-        // Follow connected lines
+    /// Trace a closed loop starting from a line or arc
+    ///
+    /// Returns the curves in loop order, each paired with whether it is
+    /// traversed from its end point back to its start point. Points in
+    /// `merged` are treated as the point they map to.
+    fn trace_closed_loop(
+        &self,
+        start_id: Uuid,
+        used: &HashSet<Uuid>,
+        merged: &HashMap<Uuid, Uuid>,
+    ) -> Option<Vec<(Uuid, bool)>> {
+        let resolve = |id: Uuid| merged.get(&id).copied().unwrap_or(id);
+        let (target, first_end) = self.entities.get(&start_id)?.endpoints()?;
+        let (target, first_end) = (resolve(target), resolve(first_end));
+
+        let mut loop_entities = vec![(start_id, false)];
+        let mut current_end = first_end;
+
+        // Follow connected curves
         for _ in 0..100 {
             // Limit iterations
             if current_end == target {
                 return Some(loop_entities);
             }
 
-            // Find next connected line
-            let next = self.entities.values().find(|e| {
-                if used.contains(&e.id()) || loop_entities.contains(&e.id()) {
-                    return false;
+            // Find next connected curve
+            let next = self.entities.values().find_map(|e| {
+                let id = e.id();
+                if used.contains(&id)
+                    || loop_entities.iter().any(|(l, _)| *l == id)
+                    || self.is_construction(id)
+                {
+                    return None;
                 }
-                if let SketchEntity::Line { start, end, .. } = e {
-                    *start == current_end || *end == current_end
+                let (start, end) = e.endpoints()?;
+                let (start, end) = (resolve(start), resolve(end));
+                if start == current_end {
+                    Some((id, false, end))
+                } else if end == current_end {
+                    Some((id, true, start))
                 } else {
-                    false
+                    None
                 }
             });
 
-            match next {
-                Some(SketchEntity::Line { id, start, end, .. }) => {
-                    loop_entities.push(*id);
-                    current_end = if *start == current_end { *end } else { *start };
-                }
-                _ => return None,
-            }
+            let (id, reversed, far_end) = next?;
+            loop_entities.push((id, reversed));
+            current_end = far_end;
         }
 
         None
     }
 
-    /// Convert entity IDs to a list of 2D points
-    fn entities_to_points(&self, entity_ids: &[Uuid]) -> Result<Vec<Vec2>, SketchError> {
+    /// Convert a traced loop to a list of 2D points
+    ///
+    /// Each curve contributes the points from where it is entered up to (but
+    /// not including) where it is left; arcs are flattened along their sweep.
+    fn entities_to_points(&self, entity_ids: &[(Uuid, bool)]) -> Result<Vec<Vec2>, SketchError> {
         let mut points = Vec::new();
 
-        for id in entity_ids {
+        for (id, reversed) in entity_ids {
             let entity = self
                 .entities
                 .get(id)
                 .ok_or(SketchError::EntityNotFound(*id))?;
 
-            if let SketchEntity::Line { start, .. } = entity {
-                let pos = self.get_point_position(*start)?;
-                points.push(pos);
+            match entity {
+                SketchEntity::Line { start, end, .. } => {
+                    let entry = if *reversed { *end } else { *start };
+                    points.push(self.get_point_position(entry)?);
+                }
+                SketchEntity::Arc {
+                    center,
+                    start,
+                    end,
+                    radius,
+                    direction,
+                    ..
+                } => {
+                    let center = self.get_point_position(*center)?;
+                    let start = self.get_point_position(*start)?;
+                    let end = self.get_point_position(*end)?;
+                    let (start_angle, sweep) = direction.sweep_angles(center, start, end);
+                    let mut arc_points =
+                        flatten_arc(center, *radius, start_angle, sweep, PROFILE_ARC_SEGMENTS);
+                    if *reversed {
+                        arc_points.reverse();
+                    }
+                    arc_points.pop();
+                    points.extend(arc_points);
+                }
+                _ => {}
             }
         }
 
         Ok(points)
     }
 
+    /// Axis-aligned 2D extents `(min, max)` of all entities in sketch coordinates
+    ///
+    /// Circles, arcs and ellipses contribute their curve extents, not just their
+    /// defining points. An empty sketch gives a small box around the origin.
+    pub fn bounds_2d(&self) -> (Vec2, Vec2) {
+        let mut min = Vec2::splat(f32::INFINITY);
+        let mut max = Vec2::splat(f32::NEG_INFINITY);
+        let mut include = |p: Vec2| {
+            min = min.min(p);
+            max = max.max(p);
+        };
+
+        for entity in self.entities.values() {
+            match entity {
+                SketchEntity::Point { position, .. } => include(*position),
+                SketchEntity::Circle { center, radius, .. } => {
+                    if let Ok(c) = self.get_point_position(*center) {
+                        include(c - Vec2::splat(*radius));
+                        include(c + Vec2::splat(*radius));
+                    }
+                }
+                SketchEntity::Arc {
+                    center,
+                    start,
+                    end,
+                    radius,
+                    direction,
+                    ..
+                } => {
+                    let (Ok(c), Ok(s), Ok(e)) = (
+                        self.get_point_position(*center),
+                        self.get_point_position(*start),
+                        self.get_point_position(*end),
+                    ) else {
+                        continue;
+                    };
+                    // End points are point entities; add the axis extremes the arc passes
+                    let (start_angle, sweep) = direction.sweep_angles(c, s, e);
+                    for quadrant in 0..4 {
+                        let angle = quadrant as f32 * std::f32::consts::FRAC_PI_2;
+                        let offset = if sweep >= 0.0 {
+                            (angle - start_angle).rem_euclid(std::f32::consts::TAU)
+                        } else {
+                            (start_angle - angle).rem_euclid(std::f32::consts::TAU)
+                        };
+                        if offset <= sweep.abs() {
+                            include(c + Vec2::from_angle(angle) * *radius);
+                        }
+                    }
+                }
+                SketchEntity::Ellipse {
+                    center,
+                    major_radius,
+                    minor_radius,
+                    rotation,
+                    ..
+                } => {
+                    if let Ok(c) = self.get_point_position(*center) {
+                        let (sin, cos) = rotation.sin_cos();
+                        let half = Vec2::new(
+                            (major_radius * cos).hypot(minor_radius * sin),
+                            (major_radius * sin).hypot(minor_radius * cos),
+                        );
+                        include(c - half);
+                        include(c + half);
+                    }
+                }
+                // Lines and splines lie within their point entities' extents
+                SketchEntity::Line { .. } | SketchEntity::Spline { .. } => {}
+            }
+        }
+
+        if min.x > max.x {
+            return (
+                Vec2::splat(-EMPTY_SKETCH_HALF_EXTENT),
+                Vec2::splat(EMPTY_SKETCH_HALF_EXTENT),
+            );
+        }
+        (min, max)
+    }
+
     /// Get the position of a point entity
     fn get_point_position(&self, id: Uuid) -> Result<Vec2, SketchError> {
         let entity = self
@@ -494,15 +758,23 @@ impl Sketch {
         })
     }
 
-    /// Add an arc
+    /// Add a counter-clockwise arc
     pub fn add_arc(&mut self, center: Uuid, start: Uuid, end: Uuid, radius: f32) -> Uuid {
-        self.add_entity(SketchEntity::Arc {
-            id: Uuid::new_v4(),
-            center,
-            start,
-            end,
-            radius,
-        })
+        self.add_entity(SketchEntity::arc(center, start, end, radius))
+    }
+
+    /// Add an arc sweeping in the given direction
+    pub fn add_arc_with_direction(
+        &mut self,
+        center: Uuid,
+        start: Uuid,
+        end: Uuid,
+        radius: f32,
+        direction: ArcDirection,
+    ) -> Uuid {
+        self.add_entity(SketchEntity::arc_with_direction(
+            center, start, end, radius, direction,
+        ))
     }
 
     /// Create a rectangle and return the corner point IDs and line IDs
@@ -543,6 +815,31 @@ mod tests {
         assert!((back - point_2d).length() < 0.001);
     }
 
+    #[test]
+    fn test_sketch_plane_flip() {
+        let original = SketchPlane::new(
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        );
+        let mut plane = original;
+        plane.flip();
+
+        assert!((plane.normal + original.normal).length() < 1e-6);
+        assert_eq!(plane.origin, original.origin);
+        let (x, y, n) = (plane.x_axis, plane.y_axis(), plane.normal);
+        for axis in [x, y, n] {
+            assert!((axis.length() - 1.0).abs() < 1e-6);
+        }
+        assert!(x.dot(y).abs() < 1e-6 && y.dot(n).abs() < 1e-6 && n.dot(x).abs() < 1e-6);
+        // Right-handed, and the rotation stays a proper rotation
+        assert!((x.cross(y) - n).length() < 1e-6);
+        assert!((plane.transform().determinant() - 1.0).abs() < 1e-5);
+
+        plane.flip();
+        assert!((plane.normal - original.normal).length() < 1e-6);
+    }
+
     #[test]
     fn test_add_rectangle() {
         let mut sketch = Sketch::default();
@@ -552,4 +849,237 @@ mod tests {
         assert_eq!(lines.len(), 4);
         assert_eq!(sketch.entities().len(), 8); // 4 points + 4 lines
     }
+
+    #[test]
+    fn test_extract_profile_bridges_small_gap() {
+        // Triangle whose closing line stops 1e-4 short of the first corner
+        let mut sketch = Sketch::default();
+        let p0 = sketch.add_point(Vec2::new(0.0, 0.0));
+        let p1 = sketch.add_point(Vec2::new(1.0, 0.0));
+        let p2 = sketch.add_point(Vec2::new(0.0, 1.0));
+        let near_p0 = sketch.add_point(Vec2::new(1e-4, 0.0));
+        sketch.add_line(p0, p1);
+        sketch.add_line(p1, p2);
+        sketch.add_line(p2, near_p0);
+
+        assert!(sketch.extract_profiles().is_err());
+        assert!(sketch.extract_profiles_with_tolerance(1e-5).is_err());
+
+        let profiles = sketch.extract_profiles_with_tolerance(1e-3).unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].points.len(), 3);
+        assert!(profiles[0].closed);
+
+        let added = sketch.close_gaps(1e-3).unwrap();
+        assert_eq!(added.len(), 1);
+        assert!(matches!(
+            sketch.get_constraint(added[0]),
+            Some(SketchConstraint::Coincident { point1, point2, .. })
+                if [*point1, *point2].contains(&p0) && [*point1, *point2].contains(&near_p0)
+        ));
+    }
+
+    #[test]
+    fn test_extract_profile_merges_endpoint_chains() {
+        // The triangle's closing corner is bridged only through the end of a
+        // dangling line: a-b and b-c are within tolerance, a-c is not
+        let mut sketch = Sketch::default();
+        let a = sketch.add_point(Vec2::new(0.0, 0.0));
+        let b = sketch.add_point(Vec2::new(0.8e-3, 0.0));
+        let c = sketch.add_point(Vec2::new(1.6e-3, 0.0));
+        let p1 = sketch.add_point(Vec2::new(1.0, 0.0));
+        let p2 = sketch.add_point(Vec2::new(0.0, 1.0));
+        let far = sketch.add_point(Vec2::new(-1.0, -1.0));
+        sketch.add_line(a, p1);
+        sketch.add_line(p1, p2);
+        sketch.add_line(p2, c);
+        sketch.add_line(b, far);
+
+        assert_eq!(sketch.endpoint_gaps(1e-3).len(), 2);
+        let profiles = sketch.extract_profiles_with_tolerance(1e-3).unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].points.len(), 3);
+    }
+
+    /// Build a "D" shape: a line closed by an arc through the given direction
+    fn arc_profile(direction: ArcDirection) -> Sketch {
+        let mut sketch = Sketch::default();
+        let center = sketch.add_point(Vec2::ZERO);
+        let a = sketch.add_point(Vec2::new(1.0, 0.0));
+        let b = sketch.add_point(Vec2::new(0.0, -1.0));
+        sketch.set_construction(center, true);
+        sketch.add_arc_with_direction(center, a, b, 1.0, direction);
+        sketch.add_line(b, a);
+        sketch
+    }
+
+    /// Signed area of a closed polygon (positive when counter-clockwise)
+    fn signed_area(points: &[Vec2]) -> f32 {
+        let n = points.len();
+        (0..n)
+            .map(|i| points[i].perp_dot(points[(i + 1) % n]))
+            .sum::<f32>()
+            / 2.0
+    }
+
+    #[test]
+    fn test_extract_profile_reflex_arc() {
+        use std::f32::consts::PI;
+
+        let profiles = arc_profile(ArcDirection::CounterClockwise)
+            .extract_profiles()
+            .unwrap();
+        assert_eq!(profiles.len(), 1);
+
+        // 270° sector of the unit circle plus the triangle back to the chord
+        let area = signed_area(&profiles[0].points);
+        let expected = 0.75 * PI + 0.5;
+        assert!((area - expected).abs() < 0.05, "area = {}", area);
+        assert!(profiles[0].points.iter().any(|p| p.y > 0.99));
+    }
+
+    #[test]
+    fn test_extract_profile_clockwise_arc() {
+        use std::f32::consts::PI;
+
+        let profiles = arc_profile(ArcDirection::Clockwise)
+            .extract_profiles()
+            .unwrap();
+        assert_eq!(profiles.len(), 1);
+
+        // 90° sector traversed clockwise, minus the triangle to the chord
+        let area = signed_area(&profiles[0].points);
+        let expected = -(0.25 * PI - 0.5);
+        assert!((area - expected).abs() < 0.05, "area = {}", area);
+        assert!(profiles[0].points.iter().all(|p| p.y <= 1e-4));
+    }
+
+    #[test]
+    fn test_constraint_add_list_remove() {
+        let mut sketch = Sketch::default();
+        let (points, lines) = sketch.add_rectangle(Vec2::new(0.0, 0.0), Vec2::new(10.0, 5.0));
+
+        let c1 = sketch
+            .add_constraint(SketchConstraint::horizontal(lines[0]))
+            .unwrap();
+        let c2 = sketch
+            .add_constraint(SketchConstraint::fixed(points[0], 0.0, 0.0))
+            .unwrap();
+        let c3 = sketch
+            .add_constraint(SketchConstraint::vertical(lines[1]))
+            .unwrap();
+
+        let ids: Vec<Uuid> = sketch.constraints().iter().map(|c| c.id()).collect();
+        assert_eq!(ids, vec![c1, c2, c3]);
+
+        let removed = sketch.remove_constraint(c2).unwrap();
+        assert_eq!(removed.id(), c2);
+        let ids: Vec<Uuid> = sketch.constraints().iter().map(|c| c.id()).collect();
+        assert_eq!(ids, vec![c1, c3]);
+
+        assert!(sketch.remove_constraint(c2).is_none());
+        assert!(sketch.get_constraint(c1).is_some());
+    }
+
+    #[test]
+    fn test_remove_entity_removes_constraints() {
+        let mut sketch = Sketch::default();
+        let p1 = sketch.add_point(Vec2::ZERO);
+        let p2 = sketch.add_point(Vec2::new(1.0, 0.0));
+        let line = sketch.add_line(p1, p2);
+        sketch
+            .add_constraint(SketchConstraint::horizontal(line))
+            .unwrap();
+        let fixed = sketch
+            .add_constraint(SketchConstraint::fixed(p1, 0.0, 0.0))
+            .unwrap();
+
+        sketch.remove_entity(line);
+        let ids: Vec<Uuid> = sketch.constraints().iter().map(|c| c.id()).collect();
+        assert_eq!(ids, vec![fixed]);
+    }
+
+    #[test]
+    fn test_toggle_fixed_adds_then_removes() {
+        let mut sketch = Sketch::default();
+        let p = sketch.add_point(Vec2::new(3.0, 4.0));
+
+        assert!(sketch.toggle_fixed(p).unwrap());
+        assert!(sketch.is_point_fixed(p));
+        assert_eq!(sketch.constraints().len(), 1);
+        match &sketch.constraints()[0] {
+            SketchConstraint::Fixed { point, x, y, .. } => {
+                assert_eq!(*point, p);
+                assert_eq!((*x, *y), (3.0, 4.0));
+            }
+            other => panic!("Expected fixed constraint, got {:?}", other),
+        }
+
+        assert!(!sketch.toggle_fixed(p).unwrap());
+        assert!(!sketch.is_point_fixed(p));
+        assert!(sketch.constraints().is_empty());
+    }
+
+    #[test]
+    fn test_toggle_fixed_rejects_non_point() {
+        let mut sketch = Sketch::default();
+        let p1 = sketch.add_point(Vec2::ZERO);
+        let p2 = sketch.add_point(Vec2::X);
+        let line = sketch.add_line(p1, p2);
+
+        assert!(sketch.toggle_fixed(line).is_err());
+        assert!(sketch.constraints().is_empty());
+    }
+
+    #[test]
+    fn test_remove_constraint_increases_dof() {
+        let mut sketch = Sketch::default();
+        let p1 = sketch.add_point(Vec2::ZERO);
+        let p2 = sketch.add_point(Vec2::new(10.0, 0.5));
+        let line = sketch.add_line(p1, p2);
+        sketch
+            .add_constraint(SketchConstraint::fixed(p1, 0.0, 0.0))
+            .unwrap();
+        let horizontal = sketch
+            .add_constraint(SketchConstraint::horizontal(line))
+            .unwrap();
+
+        sketch.solve();
+        let constrained_dof = sketch.degrees_of_freedom();
+
+        // Looking up a missing constraint doesn't invalidate the solve
+        assert!(sketch.get_constraint_mut(Uuid::new_v4()).is_none());
+        assert!(sketch.is_solved());
+
+        sketch.remove_constraint(horizontal);
+        assert!(!sketch.is_solved());
+        sketch.solve();
+        assert!(sketch.degrees_of_freedom() > constrained_dof);
+    }
+
+    #[test]
+    fn test_bounds_2d() {
+        let mut sketch = Sketch::new("bounds", SketchPlane::xy());
+        let (min, max) = sketch.bounds_2d();
+        assert_eq!(min, Vec2::splat(-EMPTY_SKETCH_HALF_EXTENT));
+        assert_eq!(max, Vec2::splat(EMPTY_SKETCH_HALF_EXTENT));
+
+        sketch.add_rectangle(Vec2::new(-1.0, 0.5), Vec2::new(2.0, 1.5));
+        let center = sketch.add_point(Vec2::new(3.0, 0.0));
+        sketch.add_circle(center, 0.5);
+        // Quarter arc from +X to +Y around (0, -2) reaches neither -X nor -Y
+        let arc_center = sketch.add_point(Vec2::new(0.0, -2.0));
+        let arc_start = sketch.add_point(Vec2::new(1.0, -2.0));
+        let arc_end = sketch.add_point(Vec2::new(0.0, -1.0));
+        sketch.add_arc(arc_center, arc_start, arc_end, 1.0);
+
+        let (min, max) = sketch.bounds_2d();
+        assert!((min - Vec2::new(-1.0, -2.0)).length() < 1e-6);
+        assert!((max - Vec2::new(3.5, 1.5)).length() < 1e-6);
+
+        // The clockwise arc between the same points sweeps through -X and -Y
+        sketch.add_arc_with_direction(arc_center, arc_start, arc_end, 1.0, ArcDirection::Clockwise);
+        let (min, _) = sketch.bounds_2d();
+        assert!((min - Vec2::new(-1.0, -3.0)).length() < 1e-6);
+    }
 }
@@ -0,0 +1,340 @@
+//! Self-collision checks between link geometry
+//!
+//! Each link's part is approximated by its bounding box, placed at the pose
+//! given by forward kinematics. Links joined directly by a joint are expected
+//! to touch and are never reported.
+
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
+
+use glam::{Mat4, Vec3};
+use uuid::Uuid;
+
+use crate::part::Part;
+use crate::types::JointType;
+
+use super::Assembly;
+
+/// Boxes must overlap by more than this along every axis to collide
+const PENETRATION_TOLERANCE: f32 = 1e-5;
+
+/// Step of the coarse sweep when searching for joint limits (radians)
+const LIMIT_SWEEP_STEP: f32 = PI / 36.0;
+
+/// Width of the bracket at which the limit bisection stops (radians)
+const LIMIT_TOLERANCE: f32 = 1e-4;
+
+/// Oriented bounding box in world space
+#[derive(Debug, Clone, Copy)]
+struct Obb {
+    center: Vec3,
+    axes: [Vec3; 3],
+    half_extents: Vec3,
+}
+
+impl Obb {
+    /// Place a part's local bounding box with `transform`
+    fn from_part(part: &Part, transform: Mat4) -> Self {
+        let min = Vec3::from_array(part.bbox_min);
+        let max = Vec3::from_array(part.bbox_max);
+        let (scale, rotation, _) = transform.to_scale_rotation_translation();
+        Self {
+            center: transform.transform_point3((min + max) * 0.5),
+            axes: [rotation * Vec3::X, rotation * Vec3::Y, rotation * Vec3::Z],
+            half_extents: (max - min) * 0.5 * scale.abs(),
+        }
+    }
+
+    /// Projected radius of the box onto `axis`
+    fn radius_along(&self, axis: Vec3) -> f32 {
+        (0..3)
+            .map(|i| self.half_extents[i] * self.axes[i].dot(axis).abs())
+            .sum()
+    }
+
+    /// Separating axis test over the 15 candidate axes
+    fn intersects(&self, other: &Obb) -> bool {
+        let offset = other.center - self.center;
+        let mut candidates: Vec<Vec3> = Vec::with_capacity(15);
+        candidates.extend(self.axes);
+        candidates.extend(other.axes);
+        for a in self.axes {
+            for b in other.axes {
+                let cross = a.cross(b);
+                // Parallel edges are already covered by the face axes
+                if cross.length_squared() > 1e-10 {
+                    candidates.push(cross.normalize());
+                }
+            }
+        }
+
+        candidates.into_iter().all(|axis| {
+            let distance = offset.dot(axis).abs();
+            distance + PENETRATION_TOLERANCE < self.radius_along(axis) + other.radius_along(axis)
+        })
+    }
+}
+
+impl Assembly {
+    /// Find pairs of links whose parts intersect at the given joint positions
+    ///
+    /// `Part::origin_transform` is taken as the part's placement at the zero
+    /// pose; it follows its link as joints move. Links without a part or with
+    /// an empty mesh are ignored, as are parent/child pairs.
+    pub fn self_collisions(
+        &self,
+        parts: &HashMap<Uuid, Part>,
+        joint_positions: &HashMap<Uuid, f32>,
+    ) -> Vec<(Uuid, Uuid)> {
+        let rest = self.forward_kinematics(&HashMap::new());
+        let posed = self.forward_kinematics(joint_positions);
+
+        let mut boxes: Vec<(Uuid, Obb)> = self
+            .links
+            .iter()
+            .filter_map(|(link_id, link)| {
+                let part = parts.get(&link.part_id?)?;
+                if part.vertices.is_empty() {
+                    return None;
+                }
+                let motion = *posed.get(link_id)? * rest.get(link_id)?.inverse();
+                Some((
+                    *link_id,
+                    Obb::from_part(part, motion * part.origin_transform),
+                ))
+            })
+            .collect();
+        // Stable output order regardless of HashMap iteration
+        boxes.sort_by_key(|(id, _)| *id);
+
+        let mut collisions = Vec::new();
+        for (i, (a_id, a)) in boxes.iter().enumerate() {
+            for (b_id, b) in &boxes[i + 1..] {
+                if !self.are_adjacent(*a_id, *b_id) && a.intersects(b) {
+                    collisions.push((*a_id, *b_id));
+                }
+            }
+        }
+        collisions
+    }
+
+    /// Check that the pose given by `joint_positions` is free of self-collision
+    pub fn is_pose_valid(
+        &self,
+        parts: &HashMap<Uuid, Part>,
+        joint_positions: &HashMap<Uuid, f32>,
+    ) -> bool {
+        self.self_collisions(parts, joint_positions).is_empty()
+    }
+
+    /// Suggest revolute limits for a joint from where its child subtree collides
+    ///
+    /// Sweeps the joint from its position `start` in `joint_positions` (other
+    /// joints held there too) up to half a turn each way, then bisects the
+    /// first colliding step. Only collisions between the moving subtree and the
+    /// rest of the robot count. Returns `(lower, upper)` as the last
+    /// collision-free angles, or `start - π` / `start + π` where nothing is
+    /// hit. `None` if the joint is not revolute or the starting pose already
+    /// collides; continuous joints have no limits to find.
+    pub fn find_joint_limits(
+        &self,
+        parts: &HashMap<Uuid, Part>,
+        joint_id: Uuid,
+        joint_positions: &HashMap<Uuid, f32>,
+    ) -> Option<(f32, f32)> {
+        let joint = self.joints.get(&joint_id)?;
+        if joint.joint_type != JointType::Revolute {
+            return None;
+        }
+
+        let moving: HashSet<Uuid> = std::iter::once(joint.child_link)
+            .chain(self.get_all_descendants(joint.child_link))
+            .collect();
+        let mut positions = joint_positions.clone();
+        let mut collides = |angle: f32| {
+            positions.insert(joint_id, angle);
+            self.self_collisions(parts, &positions)
+                .iter()
+                .any(|(a, b)| moving.contains(a) != moving.contains(b))
+        };
+
+        let start = joint_positions.get(&joint_id).copied().unwrap_or(0.0);
+        if collides(start) {
+            return None;
+        }
+
+        let mut sweep = |direction: f32| {
+            let end = start + direction * PI;
+            let mut free = start;
+            loop {
+                let next = if direction > 0.0 {
+                    (free + LIMIT_SWEEP_STEP).min(end)
+                } else {
+                    (free - LIMIT_SWEEP_STEP).max(end)
+                };
+                if collides(next) {
+                    let mut hit = next;
+                    while (hit - free).abs() > LIMIT_TOLERANCE {
+                        let mid = 0.5 * (free + hit);
+                        if collides(mid) {
+                            hit = mid;
+                        } else {
+                            free = mid;
+                        }
+                    }
+                    return free;
+                }
+                if next == end {
+                    return end;
+                }
+                free = next;
+            }
+        };
+
+        let lower = sweep(-1.0);
+        let upper = sweep(1.0);
+        Some((lower, upper))
+    }
+
+    /// Whether two links are connected directly by a joint
+    fn are_adjacent(&self, a: Uuid, b: Uuid) -> bool {
+        let parent_of = |id| self.parent.get(&id).map(|(_, parent)| *parent);
+        parent_of(a) == Some(b) || parent_of(b) == Some(a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+    use crate::assembly::{Joint, Link};
+    use crate::primitive::PrimitiveShape;
+    use crate::types::{JointLimits, Pose};
+
+    fn box_part(name: &str, center: Vec3) -> Part {
+        sized_box_part(name, center, 0.2)
+    }
+
+    fn sized_box_part(name: &str, center: Vec3, size: f32) -> Part {
+        let mut part = Part::from_primitive(name, PrimitiveShape::Box { size: [size; 3] });
+        part.origin_transform = Mat4::from_translation(center);
+        part
+    }
+
+    /// base at the origin, an elbow at x=0.5 and a tip box at x=1.0.
+    /// Turning the elbow by pi swings the tip onto the base.
+    fn folding_arm() -> (Assembly, HashMap<Uuid, Part>, Uuid) {
+        let mut assembly = Assembly::new("arm");
+        let mut parts = HashMap::new();
+
+        let base_part = box_part("base", Vec3::ZERO);
+        let tip_part = box_part("tip", Vec3::new(1.0, 0.0, 0.0));
+        let base = assembly.add_link(Link::from_part(&base_part));
+        let elbow = assembly.add_link(Link::empty("elbow"));
+        let tip = assembly.add_link(Link::from_part(&tip_part));
+        parts.insert(base_part.id, base_part);
+        parts.insert(tip_part.id, tip_part);
+
+        let half_step = Pose {
+            xyz: [0.5, 0.0, 0.0],
+            ..Default::default()
+        };
+        let hinge = Joint::revolute(
+            "hinge",
+            base,
+            elbow,
+            half_step,
+            Vec3::Z,
+            JointLimits::default_revolute(),
+        );
+        let hinge = assembly.connect(base, elbow, hinge).unwrap();
+        let mount = Joint::fixed("mount", elbow, tip, half_step);
+        assembly.connect(elbow, tip, mount).unwrap();
+        assembly.update_world_transforms();
+
+        (assembly, parts, hinge)
+    }
+
+    #[test]
+    fn test_folded_pose_collides() {
+        let (assembly, parts, hinge) = folding_arm();
+
+        assert!(assembly.is_pose_valid(&parts, &HashMap::new()));
+        assert!(assembly.is_pose_valid(&parts, &HashMap::from([(hinge, PI / 2.0)])));
+
+        let folded = HashMap::from([(hinge, PI)]);
+        assert!(!assembly.is_pose_valid(&parts, &folded));
+        assert_eq!(assembly.self_collisions(&parts, &folded).len(), 1);
+    }
+
+    #[test]
+    fn test_adjacent_links_are_ignored() {
+        let mut assembly = Assembly::new("pair");
+        let a_part = box_part("a", Vec3::ZERO);
+        let b_part = box_part("b", Vec3::new(0.05, 0.0, 0.0));
+        let a = assembly.add_link(Link::from_part(&a_part));
+        let b = assembly.add_link(Link::from_part(&b_part));
+        let parts = HashMap::from([(a_part.id, a_part), (b_part.id, b_part)]);
+
+        // Overlapping but unconnected: reported
+        assert!(!assembly.is_pose_valid(&parts, &HashMap::new()));
+
+        let joint = Joint::fixed("joint", a, b, Pose::default());
+        assembly.connect(a, b, joint).unwrap();
+        assert!(assembly.is_pose_valid(&parts, &HashMap::new()));
+    }
+
+    #[test]
+    fn test_find_joint_limits_matches_contact_angle() {
+        // A near-point tip on a 0.5 radius swing around x=0.5 enters the
+        // 0.2 base box through its y=+/-0.1 faces at pi - asin(0.2)
+        let mut assembly = Assembly::new("arm");
+        let base_part = box_part("base", Vec3::ZERO);
+        let tip_part = sized_box_part("tip", Vec3::new(1.0, 0.0, 0.0), 0.002);
+        let base = assembly.add_link(Link::from_part(&base_part));
+        let elbow = assembly.add_link(Link::empty("elbow"));
+        let tip = assembly.add_link(Link::from_part(&tip_part));
+        let parts = HashMap::from([(base_part.id, base_part), (tip_part.id, tip_part)]);
+
+        let half_step = Pose {
+            xyz: [0.5, 0.0, 0.0],
+            ..Default::default()
+        };
+        let hinge = Joint::revolute(
+            "hinge",
+            base,
+            elbow,
+            half_step,
+            Vec3::Z,
+            JointLimits::default_revolute(),
+        );
+        let hinge = assembly.connect(base, elbow, hinge).unwrap();
+        assembly
+            .connect(elbow, tip, Joint::fixed("mount", elbow, tip, half_step))
+            .unwrap();
+
+        let (lower, upper) = assembly
+            .find_joint_limits(&parts, hinge, &HashMap::new())
+            .unwrap();
+        let contact = PI - 0.2f32.asin();
+        assert!((upper - contact).abs() < 0.01, "upper {upper}");
+        assert!((lower + contact).abs() < 0.01, "lower {lower}");
+
+        // Nothing else moves with a fixed joint
+        let mount = assembly.get_parent_joint(tip).unwrap().id;
+        assert!(
+            assembly
+                .find_joint_limits(&parts, mount, &HashMap::new())
+                .is_none()
+        );
+
+        // Continuous joints don't get limits
+        assembly.get_joint_mut(hinge).unwrap().joint_type = JointType::Continuous;
+        assert!(
+            assembly
+                .find_joint_limits(&parts, hinge, &HashMap::new())
+                .is_none()
+        );
+    }
+}
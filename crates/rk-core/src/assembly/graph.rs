@@ -3,11 +3,19 @@
 use uuid::Uuid;
 
 use super::{Assembly, AssemblyError, Joint};
+use crate::naming::unique_name;
 
 impl Assembly {
     /// Add a link to the assembly (does not automatically set as root)
-    pub fn add_link(&mut self, link: super::Link) -> Uuid {
+    ///
+    /// A name already used by another link is suffixed to keep names unique.
+    pub fn add_link(&mut self, mut link: super::Link) -> Uuid {
         let id = link.id;
+        link.name = unique_name(&link.name, |name| {
+            self.link_name_index
+                .get(name)
+                .is_some_and(|&other| other != id)
+        });
         self.link_name_index.insert(link.name.clone(), id);
         self.links.insert(id, link);
         self.invalidate_cache();
@@ -56,11 +64,13 @@ impl Assembly {
     }
 
     /// Connect two links with a joint
+    ///
+    /// A joint name already in use is suffixed to keep names unique.
     pub fn connect(
         &mut self,
         parent_id: Uuid,
         child_id: Uuid,
-        joint: Joint,
+        mut joint: Joint,
     ) -> Result<Uuid, AssemblyError> {
         // Validate links exist
         if !self.links.contains_key(&parent_id) {
@@ -80,6 +90,7 @@ impl Assembly {
             return Err(AssemblyError::AlreadyHasParent(child_id));
         }
 
+        joint.name = self.unique_joint_name(&joint.name);
         let joint_id = joint.id;
 
         // Add joint and update name index
@@ -120,6 +131,46 @@ impl Assembly {
         Ok(joint)
     }
 
+    /// Copy a link and all its descendants into a new assembly
+    ///
+    /// The given link becomes the sole root with an identity world transform;
+    /// the joint connecting it to its former parent is dropped. Link and joint
+    /// IDs are preserved, along with the positions of the copied joints.
+    pub fn extract_subtree(&self, root_id: Uuid) -> Result<Assembly, AssemblyError> {
+        let root = self
+            .links
+            .get(&root_id)
+            .ok_or(AssemblyError::LinkNotFound(root_id))?;
+
+        let mut subtree = Assembly::new(self.name.clone());
+        let mut root = root.clone();
+        root.world_transform = glam::Mat4::IDENTITY;
+        subtree.add_link(root);
+
+        let mut queue = vec![root_id];
+        while let Some(parent_id) = queue.pop() {
+            for (joint_id, child_id) in self.get_children(parent_id) {
+                let child = self
+                    .links
+                    .get(&child_id)
+                    .ok_or(AssemblyError::LinkNotFound(child_id))?;
+                let joint = self
+                    .joints
+                    .get(&joint_id)
+                    .ok_or(AssemblyError::JointNotFound(joint_id))?;
+
+                subtree.add_link(child.clone());
+                subtree.connect(parent_id, child_id, joint.clone())?;
+                if let Some(position) = self.joint_positions.get(&joint_id) {
+                    subtree.set_joint_position(joint_id, *position);
+                }
+                queue.push(child_id);
+            }
+        }
+
+        Ok(subtree)
+    }
+
     /// Check if connecting parent to child would create a cycle
     pub(crate) fn would_create_cycle(&self, parent_id: Uuid, child_id: Uuid) -> bool {
         // Check if child is an ancestor of parent
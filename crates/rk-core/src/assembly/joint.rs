@@ -1,10 +1,15 @@
 //! Joint types and builder for robot assembly
 
+use std::f32::consts::{PI, TAU};
+
 use glam::Vec3;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::types::{JointDynamics, JointLimits, JointMimic, JointType, Pose};
+use crate::types::{
+    JointCalibration, JointDynamics, JointLimits, JointMimic, JointSafetyController, JointType,
+    Pose,
+};
 
 /// A joint connecting two links
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +31,12 @@ pub struct Joint {
     pub dynamics: Option<JointDynamics>,
     /// Joint mimic configuration (follows another joint)
     pub mimic: Option<JointMimic>,
+    /// Safety controller soft limits
+    #[serde(default)]
+    pub safety_controller: Option<JointSafetyController>,
+    /// Calibration reference positions
+    #[serde(default)]
+    pub calibration: Option<JointCalibration>,
 }
 
 impl Joint {
@@ -42,6 +53,8 @@ impl Joint {
             limits: None,
             dynamics: None,
             mimic: None,
+            safety_controller: None,
+            calibration: None,
         }
     }
 
@@ -65,9 +78,24 @@ impl Joint {
             limits: Some(limits),
             dynamics: None,
             mimic: None,
+            safety_controller: None,
+            calibration: None,
         }
     }
 
+    /// Set damping and friction, dropping the dynamics when both are zero
+    pub fn set_dynamics(&mut self, damping: f32, friction: f32) {
+        self.dynamics =
+            (damping != 0.0 || friction != 0.0).then_some(JointDynamics { damping, friction });
+    }
+
+    /// Wrap a continuous joint position into (-pi, pi] for display
+    ///
+    /// Forward kinematics keeps using the raw, unbounded position.
+    pub fn normalize_continuous(position: f32) -> f32 {
+        PI - (PI - position).rem_euclid(TAU)
+    }
+
     /// Create a builder for constructing joints with fluent API
     pub fn builder(name: impl Into<String>, parent: Uuid, child: Uuid) -> JointBuilder {
         JointBuilder::new(name, parent, child)
@@ -86,6 +114,8 @@ pub struct JointBuilder {
     limits: Option<JointLimits>,
     dynamics: Option<JointDynamics>,
     mimic: Option<JointMimic>,
+    safety_controller: Option<JointSafetyController>,
+    calibration: Option<JointCalibration>,
 }
 
 impl JointBuilder {
@@ -101,6 +131,8 @@ impl JointBuilder {
             limits: None,
             dynamics: None,
             mimic: None,
+            safety_controller: None,
+            calibration: None,
         }
     }
 
@@ -200,6 +232,18 @@ impl JointBuilder {
         self
     }
 
+    /// Set the safety controller soft limits
+    pub fn safety_controller(mut self, safety_controller: JointSafetyController) -> Self {
+        self.safety_controller = Some(safety_controller);
+        self
+    }
+
+    /// Set the calibration reference positions
+    pub fn calibration(mut self, calibration: JointCalibration) -> Self {
+        self.calibration = Some(calibration);
+        self
+    }
+
     /// Build the joint
     pub fn build(self) -> Joint {
         Joint {
@@ -213,6 +257,8 @@ impl JointBuilder {
             limits: self.limits,
             dynamics: self.dynamics,
             mimic: self.mimic,
+            safety_controller: self.safety_controller,
+            calibration: self.calibration,
         }
     }
 }
@@ -1,5 +1,6 @@
 //! Assembly (scene graph) for robot structure
 
+mod collision;
 mod graph;
 mod joint;
 mod queries;
@@ -139,6 +140,15 @@ impl Assembly {
         self.cache.borrow_mut().invalidate();
     }
 
+    /// Invalidate cached world transforms (call after changing a joint origin)
+    ///
+    /// Structural changes, the joint position setters and [`Assembly::get_joint_mut`]
+    /// invalidate them automatically; only direct edits through the public `joints`
+    /// or `joint_positions` maps need this.
+    pub fn invalidate_world_transforms(&self) {
+        self.cache.borrow_mut().invalidate_transforms();
+    }
+
     /// Ensure the cache is valid, rebuilding if necessary
     pub(crate) fn ensure_cache_valid(&self) {
         let mut cache = self.cache.borrow_mut();
@@ -197,6 +207,7 @@ impl Assembly {
     /// Set a joint position (in radians for revolute, meters for prismatic)
     pub fn set_joint_position(&mut self, joint_id: Uuid, position: f32) {
         self.joint_positions.insert(joint_id, position);
+        self.invalidate_world_transforms();
     }
 
     /// Get a joint position (defaults to 0.0)
@@ -207,11 +218,13 @@ impl Assembly {
     /// Reset a joint position to 0
     pub fn reset_joint_position(&mut self, joint_id: Uuid) {
         self.joint_positions.remove(&joint_id);
+        self.invalidate_world_transforms();
     }
 
     /// Reset all joint positions to 0
     pub fn reset_all_joint_positions(&mut self) {
         self.joint_positions.clear();
+        self.invalidate_world_transforms();
     }
 }
 
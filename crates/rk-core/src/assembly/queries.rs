@@ -2,6 +2,8 @@
 
 use uuid::Uuid;
 
+use crate::naming::unique_name;
+
 use super::Assembly;
 use super::joint::Joint;
 use super::types::Link;
@@ -33,6 +35,16 @@ impl Assembly {
         self.joint_name_index.get(name).copied()
     }
 
+    /// Return `base`, or `base_N` if a link already uses that name
+    pub fn unique_link_name(&self, base: &str) -> String {
+        unique_name(base, |name| self.link_name_index.contains_key(name))
+    }
+
+    /// Return `base`, or `base_N` if a joint already uses that name
+    pub fn unique_joint_name(&self, base: &str) -> String {
+        unique_name(base, |name| self.joint_name_index.contains_key(name))
+    }
+
     /// Find a link by its associated part ID
     pub fn find_link_by_part(&self, part_id: Uuid) -> Option<&Link> {
         self.links.values().find(|l| l.part_id == Some(part_id))
@@ -56,6 +68,36 @@ impl Assembly {
         }
     }
 
+    /// Get the path from a link back to its root, ordered child → … → root
+    ///
+    /// Walks the parent map directly, so the result is always consistent with
+    /// the current connections. Returns an empty vec for unknown links.
+    pub fn path_to_root(&self, link_id: Uuid) -> Vec<Uuid> {
+        if !self.links.contains_key(&link_id) {
+            return Vec::new();
+        }
+
+        let mut path = vec![link_id];
+        let mut current = link_id;
+        while let Some((_, parent_id)) = self.parent.get(&current) {
+            // Guard against malformed (cyclic) parent maps
+            if path.contains(parent_id) {
+                break;
+            }
+            path.push(*parent_id);
+            current = *parent_id;
+        }
+        path
+    }
+
+    /// Get the joints along [`Self::path_to_root`], ordered child → … → root
+    pub fn joint_path_to_root(&self, link_id: Uuid) -> Vec<Uuid> {
+        self.path_to_root(link_id)
+            .iter()
+            .filter_map(|id| self.parent.get(id).map(|(joint_id, _)| *joint_id))
+            .collect()
+    }
+
     /// Get the joint connecting a link to its parent
     pub fn get_parent_joint(&self, link_id: Uuid) -> Option<&Joint> {
         self.parent
@@ -152,7 +194,10 @@ impl Assembly {
     }
 
     /// Get a mutable joint by ID
+    ///
+    /// Invalidates cached world transforms, since the origin may be edited.
     pub fn get_joint_mut(&mut self, joint_id: Uuid) -> Option<&mut Joint> {
+        self.invalidate_world_transforms();
         self.joints.get_mut(&joint_id)
     }
 
@@ -237,3 +282,86 @@ impl Assembly {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Pose;
+
+    /// Build a linear chain base -> link1 -> ... -> link{n-1}, returning link IDs in order
+    fn build_chain(assembly: &mut Assembly, n: usize) -> Vec<Uuid> {
+        let ids: Vec<Uuid> = (0..n)
+            .map(|i| {
+                let name = if i == 0 {
+                    "base_link".to_string()
+                } else {
+                    format!("link{}", i)
+                };
+                assembly.add_link(Link::empty(name))
+            })
+            .collect();
+        for pair in ids.windows(2) {
+            let joint = Joint::fixed(
+                format!("joint_{}", pair[1]),
+                pair[0],
+                pair[1],
+                Pose::default(),
+            );
+            assembly.connect(pair[0], pair[1], joint).unwrap();
+        }
+        ids
+    }
+
+    #[test]
+    fn test_path_to_root_ordered_child_to_root() {
+        let mut assembly = Assembly::new("robot");
+        let ids = build_chain(&mut assembly, 4);
+
+        let path = assembly.path_to_root(ids[3]);
+        let expected: Vec<Uuid> = ids.iter().rev().copied().collect();
+        assert_eq!(path, expected);
+        assert_eq!(assembly.path_to_root(ids[0]), vec![ids[0]]);
+    }
+
+    #[test]
+    fn test_path_to_root_ignores_siblings() {
+        let mut assembly = Assembly::new("robot");
+        let ids = build_chain(&mut assembly, 3);
+        let sibling = assembly.add_link(Link::empty("sibling"));
+        let joint = Joint::fixed("sibling_joint", ids[1], sibling, Pose::default());
+        assembly.connect(ids[1], sibling, joint).unwrap();
+
+        assert_eq!(
+            assembly.path_to_root(sibling),
+            vec![sibling, ids[1], ids[0]]
+        );
+        assert_eq!(assembly.joint_path_to_root(sibling).len(), 2);
+    }
+
+    #[test]
+    fn test_duplicate_link_and_joint_names_are_suffixed() {
+        let mut assembly = Assembly::new("robot");
+        let a = assembly.add_link(Link::empty("link"));
+        let b = assembly.add_link(Link::empty("link"));
+        let c = assembly.add_link(Link::empty("link"));
+        assert_eq!(assembly.links[&a].name, "link");
+        assert_eq!(assembly.links[&b].name, "link_1");
+        assert_eq!(assembly.links[&c].name, "link_2");
+        assert_eq!(assembly.unique_link_name("link"), "link_3");
+        assert_eq!(assembly.unique_link_name("other"), "other");
+
+        let j1 = Joint::fixed("joint", a, b, Pose::default());
+        let j2 = Joint::fixed("joint", a, c, Pose::default());
+        let j1 = assembly.connect(a, b, j1).unwrap();
+        let j2 = assembly.connect(a, c, j2).unwrap();
+        assert_eq!(assembly.joints[&j1].name, "joint");
+        assert_eq!(assembly.joints[&j2].name, "joint_1");
+        assert_eq!(assembly.find_joint_id_by_name("joint_1"), Some(j2));
+    }
+
+    #[test]
+    fn test_path_to_root_unknown_link() {
+        let assembly = Assembly::new("robot");
+        assert!(assembly.path_to_root(Uuid::new_v4()).is_empty());
+    }
+}
@@ -15,15 +15,6 @@ trait JointTransformStrategy {
     fn compute(&self, joint_id: Uuid, joint: &Joint) -> Mat4;
 }
 
-/// No additional joint transform (origin only)
-struct NoJointTransform;
-
-impl JointTransformStrategy for NoJointTransform {
-    fn compute(&self, _joint_id: Uuid, _joint: &Joint) -> Mat4 {
-        Mat4::IDENTITY
-    }
-}
-
 /// Joint transform with positions from a HashMap
 struct WithPositions<'a> {
     positions: &'a HashMap<Uuid, f32>,
@@ -37,92 +28,98 @@ impl JointTransformStrategy for WithPositions<'_> {
 }
 
 impl Assembly {
-    /// Get the world transform of a link
+    /// Get the world transform of a link at the current joint positions
+    ///
+    /// Uses the cached transforms; see [`Assembly::cached_world_transform`].
     pub fn get_world_transform(&self, link_id: Uuid) -> Mat4 {
-        let mut transform = Mat4::IDENTITY;
-        let mut current = Some(link_id);
+        self.cached_world_transform(link_id)
+    }
 
-        // Build transform chain from root to link
-        let mut chain = Vec::new();
-        while let Some(id) = current {
-            chain.push(id);
-            current = self.parent.get(&id).map(|(_, p)| *p);
+    /// Get the world transform of a link from the transform cache
+    ///
+    /// Transforms for all links at the current joint positions are computed
+    /// in one pass on first use after a structural change, joint edit or
+    /// joint position change. Unknown links get the identity.
+    pub fn cached_world_transform(&self, link_id: Uuid) -> Mat4 {
+        self.refresh_transform_cache();
+        self.cache
+            .borrow()
+            .world_transforms
+            .get(&link_id)
+            .copied()
+            .unwrap_or(Mat4::IDENTITY)
+    }
+
+    /// Recompute the cached world transforms if they are stale
+    fn refresh_transform_cache(&self) {
+        if self.cache.borrow().transforms_valid {
+            return;
         }
+        let transforms = self.forward_kinematics(&self.joint_positions);
+        let mut cache = self.cache.borrow_mut();
+        cache.world_transforms = transforms;
+        cache.transforms_valid = true;
+    }
 
-        // Apply transforms from root to link
-        for id in chain.into_iter().rev() {
-            if let Some((joint_id, _)) = self.parent.get(&id)
-                && let Some(joint) = self.joints.get(joint_id)
-            {
-                transform *= joint.origin.to_mat4();
+    /// Compute link world transforms for the given joint positions
+    ///
+    /// Unlike the `update_world_transforms*` methods this leaves the assembly
+    /// untouched, so it can be used to evaluate hypothetical poses. Joints
+    /// missing from `joint_positions` are at zero.
+    pub fn forward_kinematics(&self, joint_positions: &HashMap<Uuid, f32>) -> HashMap<Uuid, Mat4> {
+        let strategy = WithPositions {
+            positions: joint_positions,
+        };
+        let mut transforms = HashMap::with_capacity(self.links.len());
+        let mut stack: Vec<(Uuid, Mat4)> = self
+            .get_root_links()
+            .into_iter()
+            .map(|id| (id, Mat4::IDENTITY))
+            .collect();
+        while let Some((id, transform)) = stack.pop() {
+            transforms.insert(id, transform);
+            for (joint_id, child_id) in self.children.get(&id).into_iter().flatten() {
+                let child_transform = match self.joints.get(joint_id) {
+                    Some(joint) => {
+                        transform * joint.origin.to_mat4() * strategy.compute(*joint_id, joint)
+                    }
+                    None => transform,
+                };
+                stack.push((*child_id, child_transform));
             }
         }
-
-        transform
+        transforms
     }
 
-    /// Update all world transforms
+    /// Update all world transforms (all joints at zero)
     pub fn update_world_transforms(&mut self) {
-        let roots = self.get_root_links();
-        for root_id in roots {
-            self.update_transform_recursive_impl(root_id, Mat4::IDENTITY, &NoJointTransform);
-        }
+        let transforms = self.forward_kinematics(&HashMap::new());
+        self.store_world_transforms(&transforms);
     }
 
     /// Update all world transforms with joint positions applied
     pub fn update_world_transforms_with_positions(&mut self, joint_positions: &HashMap<Uuid, f32>) {
-        let roots = self.get_root_links();
-        let strategy = WithPositions {
-            positions: joint_positions,
-        };
-        for root_id in roots {
-            self.update_transform_recursive_impl(root_id, Mat4::IDENTITY, &strategy);
-        }
+        let transforms = self.forward_kinematics(joint_positions);
+        self.store_world_transforms(&transforms);
     }
 
     /// Update all world transforms using internal joint positions
+    ///
+    /// Reads from the transform cache, so repeated calls without edits in
+    /// between do not walk the tree again.
     pub fn update_world_transforms_with_current_positions(&mut self) {
-        let roots = self.get_root_links();
-        let positions = self.joint_positions.clone();
-        let strategy = WithPositions {
-            positions: &positions,
-        };
-        for root_id in roots {
-            self.update_transform_recursive_impl(root_id, Mat4::IDENTITY, &strategy);
-        }
+        self.refresh_transform_cache();
+        let transforms = std::mem::take(&mut self.cache.borrow_mut().world_transforms);
+        self.store_world_transforms(&transforms);
+        self.cache.borrow_mut().world_transforms = transforms;
     }
 
-    /// Internal recursive transform update with strategy pattern
-    fn update_transform_recursive_impl<S: JointTransformStrategy>(
-        &mut self,
-        link_id: Uuid,
-        parent_transform: Mat4,
-        strategy: &S,
-    ) {
-        let transform = if let Some((joint_id, _)) = self.parent.get(&link_id) {
-            if let Some(joint) = self.joints.get(joint_id) {
-                let joint_transform = strategy.compute(*joint_id, joint);
-                parent_transform * joint.origin.to_mat4() * joint_transform
-            } else {
-                parent_transform
+    /// Write computed transforms into each link's `world_transform`
+    fn store_world_transforms(&mut self, transforms: &HashMap<Uuid, Mat4>) {
+        for (id, transform) in transforms {
+            if let Some(link) = self.links.get_mut(id) {
+                link.world_transform = *transform;
             }
-        } else {
-            parent_transform
-        };
-
-        if let Some(link) = self.links.get_mut(&link_id) {
-            link.world_transform = transform;
-        }
-
-        // Get children IDs first to avoid borrow issues
-        let children: Vec<Uuid> = self
-            .children
-            .get(&link_id)
-            .map(|c| c.iter().map(|(_, child_id)| *child_id).collect())
-            .unwrap_or_default();
-
-        for child_id in children {
-            self.update_transform_recursive_impl(child_id, transform, strategy);
         }
     }
 
@@ -150,3 +147,170 @@ impl Assembly {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembly::Link;
+    use crate::types::Pose;
+
+    /// Reference implementation: walk from the link to the root
+    fn compute_world_transform(assembly: &Assembly, link_id: Uuid) -> Mat4 {
+        let mut transform = Mat4::IDENTITY;
+        let mut current = Some(link_id);
+
+        // Build transform chain from root to link
+        let mut chain = Vec::new();
+        while let Some(id) = current {
+            chain.push(id);
+            current = assembly.parent.get(&id).map(|(_, p)| *p);
+        }
+
+        // Apply transforms from root to link
+        for id in chain.into_iter().rev() {
+            if let Some((joint_id, _)) = assembly.parent.get(&id)
+                && let Some(joint) = assembly.joints.get(joint_id)
+            {
+                transform *= joint.origin.to_mat4();
+            }
+        }
+
+        transform
+    }
+
+    fn assert_mat_eq(a: Mat4, b: Mat4) {
+        assert!(a.abs_diff_eq(b, 1e-5), "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_cached_world_transform_matches_recomputed() {
+        let mut assembly = Assembly::new("robot");
+        let base = assembly.add_link(Link::empty("base"));
+        let mut parent = base;
+        let mut links = vec![base];
+        for i in 0..4 {
+            let child = assembly.add_link(Link::empty(format!("link{i}")));
+            let origin = Pose::new([0.1 * i as f32, 0.2, 0.0], [0.0, 0.3, 0.1 * i as f32]);
+            let joint = Joint::fixed(format!("joint{i}"), parent, child, origin);
+            assembly.connect(parent, child, joint).unwrap();
+            links.push(child);
+            parent = child;
+        }
+        let branch = assembly.add_link(Link::empty("branch"));
+        let joint = Joint::fixed(
+            "branch_joint",
+            links[2],
+            branch,
+            Pose::from_position([0.0, 0.0, 1.0]),
+        );
+        assembly.connect(links[2], branch, joint).unwrap();
+        links.push(branch);
+
+        for id in &links {
+            assert_mat_eq(
+                assembly.cached_world_transform(*id),
+                compute_world_transform(&assembly, *id),
+            );
+        }
+        assert_eq!(
+            assembly.cached_world_transform(Uuid::new_v4()),
+            Mat4::IDENTITY
+        );
+    }
+
+    #[test]
+    fn test_cached_world_transform_invalidated_by_joint_edit() {
+        let mut assembly = Assembly::new("robot");
+        let base = assembly.add_link(Link::empty("base"));
+        let child = assembly.add_link(Link::empty("child"));
+        let grandchild = assembly.add_link(Link::empty("grandchild"));
+        let joint = Joint::fixed("j1", base, child, Pose::from_position([1.0, 0.0, 0.0]));
+        let joint_id = assembly.connect(base, child, joint).unwrap();
+        let joint = Joint::fixed(
+            "j2",
+            child,
+            grandchild,
+            Pose::from_position([0.0, 1.0, 0.0]),
+        );
+        assembly.connect(child, grandchild, joint).unwrap();
+
+        let before = assembly.cached_world_transform(grandchild);
+        assert_mat_eq(
+            before,
+            Mat4::from_translation(glam::Vec3::new(1.0, 1.0, 0.0)),
+        );
+
+        assembly.get_joint_mut(joint_id).unwrap().origin = Pose::from_position([2.0, 0.0, 0.0]);
+        let after = assembly.cached_world_transform(grandchild);
+        assert_mat_eq(
+            after,
+            Mat4::from_translation(glam::Vec3::new(2.0, 1.0, 0.0)),
+        );
+        assert_mat_eq(after, compute_world_transform(&assembly, grandchild));
+
+        // Structural changes invalidate as well
+        assembly.disconnect(grandchild).unwrap();
+        assert_mat_eq(assembly.cached_world_transform(grandchild), Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn test_cached_world_transform_follows_joint_positions() {
+        let mut assembly = Assembly::new("robot");
+        let base = assembly.add_link(Link::empty("base"));
+        let child = assembly.add_link(Link::empty("child"));
+        let tip = assembly.add_link(Link::empty("tip"));
+        let joint = Joint::revolute(
+            "j1",
+            base,
+            child,
+            Pose::default(),
+            glam::Vec3::Z,
+            Default::default(),
+        );
+        let joint_id = assembly.connect(base, child, joint).unwrap();
+        let joint = Joint::fixed("j2", child, tip, Pose::from_position([1.0, 0.0, 0.0]));
+        assembly.connect(child, tip, joint).unwrap();
+        assert_mat_eq(
+            assembly.cached_world_transform(tip),
+            Mat4::from_translation(glam::Vec3::X),
+        );
+
+        assembly.set_joint_position(joint_id, std::f32::consts::FRAC_PI_2);
+        let posed = assembly.forward_kinematics(&assembly.joint_positions.clone());
+        assert_mat_eq(assembly.cached_world_transform(tip), posed[&tip]);
+        assert!(
+            posed[&tip]
+                .w_axis
+                .truncate()
+                .abs_diff_eq(glam::Vec3::Y, 1e-5)
+        );
+
+        assembly.update_world_transforms_with_current_positions();
+        assert_mat_eq(assembly.links[&tip].world_transform, posed[&tip]);
+
+        assembly.reset_all_joint_positions();
+        assert_mat_eq(
+            assembly.cached_world_transform(tip),
+            Mat4::from_translation(glam::Vec3::X),
+        );
+    }
+
+    #[test]
+    fn test_continuous_wraps_for_display_only() {
+        use std::f32::consts::PI;
+
+        let wrapped = Joint::normalize_continuous(3.0 * PI);
+        assert!((wrapped - PI).abs() < 1e-5, "{wrapped}");
+        assert_eq!(Joint::normalize_continuous(-PI), PI);
+        assert!((Joint::normalize_continuous(-3.5 * PI) - 0.5 * PI).abs() < 1e-5);
+
+        // FK is continuous across the wrap and periodic in the raw value
+        let fk = |position| {
+            Assembly::compute_joint_transform(&JointType::Continuous, glam::Vec3::Z, position)
+        };
+        let eps = 1e-3;
+        assert!(fk(PI - eps).abs_diff_eq(fk(PI + eps), 1e-2));
+        assert_mat_eq(fk(3.0 * PI), fk(PI));
+        assert_mat_eq(fk(3.0 * PI), fk(Joint::normalize_continuous(3.0 * PI)));
+    }
+}
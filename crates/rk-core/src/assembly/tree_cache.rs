@@ -1,6 +1,8 @@
 //! Cached tree structure for efficient traversal
 
 use std::collections::HashMap;
+
+use glam::Mat4;
 use uuid::Uuid;
 
 use super::types::Link;
@@ -18,11 +20,21 @@ pub(super) struct TreeCache {
     pub roots: Vec<Uuid>,
     /// Whether cache is valid
     pub valid: bool,
+    /// World transform of each link at the current joint positions (filled lazily)
+    pub world_transforms: HashMap<Uuid, Mat4>,
+    /// Whether world transforms are valid
+    pub transforms_valid: bool,
 }
 
 impl TreeCache {
     pub fn invalidate(&mut self) {
         self.valid = false;
+        self.transforms_valid = false;
+    }
+
+    /// Invalidate only the world transforms (joint origins or positions changed)
+    pub fn invalidate_transforms(&mut self) {
+        self.transforms_valid = false;
     }
 
     pub fn rebuild(
@@ -70,6 +70,11 @@ impl Link {
             },
         }
     }
+
+    /// World transform of the frame the inertial properties are expressed in
+    pub fn inertial_world_transform(&self) -> Mat4 {
+        self.world_transform * self.inertial.origin.to_mat4()
+    }
 }
 
 /// Single visual element for a link
@@ -6,6 +6,12 @@ pub const STL_VERTEX_PRECISION: f32 = 10000.0;
 /// Default number of segments for cylinder mesh generation
 pub const CYLINDER_SEGMENTS: u32 = 32;
 
+/// Fewest segments a round primitive may be tessellated with
+pub const MIN_PRIMITIVE_SEGMENTS: u32 = 4;
+
+/// Most segments a round primitive may be tessellated with
+pub const MAX_PRIMITIVE_SEGMENTS: u32 = 256;
+
 /// Default number of latitude segments for sphere mesh generation
 pub const SPHERE_LAT_SEGMENTS: u32 = 16;
 
@@ -1,17 +1,23 @@
-//! URDF export functionality
+//! URDF and drawing export functionality
 
 mod options;
+mod svg;
+pub mod transform_convert;
 mod xml;
 
 use std::collections::HashMap;
+use std::path::Path;
 
 use uuid::Uuid;
 
 use crate::assembly::Assembly;
 use crate::mesh::save_stl;
 use crate::part::Part;
+use crate::project::Project;
 
 pub use options::ExportOptions;
+pub use svg::{parts_to_svg, silhouette_edges};
+pub use transform_convert::TargetConvention;
 pub use xml::{sanitize_filename, xml_escape};
 
 use xml::generate_urdf_string;
@@ -26,9 +32,111 @@ pub fn export_urdf(
     assembly
         .validate()
         .map_err(|errors| ExportError::Validation(format!("{:?}", errors)))?;
+    check_meshes(assembly, parts)?;
 
+    let mesh_paths = write_meshes(parts, &options.output_dir, options)?;
+
+    // Generate URDF string
+    let urdf = generate_urdf_string(assembly, parts, &mesh_paths, &options.robot_name)?;
+
+    // Write URDF file
+    let urdf_path = options
+        .output_dir
+        .join(format!("{}.urdf", options.robot_name));
+    std::fs::write(&urdf_path, &urdf).map_err(|e| ExportError::Io(e.to_string()))?;
+
+    Ok(urdf)
+}
+
+/// Export a link and its descendants as a standalone URDF (writes files to disk)
+///
+/// The selected link becomes the root of the exported robot with an identity
+/// transform. Only parts used by the subtree are exported; meshes are written
+/// next to `path` under `options.mesh_prefix`, and `options.output_dir` is
+/// ignored in favour of `path`.
+pub fn export_urdf_subtree(
+    project: &Project,
+    root_link: Uuid,
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<String, ExportError> {
+    let (subtree, parts) = subtree_with_parts(project, root_link)?;
+    subtree
+        .validate()
+        .map_err(|errors| ExportError::Validation(format!("{:?}", errors)))?;
+    check_meshes(&subtree, &parts)?;
+
+    let output_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mesh_paths = write_meshes(&parts, output_dir, options)?;
+
+    let urdf = generate_urdf_string(&subtree, &parts, &mesh_paths, &options.robot_name)?;
+    std::fs::write(path, &urdf).map_err(|e| ExportError::Io(e.to_string()))?;
+
+    Ok(urdf)
+}
+
+/// Fail with every link whose mesh geometry has no data to write
+///
+/// Mesh geometry on a link is written from its part's triangles, so a missing
+/// part or an empty mesh would produce a URDF referencing an empty file.
+fn check_meshes(assembly: &Assembly, parts: &HashMap<Uuid, Part>) -> Result<(), ExportError> {
+    let mut missing: Vec<String> = assembly
+        .links
+        .values()
+        .filter(|link| {
+            let Some(part_id) = link.part_id else {
+                return false;
+            };
+            let uses_mesh = link.visuals.iter().any(|v| v.geometry.is_mesh())
+                || link.collisions.iter().any(|c| c.geometry.is_mesh());
+            uses_mesh
+                && parts
+                    .get(&part_id)
+                    .is_none_or(|part| part.vertices.is_empty() || part.indices.len() < 3)
+        })
+        .map(|link| link.name.clone())
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+    missing.sort();
+    Err(ExportError::MissingMeshes(missing))
+}
+
+/// Build the re-rooted subtree assembly and the parts it references
+fn subtree_with_parts(
+    project: &Project,
+    root_link: Uuid,
+) -> Result<(Assembly, HashMap<Uuid, Part>), ExportError> {
+    let subtree = project
+        .assembly
+        .extract_subtree(root_link)
+        .map_err(|_| ExportError::LinkNotFound(root_link))?;
+
+    let parts = subtree
+        .links
+        .values()
+        .filter_map(|link| link.part_id)
+        .map(|part_id| {
+            project
+                .get_part(part_id)
+                .map(|part| (part_id, part.clone()))
+                .ok_or(ExportError::PartNotFound(part_id))
+        })
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    Ok((subtree, parts))
+}
+
+/// Write part meshes as STL under `output_dir/mesh_prefix`, returning mesh URIs
+fn write_meshes(
+    parts: &HashMap<Uuid, Part>,
+    output_dir: &Path,
+    options: &ExportOptions,
+) -> Result<HashMap<Uuid, String>, ExportError> {
     // Create mesh directory
-    let mesh_dir = options.output_dir.join(&options.mesh_prefix);
+    let mesh_dir = output_dir.join(&options.mesh_prefix);
     std::fs::create_dir_all(&mesh_dir).map_err(|e| ExportError::Io(e.to_string()))?;
 
     // Export meshes and collect paths
@@ -46,16 +154,7 @@ pub fn export_urdf(
         mesh_paths.insert(*part_id, uri);
     }
 
-    // Generate URDF string
-    let urdf = generate_urdf_string(assembly, parts, &mesh_paths, &options.robot_name)?;
-
-    // Write URDF file
-    let urdf_path = options
-        .output_dir
-        .join(format!("{}.urdf", options.robot_name));
-    std::fs::write(&urdf_path, &urdf).map_err(|e| ExportError::Io(e.to_string()))?;
-
-    Ok(urdf)
+    Ok(mesh_paths)
 }
 
 /// Export assembly to URDF string only (no file I/O, for WASM support)
@@ -69,6 +168,7 @@ pub fn export_urdf_to_string(
     assembly
         .validate()
         .map_err(|errors| ExportError::Validation(format!("{:?}", errors)))?;
+    check_meshes(assembly, parts)?;
 
     // Generate placeholder mesh paths
     let mut mesh_paths = HashMap::new();
@@ -98,4 +198,234 @@ pub enum ExportError {
     PartNotFound(Uuid),
     #[error("Mesh path not found for part: {0}")]
     MeshNotFound(Uuid),
+    #[error("Mesh data missing for links: {}", .0.join(", "))]
+    MissingMeshes(Vec<String>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembly::{Joint, Link};
+    use crate::primitive::PrimitiveShape;
+    use crate::types::Pose;
+
+    /// Build a 4-link serial robot with a box part on every link
+    fn four_link_robot() -> (Project, Vec<Uuid>) {
+        let mut project = Project::new("robot");
+        let mut links = Vec::new();
+        for i in 0..4 {
+            let part =
+                Part::from_primitive(format!("link{}", i), PrimitiveShape::Box { size: [0.1; 3] });
+            let link = Link::from_part(&part);
+            project.add_part(part);
+            links.push(project.assembly.add_link(link));
+        }
+        for (i, pair) in links.windows(2).enumerate() {
+            let joint = Joint::revolute(
+                format!("joint{}", i + 1),
+                pair[0],
+                pair[1],
+                Pose::default(),
+                glam::Vec3::Z,
+                crate::types::JointLimits::default_revolute(),
+            );
+            project.assembly.connect(pair[0], pair[1], joint).unwrap();
+        }
+        (project, links)
+    }
+
+    #[test]
+    fn test_export_subtree() {
+        let (project, links) = four_link_robot();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("arm.urdf");
+        let options = ExportOptions {
+            robot_name: "arm".to_string(),
+            ..Default::default()
+        };
+
+        let urdf = export_urdf_subtree(&project, links[2], &path, &options).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), urdf);
+
+        let robot = urdf_rs::read_from_string(&urdf).unwrap();
+        assert_eq!(robot.name, "arm");
+        let mut link_names: Vec<_> = robot.links.iter().map(|l| l.name.as_str()).collect();
+        link_names.sort();
+        assert_eq!(link_names, vec!["link2", "link3"]);
+        assert_eq!(robot.joints.len(), 1);
+        assert_eq!(robot.joints[0].name, "joint3");
+        assert_eq!(robot.joints[0].parent.link, "link2");
+        assert_eq!(robot.joints[0].child.link, "link3");
+
+        // Only the subtree's meshes are written
+        let mesh_count = std::fs::read_dir(dir.path().join("meshes"))
+            .unwrap()
+            .count();
+        assert_eq!(mesh_count, 2);
+    }
+
+    #[test]
+    fn test_export_subtree_unknown_link() {
+        let (project, _) = four_link_robot();
+        let dir = tempfile::tempdir().unwrap();
+        let result = export_urdf_subtree(
+            &project,
+            Uuid::new_v4(),
+            &dir.path().join("x.urdf"),
+            &ExportOptions::default(),
+        );
+        assert!(matches!(result, Err(ExportError::LinkNotFound(_))));
+    }
+
+    #[test]
+    fn test_export_has_no_duplicate_names() {
+        let mut project = Project::new("robot");
+        let mut links = Vec::new();
+        for _ in 0..3 {
+            let part = Part::from_primitive("link", PrimitiveShape::Box { size: [0.1; 3] });
+            let part_id = project.add_part(part);
+            let link = Link::from_part(project.get_part(part_id).unwrap());
+            links.push(project.assembly.add_link(link));
+        }
+        for pair in links.windows(2) {
+            let joint = Joint::fixed("joint", pair[0], pair[1], Pose::default());
+            project.assembly.connect(pair[0], pair[1], joint).unwrap();
+        }
+
+        let urdf = export_urdf_to_string(&project.assembly, project.parts(), "robot").unwrap();
+        let robot = urdf_rs::read_from_string(&urdf).unwrap();
+        let mut link_names: Vec<_> = robot.links.iter().map(|l| l.name.as_str()).collect();
+        link_names.sort();
+        assert_eq!(link_names, vec!["link", "link_1", "link_2"]);
+        let mut joint_names: Vec<_> = robot.joints.iter().map(|j| j.name.as_str()).collect();
+        joint_names.sort();
+        assert_eq!(joint_names, vec!["joint", "joint_1"]);
+    }
+
+    #[test]
+    fn test_joint_dynamics_roundtrip() {
+        let (mut project, links) = four_link_robot();
+        let joint_id = project.assembly.get_parent_joint(links[1]).unwrap().id;
+        project
+            .assembly
+            .get_joint_mut(joint_id)
+            .unwrap()
+            .set_dynamics(0.7, 0.0);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("robot.urdf");
+        let options = ExportOptions {
+            output_dir: dir.path().to_path_buf(),
+            robot_name: "robot".to_string(),
+            ..Default::default()
+        };
+        let urdf = export_urdf(&project.assembly, project.parts(), &options).unwrap();
+        assert_eq!(urdf.matches("<dynamics").count(), 1);
+
+        let imported = crate::import::import_urdf(&path, &Default::default()).unwrap();
+        let joint = imported.assembly.find_joint_by_name("joint1").unwrap();
+        let dynamics = joint.dynamics.expect("damping should survive export");
+        assert_eq!(dynamics.damping, 0.7);
+        assert_eq!(dynamics.friction, 0.0);
+
+        // Zeroing both removes the element
+        let joint = project.assembly.get_joint_mut(joint_id).unwrap();
+        joint.set_dynamics(0.0, 0.0);
+        assert!(joint.dynamics.is_none());
+    }
+
+    #[test]
+    fn test_joint_safety_and_calibration_roundtrip() {
+        let source = r#"<robot name="arm">
+  <link name="base"/>
+  <link name="upper"/>
+  <link name="lower"/>
+  <joint name="shoulder" type="revolute">
+    <parent link="base"/>
+    <child link="upper"/>
+    <axis xyz="0 0 1"/>
+    <limit lower="-1.5" upper="1.5" effort="10" velocity="2"/>
+    <calibration rising="0.25" falling="-0.5"/>
+    <safety_controller soft_lower_limit="-1.4" soft_upper_limit="1.4" k_position="15" k_velocity="0.5"/>
+  </joint>
+  <joint name="elbow" type="revolute">
+    <parent link="upper"/>
+    <child link="lower"/>
+    <axis xyz="0 1 0"/>
+    <limit lower="-1" upper="1" effort="5" velocity="1"/>
+    <calibration rising="0.1"/>
+  </joint>
+</robot>"#;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("arm.urdf");
+        std::fs::write(&path, source).unwrap();
+
+        let project = crate::import::import_urdf(&path, &Default::default()).unwrap();
+        let urdf = export_urdf_to_string(&project.assembly, project.parts(), "arm").unwrap();
+
+        let original = urdf_rs::read_from_string(source).unwrap();
+        let exported = urdf_rs::read_from_string(&urdf).unwrap();
+        for name in ["shoulder", "elbow"] {
+            let find = |robot: &urdf_rs::Robot| {
+                robot
+                    .joints
+                    .iter()
+                    .find(|j| j.name == name)
+                    .unwrap()
+                    .clone()
+            };
+            let (before, after) = (find(&original), find(&exported));
+            assert_eq!(
+                format!("{:?}", before.safety_controller),
+                format!("{:?}", after.safety_controller)
+            );
+            assert_eq!(
+                format!("{:?}", before.calibration),
+                format!("{:?}", after.calibration)
+            );
+        }
+        // Absent tags stay absent
+        assert_eq!(urdf.matches("<safety_controller").count(), 1);
+        assert!(!urdf.contains("falling=\"0\""));
+    }
+
+    #[test]
+    fn test_export_fails_on_missing_mesh_data() {
+        let (mut project, links) = four_link_robot();
+        for link_id in [links[1], links[3]] {
+            let part_id = project.assembly.links[&link_id].part_id.unwrap();
+            let part = project.get_part_mut(part_id).unwrap();
+            part.vertices.clear();
+            part.indices.clear();
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let options = ExportOptions {
+            output_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let result = export_urdf(&project.assembly, project.parts(), &options);
+        match result {
+            Err(ExportError::MissingMeshes(names)) => assert_eq!(names, ["link1", "link3"]),
+            other => panic!("expected missing meshes, got {other:?}"),
+        }
+        assert_eq!(
+            ExportError::MissingMeshes(vec!["link1".into(), "link3".into()]).to_string(),
+            "Mesh data missing for links: link1, link3"
+        );
+        // Nothing was written
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+        // The in-memory export used on the web checks as well
+        assert!(matches!(
+            export_urdf_to_string(&project.assembly, project.parts(), "robot"),
+            Err(ExportError::MissingMeshes(_))
+        ));
+
+        // Subtree export checks only the links it contains: link3 is broken
+        // until it no longer carries mesh geometry from a part
+        let path = dir.path().join("base.urdf");
+        assert!(export_urdf_subtree(&project, links[2], &path, &options).is_err());
+        project.assembly.get_link_mut(links[3]).unwrap().part_id = None;
+        export_urdf_subtree(&project, links[2], &path, &options).unwrap();
+    }
 }
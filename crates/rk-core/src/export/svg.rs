@@ -0,0 +1,174 @@
+//! SVG line-drawing export of part outlines
+
+use std::collections::HashMap;
+
+use glam::{Mat4, Vec3};
+
+use crate::part::Part;
+
+use super::xml::xml_escape;
+
+/// Stroke width of outline lines in pixels
+const STROKE_WIDTH: f32 = 1.5;
+
+/// World-space silhouette edges of a mesh seen from `eye`
+///
+/// An edge is on the silhouette when one adjacent triangle faces the eye and
+/// the other faces away, or when it borders a single front-facing triangle.
+/// Vertices are welded by position so meshes with split normals (one vertex
+/// per face corner) still share their edges. Triangles referencing missing
+/// vertices are skipped.
+pub fn silhouette_edges(
+    vertices: &[[f32; 3]],
+    indices: &[u32],
+    transform: Mat4,
+    eye: Vec3,
+) -> Vec<[Vec3; 2]> {
+    let world: Vec<Vec3> = vertices
+        .iter()
+        .map(|v| transform.transform_point3(Vec3::from_array(*v)))
+        .collect();
+
+    // Weld coincident vertices so edges can be matched across faces
+    let mut welded = HashMap::new();
+    let ids: Vec<usize> = world
+        .iter()
+        .map(|p| {
+            let key = p.to_array().map(f32::to_bits);
+            let next = welded.len();
+            *welded.entry(key).or_insert(next)
+        })
+        .collect();
+
+    // Edge (sorted welded ids) -> (world endpoints, facing of adjacent triangles)
+    let mut edges: HashMap<(usize, usize), ([Vec3; 2], Vec<bool>)> = HashMap::new();
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| i as usize);
+        let (Some(&pa), Some(&pb), Some(&pc)) = (world.get(a), world.get(b), world.get(c)) else {
+            continue;
+        };
+        let normal = (pb - pa).cross(pc - pa);
+        if normal.length_squared() <= f32::EPSILON * f32::EPSILON {
+            continue;
+        }
+        let front = normal.dot(eye - pa) > 0.0;
+        for (i, j) in [(a, b), (b, c), (c, a)] {
+            let key = (ids[i].min(ids[j]), ids[i].max(ids[j]));
+            edges
+                .entry(key)
+                .or_insert_with(|| ([world[i], world[j]], Vec::new()))
+                .1
+                .push(front);
+        }
+    }
+
+    edges
+        .into_values()
+        .filter(|(_, facing)| match facing.as_slice() {
+            [front] => *front,
+            facing => facing.iter().any(|f| *f) && facing.iter().any(|f| !*f),
+        })
+        .map(|(points, _)| points)
+        .collect()
+}
+
+/// Project a world-space point to SVG pixel coordinates (y down)
+///
+/// Returns `None` for points behind the camera.
+fn project(view_proj: Mat4, point: Vec3, width: f32, height: f32) -> Option<(f32, f32)> {
+    let clip = view_proj * point.extend(1.0);
+    if clip.w <= f32::EPSILON {
+        return None;
+    }
+    let ndc = clip.truncate() / clip.w;
+    Some(((ndc.x + 1.0) * 0.5 * width, (1.0 - ndc.y) * 0.5 * height))
+}
+
+/// Render the silhouettes of `parts` as an SVG line drawing
+///
+/// Each part is drawn with the model matrix paired with it, as a group of
+/// lines projected through `view_proj` into a `width` x `height` canvas. Pass
+/// the matrices the viewport draws with to export the current pose. Hidden
+/// lines are not removed; edges behind the camera are skipped.
+pub fn parts_to_svg<'a>(
+    parts: impl IntoIterator<Item = (&'a Part, Mat4)>,
+    view_proj: Mat4,
+    eye: Vec3,
+    width: u32,
+    height: u32,
+) -> String {
+    let (w, h) = (width as f32, height as f32);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">\n"
+    );
+    svg.push_str(&format!(
+        "  <g fill=\"none\" stroke=\"black\" stroke-width=\"{STROKE_WIDTH}\" \
+         stroke-linecap=\"round\">\n"
+    ));
+
+    for (part, transform) in parts {
+        let edges = silhouette_edges(&part.vertices, &part.indices, transform, eye);
+        svg.push_str(&format!("    <g id=\"{}\">\n", xml_escape(&part.name)));
+        for [a, b] in edges {
+            let (Some((x1, y1)), Some((x2, y2))) =
+                (project(view_proj, a, w, h), project(view_proj, b, w, h))
+            else {
+                continue;
+            };
+            svg.push_str(&format!(
+                "      <line x1=\"{x1:.2}\" y1=\"{y1:.2}\" x2=\"{x2:.2}\" y2=\"{y2:.2}\"/>\n"
+            ));
+        }
+        svg.push_str("    </g>\n");
+    }
+
+    svg.push_str("  </g>\n</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::generate_box_mesh;
+
+    #[test]
+    fn test_cube_silhouette_from_corner_view() {
+        let (vertices, _, indices) = generate_box_mesh([1.0; 3]);
+        let eye = Vec3::new(5.0, 4.0, 3.0);
+
+        // Three faces are visible; the outline is a hexagon
+        let edges = silhouette_edges(&vertices, &indices, Mat4::IDENTITY, eye);
+        assert_eq!(edges.len(), 6);
+
+        // The edges meeting at the corner nearest the eye are interior
+        let near_corner = Vec3::splat(0.5);
+        assert!(edges.iter().flatten().all(|p| *p != near_corner));
+
+        let view_proj = Mat4::perspective_rh(40f32.to_radians(), 1.0, 0.1, 100.0)
+            * Mat4::look_at_rh(eye, Vec3::ZERO, Vec3::Z);
+        let mut part = Part::new("cube");
+        part.vertices = vertices;
+        part.indices = indices;
+        let svg = parts_to_svg([(&part, Mat4::IDENTITY)], view_proj, eye, 200, 200);
+        assert_eq!(svg.matches("<line ").count(), 6);
+        assert!(svg.contains("<g id=\"cube\">"));
+
+        // The given pose is drawn, not the part's origin transform
+        let posed = Mat4::from_translation(Vec3::new(0.0, 0.0, 1.0));
+        let posed_svg = parts_to_svg([(&part, posed)], view_proj, eye, 200, 200);
+        assert_eq!(posed_svg.matches("<line ").count(), 6);
+        assert_ne!(posed_svg, svg);
+    }
+
+    #[test]
+    fn test_out_of_range_indices_are_skipped() {
+        let vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let indices = [0, 1, 2, 0, 2, 7];
+        let eye = Vec3::new(0.0, 0.0, 5.0);
+
+        // The valid triangle still outlines; the broken one is ignored
+        let edges = silhouette_edges(&vertices, &indices, Mat4::IDENTITY, eye);
+        assert_eq!(edges.len(), 3);
+    }
+}
@@ -0,0 +1,277 @@
+//! Conversion of transforms from the editor's convention into exporter targets
+//!
+//! The editor (like URDF) uses right-handed, Z-up coordinates and stores
+//! matrices column-major. Exporters that can target other tools, such as
+//! [`save_parts_stl`](crate::save_parts_stl), take a [`TargetConvention`] and
+//! convert through this module instead of flipping signs by hand. URDF output
+//! always stays in the native convention.
+
+use glam::{Mat3, Mat4, Quat, Vec3};
+
+use crate::types::Pose;
+
+/// Which axis points up in the target coordinate system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpAxis {
+    /// Z up, as in URDF
+    Z,
+    /// Y up, as in glTF; source `(x, y, z)` becomes `(x, z, -y)`
+    Y,
+}
+
+/// Handedness of the target coordinate system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handedness {
+    Right,
+    /// Left-handed targets mirror the third target axis
+    Left,
+}
+
+/// Order in which matrix elements are flattened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixLayout {
+    /// Columns one after another (glTF, glam)
+    ColumnMajor,
+    /// Rows one after another
+    RowMajor,
+}
+
+/// Order of quaternion components when flattened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuatOrder {
+    /// `[x, y, z, w]` (glTF)
+    Xyzw,
+    /// `[w, x, y, z]`
+    Wxyz,
+}
+
+/// Coordinate and storage conventions expected by an export target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetConvention {
+    pub up: UpAxis,
+    pub handedness: Handedness,
+    pub layout: MatrixLayout,
+    pub quat_order: QuatOrder,
+}
+
+impl TargetConvention {
+    /// The editor's own convention; conversion is a no-op
+    pub const NATIVE: Self = Self {
+        up: UpAxis::Z,
+        handedness: Handedness::Right,
+        layout: MatrixLayout::ColumnMajor,
+        quat_order: QuatOrder::Xyzw,
+    };
+
+    /// glTF 2.0: right-handed, Y up, column-major
+    pub const GLTF: Self = Self {
+        up: UpAxis::Y,
+        handedness: Handedness::Right,
+        layout: MatrixLayout::ColumnMajor,
+        quat_order: QuatOrder::Xyzw,
+    };
+
+    /// Presets offered in export menus
+    pub const ALL: &'static [TargetConvention] = &[Self::NATIVE, Self::GLTF];
+
+    /// Display name of a preset, or "Custom"
+    pub fn name(&self) -> &'static str {
+        if *self == Self::NATIVE {
+            "Z up (URDF)"
+        } else if *self == Self::GLTF {
+            "Y up (glTF)"
+        } else {
+            "Custom"
+        }
+    }
+
+    /// Matrix mapping editor coordinates into target coordinates
+    pub fn basis(&self) -> Mat3 {
+        let up = match self.up {
+            UpAxis::Z => Mat3::IDENTITY,
+            UpAxis::Y => Mat3::from_cols(Vec3::X, -Vec3::Z, Vec3::Y),
+        };
+        let mirror = match self.handedness {
+            Handedness::Right => Mat3::IDENTITY,
+            Handedness::Left => Mat3::from_diagonal(Vec3::new(1.0, 1.0, -1.0)),
+        };
+        mirror * up
+    }
+
+    /// Convert a point or direction into target coordinates
+    pub fn convert_vec3(&self, v: Vec3) -> Vec3 {
+        self.basis() * v
+    }
+
+    /// Convert a rigid transform into target coordinates
+    ///
+    /// The result maps target-space points the same way `m` maps editor-space
+    /// points, i.e. `B * m * B⁻¹`.
+    pub fn convert_mat4(&self, m: Mat4) -> Mat4 {
+        let basis = Mat4::from_mat3(self.basis());
+        basis * m * basis.inverse()
+    }
+
+    /// Convert a rotation into target coordinates
+    ///
+    /// Mirroring keeps rotations proper: the rotation angle flips sign along
+    /// with the mirrored axis.
+    pub fn convert_quat(&self, q: Quat) -> Quat {
+        let basis = self.basis();
+        Quat::from_mat3(&(basis * Mat3::from_quat(q) * basis.inverse())).normalize()
+    }
+
+    /// Convert a pose into target coordinates
+    pub fn convert_pose(&self, pose: &Pose) -> Pose {
+        let xyz = self.convert_vec3(pose.position()).to_array();
+        Pose::from_quat(xyz, self.convert_quat(pose.to_quat()))
+    }
+
+    /// Flatten a matrix in the target's element order
+    pub fn mat4_to_array(&self, m: Mat4) -> [f32; 16] {
+        match self.layout {
+            MatrixLayout::ColumnMajor => m.to_cols_array(),
+            MatrixLayout::RowMajor => m.transpose().to_cols_array(),
+        }
+    }
+
+    /// Flatten a quaternion in the target's component order
+    pub fn quat_to_array(&self, q: Quat) -> [f32; 4] {
+        match self.quat_order {
+            QuatOrder::Xyzw => [q.x, q.y, q.z, q.w],
+            QuatOrder::Wxyz => [q.w, q.x, q.y, q.z],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    const LEFT_Z_UP: TargetConvention = TargetConvention {
+        up: UpAxis::Z,
+        handedness: Handedness::Left,
+        layout: MatrixLayout::ColumnMajor,
+        quat_order: QuatOrder::Xyzw,
+    };
+
+    const ROW_MAJOR_WXYZ: TargetConvention = TargetConvention {
+        up: UpAxis::Z,
+        handedness: Handedness::Right,
+        layout: MatrixLayout::RowMajor,
+        quat_order: QuatOrder::Wxyz,
+    };
+
+    fn assert_vec3(actual: Vec3, expected: Vec3) {
+        assert!(
+            actual.abs_diff_eq(expected, 1e-5),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_identity_is_preserved() {
+        for target in [
+            TargetConvention::NATIVE,
+            TargetConvention::GLTF,
+            ROW_MAJOR_WXYZ,
+            LEFT_Z_UP,
+        ] {
+            assert!(
+                target
+                    .convert_mat4(Mat4::IDENTITY)
+                    .abs_diff_eq(Mat4::IDENTITY, 1e-6)
+            );
+            assert!(
+                target
+                    .convert_quat(Quat::IDENTITY)
+                    .abs_diff_eq(Quat::IDENTITY, 1e-6)
+            );
+        }
+        let pose = Pose::new([1.0, 2.0, 3.0], [0.1, 0.2, 0.3]);
+        let native = TargetConvention::NATIVE.convert_pose(&pose);
+        assert_vec3(native.position(), pose.position());
+        assert!(native.to_quat().abs_diff_eq(pose.to_quat(), 1e-5));
+    }
+
+    #[test]
+    fn test_axes_map_to_target() {
+        let gltf = TargetConvention::GLTF;
+        assert_vec3(gltf.convert_vec3(Vec3::X), Vec3::X);
+        assert_vec3(gltf.convert_vec3(Vec3::Y), -Vec3::Z);
+        assert_vec3(gltf.convert_vec3(Vec3::Z), Vec3::Y);
+        assert!((gltf.basis().determinant() - 1.0).abs() < 1e-6);
+
+        assert_vec3(LEFT_Z_UP.convert_vec3(Vec3::Z), -Vec3::Z);
+        assert!((LEFT_Z_UP.basis().determinant() + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_pure_rotation_about_each_axis() {
+        let gltf = TargetConvention::GLTF;
+        // Each source axis becomes the matching target axis; rotating a
+        // probe vector must agree whether converted before or after rotating
+        for (axis, probe) in [(Vec3::X, Vec3::Y), (Vec3::Y, Vec3::Z), (Vec3::Z, Vec3::X)] {
+            let q = Quat::from_axis_angle(axis, FRAC_PI_2);
+            for target in [gltf, LEFT_Z_UP] {
+                let converted = target.convert_quat(q);
+                assert_vec3(
+                    converted * target.convert_vec3(probe),
+                    target.convert_vec3(q * probe),
+                );
+                let m = target.convert_mat4(Mat4::from_quat(q));
+                assert_vec3(
+                    m.transform_vector3(target.convert_vec3(probe)),
+                    target.convert_vec3(q * probe),
+                );
+            }
+        }
+
+        // Yaw about Z-up is yaw about glTF's Y-up
+        let yaw = gltf.convert_quat(Quat::from_rotation_z(0.4));
+        assert!(yaw.abs_diff_eq(Quat::from_rotation_y(0.4), 1e-5));
+        // Mirroring flips the sense of rotation about the mirrored axis
+        let mirrored = LEFT_Z_UP.convert_quat(Quat::from_rotation_x(0.4));
+        assert!(mirrored.abs_diff_eq(Quat::from_rotation_x(-0.4), 1e-5));
+    }
+
+    #[test]
+    fn test_composed_translate_rotate() {
+        let pose = Pose::new([1.0, 2.0, 3.0], [0.3, -0.5, 0.8]);
+        let point = Vec3::new(0.5, -1.0, 2.0);
+        let expected = pose.to_mat4().transform_point3(point);
+
+        for target in [TargetConvention::GLTF, LEFT_Z_UP, ROW_MAJOR_WXYZ] {
+            let m = target.convert_mat4(pose.to_mat4());
+            assert_vec3(
+                m.transform_point3(target.convert_vec3(point)),
+                target.convert_vec3(expected),
+            );
+
+            let converted = target.convert_pose(&pose);
+            assert_vec3(
+                converted
+                    .to_mat4()
+                    .transform_point3(target.convert_vec3(point)),
+                target.convert_vec3(expected),
+            );
+        }
+    }
+
+    #[test]
+    fn test_flattening_order() {
+        let m = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        let cols = TargetConvention::GLTF.mat4_to_array(m);
+        assert_eq!(&cols[12..15], &[1.0, 2.0, 3.0]);
+        let rows = ROW_MAJOR_WXYZ.mat4_to_array(m);
+        assert_eq!([rows[3], rows[7], rows[11]], [1.0, 2.0, 3.0]);
+
+        let q = Quat::from_xyzw(0.1, 0.2, 0.3, 0.9);
+        assert_eq!(
+            TargetConvention::GLTF.quat_to_array(q),
+            [0.1, 0.2, 0.3, 0.9]
+        );
+        assert_eq!(ROW_MAJOR_WXYZ.quat_to_array(q), [0.9, 0.1, 0.2, 0.3]);
+    }
+}
@@ -298,6 +298,24 @@ pub fn write_joint(
         ));
     }
 
+    if let Some(ref calibration) = joint.calibration {
+        urdf.push_str("    <calibration");
+        if let Some(rising) = calibration.rising {
+            urdf.push_str(&format!(" rising=\"{}\"", rising));
+        }
+        if let Some(falling) = calibration.falling {
+            urdf.push_str(&format!(" falling=\"{}\"", falling));
+        }
+        urdf.push_str("/>\n");
+    }
+
+    if let Some(ref safety) = joint.safety_controller {
+        urdf.push_str(&format!(
+            "    <safety_controller soft_lower_limit=\"{}\" soft_upper_limit=\"{}\" k_position=\"{}\" k_velocity=\"{}\"/>\n",
+            safety.soft_lower_limit, safety.soft_upper_limit, safety.k_position, safety.k_velocity
+        ));
+    }
+
     if let Some(ref mimic) = joint.mimic {
         // Resolve joint ID to name for URDF export
         if let Some(mimic_joint) = assembly.joints.get(&mimic.joint_id) {
@@ -4,10 +4,11 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::assembly::{CollisionElement, VisualElement};
+use crate::constants::{CYLINDER_SEGMENTS, SPHERE_LON_SEGMENTS};
 use crate::inertia::InertiaMatrix;
 use crate::mesh::{MeshFormat, load_mesh};
 use crate::part::Part;
-use crate::primitive::{generate_box_mesh, generate_cylinder_mesh, generate_sphere_mesh};
+use crate::primitive::PrimitiveShape;
 use crate::types::GeometryType;
 use crate::types::Pose;
 
@@ -77,6 +78,17 @@ pub fn process_visual_geometry(
         });
     }
 
+    // Without a URDF material the primary visual shows the mesh file's material
+    if first_visual.material.is_none()
+        && let Some(p) = &part
+        && let Some(material) = p.primary_material()
+        && let Some(primary) = visual_elements.first_mut()
+    {
+        primary.color = p.color;
+        primary.material_name = p.material_name.clone();
+        primary.texture = material.texture.clone();
+    }
+
     Ok((part, visual_elements))
 }
 
@@ -137,10 +149,14 @@ pub fn process_geometry(
             if let Some(s) = scale {
                 apply_scale(&mut part, [s.0[0] as f32, s.0[1] as f32, s.0[2] as f32]);
             }
+            ctx.options.postprocess_mesh(&mut part);
 
             part.name = link_name.to_string();
-            part.color = color;
-            part.material_name = material_name;
+            // Keep the mesh file's own material unless the URDF sets one
+            if material_name.is_some() || part.submeshes.is_empty() {
+                part.color = color;
+                part.material_name = material_name;
+            }
 
             Some(part)
         }
@@ -149,7 +165,7 @@ pub fn process_geometry(
             let size = [size.0[0] as f32, size.0[1] as f32, size.0[2] as f32];
             Some(create_primitive_part(
                 link_name,
-                generate_box_mesh(size),
+                PrimitiveShape::Box { size },
                 color,
                 material_name,
             ))
@@ -157,14 +173,21 @@ pub fn process_geometry(
 
         urdf_rs::Geometry::Cylinder { radius, length } => Some(create_primitive_part(
             link_name,
-            generate_cylinder_mesh(*radius as f32, *length as f32),
+            PrimitiveShape::Cylinder {
+                radius: *radius as f32,
+                length: *length as f32,
+                segments: CYLINDER_SEGMENTS,
+            },
             color,
             material_name,
         )),
 
         urdf_rs::Geometry::Sphere { radius } => Some(create_primitive_part(
             link_name,
-            generate_sphere_mesh(*radius as f32),
+            PrimitiveShape::Sphere {
+                radius: *radius as f32,
+                segments: SPHERE_LON_SEGMENTS,
+            },
             color,
             material_name,
         )),
@@ -172,7 +195,11 @@ pub fn process_geometry(
         // Approximate capsule as cylinder (capsule mesh generation would be more complex)
         urdf_rs::Geometry::Capsule { radius, length } => Some(create_primitive_part(
             link_name,
-            generate_cylinder_mesh(*radius as f32, *length as f32),
+            PrimitiveShape::Cylinder {
+                radius: *radius as f32,
+                length: *length as f32,
+                segments: CYLINDER_SEGMENTS,
+            },
             color,
             material_name,
         )),
@@ -197,15 +224,17 @@ pub fn process_collision_geometry(collisions: &[urdf_rs::Collision]) -> Vec<Coll
         .collect()
 }
 
-/// Create a Part from primitive geometry mesh data (box, cylinder, sphere, capsule)
+/// Create a Part from primitive geometry (box, cylinder, sphere, capsule)
 fn create_primitive_part(
     name: &str,
-    mesh_data: (Vec<[f32; 3]>, Vec<[f32; 3]>, Vec<u32>),
+    shape: PrimitiveShape,
     color: [f32; 4],
     material_name: Option<String>,
 ) -> Part {
-    let (vertices, normals, indices) = mesh_data;
-    create_part_from_mesh(name, vertices, normals, indices, color, material_name)
+    let (vertices, normals, indices) = shape.generate();
+    let mut part = create_part_from_mesh(name, vertices, normals, indices, color, material_name);
+    part.primitive = Some(shape);
+    part
 }
 
 /// Create a Part from mesh data
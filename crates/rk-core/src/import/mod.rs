@@ -5,7 +5,7 @@
 mod geometry;
 mod options;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use glam::Vec3;
@@ -13,9 +13,13 @@ use uuid::Uuid;
 
 use crate::assembly::{Assembly, InertialProperties, Joint, Link};
 use crate::inertia::InertiaMatrix;
+use crate::naming::unique_name;
 use crate::part::Part;
 use crate::project::{MaterialDef, Project};
-use crate::types::{JointDynamics, JointLimits, JointMimic, JointType, Pose};
+use crate::types::{
+    JointCalibration, JointDynamics, JointLimits, JointMimic, JointSafetyController, JointType,
+    Pose,
+};
 
 pub use geometry::{
     GeometryContext, create_part_from_mesh, process_collision_geometry, process_geometry,
@@ -151,12 +155,18 @@ fn process_urdf_links(
     let mut parts: HashMap<Uuid, Part> = HashMap::new();
     let mut links: HashMap<Uuid, Link> = HashMap::new();
     let mut link_name_to_id: HashMap<String, Uuid> = HashMap::new();
+    let mut used_names: HashSet<String> = HashSet::new();
 
     for urdf_link in urdf_links {
         let link_id = Uuid::new_v4();
-        link_name_to_id.insert(urdf_link.name.clone(), link_id);
+        // Joints refer to the first link with a given name; later duplicates are renamed
+        link_name_to_id
+            .entry(urdf_link.name.clone())
+            .or_insert(link_id);
+        let name = unique_name(&urdf_link.name, |n| used_names.contains(n));
+        used_names.insert(name.clone());
 
-        let (part_opt, visuals) = process_visual_geometry(&urdf_link.visual, &urdf_link.name, ctx)?;
+        let (part_opt, visuals) = process_visual_geometry(&urdf_link.visual, &name, ctx)?;
 
         let inertial_props = InertialProperties {
             origin: Pose::from(&urdf_link.inertial.origin),
@@ -178,7 +188,7 @@ fn process_urdf_links(
 
         let link = Link {
             id: link_id,
-            name: urdf_link.name.clone(),
+            name,
             part_id,
             world_transform: glam::Mat4::IDENTITY,
             visuals,
@@ -199,9 +209,14 @@ fn process_urdf_joints(
     assembly: &mut Assembly,
 ) -> Result<(), ImportError> {
     let mut joint_name_to_id: HashMap<String, Uuid> = HashMap::new();
+    let mut used_names: HashSet<String> = HashSet::new();
+    let mut joint_ids: Vec<Uuid> = Vec::with_capacity(urdf_joints.len());
 
     // First pass: create joints without mimic
     for urdf_joint in urdf_joints {
+        let name = unique_name(&urdf_joint.name, |n| used_names.contains(n));
+        used_names.insert(name.clone());
+
         let parent_link_id = link_name_to_id
             .get(&urdf_joint.parent.link)
             .ok_or_else(|| ImportError::LinkNotFound(urdf_joint.parent.link.clone()))?;
@@ -212,7 +227,7 @@ fn process_urdf_joints(
 
         let joint = Joint {
             id: Uuid::new_v4(),
-            name: urdf_joint.name.clone(),
+            name,
             joint_type: JointType::from(&urdf_joint.joint_type),
             parent_link: *parent_link_id,
             child_link: *child_link_id,
@@ -233,10 +248,25 @@ fn process_urdf_joints(
                 friction: d.friction as f32,
             }),
             mimic: None,
+            safety_controller: urdf_joint.safety_controller.as_ref().map(|s| {
+                JointSafetyController {
+                    soft_lower_limit: s.soft_lower_limit as f32,
+                    soft_upper_limit: s.soft_upper_limit as f32,
+                    k_position: s.k_position as f32,
+                    k_velocity: s.k_velocity as f32,
+                }
+            }),
+            calibration: urdf_joint.calibration.as_ref().map(|c| JointCalibration {
+                rising: c.rising.map(|v| v as f32),
+                falling: c.falling.map(|v| v as f32),
+            }),
         };
 
         let joint_id = joint.id;
-        joint_name_to_id.insert(urdf_joint.name.clone(), joint_id);
+        joint_name_to_id
+            .entry(urdf_joint.name.clone())
+            .or_insert(joint_id);
+        joint_ids.push(joint_id);
         assembly.joints.insert(joint_id, joint);
 
         assembly
@@ -250,10 +280,9 @@ fn process_urdf_joints(
     }
 
     // Second pass: resolve mimic references
-    for urdf_joint in urdf_joints {
+    for (urdf_joint, joint_id) in urdf_joints.iter().zip(joint_ids) {
         if let Some(ref mimic) = urdf_joint.mimic
             && let Some(&mimic_joint_id) = joint_name_to_id.get(&mimic.joint)
-            && let Some(&joint_id) = joint_name_to_id.get(&urdf_joint.name)
             && let Some(joint) = assembly.joints.get_mut(&joint_id)
         {
             joint.mimic = Some(JointMimic {
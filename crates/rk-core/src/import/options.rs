@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::mesh::StlUnit;
+use crate::part::Part;
 
 /// Import options for URDF loading
 #[derive(Debug, Clone)]
@@ -17,6 +18,10 @@ pub struct ImportOptions {
     /// Package path mappings for resolving package:// URIs
     /// Maps package name to its root directory
     pub package_paths: HashMap<String, PathBuf>,
+    /// Reverse triangle winding of imported meshes (for inside-out CAD exports)
+    pub flip_normals: bool,
+    /// Recompute normals of imported meshes from their (possibly flipped) winding
+    pub recompute_normals: bool,
 }
 
 impl Default for ImportOptions {
@@ -26,6 +31,8 @@ impl Default for ImportOptions {
             stl_unit: StlUnit::Meters,
             default_color: [0.7, 0.7, 0.7, 1.0],
             package_paths: HashMap::new(),
+            flip_normals: false,
+            recompute_normals: false,
         }
     }
 }
@@ -39,6 +46,18 @@ impl ImportOptions {
         }
     }
 
+    /// Apply the normal post-processing options to an imported mesh
+    ///
+    /// Winding is flipped first so recomputed normals follow the new winding.
+    pub fn postprocess_mesh(&self, part: &mut Part) {
+        if self.flip_normals {
+            part.flip_normals();
+        }
+        if self.recompute_normals {
+            part.recompute_normals();
+        }
+    }
+
     /// Add a package path mapping
     pub fn add_package_path(&mut self, package_name: impl Into<String>, path: impl Into<PathBuf>) {
         self.package_paths.insert(package_name.into(), path.into());
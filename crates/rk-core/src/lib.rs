@@ -11,6 +11,7 @@ pub mod export;
 pub mod import;
 pub mod inertia;
 pub mod mesh;
+pub mod naming;
 pub mod part;
 pub mod primitive;
 pub mod project;
@@ -22,6 +23,7 @@ pub use export::*;
 pub use import::*;
 pub use inertia::*;
 pub use mesh::*;
+pub use naming::*;
 pub use part::*;
 pub use primitive::*;
 pub use project::*;
@@ -0,0 +1,142 @@
+//! Mesh health analysis (degenerate, duplicate and non-manifold geometry)
+
+use std::collections::{HashMap, HashSet};
+
+/// Relative area threshold below which a triangle is treated as degenerate.
+///
+/// Compared against the squared length of the triangle's longest edge so the
+/// check does not depend on the mesh's unit scale.
+const DEGENERATE_AREA_RATIO: f32 = 1e-7;
+
+/// Health report for a triangle mesh
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MeshReport {
+    /// Number of triangles (complete index triples)
+    pub triangle_count: usize,
+    /// Number of vertices
+    pub vertex_count: usize,
+    /// Triangles with zero area, repeated corners or out-of-range indices
+    pub degenerate_triangles: usize,
+    /// Triangles sharing all three corners with an earlier triangle
+    pub duplicate_triangles: usize,
+    /// Edges shared by more than two triangles
+    pub non_manifold_edges: usize,
+}
+
+impl MeshReport {
+    /// Total number of problems found
+    pub fn issue_count(&self) -> usize {
+        self.degenerate_triangles + self.duplicate_triangles + self.non_manifold_edges
+    }
+
+    /// Whether the mesh has no detected problems
+    pub fn is_clean(&self) -> bool {
+        self.issue_count() == 0
+    }
+}
+
+/// Analyze an indexed triangle mesh for common import problems
+///
+/// Vertices at bit-identical positions are treated as the same corner, so
+/// unwelded (triangle soup) meshes are analyzed the same as welded ones.
+pub fn analyze_mesh(vertices: &[[f32; 3]], indices: &[u32]) -> MeshReport {
+    let mut report = MeshReport {
+        triangle_count: indices.len() / 3,
+        vertex_count: vertices.len(),
+        ..Default::default()
+    };
+
+    // Map each vertex index to the first vertex at the same position
+    let mut first_at: HashMap<[u32; 3], u32> = HashMap::with_capacity(vertices.len());
+    let canonical: Vec<u32> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let key = [v[0].to_bits(), v[1].to_bits(), v[2].to_bits()];
+            *first_at.entry(key).or_insert(i as u32)
+        })
+        .collect();
+
+    let mut seen_triangles: HashSet<[u32; 3]> = HashSet::with_capacity(report.triangle_count);
+    let mut edge_uses: HashMap<(u32, u32), u32> = HashMap::with_capacity(report.triangle_count * 3);
+
+    for tri in indices.chunks_exact(3) {
+        let Some(corners) = tri
+            .iter()
+            .map(|&i| canonical.get(i as usize).copied())
+            .collect::<Option<Vec<u32>>>()
+        else {
+            report.degenerate_triangles += 1;
+            continue;
+        };
+        let (a, b, c) = (corners[0], corners[1], corners[2]);
+
+        if a == b || b == c || a == c || is_zero_area(vertices, a, b, c) {
+            report.degenerate_triangles += 1;
+            continue;
+        }
+
+        let mut key = [a, b, c];
+        key.sort_unstable();
+        if !seen_triangles.insert(key) {
+            report.duplicate_triangles += 1;
+            continue;
+        }
+
+        for (p, q) in [(a, b), (b, c), (c, a)] {
+            *edge_uses.entry((p.min(q), p.max(q))).or_insert(0) += 1;
+        }
+    }
+
+    report.non_manifold_edges = edge_uses.values().filter(|&&n| n > 2).count();
+    report
+}
+
+fn is_zero_area(vertices: &[[f32; 3]], a: u32, b: u32, c: u32) -> bool {
+    let [p0, p1, p2] = [a, b, c].map(|i| glam::Vec3::from(vertices[i as usize]));
+    let (e0, e1, e2) = (p1 - p0, p2 - p1, p0 - p2);
+    let longest = e0
+        .length_squared()
+        .max(e1.length_squared())
+        .max(e2.length_squared());
+    // |e0 x e2| is twice the area; compare against the longest edge squared
+    e0.cross(e2).length() <= DEGENERATE_AREA_RATIO * longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::fixtures::unit_cube;
+
+    #[test]
+    fn test_clean_cube_has_no_issues() {
+        let (vertices, indices) = unit_cube();
+        let report = analyze_mesh(&vertices, &indices);
+        assert_eq!(report.triangle_count, 12);
+        assert_eq!(report.vertex_count, 8);
+        assert!(report.is_clean(), "{report:?}");
+    }
+
+    #[test]
+    fn test_detects_degenerate_duplicate_and_non_manifold() {
+        let (mut vertices, mut indices) = unit_cube();
+
+        // Collinear triangle along the bottom front edge
+        vertices.push([0.5, 0.0, 0.0]);
+        indices.extend_from_slice(&[0, 8, 1]);
+        let report = analyze_mesh(&vertices, &indices);
+        assert_eq!(report.degenerate_triangles, 1);
+        assert_eq!(report.duplicate_triangles, 0);
+        assert_eq!(report.non_manifold_edges, 0);
+
+        // Same face again with reversed winding, and a fin on edge 0-1
+        indices.extend_from_slice(&[0, 1, 2]);
+        vertices.push([0.5, -1.0, 0.5]);
+        indices.extend_from_slice(&[0, 1, 9]);
+        let report = analyze_mesh(&vertices, &indices);
+        assert_eq!(report.degenerate_triangles, 1);
+        assert_eq!(report.duplicate_triangles, 1);
+        assert_eq!(report.non_manifold_edges, 1);
+        assert_eq!(report.issue_count(), 3);
+    }
+}
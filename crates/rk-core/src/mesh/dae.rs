@@ -1,14 +1,19 @@
 //! DAE (COLLADA) mesh file loading
 
+use std::collections::HashMap;
 use std::path::Path;
 
-use dae_parser::{Document, Geometry, Primitive, Semantic, Source};
+use dae_parser::{
+    ColorParam, Document, Effect, Geometry, Image, ImageSource, LocalMap, Material, NewParam, Node,
+    ParamType, Primitive, Profile, ProfileCommon, Semantic, Shader, Source, SurfaceInit, Url,
+};
 
 use crate::part::Part;
+use crate::project::MaterialDef;
 
-use super::MeshError;
 use super::normals::{calculate_face_normals, calculate_triangle_normal};
 use super::stl::StlUnit;
+use super::{MeshError, Submesh};
 
 /// Load a DAE (COLLADA) file and create a Part
 pub fn load_dae(path: impl AsRef<Path>) -> Result<Part, MeshError> {
@@ -27,6 +32,9 @@ pub fn load_dae_with_unit(path: impl AsRef<Path>, unit: StlUnit) -> Result<Part,
     let mut all_vertices: Vec<[f32; 3]> = Vec::new();
     let mut all_normals: Vec<[f32; 3]> = Vec::new();
     let mut all_indices: Vec<u32> = Vec::new();
+    let mut submeshes: Vec<Submesh> = Vec::new();
+
+    let materials = DaeMaterials::collect(&document);
 
     // Get the local map to access geometry elements
     let geom_map = document
@@ -76,6 +84,12 @@ pub fn load_dae_with_unit(path: impl AsRef<Path>, unit: StlUnit) -> Result<Part,
         // Process primitives (triangles, polylist, etc.)
         for primitive in &mesh.elements {
             let vertex_offset = all_vertices.len() as u32;
+            let first_triangle = (all_indices.len() / 3) as u32;
+            let material_symbol = match primitive {
+                Primitive::Triangles(tris) => tris.material.as_deref(),
+                Primitive::PolyList(polylist) => polylist.material.as_deref(),
+                _ => None,
+            };
 
             match primitive {
                 Primitive::Triangles(tris) => {
@@ -164,6 +178,17 @@ pub fn load_dae_with_unit(path: impl AsRef<Path>, unit: StlUnit) -> Result<Part,
                     // Skip other primitive types (lines, etc.)
                 }
             }
+
+            let triangle_count = (all_indices.len() / 3) as u32 - first_triangle;
+            if triangle_count > 0
+                && let Some(material) = material_symbol.and_then(|s| materials.resolve(s))
+            {
+                submeshes.push(Submesh {
+                    material,
+                    first_triangle,
+                    triangle_count,
+                });
+            }
         }
     }
 
@@ -185,12 +210,135 @@ pub fn load_dae_with_unit(path: impl AsRef<Path>, unit: StlUnit) -> Result<Part,
             vertices: all_vertices,
             normals: all_normals,
             indices: all_indices,
+            submeshes,
         },
     );
 
     Ok(part)
 }
 
+/// Material lookup for the symbols used by geometry primitives
+struct DaeMaterials<'a> {
+    materials: LocalMap<'a, Material>,
+    effects: LocalMap<'a, Effect>,
+    images: LocalMap<'a, Image>,
+    /// Symbol to material bindings from `<instance_geometry>` nodes
+    bindings: HashMap<&'a str, &'a Material>,
+}
+
+impl<'a> DaeMaterials<'a> {
+    fn collect(document: &'a Document) -> Self {
+        let materials = document.local_map::<Material>().unwrap_or_default();
+        let mut bindings = HashMap::new();
+        document.for_each(|node: &'a Node| {
+            for instance in &node.instance_geometry {
+                let Some(bind) = &instance.data.bind_material else {
+                    continue;
+                };
+                for im in &bind.instance_material {
+                    if let Some(material) = materials.get(&im.target) {
+                        bindings.insert(im.symbol.as_str(), material);
+                    }
+                }
+            }
+        });
+
+        Self {
+            materials,
+            effects: document.local_map::<Effect>().unwrap_or_default(),
+            images: document.local_map::<Image>().unwrap_or_default(),
+            bindings,
+        }
+    }
+
+    /// Resolve a primitive's material symbol to a material definition
+    ///
+    /// Unbound symbols are looked up as material ids, which is what most
+    /// exporters write when a geometry is only instanced once.
+    fn resolve(&self, symbol: &str) -> Option<MaterialDef> {
+        let material = self
+            .bindings
+            .get(symbol)
+            .copied()
+            .or_else(|| self.materials.get_str(symbol))?;
+        let name = material
+            .name
+            .clone()
+            .or_else(|| material.id.clone())
+            .unwrap_or_else(|| symbol.to_string());
+
+        let mut def = MaterialDef::new(name, [1.0; 4]);
+        let Some(profile) = self
+            .effects
+            .get(&material.instance_effect.url)
+            .and_then(|effect| {
+                effect.profile.iter().find_map(|p| match p {
+                    Profile::Common(common) => Some((effect, common)),
+                    _ => None,
+                })
+            })
+        else {
+            return Some(def);
+        };
+
+        if let Some(diffuse) = profile
+            .1
+            .technique
+            .data
+            .shaders
+            .first()
+            .and_then(diffuse_of)
+        {
+            if let Some(color) = diffuse.as_color() {
+                def.color = *color;
+            } else if let Some(texture) = diffuse.as_texture() {
+                def.texture = self.texture_path(profile.0, profile.1, &texture.texture);
+            }
+        }
+        Some(def)
+    }
+
+    /// Follow a texture's sampler and surface params to the image file
+    fn texture_path(&self, effect: &Effect, common: &ProfileCommon, name: &str) -> Option<String> {
+        let find_param = |sid: &str| -> Option<&NewParam> {
+            common
+                .new_param
+                .iter()
+                .chain(&effect.new_param)
+                .find(|p| p.sid == sid)
+        };
+
+        let image = match find_param(name).map(|p| &p.ty) {
+            Some(ParamType::Sampler2D(sampler)) => {
+                match find_param(&sampler.source.val).map(|p| &p.ty) {
+                    Some(ParamType::Surface(surface)) => match &surface.init {
+                        SurfaceInit::From { image, .. } => self.images.get_name(image),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            // COLLADA 1.5 style: the texture names the image directly
+            _ => self.images.get_str(name),
+        }?;
+
+        match &image.source {
+            ImageSource::InitFrom(Url::Fragment(path) | Url::Other(path)) => Some(path.clone()),
+            ImageSource::Data(_) => None,
+        }
+    }
+}
+
+/// The diffuse (or emissive, for constant shading) color parameter of a shader
+fn diffuse_of(shader: &Shader) -> Option<&ColorParam> {
+    match shader {
+        Shader::Lambert(s) => s.diffuse.as_deref(),
+        Shader::Phong(s) => s.diffuse.as_deref(),
+        Shader::Blinn(s) => s.diffuse.as_deref(),
+        Shader::Constant(s) => s.emission.as_deref(),
+    }
+}
+
 /// Extract Vec3 data from a COLLADA source
 fn extract_vec3_from_source(source: &Source, scale: f32) -> Result<Vec<[f32; 3]>, MeshError> {
     let accessor = &source.accessor;
@@ -335,3 +483,116 @@ fn process_polylist(
         prim_offset += vert_count * stride;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two triangles, one per material: a red Lambert and a textured Phong.
+    /// The red material is bound through `<bind_material>`, the textured one
+    /// is referenced by id directly.
+    const COLORED_DAE: &str = r##"<?xml version="1.0" encoding="utf-8"?>
+<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+  <asset>
+    <created>2024-01-01T00:00:00</created>
+    <modified>2024-01-01T00:00:00</modified>
+  </asset>
+  <library_images>
+    <image id="metal-img"><init_from>textures/metal.png</init_from></image>
+  </library_images>
+  <library_effects>
+    <effect id="red-fx">
+      <profile_COMMON>
+        <technique sid="common">
+          <lambert><diffuse><color>0.8 0.1 0.1 1</color></diffuse></lambert>
+        </technique>
+      </profile_COMMON>
+    </effect>
+    <effect id="metal-fx">
+      <profile_COMMON>
+        <newparam sid="metal-surface">
+          <surface type="2D"><init_from>metal-img</init_from></surface>
+        </newparam>
+        <newparam sid="metal-sampler">
+          <sampler2D><source>metal-surface</source></sampler2D>
+        </newparam>
+        <technique sid="common">
+          <phong><diffuse><texture texture="metal-sampler" texcoord="UV"/></diffuse></phong>
+        </technique>
+      </profile_COMMON>
+    </effect>
+  </library_effects>
+  <library_materials>
+    <material id="red-mat" name="red"><instance_effect url="#red-fx"/></material>
+    <material id="metal-mat" name="metal"><instance_effect url="#metal-fx"/></material>
+  </library_materials>
+  <library_geometries>
+    <geometry id="quad">
+      <mesh>
+        <source id="quad-pos">
+          <float_array id="quad-pos-array" count="12">0 0 0 1 0 0 1 1 0 0 1 0</float_array>
+          <technique_common>
+            <accessor source="#quad-pos-array" count="4" stride="3">
+              <param name="X" type="float"/><param name="Y" type="float"/><param name="Z" type="float"/>
+            </accessor>
+          </technique_common>
+        </source>
+        <vertices id="quad-vtx"><input semantic="POSITION" source="#quad-pos"/></vertices>
+        <triangles material="red-sym" count="1">
+          <input semantic="VERTEX" source="#quad-vtx" offset="0"/>
+          <p>0 1 2</p>
+        </triangles>
+        <triangles material="metal-mat" count="1">
+          <input semantic="VERTEX" source="#quad-vtx" offset="0"/>
+          <p>0 2 3</p>
+        </triangles>
+      </mesh>
+    </geometry>
+  </library_geometries>
+  <library_visual_scenes>
+    <visual_scene id="scene">
+      <node id="quad-node">
+        <instance_geometry url="#quad">
+          <bind_material>
+            <technique_common>
+              <instance_material symbol="red-sym" target="#red-mat"/>
+            </technique_common>
+          </bind_material>
+        </instance_geometry>
+      </node>
+    </visual_scene>
+  </library_visual_scenes>
+  <scene><instance_visual_scene url="#scene"/></scene>
+</COLLADA>
+"##;
+
+    #[test]
+    fn test_load_dae_materials() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("quad.dae");
+        std::fs::write(&path, COLORED_DAE).unwrap();
+
+        let part = load_dae(&path).unwrap();
+        assert_eq!(part.indices.len(), 6);
+        assert_eq!(part.submeshes.len(), 2);
+
+        let red = part.triangle_material(0).unwrap();
+        assert_eq!(red.name, "red");
+        assert_eq!(red.color, [0.8, 0.1, 0.1, 1.0]);
+        assert_eq!(red.texture, None);
+
+        let metal = part.triangle_material(1).unwrap();
+        assert_eq!(metal.name, "metal");
+        assert_eq!(metal.texture.as_deref(), Some("textures/metal.png"));
+
+        // Equal triangle counts: the first submesh is the part's material
+        assert_eq!(part.color, [0.8, 0.1, 0.1, 1.0]);
+        assert_eq!(part.material_name.as_deref(), Some("red"));
+        assert!(part.has_submesh_colors());
+
+        let mut project = crate::Project::new("test");
+        project.add_part(part);
+        let names: Vec<&str> = project.materials.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["red", "metal"]);
+    }
+}
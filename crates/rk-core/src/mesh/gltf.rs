@@ -0,0 +1,214 @@
+//! glTF 2.0 mesh file loading (.gltf and .glb)
+
+use std::path::Path;
+
+use glam::Mat4;
+
+use crate::part::Part;
+use crate::project::MaterialDef;
+
+use super::normals::calculate_face_normals;
+use super::stl::StlUnit;
+use super::{MeshError, RawMeshData, Submesh};
+
+/// Load a glTF file and create a Part
+pub fn load_gltf(path: impl AsRef<Path>) -> Result<Part, MeshError> {
+    load_gltf_with_unit(path, StlUnit::Meters)
+}
+
+/// Load a glTF file with specified unit
+///
+/// All meshes in the default scene are merged into one part, with node
+/// transforms applied. Each triangle primitive with a material becomes a
+/// submesh carrying its base color and base color texture URI.
+pub fn load_gltf_with_unit(path: impl AsRef<Path>, unit: StlUnit) -> Result<Part, MeshError> {
+    let path = path.as_ref();
+
+    let gltf =
+        gltf::Gltf::open(path).map_err(|e| MeshError::Parse(format!("glTF parse error: {}", e)))?;
+    let buffers = gltf::import_buffers(&gltf.document, path.parent(), gltf.blob.clone())
+        .map_err(|e| MeshError::Io(e.to_string()))?;
+
+    let mut mesh = RawMeshData {
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        indices: Vec::new(),
+        submeshes: Vec::new(),
+    };
+    let root = Mat4::from_scale(glam::Vec3::splat(unit.scale_factor()));
+
+    match gltf
+        .document
+        .default_scene()
+        .or_else(|| gltf.document.scenes().next())
+    {
+        Some(scene) => {
+            for node in scene.nodes() {
+                append_node(&node, root, &buffers, &mut mesh);
+            }
+        }
+        // No scene: take every mesh in its own frame
+        None => {
+            for gltf_mesh in gltf.document.meshes() {
+                append_mesh(&gltf_mesh, root, &buffers, &mut mesh);
+            }
+        }
+    }
+
+    if mesh.vertices.is_empty() {
+        return Err(MeshError::EmptyMesh);
+    }
+
+    // Part normals are per triangle; glTF vertex normals are not used
+    mesh.normals = calculate_face_normals(&mesh.vertices, &mesh.indices);
+
+    let (name, mesh_path) = super::extract_name_and_path(path);
+    let mut part = Part::new(name);
+    super::finalize_part(&mut part, mesh_path, mesh);
+
+    Ok(part)
+}
+
+/// Append a node's mesh and its children, accumulating transforms
+fn append_node(
+    node: &gltf::Node,
+    parent: Mat4,
+    buffers: &[gltf::buffer::Data],
+    out: &mut RawMeshData,
+) {
+    let transform = parent * Mat4::from_cols_array_2d(&node.transform().matrix());
+    if let Some(gltf_mesh) = node.mesh() {
+        append_mesh(&gltf_mesh, transform, buffers, out);
+    }
+    for child in node.children() {
+        append_node(&child, transform, buffers, out);
+    }
+}
+
+/// Append the triangle primitives of a mesh
+fn append_mesh(
+    gltf_mesh: &gltf::Mesh,
+    transform: Mat4,
+    buffers: &[gltf::buffer::Data],
+    out: &mut RawMeshData,
+) {
+    for primitive in gltf_mesh.primitives() {
+        if primitive.mode() != gltf::mesh::Mode::Triangles {
+            continue;
+        }
+
+        let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|d| &d.0[..]));
+        let Some(positions) = reader.read_positions() else {
+            continue;
+        };
+
+        let vertex_offset = out.vertices.len() as u32;
+        let first_triangle = (out.indices.len() / 3) as u32;
+
+        out.vertices
+            .extend(positions.map(|p| transform.transform_point3(p.into()).to_array()));
+        let vertex_count = out.vertices.len() as u32 - vertex_offset;
+
+        match reader.read_indices() {
+            Some(indices) => out
+                .indices
+                .extend(indices.into_u32().map(|i| vertex_offset + i)),
+            None => out
+                .indices
+                .extend(vertex_offset..vertex_offset + vertex_count),
+        }
+        // Drop a trailing partial triangle from malformed index data
+        out.indices.truncate(out.indices.len() / 3 * 3);
+
+        let triangle_count = (out.indices.len() / 3) as u32 - first_triangle;
+        if triangle_count > 0
+            && let Some(material) = material_def(&primitive.material())
+        {
+            out.submeshes.push(Submesh {
+                material,
+                first_triangle,
+                triangle_count,
+            });
+        }
+    }
+}
+
+/// Convert a glTF material to a material definition
+///
+/// Returns `None` for the implicit default material.
+fn material_def(material: &gltf::Material) -> Option<MaterialDef> {
+    let index = material.index()?;
+    let name = material
+        .name()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("material_{}", index));
+    let pbr = material.pbr_metallic_roughness();
+
+    let mut def = MaterialDef::new(name, pbr.base_color_factor());
+    def.texture =
+        pbr.base_color_texture()
+            .and_then(|info| match info.texture().source().source() {
+                gltf::image::Source::Uri { uri, .. } => Some(uri.to_string()),
+                gltf::image::Source::View { .. } => None,
+            });
+    Some(def)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A unit quad split into a red and a blue triangle
+    const COLORED_GLTF: &str = r#"{
+  "asset": { "version": "2.0" },
+  "scene": 0,
+  "scenes": [{ "nodes": [0] }],
+  "nodes": [{ "mesh": 0 }],
+  "meshes": [{
+    "primitives": [
+      { "attributes": { "POSITION": 0 }, "indices": 1, "material": 0 },
+      { "attributes": { "POSITION": 0 }, "indices": 2, "material": 1 }
+    ]
+  }],
+  "materials": [
+    { "name": "red", "pbrMetallicRoughness": { "baseColorFactor": [0.8, 0.1, 0.1, 1.0] } },
+    { "name": "blue", "pbrMetallicRoughness": { "baseColorFactor": [0.1, 0.2, 0.9, 1.0] } }
+  ],
+  "buffers": [{
+    "byteLength": 60,
+    "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAACAPwAAgD8AAAAAAAAAAAAAgD8AAAAAAAABAAIAAAACAAMA"
+  }],
+  "bufferViews": [
+    { "buffer": 0, "byteOffset": 0, "byteLength": 48 },
+    { "buffer": 0, "byteOffset": 48, "byteLength": 12 }
+  ],
+  "accessors": [
+    { "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3",
+      "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0] },
+    { "bufferView": 1, "byteOffset": 0, "componentType": 5123, "count": 3, "type": "SCALAR" },
+    { "bufferView": 1, "byteOffset": 6, "componentType": 5123, "count": 3, "type": "SCALAR" }
+  ]
+}"#;
+
+    #[test]
+    fn test_load_gltf_submesh_colors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("quad.gltf");
+        std::fs::write(&path, COLORED_GLTF).unwrap();
+
+        let mut part = load_gltf(&path).unwrap();
+        assert_eq!(part.indices.len(), 6);
+        assert_eq!(part.submeshes.len(), 2);
+        assert_eq!(part.triangle_material(0).unwrap().name, "red");
+        let blue = part.triangle_material(1).unwrap();
+        assert_eq!(blue.name, "blue");
+        assert_eq!(blue.color, [0.1, 0.2, 0.9, 1.0]);
+        assert!(part.has_submesh_colors());
+
+        // A user-set color replaces the imported ones for good
+        part.set_color([0.2, 0.7, 0.3, 1.0]);
+        assert!(!part.has_submesh_colors());
+        assert!(part.triangle_material(1).is_none());
+        assert_eq!(part.color, [0.2, 0.7, 0.3, 1.0]);
+    }
+}
@@ -1,24 +1,56 @@
-//! Mesh file loading (STL, OBJ, DAE formats)
+//! Mesh file loading (STL, OBJ, DAE, glTF formats)
 
+mod analysis;
 mod dae;
+mod gltf;
 mod normals;
 mod obj;
 mod stl;
 
 use std::path::Path;
 
+use serde::{Deserialize, Serialize};
+
 use crate::part::Part;
+use crate::project::MaterialDef;
 
+pub use analysis::{MeshReport, analyze_mesh};
 pub use dae::{load_dae, load_dae_with_unit};
-pub use normals::{calculate_face_normals, calculate_triangle_normal};
+pub use gltf::{load_gltf, load_gltf_with_unit};
+pub use normals::{
+    calculate_face_normals, calculate_triangle_normal, calculate_vertex_normals, flip_winding,
+};
 pub use obj::{load_obj, load_obj_with_unit};
-pub use stl::{StlError, StlUnit, load_stl, load_stl_from_bytes, load_stl_with_unit, save_stl};
+pub use stl::{
+    StlError, StlFormat, StlUnit, load_stl, load_stl_from_bytes, load_stl_with_unit,
+    save_parts_stl, save_stl, save_stl_with_options,
+};
+
+/// A run of triangles sharing one material from the source mesh file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submesh {
+    /// Material read from the mesh file
+    pub material: MaterialDef,
+    /// First triangle of the run (counted in triangles, not indices)
+    pub first_triangle: u32,
+    /// Number of triangles in the run
+    pub triangle_count: u32,
+}
+
+impl Submesh {
+    /// Whether `triangle` falls within this run
+    pub fn contains(&self, triangle: u32) -> bool {
+        (self.first_triangle..self.first_triangle + self.triangle_count).contains(&triangle)
+    }
+}
 
 /// Raw mesh data extracted from a file (before Part creation)
 pub(crate) struct RawMeshData {
     pub vertices: Vec<[f32; 3]>,
     pub normals: Vec<[f32; 3]>,
     pub indices: Vec<u32>,
+    /// Material runs, empty for formats without materials
+    pub submeshes: Vec<Submesh>,
 }
 
 /// Finalize a Part from raw mesh data
@@ -27,11 +59,17 @@ pub(crate) struct RawMeshData {
 /// - Setting the mesh path
 /// - Calculating bounding box
 /// - Calculating default inertia from bounding box
+/// - Taking the color and material name of the largest submesh
 pub(crate) fn finalize_part(part: &mut Part, mesh_path: Option<String>, mesh_data: RawMeshData) {
     part.stl_path = mesh_path;
     part.vertices = mesh_data.vertices;
     part.normals = mesh_data.normals;
     part.indices = mesh_data.indices;
+    part.submeshes = mesh_data.submeshes;
+    if let Some(material) = part.primary_material().cloned() {
+        part.color = material.color;
+        part.material_name = Some(material.name);
+    }
     part.calculate_bounding_box();
     part.inertia =
         crate::inertia::InertiaMatrix::from_bounding_box(part.mass, part.bbox_min, part.bbox_max);
@@ -54,6 +92,7 @@ pub enum MeshFormat {
     Stl,
     Obj,
     Dae,
+    Gltf,
     Unknown,
 }
 
@@ -69,13 +108,17 @@ impl MeshFormat {
             Some("stl") => MeshFormat::Stl,
             Some("obj") => MeshFormat::Obj,
             Some("dae") => MeshFormat::Dae,
+            Some("gltf" | "glb") => MeshFormat::Gltf,
             _ => MeshFormat::Unknown,
         }
     }
 
     /// Check if the format is supported
     pub fn is_supported(&self) -> bool {
-        matches!(self, MeshFormat::Stl | MeshFormat::Obj | MeshFormat::Dae)
+        matches!(
+            self,
+            MeshFormat::Stl | MeshFormat::Obj | MeshFormat::Dae | MeshFormat::Gltf
+        )
     }
 
     /// Get format name
@@ -84,6 +127,7 @@ impl MeshFormat {
             MeshFormat::Stl => "STL",
             MeshFormat::Obj => "OBJ",
             MeshFormat::Dae => "DAE (COLLADA)",
+            MeshFormat::Gltf => "glTF",
             MeshFormat::Unknown => "Unknown",
         }
     }
@@ -100,6 +144,7 @@ pub fn load_mesh(path: impl AsRef<Path>, unit: StlUnit) -> Result<Part, MeshErro
         }
         MeshFormat::Obj => load_obj_with_unit(path, unit),
         MeshFormat::Dae => load_dae_with_unit(path, unit),
+        MeshFormat::Gltf => load_gltf_with_unit(path, unit),
         MeshFormat::Unknown => Err(MeshError::UnsupportedFormat(
             path.extension()
                 .and_then(|e| e.to_str())
@@ -121,3 +166,31 @@ pub enum MeshError {
     #[error("Unsupported format: {0}")]
     UnsupportedFormat(String),
 }
+
+/// Meshes shared by tests across the crate
+#[cfg(test)]
+pub(crate) mod fixtures {
+    /// Closed unit cube with shared corners and outward (counter-clockwise) winding
+    pub fn unit_cube() -> (Vec<[f32; 3]>, Vec<u32>) {
+        let vertices = vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ];
+        #[rustfmt::skip]
+        let indices = vec![
+            0, 2, 1, 0, 3, 2, // bottom
+            4, 5, 6, 4, 6, 7, // top
+            0, 1, 5, 0, 5, 4, // front
+            2, 3, 7, 2, 7, 6, // back
+            1, 2, 6, 1, 6, 5, // right
+            0, 4, 7, 0, 7, 3, // left
+        ];
+        (vertices, indices)
+    }
+}
@@ -56,3 +56,104 @@ pub fn calculate_face_normals(vertices: &[[f32; 3]], indices: &[u32]) -> Vec<[f3
 
     normals
 }
+
+/// Reverse the winding of every triangle by swapping its last two corners
+pub fn flip_winding(indices: &mut [u32]) {
+    for tri in indices.chunks_exact_mut(3) {
+        tri.swap(1, 2);
+    }
+}
+
+/// Calculate area-weighted vertex normals
+///
+/// Each triangle adds its unnormalized cross product to its corners, so large
+/// faces dominate and slivers barely move the result.
+pub fn calculate_vertex_normals(vertices: &[[f32; 3]], indices: &[u32]) -> Vec<[f32; 3]> {
+    let mut sums = vec![glam::Vec3::ZERO; vertices.len()];
+
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| i as usize);
+        if a.max(b).max(c) >= vertices.len() {
+            continue;
+        }
+        let [p0, p1, p2] = [a, b, c].map(|i| glam::Vec3::from(vertices[i]));
+        let weighted = (p1 - p0).cross(p2 - p0);
+        for i in [a, b, c] {
+            sums[i] += weighted;
+        }
+    }
+
+    sums.into_iter()
+        .map(|n| n.try_normalize().unwrap_or(glam::Vec3::Z).to_array())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part::Part;
+
+    fn unit_cube() -> Part {
+        let mut part = Part::new("cube");
+        (part.vertices, part.indices) = crate::mesh::fixtures::unit_cube();
+        part
+    }
+
+    /// Whether every triangle's normal points away from the cube center
+    fn faces_outward(part: &Part) -> bool {
+        let center = glam::Vec3::splat(0.5);
+        part.indices
+            .chunks_exact(3)
+            .zip(&part.normals)
+            .all(|(tri, n)| {
+                let centroid = tri
+                    .iter()
+                    .map(|&i| glam::Vec3::from(part.vertices[i as usize]))
+                    .sum::<glam::Vec3>()
+                    / 3.0;
+                glam::Vec3::from(*n).dot(centroid - center) > 0.0
+            })
+    }
+
+    #[test]
+    fn test_flip_reverses_winding() {
+        let mut part = unit_cube();
+        part.normals = calculate_face_normals(&part.vertices, &part.indices);
+        let original = part.indices.clone();
+
+        part.flip_normals();
+        for (flipped, tri) in part.indices.chunks_exact(3).zip(original.chunks_exact(3)) {
+            assert_eq!(flipped, [tri[0], tri[2], tri[1]]);
+        }
+        assert!(!faces_outward(&part));
+
+        // Recomputing follows the new winding, so the flip sticks
+        part.recompute_normals();
+        assert!(!faces_outward(&part));
+        part.flip_normals();
+        part.recompute_normals();
+        assert_eq!(part.indices, original);
+        assert!(faces_outward(&part));
+    }
+
+    #[test]
+    fn test_recompute_normals_outward_on_cube() {
+        let mut part = unit_cube();
+
+        // Bogus per-triangle normals are replaced by face normals
+        part.normals = vec![[0.0, 0.0, -1.0]; 12];
+        part.recompute_normals();
+        assert_eq!(part.normals.len(), 12);
+        assert!(faces_outward(&part));
+
+        // One normal per vertex is kept as area-weighted vertex normals
+        part.normals = vec![[0.0; 3]; 8];
+        part.recompute_normals();
+        assert_eq!(part.normals.len(), 8);
+        let center = glam::Vec3::splat(0.5);
+        for (v, n) in part.vertices.iter().zip(&part.normals) {
+            let outward = (glam::Vec3::from(*v) - center).normalize();
+            assert!(glam::Vec3::from(*n).dot(outward) > 0.5);
+        }
+    }
+}
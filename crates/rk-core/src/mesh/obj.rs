@@ -80,6 +80,7 @@ pub fn load_obj_with_unit(path: impl AsRef<Path>, unit: StlUnit) -> Result<Part,
             vertices: all_vertices,
             normals: all_normals,
             indices: all_indices,
+            submeshes: Vec::new(),
         },
     );
 
@@ -1,15 +1,17 @@
-//! STL file loading
+//! STL file loading and saving
 
-use std::collections::HashMap;
-use std::io::BufReader;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use crate::export::{TargetConvention, sanitize_filename};
+use crate::naming::unique_name;
 use crate::part::Part;
 
-/// STL import scale unit
-#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+/// Length unit for mesh import scaling and length display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum StlUnit {
     /// Meters (no scaling)
     Meters,
@@ -47,6 +49,45 @@ impl StlUnit {
         StlUnit::Centimeters,
         StlUnit::Inches,
     ];
+
+    /// Convert meters to a value in this unit
+    pub fn from_meters(&self, meters: f32) -> f32 {
+        meters / self.scale_factor()
+    }
+
+    /// Convert a value in this unit to meters
+    pub fn to_meters(&self, value: f32) -> f32 {
+        value * self.scale_factor()
+    }
+
+    /// Decimal places shown for this unit
+    pub fn decimals(&self) -> usize {
+        match self {
+            StlUnit::Millimeters => 1,
+            StlUnit::Centimeters => 2,
+            StlUnit::Meters | StlUnit::Inches => 3,
+        }
+    }
+
+    /// Suffix for display
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            StlUnit::Meters => " m",
+            StlUnit::Millimeters => " mm",
+            StlUnit::Centimeters => " cm",
+            StlUnit::Inches => " in",
+        }
+    }
+
+    /// Format a length in meters as a display string with unit suffix
+    pub fn format(&self, meters: f32) -> String {
+        format!(
+            "{:.*}{}",
+            self.decimals(),
+            self.from_meters(meters),
+            self.suffix()
+        )
+    }
 }
 
 /// Load an STL file and create a Part (no scaling)
@@ -57,8 +98,7 @@ pub fn load_stl(path: impl AsRef<Path>) -> Result<Part, StlError> {
 /// Load an STL file with specified unit
 pub fn load_stl_with_unit(path: impl AsRef<Path>, unit: StlUnit) -> Result<Part, StlError> {
     let path = path.as_ref();
-    let file = std::fs::File::open(path).map_err(|e| StlError::Io(e.to_string()))?;
-    let mut reader = BufReader::new(file);
+    let data = std::fs::read(path).map_err(|e| StlError::Io(e.to_string()))?;
 
     let name = path
         .file_stem()
@@ -67,28 +107,27 @@ pub fn load_stl_with_unit(path: impl AsRef<Path>, unit: StlUnit) -> Result<Part,
         .to_string();
 
     let stl_path = Some(path.to_string_lossy().to_string());
-    load_stl_from_reader(&mut reader, &name, stl_path, unit)
+    load_stl_from_data(&data, &name, stl_path, unit)
 }
 
 /// Load an STL from bytes with specified unit (for WASM support)
 pub fn load_stl_from_bytes(name: &str, data: &[u8], unit: StlUnit) -> Result<Part, StlError> {
-    let mut cursor = std::io::Cursor::new(data);
-    load_stl_from_reader(&mut cursor, name, None, unit)
+    load_stl_from_data(data, name, None, unit)
 }
 
-/// Internal function to load STL from any reader
-fn load_stl_from_reader<R: std::io::Read + std::io::Seek>(
-    reader: &mut R,
+/// Internal function to load STL from an in-memory file
+fn load_stl_from_data(
+    data: &[u8],
     name: &str,
     stl_path: Option<String>,
     unit: StlUnit,
 ) -> Result<Part, StlError> {
-    let mesh = stl_io::read_stl(reader).map_err(|e| StlError::Parse(e.to_string()))?;
+    let triangles = parse_triangles(data)?;
 
     let scale = unit.scale_factor();
 
     // Convert to indexed mesh with scale
-    let (vertices, normals, indices) = index_mesh_with_scale(&mesh, scale);
+    let (vertices, normals, indices) = index_mesh_with_scale(&triangles, scale);
 
     let mut part = Part::new(name.to_string());
     super::finalize_part(
@@ -98,30 +137,116 @@ fn load_stl_from_reader<R: std::io::Read + std::io::Seek>(
             vertices,
             normals,
             indices,
+            submeshes: Vec::new(),
         },
     );
 
     Ok(part)
 }
 
+/// Size of the binary STL header (80-byte comment + triangle count)
+const BINARY_HEADER_LEN: usize = 84;
+/// Size of one binary STL triangle record (normal, 3 vertices, attribute)
+const BINARY_RECORD_LEN: usize = 50;
+
+/// A triangle as stored in an STL file
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StlTriangle {
+    normal: [f32; 3],
+    vertices: [[f32; 3]; 3],
+}
+
+/// Parse all triangles of a binary or ASCII STL file
+fn parse_triangles(data: &[u8]) -> Result<Vec<StlTriangle>, StlError> {
+    if let Some(records) = binary_records(data) {
+        return Ok(parse_binary(records));
+    }
+
+    // ASCII (or malformed binary) files go through stl_io serially
+    let mesh = stl_io::read_stl(&mut std::io::Cursor::new(data))
+        .map_err(|e| StlError::Parse(e.to_string()))?;
+    Ok(mesh
+        .faces
+        .iter()
+        .map(|face| StlTriangle {
+            normal: [face.normal[0], face.normal[1], face.normal[2]],
+            vertices: face.vertices.map(|i| {
+                let v = mesh.vertices[i];
+                [v[0], v[1], v[2]]
+            }),
+        })
+        .collect())
+}
+
+/// Return the triangle records if `data` is a well-formed binary STL
+///
+/// A file is treated as binary when its size matches the triangle count in
+/// the header exactly, which also catches binary files whose header starts
+/// with "solid".
+fn binary_records(data: &[u8]) -> Option<&[u8]> {
+    let count_bytes = data.get(80..BINARY_HEADER_LEN)?;
+    let count = u32::from_le_bytes(count_bytes.try_into().ok()?) as usize;
+    let expected = count
+        .checked_mul(BINARY_RECORD_LEN)?
+        .checked_add(BINARY_HEADER_LEN)?;
+    (data.len() == expected).then(|| &data[BINARY_HEADER_LEN..])
+}
+
+/// Decode one 50-byte binary triangle record
+fn parse_record(record: &[u8]) -> StlTriangle {
+    let f = |i: usize| f32::from_le_bytes(record[i * 4..i * 4 + 4].try_into().unwrap());
+    StlTriangle {
+        normal: [f(0), f(1), f(2)],
+        vertices: [[f(3), f(4), f(5)], [f(6), f(7), f(8)], [f(9), f(10), f(11)]],
+    }
+}
+
+/// Decode binary triangle records, in parallel when the `parallel` feature is enabled
+fn parse_binary(records: &[u8]) -> Vec<StlTriangle> {
+    #[cfg(feature = "parallel")]
+    {
+        parse_binary_parallel(records)
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        parse_binary_serial(records)
+    }
+}
+
+#[cfg_attr(feature = "parallel", allow(dead_code))]
+fn parse_binary_serial(records: &[u8]) -> Vec<StlTriangle> {
+    records
+        .chunks_exact(BINARY_RECORD_LEN)
+        .map(parse_record)
+        .collect()
+}
+
+#[cfg(feature = "parallel")]
+fn parse_binary_parallel(records: &[u8]) -> Vec<StlTriangle> {
+    use rayon::prelude::*;
+
+    records
+        .par_chunks_exact(BINARY_RECORD_LEN)
+        .map(parse_record)
+        .collect()
+}
+
 /// Convert triangle soup to indexed mesh with scale factor
 fn index_mesh_with_scale(
-    mesh: &stl_io::IndexedMesh,
+    triangles: &[StlTriangle],
     scale: f32,
 ) -> (Vec<[f32; 3]>, Vec<[f32; 3]>, Vec<u32>) {
     let mut unique_vertices: Vec<[f32; 3]> = Vec::new();
     let mut vertex_map: HashMap<[i32; 3], u32> = HashMap::new();
-    let mut indices: Vec<u32> = Vec::new();
-    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut indices: Vec<u32> = Vec::with_capacity(triangles.len() * 3);
+    let mut normals: Vec<[f32; 3]> = Vec::with_capacity(triangles.len());
 
     use crate::constants::STL_VERTEX_PRECISION;
 
-    for face in &mesh.faces {
-        let normal = face.normal;
-        normals.push([normal[0], normal[1], normal[2]]);
+    for triangle in triangles {
+        normals.push(triangle.normal);
 
-        for &vertex_idx in &face.vertices {
-            let vertex = mesh.vertices[vertex_idx];
+        for vertex in &triangle.vertices {
             // Apply scale factor
             let v = [vertex[0] * scale, vertex[1] * scale, vertex[2] * scale];
 
@@ -148,19 +273,45 @@ fn index_mesh_with_scale(
     (unique_vertices, normals, indices)
 }
 
-/// Save a Part as an STL file (with origin transform applied)
-pub fn save_stl(part: &Part, path: impl AsRef<Path>) -> Result<(), StlError> {
-    let path = path.as_ref();
+/// STL file encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StlFormat {
+    /// Compact binary records
+    #[default]
+    Binary,
+    /// Human-readable `facet`/`vertex` text
+    Ascii,
+}
+
+impl StlFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StlFormat::Binary => "Binary",
+            StlFormat::Ascii => "ASCII",
+        }
+    }
+
+    pub const ALL: &'static [StlFormat] = &[StlFormat::Binary, StlFormat::Ascii];
+}
+
+/// Triangles of a part with its origin transform applied, in `unit` and the
+/// axes of `convention`
+fn part_triangles(part: &Part, unit: StlUnit, convention: &TargetConvention) -> Vec<StlTriangle> {
+    let scale = unit.scale_factor();
+    let basis = convention.basis();
+    // A mirrored target flips every triangle's winding
+    let mirrored = basis.determinant() < 0.0;
 
     // Apply origin transform to vertices
     let transformed_vertices: Vec<[f32; 3]> = part
         .vertices
         .iter()
         .map(|v| {
-            let p = part.origin_transform.transform_point3(glam::Vec3::from(*v));
-            [p.x, p.y, p.z]
+            let p = part.origin_transform.transform_point3(glam::Vec3::from(*v)) / scale;
+            (basis * p).to_array()
         })
         .collect();
+    let normal_mat = glam::Mat4::from_mat3(basis) * part.origin_transform.inverse().transpose();
 
     // Rebuild triangles
     let mut triangles = Vec::new();
@@ -170,16 +321,19 @@ pub fn save_stl(part: &Part, path: impl AsRef<Path>) -> Result<(), StlError> {
         }
 
         let v0 = transformed_vertices[chunk[0] as usize];
-        let v1 = transformed_vertices[chunk[1] as usize];
-        let v2 = transformed_vertices[chunk[2] as usize];
+        let (mut v1, mut v2) = (
+            transformed_vertices[chunk[1] as usize],
+            transformed_vertices[chunk[2] as usize],
+        );
+        if mirrored {
+            std::mem::swap(&mut v1, &mut v2);
+        }
 
         // Get or calculate normal
         let normal = if i < part.normals.len() {
-            let n = part.normals[i];
             // Transform normal
-            let normal_mat = part.origin_transform.inverse().transpose();
             let transformed = normal_mat
-                .transform_vector3(glam::Vec3::from(n))
+                .transform_vector3(glam::Vec3::from(part.normals[i]))
                 .normalize();
             [transformed.x, transformed.y, transformed.z]
         } else {
@@ -199,20 +353,129 @@ pub fn save_stl(part: &Part, path: impl AsRef<Path>) -> Result<(), StlError> {
             }
         };
 
-        triangles.push(stl_io::Triangle {
-            normal: stl_io::Normal::new(normal),
-            vertices: [
-                stl_io::Vertex::new(v0),
-                stl_io::Vertex::new(v1),
-                stl_io::Vertex::new(v2),
-            ],
+        triangles.push(StlTriangle {
+            normal,
+            vertices: [v0, v1, v2],
         });
     }
+    triangles
+}
 
-    let mut file = std::fs::File::create(path).map_err(|e| StlError::Io(e.to_string()))?;
-    stl_io::write_stl(&mut file, triangles.iter()).map_err(|e| StlError::Write(e.to_string()))?;
+/// Encode triangles as an STL file named `name` (used for the ASCII `solid` line)
+fn encode_stl(
+    triangles: &[StlTriangle],
+    name: &str,
+    format: StlFormat,
+) -> Result<Vec<u8>, StlError> {
+    match format {
+        StlFormat::Binary => {
+            let mut data =
+                Vec::with_capacity(BINARY_HEADER_LEN + triangles.len() * BINARY_RECORD_LEN);
+            let triangles = triangles.iter().map(|t| stl_io::Triangle {
+                normal: stl_io::Normal::new(t.normal),
+                vertices: t.vertices.map(stl_io::Vertex::new),
+            });
+            stl_io::write_stl(&mut data, triangles).map_err(|e| StlError::Write(e.to_string()))?;
+            Ok(data)
+        }
+        StlFormat::Ascii => {
+            use std::fmt::Write;
+
+            // Solid names end at whitespace
+            let name: String = name
+                .chars()
+                .map(|c| if c.is_whitespace() { '_' } else { c })
+                .collect();
+            let mut text = format!("solid {name}\n");
+            for t in triangles {
+                let [nx, ny, nz] = t.normal;
+                let _ = writeln!(text, "  facet normal {nx:e} {ny:e} {nz:e}");
+                text.push_str("    outer loop\n");
+                for [x, y, z] in t.vertices {
+                    let _ = writeln!(text, "      vertex {x:e} {y:e} {z:e}");
+                }
+                text.push_str("    endloop\n  endfacet\n");
+            }
+            let _ = writeln!(text, "endsolid {name}");
+            Ok(text.into_bytes())
+        }
+    }
+}
+
+/// Save a Part as an STL file (with origin transform applied)
+pub fn save_stl(part: &Part, path: impl AsRef<Path>) -> Result<(), StlError> {
+    save_stl_with_options(part, path, StlUnit::Meters, StlFormat::Binary)
+}
+
+/// Save a Part as an STL file in the given unit and encoding
+pub fn save_stl_with_options(
+    part: &Part,
+    path: impl AsRef<Path>,
+    unit: StlUnit,
+    format: StlFormat,
+) -> Result<(), StlError> {
+    let data = encode_stl(
+        &part_triangles(part, unit, &TargetConvention::NATIVE),
+        &part.name,
+        format,
+    )?;
+    std::fs::write(path, data).map_err(|e| StlError::Io(e.to_string()))
+}
+
+/// Save each part as `<name>.stl` in `dir`, returning the file used by each part
+///
+/// Coordinates are written in the axes of `convention`.
+///
+/// File names are sanitized part names, suffixed with `_1`, `_2`, ... when they
+/// would collide with each other or with a file already in `dir`
+/// (case-insensitively, for case-insensitive file systems), so existing files
+/// are never overwritten. Parts with the same [`Part::mesh_hash`] and origin
+/// transform share the first part's file.
+pub fn save_parts_stl<'a>(
+    parts: impl IntoIterator<Item = &'a Part>,
+    dir: impl AsRef<Path>,
+    unit: StlUnit,
+    format: StlFormat,
+    convention: &TargetConvention,
+) -> Result<Vec<(Uuid, PathBuf)>, StlError> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).map_err(|e| StlError::Io(e.to_string()))?;
+
+    let mut taken: HashSet<String> = std::fs::read_dir(dir)
+        .map_err(|e| StlError::Io(e.to_string()))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("stl"))
+        })
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_lowercase()))
+        .collect();
+    let mut written: HashMap<(u64, [u32; 16]), PathBuf> = HashMap::new();
+    let mut files = Vec::new();
+    for part in parts {
+        // Exported triangles have the origin transform baked in
+        let key = (
+            part.mesh_hash(),
+            part.origin_transform.to_cols_array().map(f32::to_bits),
+        );
+        if let Some(path) = written.get(&key) {
+            files.push((part.id, path.clone()));
+            continue;
+        }
+        let triangles = part_triangles(part, unit, convention);
+
+        let stem = unique_name(&sanitize_filename(&part.name), |name| {
+            taken.contains(&name.to_lowercase())
+        });
+        taken.insert(stem.to_lowercase());
+        let path = dir.join(format!("{stem}.stl"));
+        std::fs::write(&path, encode_stl(&triangles, &stem, format)?)
+            .map_err(|e| StlError::Io(e.to_string()))?;
 
-    Ok(())
+        written.insert(key, path.clone());
+        files.push((part.id, path));
+    }
+    Ok(files)
 }
 
 /// STL-related errors
@@ -225,3 +488,235 @@ pub enum StlError {
     #[error("Write error: {0}")]
     Write(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::PrimitiveShape;
+
+    #[test]
+    fn test_unit_round_trip() {
+        for unit in StlUnit::ALL {
+            for meters in [0.0, 0.0254, 0.125, -1.5, 12.0] {
+                let back = unit.to_meters(unit.from_meters(meters));
+                assert!((back - meters).abs() < 1e-6, "{unit:?}: {meters} -> {back}");
+            }
+        }
+        assert!((StlUnit::Inches.from_meters(0.0254) - 1.0).abs() < 1e-6);
+        assert!((StlUnit::Millimeters.to_meters(250.0) - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_unit_format() {
+        assert_eq!(StlUnit::Meters.format(0.125), "0.125 m");
+        assert_eq!(StlUnit::Millimeters.format(0.0125), "12.5 mm");
+        assert_eq!(StlUnit::Centimeters.format(0.0125), "1.25 cm");
+        assert_eq!(StlUnit::Inches.format(0.0508), "2.000 in");
+    }
+
+    /// Triangulated height field with `n * n` quads
+    fn grid_triangles(n: usize) -> Vec<StlTriangle> {
+        let p = |x: usize, y: usize| [x as f32, y as f32, ((x * 7 + y * 3) % 5) as f32 * 0.1];
+        let mut triangles = Vec::new();
+        for x in 0..n {
+            for y in 0..n {
+                let normal = [0.0, 0.0, 1.0];
+                triangles.push(StlTriangle {
+                    normal,
+                    vertices: [p(x, y), p(x + 1, y), p(x + 1, y + 1)],
+                });
+                triangles.push(StlTriangle {
+                    normal,
+                    vertices: [p(x, y), p(x + 1, y + 1), p(x, y + 1)],
+                });
+            }
+        }
+        triangles
+    }
+
+    fn to_binary(triangles: &[StlTriangle]) -> Vec<u8> {
+        // Header deliberately starts with "solid" like many exporters write
+        let mut data = b"solid binary".to_vec();
+        data.resize(80, 0);
+        data.extend_from_slice(&(triangles.len() as u32).to_le_bytes());
+        for t in triangles {
+            for f in t.normal.iter().chain(t.vertices.iter().flatten()) {
+                data.extend_from_slice(&f.to_le_bytes());
+            }
+            data.extend_from_slice(&[0, 0]);
+        }
+        data
+    }
+
+    fn to_ascii(triangles: &[StlTriangle]) -> Vec<u8> {
+        let mut text = String::from("solid ascii\n");
+        for t in triangles {
+            let [nx, ny, nz] = t.normal;
+            text += &format!("facet normal {nx} {ny} {nz}\nouter loop\n");
+            for [x, y, z] in t.vertices {
+                text += &format!("vertex {x} {y} {z}\n");
+            }
+            text += "endloop\nendfacet\n";
+        }
+        text += "endsolid ascii\n";
+        text.into_bytes()
+    }
+
+    #[test]
+    fn test_binary_detection() {
+        let triangles = grid_triangles(2);
+        let binary = to_binary(&triangles);
+        assert_eq!(binary_records(&binary).map(<[u8]>::len), Some(8 * 50));
+        assert!(binary_records(&binary[..binary.len() - 1]).is_none());
+        assert!(binary_records(&to_ascii(&triangles)).is_none());
+        assert_eq!(parse_triangles(&binary).unwrap(), triangles);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        let triangles = grid_triangles(64);
+        let binary = to_binary(&triangles);
+        let records = binary_records(&binary).unwrap();
+
+        let serial = parse_binary_serial(records);
+        let parallel = parse_binary_parallel(records);
+        assert_eq!(serial.len(), 64 * 64 * 2);
+        assert_eq!(parallel, serial);
+        assert_eq!(serial, triangles);
+    }
+
+    #[test]
+    fn test_binary_and_ascii_weld_identically() {
+        let triangles = grid_triangles(4);
+        let binary = load_stl_from_bytes("grid", &to_binary(&triangles), StlUnit::Meters).unwrap();
+        let ascii = load_stl_from_bytes("grid", &to_ascii(&triangles), StlUnit::Meters).unwrap();
+
+        // (n + 1)^2 shared vertices after welding
+        assert_eq!(binary.vertices.len(), 25);
+        assert_eq!(binary.indices.len(), 4 * 4 * 2 * 3);
+        assert_eq!(ascii.vertices.len(), binary.vertices.len());
+        assert_eq!(ascii.indices.len(), binary.indices.len());
+    }
+
+    #[test]
+    fn test_save_parts_stl_names_and_dedup() {
+        let mut parts: Vec<Part> = ["arm", "arm", "Arm", "base plate", "copy"]
+            .iter()
+            .map(|name| Part::from_primitive(*name, PrimitiveShape::Box { size: [0.1; 3] }))
+            .collect();
+        // Every mesh differs except "copy", which matches the first "arm"
+        for (i, part) in parts.iter_mut().enumerate().take(4) {
+            part.origin_transform = glam::Mat4::from_translation(glam::Vec3::X * i as f32);
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let files = save_parts_stl(
+            &parts,
+            dir.path(),
+            StlUnit::Millimeters,
+            StlFormat::Ascii,
+            &TargetConvention::NATIVE,
+        )
+        .unwrap();
+
+        let names: Vec<String> = files
+            .iter()
+            .map(|(_, path)| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "arm.stl",
+                "arm_1.stl",
+                "Arm_2.stl",
+                "base_plate.stl",
+                "arm.stl"
+            ]
+        );
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 4);
+        assert!(
+            files
+                .iter()
+                .zip(&parts)
+                .all(|((id, _), part)| *id == part.id)
+        );
+
+        // Written in the chosen unit: the second arm sits 1 m = 1000 mm along X
+        let reloaded = load_stl_with_unit(&files[1].1, StlUnit::Millimeters).unwrap();
+        assert!((reloaded.bbox_min[0] - 0.95).abs() < 1e-4);
+        assert_eq!(reloaded.indices.len(), parts[1].indices.len());
+    }
+
+    #[test]
+    fn test_save_parts_stl_keeps_existing_files() {
+        let part = Part::from_primitive("arm", PrimitiveShape::Box { size: [0.1; 3] });
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ARM.stl"), "keep").unwrap();
+
+        let files = save_parts_stl(
+            [&part],
+            dir.path(),
+            StlUnit::Meters,
+            StlFormat::Binary,
+            &TargetConvention::NATIVE,
+        )
+        .unwrap();
+        assert_eq!(files[0].1.file_name().unwrap(), "arm_1.stl");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("ARM.stl")).unwrap(),
+            "keep"
+        );
+    }
+
+    #[test]
+    fn test_save_parts_stl_converts_to_target_convention() {
+        // A box lifted along Z, so the up axis shows in the coordinates
+        let mut part = Part::from_primitive("box", PrimitiveShape::Box { size: [0.1; 3] });
+        part.origin_transform = glam::Mat4::from_translation(glam::Vec3::Z);
+        let export = |convention: &TargetConvention| {
+            let dir = tempfile::tempdir().unwrap();
+            let files = save_parts_stl(
+                [&part],
+                dir.path(),
+                StlUnit::Meters,
+                StlFormat::Binary,
+                convention,
+            )
+            .unwrap();
+            load_stl_with_unit(&files[0].1, StlUnit::Meters).unwrap()
+        };
+
+        let native = export(&TargetConvention::NATIVE);
+        let gltf = export(&TargetConvention::GLTF);
+        let center = |part: &Part| {
+            part.vertices
+                .iter()
+                .map(|v| glam::Vec3::from(*v))
+                .sum::<glam::Vec3>()
+                / part.vertices.len() as f32
+        };
+        assert!(center(&native).abs_diff_eq(glam::Vec3::Z, 1e-4));
+        assert!(center(&gltf).abs_diff_eq(glam::Vec3::Y, 1e-4));
+        assert_ne!(native.vertices, gltf.vertices);
+
+        // Winding stays outward after a mirror
+        let left = TargetConvention {
+            handedness: crate::export::transform_convert::Handedness::Left,
+            ..TargetConvention::NATIVE
+        };
+        let mirrored = export(&left);
+        let volume = |part: &Part| {
+            part.indices
+                .chunks(3)
+                .map(|t| {
+                    let [a, b, c] =
+                        [0, 1, 2].map(|k| glam::Vec3::from(part.vertices[t[k] as usize]));
+                    a.dot(b.cross(c)) / 6.0
+                })
+                .sum::<f32>()
+        };
+        assert!(volume(&mirrored) > 0.0);
+        assert!((volume(&mirrored) - volume(&native)).abs() < 1e-6);
+    }
+}
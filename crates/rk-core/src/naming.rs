@@ -0,0 +1,28 @@
+//! Name de-duplication shared by parts, links and joints
+//!
+//! URDF requires link and joint names to be unique, so every creation path
+//! suffixes colliding names with `_1`, `_2`, ...
+
+/// Return `base` if it is free, otherwise the first free `base_N` (N >= 1)
+pub fn unique_name(base: &str, is_taken: impl Fn(&str) -> bool) -> String {
+    if !is_taken(base) {
+        return base.to_string();
+    }
+    (1..)
+        .map(|n| format!("{base}_{n}"))
+        .find(|candidate| !is_taken(candidate))
+        .expect("unbounded suffix search always finds a free name")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_name_skips_taken_suffixes() {
+        let taken = ["arm", "arm_1", "arm_3"];
+        let is_taken = |name: &str| taken.contains(&name);
+        assert_eq!(unique_name("leg", is_taken), "leg");
+        assert_eq!(unique_name("arm", is_taken), "arm_2");
+    }
+}
@@ -5,6 +5,12 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::inertia::InertiaMatrix;
+use crate::mesh::{
+    MeshReport, Submesh, analyze_mesh, calculate_face_normals, calculate_vertex_normals,
+    flip_winding,
+};
+use crate::primitive::PrimitiveShape;
+use crate::project::MaterialDef;
 use crate::types::{JointLimits, JointType};
 
 /// A part loaded from an STL file with metadata
@@ -34,8 +40,20 @@ pub struct Part {
     pub color: [f32; 4],
     /// Material name for URDF
     pub material_name: Option<String>,
+    /// Per-triangle materials imported from the mesh file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub submeshes: Vec<Submesh>,
     /// Mirror pair information
     pub mirror_pair: Option<MirrorPair>,
+    /// Locked parts can be selected but not moved with the gizmo
+    #[serde(default)]
+    pub locked: bool,
+    /// Connector frames used to mate this part to others
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub joint_points: Vec<JointPoint>,
+    /// Shape the mesh was generated from, for primitive parts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primitive: Option<PrimitiveShape>,
 }
 
 impl Part {
@@ -55,10 +73,123 @@ impl Part {
             bbox_max: [0.0; 3],
             color: [0.7, 0.7, 0.7, 1.0],
             material_name: None,
+            submeshes: Vec::new(),
             mirror_pair: None,
+            locked: false,
+            joint_points: Vec::new(),
+            primitive: None,
         }
     }
 
+    /// Create a part meshed from a primitive shape
+    pub fn from_primitive(name: impl Into<String>, shape: PrimitiveShape) -> Self {
+        let mut part = Self::new(name);
+        part.set_primitive(shape);
+        part
+    }
+
+    /// Replace the mesh with a freshly tessellated primitive shape
+    pub fn set_primitive(&mut self, shape: PrimitiveShape) {
+        let (vertices, normals, indices) = shape.generate();
+        self.vertices = vertices;
+        self.normals = normals;
+        self.indices = indices;
+        self.primitive = Some(shape);
+        self.calculate_bounding_box();
+    }
+
+    /// Re-tessellate a round primitive with a new segment count
+    ///
+    /// Returns false if the part is not a round primitive or already uses that count.
+    pub fn set_primitive_segments(&mut self, segments: u32) -> bool {
+        let Some(shape) = self.primitive else {
+            return false;
+        };
+        let updated = shape.with_segments(segments);
+        if updated == shape {
+            return false;
+        }
+        self.set_primitive(updated);
+        true
+    }
+
+    /// Set a user-chosen color for the whole part
+    ///
+    /// Imported submesh materials are dropped, so the color also wins after
+    /// the mesh is re-uploaded or the project is reloaded.
+    pub fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+        self.submeshes.clear();
+    }
+
+    /// Material covering the most triangles, if the mesh file had materials
+    pub fn primary_material(&self) -> Option<&MaterialDef> {
+        // Reversed so ties go to the earliest submesh
+        self.submeshes
+            .iter()
+            .rev()
+            .max_by_key(|s| s.triangle_count)
+            .map(|s| &s.material)
+    }
+
+    /// Whether imported submeshes use more than one color
+    pub fn has_submesh_colors(&self) -> bool {
+        self.submeshes
+            .iter()
+            .any(|s| s.material.color != self.submeshes[0].material.color)
+    }
+
+    /// Imported material of the given triangle, if any
+    pub fn triangle_material(&self, triangle: u32) -> Option<&MaterialDef> {
+        self.submeshes
+            .iter()
+            .find(|s| s.contains(triangle))
+            .map(|s| &s.material)
+    }
+
+    /// Reverse triangle winding and negate normals, turning the mesh inside out
+    pub fn flip_normals(&mut self) {
+        flip_winding(&mut self.indices);
+        for n in &mut self.normals {
+            *n = [-n[0], -n[1], -n[2]];
+        }
+    }
+
+    /// Recompute normals from the current triangle winding
+    ///
+    /// A part with one normal per vertex gets area-weighted vertex normals;
+    /// otherwise one face normal per triangle, as the renderer expects.
+    pub fn recompute_normals(&mut self) {
+        let per_vertex = self.normals.len() == self.vertices.len()
+            && self.normals.len() != self.indices.len() / 3;
+        self.normals = if per_vertex {
+            calculate_vertex_normals(&self.vertices, &self.indices)
+        } else {
+            calculate_face_normals(&self.vertices, &self.indices)
+        };
+    }
+
+    /// Analyze the mesh for degenerate, duplicate and non-manifold geometry
+    pub fn analyze_mesh(&self) -> MeshReport {
+        analyze_mesh(&self.vertices, &self.indices)
+    }
+
+    /// Hash of the mesh geometry (vertex positions and indices)
+    ///
+    /// Parts loaded from the same mesh file hash equal regardless of name,
+    /// placement or mass. Only stable within a single build of the editor.
+    pub fn mesh_hash(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.vertices.len().hash(&mut hasher);
+        for v in &self.vertices {
+            v.map(f32::to_bits).hash(&mut hasher);
+        }
+        self.indices.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Calculate bounding box from vertices
     pub fn calculate_bounding_box(&mut self) {
         if self.vertices.is_empty() {
@@ -98,6 +229,42 @@ impl Part {
             self.bbox_max[2] - self.bbox_min[2],
         )
     }
+
+    /// Get a joint point by ID
+    pub fn joint_point(&self, id: Uuid) -> Option<&JointPoint> {
+        self.joint_points.iter().find(|p| p.id == id)
+    }
+
+    /// World position and orientation of a joint point under `origin_transform`
+    pub fn joint_point_world(&self, id: Uuid) -> Option<(Vec3, Quat)> {
+        let point = self.joint_point(id)?;
+        let (_, rotation, _) = self.origin_transform.to_scale_rotation_translation();
+        Some((
+            self.origin_transform.transform_point3(point.position),
+            (rotation * point.orientation).normalize(),
+        ))
+    }
+
+    /// Origin transform that brings joint point `point_id` onto a world frame
+    ///
+    /// The part keeps its scale; only its rotation and translation change so
+    /// the point's position and orientation match the target frame.
+    pub fn mate_transform(
+        &self,
+        point_id: Uuid,
+        target_position: Vec3,
+        target_orientation: Quat,
+    ) -> Option<Mat4> {
+        let point = self.joint_point(point_id)?;
+        let (scale, _, _) = self.origin_transform.to_scale_rotation_translation();
+        let rotation = (target_orientation * point.orientation.inverse()).normalize();
+        let translation = target_position - rotation * (scale * point.position);
+        Some(Mat4::from_scale_rotation_translation(
+            scale,
+            rotation,
+            translation,
+        ))
+    }
 }
 
 /// Joint connection point on a part
@@ -26,7 +26,7 @@ pub fn generate_cylinder_mesh_with_segments(radius: f32, length: f32, segments:
 
     // Generate side vertices
     for i in 0..=segments {
-        let theta = (i as f32 / segments as f32) * 2.0 * PI;
+        let theta = seam_angle(i, segments);
         let x = radius * theta.cos();
         let y = radius * theta.sin();
         let nx = theta.cos();
@@ -62,7 +62,7 @@ pub fn generate_cylinder_mesh_with_segments(radius: f32, length: f32, segments:
     // Top cap rim vertices
     let top_rim_start = vertices.len() as u32;
     for i in 0..=segments {
-        let theta = (i as f32 / segments as f32) * 2.0 * PI;
+        let theta = seam_angle(i, segments);
         let x = radius * theta.cos();
         let y = radius * theta.sin();
         vertices.push([x, y, half_length]);
@@ -84,7 +84,7 @@ pub fn generate_cylinder_mesh_with_segments(radius: f32, length: f32, segments:
     // Bottom cap rim vertices
     let bottom_rim_start = vertices.len() as u32;
     for i in 0..=segments {
-        let theta = (i as f32 / segments as f32) * 2.0 * PI;
+        let theta = seam_angle(i, segments);
         let x = radius * theta.cos();
         let y = radius * theta.sin();
         vertices.push([x, y, -half_length]);
@@ -100,3 +100,9 @@ pub fn generate_cylinder_mesh_with_segments(radius: f32, length: f32, segments:
 
     (vertices, normals, indices)
 }
+
+/// Angle of rim vertex `i`; the last vertex repeats the first exactly so the
+/// seam closes
+fn seam_angle(i: u32, segments: u32) -> f32 {
+    ((i % segments) as f32 / segments as f32) * 2.0 * PI
+}
@@ -9,6 +9,10 @@ mod box_mesh;
 mod cylinder;
 mod sphere;
 
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{MAX_PRIMITIVE_SEGMENTS, MIN_PRIMITIVE_SEGMENTS};
+
 pub use box_mesh::generate_box_mesh;
 pub use cylinder::{generate_cylinder_mesh, generate_cylinder_mesh_with_segments};
 pub use sphere::{generate_sphere_mesh, generate_sphere_mesh_with_segments};
@@ -16,6 +20,62 @@ pub use sphere::{generate_sphere_mesh, generate_sphere_mesh_with_segments};
 /// Mesh data: vertices, normals, and triangle indices
 pub type MeshData = (Vec<[f32; 3]>, Vec<[f32; 3]>, Vec<u32>);
 
+/// Parameters a primitive part was generated from, kept so it can be re-tessellated
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PrimitiveShape {
+    Box {
+        size: [f32; 3],
+    },
+    /// Cylinder along Z with `segments` around its circumference
+    Cylinder {
+        radius: f32,
+        length: f32,
+        segments: u32,
+    },
+    /// UV sphere with `segments` around the equator and half as many bands
+    Sphere {
+        radius: f32,
+        segments: u32,
+    },
+}
+
+impl PrimitiveShape {
+    /// Segment count for round shapes, `None` for boxes
+    pub fn segments(&self) -> Option<u32> {
+        match self {
+            Self::Box { .. } => None,
+            Self::Cylinder { segments, .. } | Self::Sphere { segments, .. } => Some(*segments),
+        }
+    }
+
+    /// Same shape with a new segment count, clamped to the supported range
+    pub fn with_segments(mut self, new_segments: u32) -> Self {
+        let new_segments = new_segments.clamp(MIN_PRIMITIVE_SEGMENTS, MAX_PRIMITIVE_SEGMENTS);
+        match &mut self {
+            Self::Box { .. } => {}
+            Self::Cylinder { segments, .. } | Self::Sphere { segments, .. } => {
+                *segments = new_segments;
+            }
+        }
+        self
+    }
+
+    /// Tessellate the shape
+    pub fn generate(&self) -> MeshData {
+        match *self {
+            Self::Box { size } => generate_box_mesh(size),
+            Self::Cylinder {
+                radius,
+                length,
+                segments,
+            } => generate_cylinder_mesh_with_segments(radius, length, segments),
+            Self::Sphere { radius, segments } => {
+                generate_sphere_mesh_with_segments(radius, (segments / 2).max(2), segments)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,4 +121,44 @@ mod tests {
         assert!((max[1] - min[1] - 4.0).abs() < 0.001);
         assert!((max[2] - min[2] - 6.0).abs() < 0.001);
     }
+
+    /// Whether every edge, matched by vertex position, borders exactly two triangles
+    fn is_closed((vertices, _, indices): &MeshData) -> bool {
+        let key = |i: u32| vertices[i as usize].map(|c| (c * 1e5).round() as i64);
+        let mut edges = std::collections::HashMap::new();
+        for tri in indices.chunks_exact(3) {
+            for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                let (a, b) = (key(a), key(b));
+                let edge = if a < b { (a, b) } else { (b, a) };
+                *edges.entry(edge).or_insert(0) += 1;
+            }
+        }
+        edges.values().all(|&uses| uses == 2)
+    }
+
+    #[test]
+    fn test_cylinder_segments_refine_closed_mesh() {
+        let coarse = PrimitiveShape::Cylinder {
+            radius: 0.05,
+            length: 0.1,
+            segments: 8,
+        };
+        let fine = coarse.with_segments(64);
+        assert_eq!(fine.segments(), Some(64));
+
+        let coarse_mesh = coarse.generate();
+        let fine_mesh = fine.generate();
+        assert!(fine_mesh.2.len() > coarse_mesh.2.len());
+        // Side quads plus two caps: 4 triangles per segment
+        assert_eq!(fine_mesh.2.len() / 3, 4 * 64);
+        assert!(is_closed(&coarse_mesh));
+        assert!(is_closed(&fine_mesh));
+
+        assert_eq!(
+            coarse.with_segments(1).segments(),
+            Some(MIN_PRIMITIVE_SEGMENTS)
+        );
+        let cube = PrimitiveShape::Box { size: [1.0; 3] };
+        assert_eq!(cube.with_segments(64), cube);
+    }
 }
@@ -38,7 +38,7 @@ pub fn generate_sphere_mesh_with_segments(
         let cos_theta = theta.cos();
 
         for lon in 0..=lon_segments {
-            let phi = (lon as f32 / lon_segments as f32) * 2.0 * PI; // 0 to 2*PI
+            let phi = ((lon % lon_segments) as f32 / lon_segments as f32) * 2.0 * PI; // seam repeats 0
             let sin_phi = phi.sin();
             let cos_phi = phi.cos();
 
@@ -1,13 +1,16 @@
 //! Project file serialization
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
+use glam::{Mat4, Vec3};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::assembly::Assembly;
+use crate::naming::unique_name;
 use crate::part::Part;
+use crate::types::JointType;
 
 /// Serialization format for backward compatibility
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +20,8 @@ struct ProjectData {
     parts: Vec<Part>,
     assembly: Assembly,
     materials: Vec<MaterialDef>,
+    #[serde(default)]
+    viewport: Option<ViewportSettings>,
 }
 
 /// Project file containing all editor state
@@ -32,6 +37,8 @@ pub struct Project {
     pub assembly: Assembly,
     /// Material definitions
     pub materials: Vec<MaterialDef>,
+    /// Viewport state from the last save (`None` uses the editor defaults)
+    pub viewport: Option<ViewportSettings>,
 }
 
 impl From<Project> for ProjectData {
@@ -42,6 +49,7 @@ impl From<Project> for ProjectData {
             parts: project.parts.into_values().collect(),
             assembly: project.assembly,
             materials: project.materials,
+            viewport: project.viewport,
         }
     }
 }
@@ -55,6 +63,7 @@ impl From<ProjectData> for Project {
             parts,
             assembly: data.assembly,
             materials: data.materials,
+            viewport: data.viewport,
         }
     }
 }
@@ -70,6 +79,7 @@ impl Serialize for Project {
             parts: self.parts.values().cloned().collect(),
             assembly: self.assembly.clone(),
             materials: self.materials.clone(),
+            viewport: self.viewport.clone(),
         };
         data.serialize(serializer)
     }
@@ -100,22 +110,27 @@ impl Project {
             parts: HashMap::new(),
             assembly: Assembly::default(),
             materials: Vec::new(),
+            viewport: None,
         }
     }
 
     /// Create a project with all fields specified (used by import)
     pub fn with_parts(
         name: impl Into<String>,
-        parts: HashMap<Uuid, Part>,
+        mut parts: HashMap<Uuid, Part>,
         assembly: Assembly,
-        materials: Vec<MaterialDef>,
+        mut materials: Vec<MaterialDef>,
     ) -> Self {
+        for part in parts.values_mut() {
+            merge_part_materials(&mut materials, part);
+        }
         Self {
             version: 1,
             name: name.into(),
             parts,
             assembly,
             materials,
+            viewport: None,
         }
     }
 
@@ -169,9 +184,21 @@ impl Project {
         self.parts.values()
     }
 
+    /// Return `base`, or `base_N` if a part already uses that name
+    pub fn unique_part_name(&self, base: &str) -> String {
+        unique_name(base, |name| self.parts.values().any(|p| p.name == name))
+    }
+
     /// Add a part to the project, returns the part ID
-    pub fn add_part(&mut self, part: Part) -> Uuid {
+    ///
+    /// A name already used by another part is suffixed to keep names unique.
+    /// Materials imported with the part's mesh are added to the project.
+    pub fn add_part(&mut self, mut part: Part) -> Uuid {
         let id = part.id;
+        part.name = unique_name(&part.name, |name| {
+            self.parts.values().any(|p| p.id != id && p.name == name)
+        });
+        merge_part_materials(&mut self.materials, &mut part);
         self.parts.insert(id, part);
         id
     }
@@ -190,10 +217,234 @@ impl Project {
     pub fn remove_part(&mut self, id: Uuid) -> Option<Part> {
         self.parts.remove(&id)
     }
+
+    /// Move a part so one of its joint points mates with another part's
+    ///
+    /// Returns the part's new origin transform, or `None` if either part or
+    /// point is missing, or the moving part is locked.
+    pub fn mate_part(
+        &mut self,
+        part_id: Uuid,
+        point_id: Uuid,
+        target_part_id: Uuid,
+        target_point_id: Uuid,
+    ) -> Option<Mat4> {
+        if part_id == target_part_id {
+            return None;
+        }
+        let (position, orientation) = self
+            .parts
+            .get(&target_part_id)?
+            .joint_point_world(target_point_id)?;
+        let part = self.parts.get_mut(&part_id)?;
+        if part.locked {
+            return None;
+        }
+        part.origin_transform = part.mate_transform(point_id, position, orientation)?;
+        Some(part.origin_transform)
+    }
+
+    // ============== Statistics ==============
+
+    /// Summarize the robot model for a quick health check
+    ///
+    /// Mass is summed over link inertials; triangles and bounds cover the
+    /// parts referenced by links, placed by their origin transform.
+    pub fn statistics(&self) -> ProjectStats {
+        let assembly = &self.assembly;
+        let mut stats = ProjectStats {
+            link_count: assembly.links.len(),
+            joint_count: assembly.joints.len(),
+            movable_dof: assembly.joints.values().map(|j| j.joint_type.dof()).sum(),
+            total_mass: assembly.links.values().map(|l| l.inertial.mass).sum(),
+            ..Default::default()
+        };
+
+        let mut bounds: Option<(Vec3, Vec3)> = None;
+        for part in assembly
+            .links
+            .values()
+            .filter_map(|link| link.part_id.and_then(|id| self.parts.get(&id)))
+        {
+            stats.triangle_count += part.indices.len() / 3;
+            if part.vertices.is_empty() {
+                continue;
+            }
+            let (min, max) = (Vec3::from(part.bbox_min), Vec3::from(part.bbox_max));
+            for i in 0..8 {
+                let corner = Vec3::new(
+                    if i & 1 == 0 { min.x } else { max.x },
+                    if i & 2 == 0 { min.y } else { max.y },
+                    if i & 4 == 0 { min.z } else { max.z },
+                );
+                let p = part.origin_transform.transform_point3(corner);
+                bounds = Some(match bounds {
+                    Some((lo, hi)) => (lo.min(p), hi.max(p)),
+                    None => (p, p),
+                });
+            }
+        }
+        stats.bounding_box = bounds.map(|(lo, hi)| (lo.to_array(), hi.to_array()));
+        stats
+    }
+
+    // ============== Bill of Materials ==============
+
+    /// List each unique part with its quantity, mass and bounding dimensions
+    ///
+    /// Parts are grouped by `Part::mesh_hash`; parts without geometry are
+    /// grouped by name instead. Rows are sorted by name.
+    pub fn bill_of_materials(&self) -> Vec<BomRow> {
+        let mut groups: HashMap<(u64, Option<&str>), BomRow> = HashMap::new();
+        for part in self.parts.values() {
+            let key = if part.vertices.is_empty() {
+                (0, Some(part.name.as_str()))
+            } else {
+                (part.mesh_hash(), None)
+            };
+            let row = groups.entry(key).or_insert_with(|| BomRow {
+                name: part.name.clone(),
+                mesh_hash: key.0,
+                quantity: 0,
+                total_mass: 0.0,
+                dimensions: part.size().to_array(),
+            });
+            row.quantity += 1;
+            row.total_mass += part.mass;
+            // Name the row after the alphabetically first instance
+            if part.name < row.name {
+                row.name = part.name.clone();
+            }
+        }
+
+        let mut rows: Vec<BomRow> = groups.into_values().collect();
+        rows.sort_by(|a, b| a.name.cmp(&b.name).then(a.mesh_hash.cmp(&b.mesh_hash)));
+        rows
+    }
+
+    /// Bill of materials as CSV with a header row
+    pub fn bill_of_materials_csv(&self) -> String {
+        let mut csv =
+            String::from("name,quantity,unit_mass_kg,total_mass_kg,size_x,size_y,size_z\n");
+        for row in self.bill_of_materials() {
+            let [x, y, z] = row.dimensions;
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{}\n",
+                csv_field(&row.name),
+                row.quantity,
+                row.unit_mass(),
+                row.total_mass,
+                x,
+                y,
+                z
+            ));
+        }
+        csv
+    }
+
+    // ============== Joint State ==============
+
+    /// Export the positions of all movable joints keyed by joint name
+    ///
+    /// Joints without an explicit position are exported as 0.
+    pub fn export_joint_state(&self) -> BTreeMap<String, f32> {
+        self.assembly
+            .joints
+            .iter()
+            .filter(|(_, joint)| joint.joint_type.has_axis())
+            .map(|(id, joint)| (joint.name.clone(), self.assembly.get_joint_position(*id)))
+            .collect()
+    }
+
+    /// Apply joint positions keyed by joint name and update world transforms
+    ///
+    /// Positions outside a limited joint's range are clamped to it. Names that
+    /// didn't match any joint are ignored.
+    pub fn apply_joint_state(&mut self, state: &BTreeMap<String, f32>) -> JointStateReport {
+        let mut report = JointStateReport::default();
+        for (name, &position) in state {
+            let Some(joint_id) = self.assembly.find_joint_id_by_name(name) else {
+                report.unknown.push(name.clone());
+                continue;
+            };
+            let joint = &self.assembly.joints[&joint_id];
+            let clamped = match (joint.joint_type, joint.limits) {
+                (JointType::Continuous, _) | (_, None) => position,
+                (_, Some(limits)) => position.clamp(limits.lower, limits.upper),
+            };
+            if clamped != position {
+                report.clamped.push(name.clone());
+            }
+            self.assembly.set_joint_position(joint_id, clamped);
+            report.applied += 1;
+        }
+        self.assembly
+            .update_world_transforms_with_current_positions();
+        report
+    }
+}
+
+/// Outcome of [`Project::apply_joint_state`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JointStateReport {
+    /// Number of positions applied to joints
+    pub applied: usize,
+    /// Names that didn't match any joint
+    pub unknown: Vec<String>,
+    /// Joints whose position was clamped to their limits
+    pub clamped: Vec<String>,
+}
+
+/// Summary statistics of a project's robot model
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProjectStats {
+    /// Number of links in the assembly
+    pub link_count: usize,
+    /// Number of joints in the assembly
+    pub joint_count: usize,
+    /// Degrees of freedom summed over all joints
+    pub movable_dof: usize,
+    /// Sum of link masses (kg)
+    pub total_mass: f32,
+    /// Triangles in the meshes of linked parts
+    pub triangle_count: usize,
+    /// World-space bounds of linked part meshes (min, max), if any have geometry
+    pub bounding_box: Option<([f32; 3], [f32; 3])>,
+}
+
+/// One line of the bill of materials: a unique part and how often it's used
+#[derive(Debug, Clone, PartialEq)]
+pub struct BomRow {
+    /// Name of the first instance (alphabetically)
+    pub name: String,
+    /// Mesh hash shared by all instances (0 for parts without geometry)
+    pub mesh_hash: u64,
+    /// Number of parts using this mesh
+    pub quantity: usize,
+    /// Sum of the instances' masses (kg)
+    pub total_mass: f32,
+    /// Bounding box size in the part frame (m)
+    pub dimensions: [f32; 3],
+}
+
+impl BomRow {
+    /// Average mass of one instance (kg)
+    pub fn unit_mass(&self) -> f32 {
+        self.total_mass / self.quantity.max(1) as f32
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 /// Material definition for URDF
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MaterialDef {
     pub name: String,
     pub color: [f32; 4],
@@ -210,6 +461,76 @@ impl MaterialDef {
     }
 }
 
+/// Transform gizmo mode, shared by the renderer and the saved viewport settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GizmoMode {
+    /// Translation mode (move along axes).
+    #[default]
+    Translate,
+    /// Rotation mode (rotate around axes).
+    Rotate,
+    /// Scale mode (scale along axes).
+    Scale,
+}
+
+/// Camera, display toggles and gizmo mode restored when a project is reopened
+///
+/// The default is also the state a new renderer starts in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewportSettings {
+    /// Point the camera orbits around
+    pub camera_target: Vec3,
+    /// Distance from the target
+    pub camera_distance: f32,
+    /// Orbit yaw in radians
+    pub camera_yaw: f32,
+    /// Orbit pitch in radians
+    pub camera_pitch: f32,
+    pub show_grid: bool,
+    pub show_axes: bool,
+    pub show_markers: bool,
+    pub show_inertial_frames: bool,
+    pub show_visuals: bool,
+    pub show_collisions: bool,
+    pub gizmo_mode: GizmoMode,
+}
+
+impl Default for ViewportSettings {
+    fn default() -> Self {
+        Self {
+            camera_target: Vec3::ZERO,
+            camera_distance: 5.0,
+            camera_yaw: 45f32.to_radians(),
+            camera_pitch: 30f32.to_radians(),
+            show_grid: true,
+            show_axes: true,
+            show_markers: true,
+            show_inertial_frames: false,
+            show_visuals: true,
+            show_collisions: true,
+            gizmo_mode: GizmoMode::Translate,
+        }
+    }
+}
+
+/// Add a part's imported mesh materials to the project's materials
+///
+/// A material whose name is already defined with different properties is
+/// renamed (in the part too) so it doesn't silently take the other's color.
+fn merge_part_materials(materials: &mut Vec<MaterialDef>, part: &mut Part) {
+    for submesh in &mut part.submeshes {
+        let material = &mut submesh.material;
+        if materials.contains(material) {
+            continue;
+        }
+        material.name = unique_name(&material.name, |name| {
+            materials.iter().any(|m| m.name == name)
+        });
+        materials.push(material.clone());
+    }
+}
+
 /// Project-related errors
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ProjectError {
@@ -220,3 +541,293 @@ pub enum ProjectError {
     #[error("Deserialization error: {0}")]
     Deserialize(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembly::{Joint, Link};
+    use crate::primitive::PrimitiveShape;
+    use crate::types::Pose;
+    use glam::{Mat4, Vec3};
+
+    #[test]
+    fn test_mate_part_aligns_joint_points() {
+        use crate::part::JointPoint;
+        use glam::Quat;
+
+        let mut project = Project::new("robot");
+
+        let mut base = Part::new("base");
+        base.origin_transform =
+            Mat4::from_rotation_translation(Quat::from_rotation_y(0.4), Vec3::new(0.5, 0.0, 0.2));
+        let mut flange = JointPoint::new("flange", base.id, Vec3::new(0.0, 0.0, 0.3));
+        flange.orientation = Quat::from_rotation_x(0.7);
+        let flange_id = flange.id;
+        base.joint_points.push(flange);
+
+        let mut arm = Part::new("arm");
+        arm.origin_transform = Mat4::from_scale_rotation_translation(
+            Vec3::splat(2.0),
+            Quat::from_rotation_z(1.0),
+            Vec3::new(-1.0, 2.0, 0.0),
+        );
+        let mut socket = JointPoint::new("socket", arm.id, Vec3::new(0.1, -0.05, 0.0));
+        socket.orientation = Quat::from_rotation_z(-0.3);
+        let socket_id = socket.id;
+        arm.joint_points.push(socket);
+
+        let base_id = project.add_part(base);
+        let arm_id = project.add_part(arm);
+
+        let new_transform = project
+            .mate_part(arm_id, socket_id, base_id, flange_id)
+            .unwrap();
+        let arm = project.get_part(arm_id).unwrap();
+        assert_eq!(arm.origin_transform, new_transform);
+
+        let (target_pos, target_rot) = project
+            .get_part(base_id)
+            .unwrap()
+            .joint_point_world(flange_id)
+            .unwrap();
+        let (pos, rot) = arm.joint_point_world(socket_id).unwrap();
+        assert!(pos.abs_diff_eq(target_pos, 1e-5), "{pos} != {target_pos}");
+        assert!(rot.dot(target_rot).abs() > 1.0 - 1e-5);
+        // Scale is preserved
+        let (scale, _, _) = arm.origin_transform.to_scale_rotation_translation();
+        assert!(scale.abs_diff_eq(Vec3::splat(2.0), 1e-5));
+
+        // Locked parts stay put
+        project.get_part_mut(arm_id).unwrap().locked = true;
+        project.get_part_mut(base_id).unwrap().origin_transform = Mat4::IDENTITY;
+        assert_eq!(
+            project.mate_part(arm_id, socket_id, base_id, flange_id),
+            None
+        );
+        assert_eq!(
+            project.get_part(arm_id).unwrap().origin_transform,
+            new_transform
+        );
+    }
+
+    fn two_joint_project() -> Project {
+        let mut project = Project::new("robot");
+        let assembly = &mut project.assembly;
+        let base = assembly.add_link(Link::empty("base"));
+        let upper = assembly.add_link(Link::empty("upper"));
+        let slider = assembly.add_link(Link::empty("slider"));
+        let tool = assembly.add_link(Link::empty("tool"));
+
+        let shoulder = Joint::builder("shoulder", base, upper)
+            .revolute()
+            .xyz(0.0, 0.0, 0.5)
+            .axis(Vec3::Y)
+            .build();
+        assembly.connect(base, upper, shoulder).unwrap();
+        let extend = Joint::builder("extend", upper, slider)
+            .prismatic()
+            .xyz(0.3, 0.0, 0.0)
+            .axis(Vec3::X)
+            .build();
+        assembly.connect(upper, slider, extend).unwrap();
+        let mount = Joint::fixed("mount", slider, tool, Pose::from_position([0.1, 0.0, 0.0]));
+        assembly.connect(slider, tool, mount).unwrap();
+        project
+    }
+
+    fn world_transforms(project: &Project) -> BTreeMap<String, Mat4> {
+        project
+            .assembly
+            .links
+            .values()
+            .map(|link| (link.name.clone(), link.world_transform))
+            .collect()
+    }
+
+    #[test]
+    fn test_joint_state_roundtrip_reproduces_fk() {
+        let mut project = two_joint_project();
+        let shoulder = project.assembly.find_joint_id_by_name("shoulder").unwrap();
+        let extend = project.assembly.find_joint_id_by_name("extend").unwrap();
+        project.assembly.set_joint_position(shoulder, 0.7);
+        project.assembly.set_joint_position(extend, 0.05);
+        project
+            .assembly
+            .update_world_transforms_with_current_positions();
+        let expected = world_transforms(&project);
+
+        let state = project.export_joint_state();
+        // Fixed joints are not part of the joint state
+        assert_eq!(state.keys().collect::<Vec<_>>(), ["extend", "shoulder"]);
+
+        let mut other = project.clone();
+        other.assembly.reset_all_joint_positions();
+        other
+            .assembly
+            .update_world_transforms_with_current_positions();
+        assert!(other.apply_joint_state(&state).unknown.is_empty());
+
+        let actual = world_transforms(&other);
+        for (name, transform) in &expected {
+            assert!(actual[name].abs_diff_eq(*transform, 1e-6), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_apply_joint_state_reports_unknown_joints() {
+        let mut project = two_joint_project();
+        let state = BTreeMap::from([("shoulder".to_string(), 0.2), ("elbow".to_string(), 1.0)]);
+
+        let report = project.apply_joint_state(&state);
+        assert_eq!(report.unknown, vec!["elbow".to_string()]);
+        assert_eq!(report.applied, 1);
+
+        let shoulder = project.assembly.find_joint_id_by_name("shoulder").unwrap();
+        assert_eq!(project.assembly.get_joint_position(shoulder), 0.2);
+    }
+
+    #[test]
+    fn test_apply_joint_state_clamps_to_limits() {
+        let mut project = two_joint_project();
+        let state = BTreeMap::from([("shoulder".to_string(), 10.0), ("extend".to_string(), 0.5)]);
+
+        let report = project.apply_joint_state(&state);
+        assert_eq!(report.clamped, vec!["shoulder".to_string()]);
+        assert_eq!(report.applied, 2);
+
+        let shoulder = project.assembly.find_joint_id_by_name("shoulder").unwrap();
+        let extend = project.assembly.find_joint_id_by_name("extend").unwrap();
+        assert_eq!(
+            project.assembly.get_joint_position(shoulder),
+            std::f32::consts::PI
+        );
+        assert_eq!(project.assembly.get_joint_position(extend), 0.5);
+    }
+
+    #[test]
+    fn test_statistics_two_revolute_robot() {
+        let mut project = Project::new("arm");
+        let mut part = Part::new("forearm");
+        part.vertices = vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 2.0, 0.0]];
+        part.indices = vec![0, 1, 2];
+        part.calculate_bounding_box();
+        part.origin_transform = Mat4::from_translation(Vec3::new(0.0, 0.0, 1.0));
+        let part_id = project.add_part(part);
+
+        let assembly = &mut project.assembly;
+        let mut links = Vec::new();
+        for (name, mass) in [("base", 2.0), ("upper", 1.5), ("fore", 0.5)] {
+            let mut link = Link::empty(name);
+            link.inertial.mass = mass;
+            links.push(assembly.add_link(link));
+        }
+        assembly.links.get_mut(&links[2]).unwrap().part_id = Some(part_id);
+        for (i, name) in ["shoulder", "elbow"].into_iter().enumerate() {
+            let joint = Joint::builder(name, links[i], links[i + 1])
+                .revolute()
+                .axis(Vec3::Z)
+                .build();
+            assembly.connect(links[i], links[i + 1], joint).unwrap();
+        }
+
+        let stats = project.statistics();
+        assert_eq!(stats.link_count, 3);
+        assert_eq!(stats.joint_count, 2);
+        assert_eq!(stats.movable_dof, 2);
+        assert!((stats.total_mass - 4.0).abs() < 1e-6);
+        assert_eq!(stats.triangle_count, 1);
+        assert_eq!(stats.bounding_box, Some(([0.0, 0.0, 1.0], [1.0, 2.0, 1.0])));
+    }
+
+    #[test]
+    fn test_conflicting_part_materials_are_renamed() {
+        use crate::mesh::Submesh;
+
+        let with_material = |name: &str, color: [f32; 4]| {
+            let mut part = Part::new(name);
+            part.submeshes.push(Submesh {
+                material: MaterialDef::new("paint", color),
+                first_triangle: 0,
+                triangle_count: 0,
+            });
+            part
+        };
+        let mut project = Project::new("robot");
+        project.add_part(with_material("red", [1.0, 0.0, 0.0, 1.0]));
+        project.add_part(with_material("red_again", [1.0, 0.0, 0.0, 1.0]));
+        let blue_id = project.add_part(with_material("blue", [0.0, 0.0, 1.0, 1.0]));
+
+        let names: Vec<_> = project.materials.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["paint", "paint_1"]);
+        let blue = &project.get_part(blue_id).unwrap().submeshes[0].material;
+        assert_eq!(blue.name, "paint_1");
+        assert_eq!(project.materials[1].color, [0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_bill_of_materials_groups_identical_meshes() {
+        let wheel = |name: &str| {
+            let shape = PrimitiveShape::Box {
+                size: [0.1, 0.1, 0.05],
+            };
+            let mut part = Part::from_primitive(name, shape);
+            part.mass = 0.25;
+            part
+        };
+
+        let mut project = Project::new("rover");
+        project.add_part(wheel("wheel_right"));
+        let mut left = wheel("wheel_left");
+        left.origin_transform = Mat4::from_translation(Vec3::new(0.0, 0.3, 0.0));
+        project.add_part(left);
+        let mut chassis = Part::new("chassis");
+        chassis.vertices = vec![[0.0, 0.0, 0.0], [0.5, 0.0, 0.0], [0.0, 0.3, 0.0]];
+        chassis.indices = vec![0, 1, 2];
+        chassis.mass = 2.0;
+        chassis.calculate_bounding_box();
+        project.add_part(chassis);
+
+        let bom = project.bill_of_materials();
+        assert_eq!(bom.len(), 2);
+        assert_eq!(bom[0].name, "chassis");
+        assert_eq!(bom[0].quantity, 1);
+
+        let wheels = &bom[1];
+        assert_eq!(wheels.name, "wheel_left");
+        assert_eq!(wheels.quantity, 2);
+        assert!((wheels.total_mass - 0.5).abs() < 1e-6);
+        assert!((wheels.unit_mass() - 0.25).abs() < 1e-6);
+        assert!((wheels.dimensions[2] - 0.05).abs() < 1e-6);
+
+        let csv = project.bill_of_materials_csv();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.lines().nth(2).unwrap().starts_with("wheel_left,2,"));
+    }
+
+    #[test]
+    fn test_viewport_settings_roundtrip() {
+        let mut project = Project::new("robot");
+        assert!(
+            Project::load_from_bytes(&project.to_bytes().unwrap())
+                .unwrap()
+                .viewport
+                .is_none()
+        );
+
+        let settings = ViewportSettings {
+            camera_target: Vec3::new(0.2, -0.1, 0.4),
+            camera_distance: 1.5,
+            camera_yaw: 1.0,
+            camera_pitch: -0.3,
+            show_grid: false,
+            show_collisions: false,
+            show_inertial_frames: true,
+            gizmo_mode: GizmoMode::Rotate,
+            ..Default::default()
+        };
+        project.viewport = Some(settings.clone());
+
+        let loaded = Project::load_from_bytes(&project.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.viewport, Some(settings));
+    }
+}
@@ -29,6 +29,18 @@ impl JointType {
         matches!(self, JointType::Revolute | JointType::Prismatic)
     }
 
+    /// Number of degrees of freedom this joint type allows
+    ///
+    /// Planar joints move in x, y and yaw; floating joints are free in 6D.
+    pub fn dof(&self) -> usize {
+        match self {
+            JointType::Fixed => 0,
+            JointType::Revolute | JointType::Continuous | JointType::Prismatic => 1,
+            JointType::Planar => 3,
+            JointType::Floating => 6,
+        }
+    }
+
     /// Get display name
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -69,7 +81,7 @@ impl From<&urdf_rs::JointType> for JointType {
 }
 
 /// Joint limits
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct JointLimits {
     /// Lower position limit (rad or m)
     pub lower: f32,
@@ -134,6 +146,24 @@ impl Default for JointDynamics {
     }
 }
 
+/// Joint safety controller, as used by ros_control soft limits
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct JointSafetyController {
+    pub soft_lower_limit: f32,
+    pub soft_upper_limit: f32,
+    pub k_position: f32,
+    pub k_velocity: f32,
+}
+
+/// Joint calibration reference positions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct JointCalibration {
+    /// Joint position at the rising edge of the reference sensor
+    pub rising: Option<f32>,
+    /// Joint position at the falling edge of the reference sensor
+    pub falling: Option<f32>,
+}
+
 /// Joint mimic configuration
 /// Makes this joint follow another joint's position: value = multiplier * other_joint + offset
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +30,17 @@ impl Pose {
         Quat::from_euler(glam::EulerRot::XYZ, self.rpy[0], self.rpy[1], self.rpy[2])
     }
 
+    /// Create a pose from a position and a quaternion
+    ///
+    /// The quaternion is normalized before being converted to RPY.
+    pub fn from_quat(xyz: [f32; 3], rotation: Quat) -> Self {
+        let (roll, pitch, yaw) = rotation.normalize().to_euler(glam::EulerRot::XYZ);
+        Self {
+            xyz,
+            rpy: [roll, pitch, yaw],
+        }
+    }
+
     /// Get position as Vec3
     pub fn position(&self) -> Vec3 {
         Vec3::from(self.xyz)
@@ -52,3 +63,39 @@ impl From<&urdf_rs::Pose> for Pose {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpy_quat_roundtrip() {
+        let cases = [
+            [0.0, 0.0, 0.0],
+            [0.3, -0.7, 1.2],
+            [-2.5, 1.0, -3.0],
+            [1.5, -1.2, 0.1],
+            [3.0, 0.4, 2.9],
+        ];
+        for rpy in cases {
+            let pose = Pose::new([1.0, 2.0, 3.0], rpy);
+            let back = Pose::from_quat(pose.xyz, pose.to_quat());
+            assert_eq!(back.xyz, pose.xyz);
+            for i in 0..3 {
+                assert!(
+                    (back.rpy[i] - rpy[i]).abs() < 1e-4,
+                    "{:?} -> {:?}",
+                    rpy,
+                    back.rpy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_quat_normalizes() {
+        let rotation = Quat::from_rotation_z(0.5);
+        let pose = Pose::from_quat([0.0; 3], rotation * 3.0);
+        assert!(pose.to_quat().abs_diff_eq(rotation, 1e-5));
+    }
+}
@@ -0,0 +1,281 @@
+//! Appearance action handlers (colorization and subtree colors)
+//!
+//! Colorizing never touches `Part::color`; the generated colors are only pushed
+//! to the renderer, and the colors shown before colorizing are remembered so
+//! they can be restored. Applying a color to a subtree edits the parts instead.
+
+use std::collections::HashMap;
+
+use rk_core::Assembly;
+use uuid::Uuid;
+
+use crate::state::{AppAction, AppState};
+
+use super::ActionContext;
+
+/// Hue used for the root of the depth gradient (blue)
+const DEPTH_HUE_ROOT: f32 = 240.0;
+/// Hue used for the deepest link of the depth gradient (red)
+const DEPTH_HUE_LEAF: f32 = 0.0;
+/// Golden-ratio hue step for generating well-separated colors
+const GOLDEN_RATIO_HUE_STEP: f32 = 0.618_034 * 360.0;
+
+/// Handle appearance-related actions
+pub fn handle_appearance_action(action: AppAction, ctx: &ActionContext) {
+    match action {
+        AppAction::ColorizeByDepth => {
+            apply_colors(ctx, |state| {
+                let depths = link_depths(state);
+                let max_depth = depths.values().copied().max().unwrap_or(0);
+                depths
+                    .into_iter()
+                    .map(|(link_id, depth)| (link_id, depth_to_color(depth, max_depth)))
+                    .collect()
+            });
+        }
+        AppAction::ColorizeDistinct => {
+            apply_colors(ctx, |state| {
+                state
+                    .project
+                    .assembly
+                    .links_depth_first()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, link_id)| (link_id, distinct_color(index)))
+                    .collect()
+            });
+        }
+        AppAction::RestorePartColors => handle_restore_colors(ctx),
+        AppAction::ApplyColorToSubtree { part_id, color } => {
+            handle_apply_color_to_subtree(part_id, color, ctx)
+        }
+        _ => {}
+    }
+}
+
+/// Map a link depth to a gradient color, from blue (root) to red (deepest)
+pub fn depth_to_color(depth: usize, max_depth: usize) -> [f32; 4] {
+    let t = if max_depth == 0 {
+        0.0
+    } else {
+        (depth.min(max_depth) as f32) / (max_depth as f32)
+    };
+    let hue = DEPTH_HUE_ROOT + (DEPTH_HUE_LEAF - DEPTH_HUE_ROOT) * t;
+    let [r, g, b] = hsv_to_rgb(hue, 0.7, 0.9);
+    [r, g, b, 1.0]
+}
+
+/// Generate the `index`-th color of a sequence of visually distinct colors
+pub fn distinct_color(index: usize) -> [f32; 4] {
+    let hue = (index as f32 * GOLDEN_RATIO_HUE_STEP) % 360.0;
+    let [r, g, b] = hsv_to_rgb(hue, 0.65, 0.9);
+    [r, g, b, 1.0]
+}
+
+/// Convert HSV (hue in degrees, saturation/value in 0..=1) to RGB
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> [f32; 3] {
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let c = value * saturation;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let m = value - c;
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    [r + m, g + m, b + m]
+}
+
+/// Compute the depth of every link (root = 0) from the parent map
+fn link_depths(state: &AppState) -> HashMap<Uuid, usize> {
+    let assembly = &state.project.assembly;
+    let mut depths = HashMap::new();
+    // Depth-first order guarantees parents are visited before their children
+    for link_id in assembly.links_depth_first() {
+        let depth = assembly
+            .get_parent_link_id(link_id)
+            .and_then(|parent_id| depths.get(&parent_id))
+            .map_or(0, |d| d + 1);
+        depths.insert(link_id, depth);
+    }
+    depths
+}
+
+/// Push per-link colors to the renderer, remembering the colors they replace
+fn apply_colors<F>(ctx: &ActionContext, colors_for_links: F)
+where
+    F: FnOnce(&AppState) -> HashMap<Uuid, [f32; 4]>,
+{
+    let mut state = ctx.app_state.lock();
+    let link_colors = colors_for_links(&state);
+
+    let part_colors: Vec<(Uuid, [f32; 4])> = link_colors
+        .into_iter()
+        .filter_map(|(link_id, color)| {
+            let part_id = state.project.assembly.get_link(link_id)?.part_id?;
+            Some((part_id, color))
+        })
+        .collect();
+
+    for (part_id, _) in &part_colors {
+        if !state.original_part_colors.contains_key(part_id)
+            && let Some(part) = state.get_part(*part_id)
+        {
+            let color = part.color;
+            state.original_part_colors.insert(*part_id, color);
+        }
+    }
+    drop(state);
+
+    if let Some(viewport_state) = ctx.viewport_state {
+        let mut vp = viewport_state.lock();
+        for (part_id, color) in &part_colors {
+            vp.update_part_color(*part_id, *color);
+        }
+    }
+    tracing::info!("Colorized {} parts", part_colors.len());
+}
+
+fn handle_restore_colors(ctx: &ActionContext) {
+    let originals = std::mem::take(&mut ctx.app_state.lock().original_part_colors);
+
+    if let Some(viewport_state) = ctx.viewport_state {
+        let mut vp = viewport_state.lock();
+        for (part_id, color) in &originals {
+            vp.update_part_color(*part_id, *color);
+        }
+    }
+    tracing::info!("Restored colors of {} parts", originals.len());
+}
+
+/// Parts on `link_id` and on every link below it
+pub fn subtree_parts(assembly: &Assembly, link_id: Uuid) -> Vec<Uuid> {
+    std::iter::once(link_id)
+        .chain(assembly.get_all_descendants(link_id))
+        .filter_map(|id| assembly.get_link(id)?.part_id)
+        .collect()
+}
+
+/// Set `color` on a part and on all parts below its link
+///
+/// A part without a link is recolored on its own. Returns the recolored parts.
+pub fn apply_color_to_subtree(state: &mut AppState, part_id: Uuid, color: [f32; 4]) -> Vec<Uuid> {
+    let part_ids = match state.project.assembly.find_link_by_part(part_id) {
+        Some(link) => subtree_parts(&state.project.assembly, link.id),
+        None => vec![part_id],
+    };
+    let mut recolored = Vec::with_capacity(part_ids.len());
+    for id in part_ids {
+        if let Some(part) = state.get_part_mut(id) {
+            part.set_color(color);
+            // The new color is the real one; restoring must not undo it
+            state.original_part_colors.remove(&id);
+            recolored.push(id);
+        }
+    }
+    recolored
+}
+
+fn handle_apply_color_to_subtree(part_id: Uuid, color: [f32; 4], ctx: &ActionContext) {
+    let recolored = apply_color_to_subtree(&mut ctx.app_state.lock(), part_id, color);
+
+    if let Some(viewport_state) = ctx.viewport_state {
+        let mut vp = viewport_state.lock();
+        for id in &recolored {
+            vp.update_part_color(*id, color);
+        }
+    }
+    tracing::info!("Applied color to {} parts", recolored.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rk_core::{Joint, Link, Part, Pose};
+
+    fn approx_eq(a: [f32; 4], b: [f32; 4]) -> bool {
+        a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-4)
+    }
+
+    #[test]
+    fn test_depth_to_color_endpoints() {
+        // Root is blue, deepest link is red
+        let root = depth_to_color(0, 4);
+        let leaf = depth_to_color(4, 4);
+        assert!(root[2] > root[0] && root[2] > root[1]);
+        assert!(leaf[0] > leaf[1] && leaf[0] > leaf[2]);
+        assert_eq!(root[3], 1.0);
+    }
+
+    #[test]
+    fn test_depth_to_color_single_level() {
+        // A flat assembly maps everything to the root color
+        assert!(approx_eq(depth_to_color(0, 0), depth_to_color(0, 3)));
+        // Depths beyond the maximum are clamped
+        assert!(approx_eq(depth_to_color(10, 3), depth_to_color(3, 3)));
+    }
+
+    #[test]
+    fn test_depth_to_color_midpoint() {
+        // Halfway down the tree sits halfway between blue and red (green)
+        let mid = depth_to_color(2, 4);
+        assert!(mid[1] > mid[0] && mid[1] > mid[2]);
+    }
+
+    #[test]
+    fn test_apply_color_to_subtree_skips_siblings() {
+        let mut state = AppState::default();
+        // base -> arm -> hand, base -> leg
+        let mut link_ids = HashMap::new();
+        let mut part_ids = HashMap::new();
+        for name in ["base", "arm", "hand", "leg"] {
+            let part = Part::new(name);
+            part_ids.insert(name, part.id);
+            let mut link = Link::empty(name);
+            link.part_id = Some(part.id);
+            link_ids.insert(name, state.project.assembly.add_link(link));
+            state.add_part(part);
+        }
+        for (parent, child) in [("base", "arm"), ("arm", "hand"), ("base", "leg")] {
+            let (parent, child) = (link_ids[parent], link_ids[child]);
+            let joint = Joint::fixed(child.to_string(), parent, child, Pose::default());
+            state
+                .project
+                .assembly
+                .connect(parent, child, joint)
+                .unwrap();
+        }
+        let before = |state: &AppState, name| state.get_part(part_ids[name]).unwrap().color;
+        let (base_color, leg_color) = (before(&state, "base"), before(&state, "leg"));
+        state
+            .original_part_colors
+            .insert(part_ids["hand"], [0.0; 4]);
+
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let mut recolored = apply_color_to_subtree(&mut state, part_ids["arm"], red);
+        recolored.sort();
+        let mut expected = vec![part_ids["arm"], part_ids["hand"]];
+        expected.sort();
+        assert_eq!(recolored, expected);
+
+        let color = |name| state.get_part(part_ids[name]).unwrap().color;
+        assert_eq!(color("arm"), red);
+        assert_eq!(color("hand"), red);
+        assert_eq!(color("base"), base_color);
+        assert_eq!(color("leg"), leg_color);
+        assert!(state.original_part_colors.is_empty());
+    }
+
+    #[test]
+    fn test_distinct_colors_differ() {
+        let colors: Vec<[f32; 4]> = (0..8).map(distinct_color).collect();
+        for i in 0..colors.len() {
+            for j in (i + 1)..colors.len() {
+                assert!(!approx_eq(colors[i], colors[j]));
+            }
+        }
+    }
+}
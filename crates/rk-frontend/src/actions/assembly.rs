@@ -3,7 +3,7 @@
 use uuid::Uuid;
 
 use glam::Vec3;
-use rk_core::{CollisionElement, GeometryType, Joint, JointLimits, JointType, Link, Pose};
+use rk_core::{CollisionElement, GeometryType, JointLimits, JointType, Link, Pose};
 
 use crate::state::{AppAction, AppState};
 
@@ -50,6 +50,12 @@ pub fn handle_assembly_action(action: AppAction, ctx: &ActionContext) {
         AppAction::UpdateJointLimits { joint_id, limits } => {
             handle_update_joint_limits(joint_id, limits, ctx)
         }
+        AppAction::FindJointLimits { joint_id } => handle_find_joint_limits(joint_id, ctx),
+        AppAction::UpdateJointDynamics {
+            joint_id,
+            damping,
+            friction,
+        } => handle_update_joint_dynamics(joint_id, damping, friction, ctx),
         _ => {}
     }
 }
@@ -85,12 +91,11 @@ fn handle_connect_parts(parent: Uuid, child: Uuid, ctx: &ActionContext) {
             .map(|l| l.name.clone())
             .unwrap_or_default();
 
-        // Create fixed joint
-        let joint = Joint::fixed(
+        // Create joint from the configured defaults
+        let joint = state.joint_defaults.build_joint(
             format!("{}_to_{}", parent_name, child_name),
             parent_link_id,
             child_link_id,
-            Pose::default(),
         );
 
         match state
@@ -182,9 +187,11 @@ fn find_or_create_link(state: &mut AppState, part_id: Uuid) -> Option<Uuid> {
 fn handle_update_joint_position(joint_id: Uuid, position: f32, ctx: &ActionContext) {
     let mut state = ctx.app_state.lock();
 
-    // Clamp to limits if applicable
+    // Clamp to limits if applicable; continuous joints spin freely
     let clamped_position = if let Some(joint) = state.project.assembly.joints.get(&joint_id) {
-        if let Some(limits) = &joint.limits {
+        if joint.joint_type == JointType::Continuous {
+            position
+        } else if let Some(limits) = &joint.limits {
             position.clamp(limits.lower, limits.upper)
         } else {
             position
@@ -234,7 +241,7 @@ fn handle_reset_all_joint_positions(ctx: &ActionContext) {
 }
 
 /// Sync renderer transforms with assembly world transforms
-fn sync_renderer_transforms(state: &AppState, ctx: &ActionContext) {
+pub(super) fn sync_renderer_transforms(state: &AppState, ctx: &ActionContext) {
     use glam::{Mat4, Quat, Vec3};
 
     if let Some(viewport_state) = ctx.viewport_state {
@@ -421,18 +428,15 @@ fn handle_update_collision_geometry(
 
 fn handle_update_joint_type(joint_id: Uuid, joint_type: JointType, ctx: &ActionContext) {
     let mut state = ctx.app_state.lock();
+    let default_limits = state.joint_defaults.limits_for(joint_type);
 
     if let Some(joint) = state.project.assembly.get_joint_mut(joint_id) {
         let old_type = joint.joint_type;
         joint.joint_type = joint_type;
 
-        // Add default limits when switching to a type that needs them
+        // Add the configured default limits when switching to a type that needs them
         if joint_type.has_limits() && joint.limits.is_none() {
-            joint.limits = Some(if joint_type == JointType::Prismatic {
-                JointLimits::default_prismatic()
-            } else {
-                JointLimits::default_revolute()
-            });
+            joint.limits = default_limits;
         }
 
         // Clear limits when switching to a type that doesn't need them
@@ -508,6 +512,50 @@ fn handle_update_joint_axis(joint_id: Uuid, axis: Vec3, ctx: &ActionContext) {
     }
 }
 
+fn handle_update_joint_dynamics(joint_id: Uuid, damping: f32, friction: f32, ctx: &ActionContext) {
+    let mut state = ctx.app_state.lock();
+    if let Some(joint) = state.project.assembly.get_joint_mut(joint_id) {
+        joint.set_dynamics(damping, friction);
+        tracing::debug!("Updated joint {} dynamics", joint_id);
+        state.modified = true;
+    } else {
+        tracing::warn!("Joint {} not found for updating dynamics", joint_id);
+    }
+}
+
+fn handle_find_joint_limits(joint_id: Uuid, ctx: &ActionContext) {
+    let limits = {
+        let state = ctx.app_state.lock();
+        let assembly = &state.project.assembly;
+        let Some(joint) = assembly.get_joint(joint_id) else {
+            tracing::warn!("Joint {} not found for finding limits", joint_id);
+            return;
+        };
+        let current = joint.limits.unwrap_or_else(JointLimits::default_revolute);
+        match assembly.find_joint_limits(state.project.parts(), joint_id, &assembly.joint_positions)
+        {
+            Some((lower, upper)) => JointLimits {
+                lower,
+                upper,
+                ..current
+            },
+            None => {
+                tracing::warn!(
+                    "Could not find limits for joint {}: not revolute or already colliding",
+                    joint_id
+                );
+                return;
+            }
+        }
+    };
+    tracing::info!(
+        "Found joint limits [{:.1}°, {:.1}°]",
+        limits.lower.to_degrees(),
+        limits.upper.to_degrees()
+    );
+    handle_update_joint_limits(joint_id, Some(limits), ctx);
+}
+
 fn handle_update_joint_limits(joint_id: Uuid, limits: Option<JointLimits>, ctx: &ActionContext) {
     let mut state = ctx.app_state.lock();
 
@@ -547,3 +595,39 @@ fn handle_update_joint_limits(joint_id: Uuid, limits: Option<JointLimits>, ctx:
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::create_shared_state;
+
+    #[test]
+    fn test_type_change_uses_configured_limits() {
+        let app_state = create_shared_state();
+        let joint_id = {
+            let mut state = app_state.lock();
+            state.joint_defaults.limits = JointLimits::with_range(-0.5, 0.5);
+            state.joint_defaults.prismatic_limits = JointLimits::with_range(0.0, 0.2);
+            let parent = state.project.assembly.add_link(Link::empty("base"));
+            let child = state.project.assembly.add_link(Link::empty("arm"));
+            let joint = rk_core::Joint::fixed("j", parent, child, Pose::default());
+            state
+                .project
+                .assembly
+                .connect(parent, child, joint)
+                .unwrap()
+        };
+        let ctx = ActionContext::new(&app_state, &None);
+        let limits = || app_state.lock().project.assembly.joints[&joint_id].limits;
+
+        handle_update_joint_type(joint_id, JointType::Prismatic, &ctx);
+        assert_eq!(limits(), Some(JointLimits::with_range(0.0, 0.2)));
+
+        handle_update_joint_type(joint_id, JointType::Fixed, &ctx);
+        assert_eq!(limits(), None);
+
+        handle_update_joint_type(joint_id, JointType::Revolute, &ctx);
+        assert_eq!(limits(), Some(JointLimits::with_range(-0.5, 0.5)));
+        assert!(app_state.lock().modified);
+    }
+}
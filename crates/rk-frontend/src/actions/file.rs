@@ -1,12 +1,13 @@
 //! File I/O action handlers
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashSet};
 
-use rk_core::{ImportOptions, Project, import_urdf, load_mesh};
+use rk_core::{Project, import_urdf, load_mesh, save_parts_stl};
 
 use crate::state::AppAction;
 
 use super::ActionContext;
+use super::assembly::sync_renderer_transforms;
 
 /// Handle file-related actions
 pub fn handle_file_action(action: AppAction, ctx: &ActionContext) {
@@ -16,15 +17,25 @@ pub fn handle_file_action(action: AppAction, ctx: &ActionContext) {
         AppAction::SaveProject(path) => handle_save_project(path, ctx),
         AppAction::LoadProject(path) => handle_load_project(path, ctx),
         AppAction::ExportUrdf { path, robot_name } => handle_export_urdf(path, robot_name, ctx),
+        AppAction::ExportUrdfSubtree { path, robot_name } => {
+            handle_export_urdf_subtree(path, robot_name, ctx)
+        }
+        AppAction::SaveJointState(path) => handle_save_joint_state(path, ctx),
+        AppAction::LoadJointState(path) => handle_load_joint_state(path, ctx),
+        AppAction::ExportBom(path) => handle_export_bom(path, ctx),
+        AppAction::ExportViewSvg(path) => handle_export_view_svg(path, ctx),
+        AppAction::ExportAllPartsStl(dir) => handle_export_all_parts_stl(dir, ctx),
         AppAction::NewProject => handle_new_project(ctx),
         _ => {}
     }
 }
 
 fn handle_import_mesh(path: std::path::PathBuf, ctx: &ActionContext) {
-    let unit = ctx.app_state.lock().stl_import_unit;
+    let options = ctx.app_state.lock().import_options();
+    let unit = options.stl_unit;
     match load_mesh(&path, unit) {
-        Ok(part) => {
+        Ok(mut part) => {
+            options.postprocess_mesh(&mut part);
             tracing::info!(
                 "Loaded mesh: {} ({} vertices, unit={:?})",
                 part.name,
@@ -71,16 +82,11 @@ fn handle_import_mesh(path: std::path::PathBuf, ctx: &ActionContext) {
 }
 
 fn handle_import_urdf(path: std::path::PathBuf, ctx: &ActionContext) {
-    let stl_unit = ctx.app_state.lock().stl_import_unit;
-    let options = ImportOptions {
-        base_dir: path
-            .parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| std::path::PathBuf::from(".")),
-        stl_unit,
-        default_color: [0.7, 0.7, 0.7, 1.0],
-        package_paths: HashMap::new(),
-    };
+    let mut options = ctx.app_state.lock().import_options();
+    options.base_dir = path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| std::path::PathBuf::from("."));
 
     match import_urdf(&path, &options) {
         Ok(project) => {
@@ -148,6 +154,9 @@ fn handle_save_project(path: Option<std::path::PathBuf>, ctx: &ActionContext) {
 
     if let Some(ref path) = save_path {
         // No sync needed - parts are stored directly in project
+        if let Some(viewport_state) = ctx.viewport_state {
+            state.project.viewport = Some(viewport_state.lock().renderer.viewport_settings());
+        }
 
         match state.project.save(path) {
             Ok(()) => {
@@ -179,6 +188,7 @@ fn handle_load_project(path: std::path::PathBuf, ctx: &ActionContext) {
                     viewport_state.lock().add_part(part);
                 }
             }
+            ctx.restore_viewport(project.viewport.as_ref());
 
             // Load into app state
             ctx.app_state.lock().load_project(project, path);
@@ -190,7 +200,7 @@ fn handle_load_project(path: std::path::PathBuf, ctx: &ActionContext) {
 }
 
 fn handle_export_urdf(path: std::path::PathBuf, robot_name: String, ctx: &ActionContext) {
-    let state = ctx.app_state.lock();
+    let mut state = ctx.app_state.lock();
     let options = rk_core::ExportOptions {
         output_dir: path,
         robot_name,
@@ -204,14 +214,158 @@ fn handle_export_urdf(path: std::path::PathBuf, robot_name: String, ctx: &Action
         }
         Err(e) => {
             tracing::error!("Failed to export URDF: {}", e);
+            state.export_error = Some(e.to_string());
+        }
+    }
+}
+
+fn handle_export_urdf_subtree(path: std::path::PathBuf, robot_name: String, ctx: &ActionContext) {
+    let mut state = ctx.app_state.lock();
+    let Some(root_link) = state
+        .selected_part
+        .and_then(|part_id| state.project.assembly.find_link_by_part(part_id))
+        .map(|link| link.id)
+    else {
+        tracing::warn!("No link selected for subtree export");
+        return;
+    };
+
+    let options = rk_core::ExportOptions {
+        robot_name,
+        ..Default::default()
+    };
+
+    match rk_core::export_urdf_subtree(&state.project, root_link, &path, &options) {
+        Ok(_urdf) => {
+            tracing::info!("Exported URDF subtree to {:?}", path);
+        }
+        Err(e) => {
+            tracing::error!("Failed to export URDF subtree: {}", e);
+            state.export_error = Some(e.to_string());
+        }
+    }
+}
+
+fn handle_save_joint_state(path: std::path::PathBuf, ctx: &ActionContext) {
+    let joint_state = ctx.app_state.lock().project.export_joint_state();
+
+    let content = serde_json::to_string_pretty(&joint_state).map_err(|e| e.to_string());
+
+    match content.and_then(|c| std::fs::write(&path, c).map_err(|e| e.to_string())) {
+        Ok(()) => {
+            tracing::info!("Saved {} joint positions to {:?}", joint_state.len(), path);
+        }
+        Err(e) => {
+            tracing::error!("Failed to save joint state: {}", e);
+        }
+    }
+}
+
+fn handle_export_bom(path: std::path::PathBuf, ctx: &ActionContext) {
+    let csv = ctx.app_state.lock().project.bill_of_materials_csv();
+    match std::fs::write(&path, csv) {
+        Ok(()) => tracing::info!("Exported bill of materials to {:?}", path),
+        Err(e) => tracing::error!("Failed to export bill of materials: {}", e),
+    }
+}
+
+fn handle_export_all_parts_stl(dir: std::path::PathBuf, ctx: &ActionContext) {
+    let state = ctx.app_state.lock();
+    let mut parts: Vec<_> = state.project.parts_iter().collect();
+    parts.sort_by(|a, b| a.name.cmp(&b.name));
+
+    match save_parts_stl(
+        parts,
+        &dir,
+        state.stl_export_unit,
+        state.stl_export_format,
+        &state.stl_export_convention,
+    ) {
+        Ok(files) => {
+            let unique: HashSet<_> = files.iter().map(|(_, path)| path).collect();
+            tracing::info!(
+                "Exported {} parts as {} STL files to {:?}",
+                files.len(),
+                unique.len(),
+                dir
+            );
         }
+        Err(e) => tracing::error!("Failed to export parts as STL: {}", e),
+    }
+}
+
+fn handle_export_view_svg(path: std::path::PathBuf, ctx: &ActionContext) {
+    let Some(viewport_state) = ctx.viewport_state else {
+        tracing::warn!("No viewport to export");
+        return;
+    };
+    let vp = viewport_state.lock();
+    let camera = vp.renderer.camera();
+    let (view_proj, eye) = (
+        camera.projection_matrix() * camera.view_matrix(),
+        camera.position,
+    );
+    let (width, height) = vp.view_size();
+
+    // Draw each part where the viewport shows it, not at its zero pose
+    let svg = {
+        let state = ctx.app_state.lock();
+        let parts = state
+            .project
+            .parts()
+            .values()
+            .filter(|part| !state.hidden_visuals.contains(&part.id))
+            .map(|part| {
+                let transform = vp
+                    .renderer
+                    .part_transform(part.id)
+                    .unwrap_or(part.origin_transform);
+                (part, transform)
+            });
+        rk_core::parts_to_svg(parts, view_proj, eye, width, height)
+    };
+    drop(vp);
+    match std::fs::write(&path, svg) {
+        Ok(()) => tracing::info!("Exported view drawing to {:?}", path),
+        Err(e) => tracing::error!("Failed to export view drawing: {}", e),
     }
 }
 
+fn handle_load_joint_state(path: std::path::PathBuf, ctx: &ActionContext) {
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::error!("Failed to read joint state {:?}: {}", path, e);
+            return;
+        }
+    };
+
+    let joint_state: BTreeMap<String, f32> = match serde_json::from_str(&content) {
+        Ok(joint_state) => joint_state,
+        Err(e) => {
+            tracing::error!("Failed to parse joint state {:?}: {}", path, e);
+            return;
+        }
+    };
+
+    let mut state = ctx.app_state.lock();
+    let report = state.project.apply_joint_state(&joint_state);
+    for name in &report.unknown {
+        tracing::warn!("Ignoring unknown joint '{}' in joint state", name);
+    }
+    for name in &report.clamped {
+        tracing::warn!("Clamped joint '{}' to its limits", name);
+    }
+    tracing::info!("Applied {} joint positions from {:?}", report.applied, path);
+
+    sync_renderer_transforms(&state, ctx);
+}
+
 fn handle_new_project(ctx: &ActionContext) {
     ctx.app_state.lock().new_project();
     if let Some(viewport_state) = ctx.viewport_state {
         viewport_state.lock().clear_parts();
         viewport_state.lock().clear_overlays();
     }
+    ctx.restore_viewport(None);
 }
@@ -16,9 +16,11 @@ pub fn handle_file_action_wasm(action: AppAction, ctx: &ActionContext) {
 }
 
 fn handle_import_mesh_bytes(name: &str, data: &[u8], ctx: &ActionContext) {
-    let unit = ctx.app_state.lock().stl_import_unit;
+    let options = ctx.app_state.lock().import_options();
+    let unit = options.stl_unit;
     match load_stl_from_bytes(name, data, unit) {
-        Ok(part) => {
+        Ok(mut part) => {
+            options.postprocess_mesh(&mut part);
             tracing::info!(
                 "Loaded mesh from bytes: {} ({} vertices, unit={:?})",
                 part.name,
@@ -73,6 +75,8 @@ fn handle_load_project_bytes(_name: &str, data: &[u8], ctx: &ActionContext) {
                 }
             }
 
+            ctx.restore_viewport(project.viewport.as_ref());
+
             // Load into app state (without file path for WASM)
             // No manual sync needed - parts are stored directly in project
             let mut state = ctx.app_state.lock();
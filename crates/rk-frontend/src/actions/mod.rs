@@ -3,6 +3,7 @@
 //! This module contains the action dispatch system for the URDF editor.
 //! Actions are queued in AppState and processed each frame.
 
+mod appearance;
 mod assembly;
 #[cfg(not(target_arch = "wasm32"))]
 mod file;
@@ -11,8 +12,11 @@ mod file_wasm;
 mod part;
 mod sketch;
 
+use rk_core::ViewportSettings;
+
 use crate::state::{AppAction, SharedAppState, SharedViewportState};
 
+pub use appearance::handle_appearance_action;
 pub use assembly::handle_assembly_action;
 #[cfg(not(target_arch = "wasm32"))]
 pub use file::handle_file_action;
@@ -37,6 +41,25 @@ impl<'a> ActionContext<'a> {
             viewport_state,
         }
     }
+
+    /// Apply a project's saved viewport, or the configured defaults without one
+    pub fn restore_viewport(&self, saved: Option<&ViewportSettings>) {
+        let Some(viewport_state) = self.viewport_state else {
+            return;
+        };
+        let defaults;
+        let settings = match saved {
+            Some(settings) => settings,
+            None => {
+                defaults = self.app_state.lock().default_viewport.clone();
+                &defaults
+            }
+        };
+        viewport_state
+            .lock()
+            .renderer
+            .apply_viewport_settings(settings);
+    }
 }
 
 /// Dispatch an action to the appropriate handler
@@ -49,6 +72,12 @@ pub fn dispatch_action(action: AppAction, ctx: &ActionContext) {
         | AppAction::SaveProject(_)
         | AppAction::LoadProject(_)
         | AppAction::ExportUrdf { .. }
+        | AppAction::ExportUrdfSubtree { .. }
+        | AppAction::SaveJointState(_)
+        | AppAction::LoadJointState(_)
+        | AppAction::ExportBom(_)
+        | AppAction::ExportViewSvg(_)
+        | AppAction::ExportAllPartsStl(_)
         | AppAction::NewProject => {
             handle_file_action(action, ctx);
         }
@@ -59,7 +88,13 @@ pub fn dispatch_action(action: AppAction, ctx: &ActionContext) {
         | AppAction::ImportUrdf(_)
         | AppAction::SaveProject(_)
         | AppAction::LoadProject(_)
-        | AppAction::ExportUrdf { .. } => {
+        | AppAction::ExportUrdf { .. }
+        | AppAction::ExportUrdfSubtree { .. }
+        | AppAction::SaveJointState(_)
+        | AppAction::LoadJointState(_)
+        | AppAction::ExportBom(_)
+        | AppAction::ExportViewSvg(_)
+        | AppAction::ExportAllPartsStl(_) => {
             tracing::warn!("File actions are not supported in WASM");
         }
 
@@ -87,10 +122,15 @@ pub fn dispatch_action(action: AppAction, ctx: &ActionContext) {
 
         // Part actions
         AppAction::CreatePrimitive { .. }
+        | AppAction::SetPrimitiveSegments { .. }
         | AppAction::CreateEmpty { .. }
         | AppAction::SelectPart(_)
+        | AppAction::FocusPart(_)
+        | AppAction::SelectJoint(_)
         | AppAction::DeleteSelectedPart
-        | AppAction::UpdatePartTransform { .. } => {
+        | AppAction::UpdatePartTransform { .. }
+        | AppAction::SetPartLocked { .. }
+        | AppAction::MatePart { .. } => {
             handle_part_action(action, ctx);
         }
 
@@ -103,7 +143,9 @@ pub fn dispatch_action(action: AppAction, ctx: &ActionContext) {
         | AppAction::UpdateJointType { .. }
         | AppAction::UpdateJointOrigin { .. }
         | AppAction::UpdateJointAxis { .. }
-        | AppAction::UpdateJointLimits { .. } => {
+        | AppAction::UpdateJointLimits { .. }
+        | AppAction::FindJointLimits { .. }
+        | AppAction::UpdateJointDynamics { .. } => {
             handle_assembly_action(action, ctx);
         }
 
@@ -116,6 +158,14 @@ pub fn dispatch_action(action: AppAction, ctx: &ActionContext) {
             handle_assembly_action(action, ctx);
         }
 
+        // Appearance actions
+        AppAction::ColorizeByDepth
+        | AppAction::ColorizeDistinct
+        | AppAction::RestorePartColors
+        | AppAction::ApplyColorToSubtree { .. } => {
+            handle_appearance_action(action, ctx);
+        }
+
         // Sketch/CAD actions
         AppAction::SketchAction(_) => {
             handle_sketch_action(action, ctx);
@@ -3,7 +3,7 @@
 use glam::Mat4;
 use uuid::Uuid;
 
-use rk_core::{Part, generate_box_mesh, generate_cylinder_mesh, generate_sphere_mesh};
+use rk_core::{Part, PrimitiveShape};
 
 use crate::state::{AppAction, PrimitiveType};
 
@@ -16,12 +16,26 @@ pub fn handle_part_action(action: AppAction, ctx: &ActionContext) {
             primitive_type,
             name,
         } => handle_create_primitive(primitive_type, name, ctx),
+        AppAction::SetPrimitiveSegments { part_id, segments } => {
+            handle_set_primitive_segments(part_id, segments, ctx)
+        }
         AppAction::CreateEmpty { name } => handle_create_empty(name, ctx),
         AppAction::SelectPart(part_id) => handle_select_part(part_id, ctx),
+        AppAction::FocusPart(part_id) => handle_focus_part(part_id, ctx),
+        AppAction::SelectJoint(joint_id) => handle_select_joint(joint_id, ctx),
         AppAction::DeleteSelectedPart => handle_delete_selected_part(ctx),
         AppAction::UpdatePartTransform { part_id, transform } => {
             handle_update_part_transform(part_id, transform, ctx)
         }
+        AppAction::SetPartLocked { part_id, locked } => {
+            handle_set_part_locked(part_id, locked, ctx)
+        }
+        AppAction::MatePart {
+            part_id,
+            point_id,
+            target_part_id,
+            target_point_id,
+        } => handle_mate_part(part_id, point_id, target_part_id, target_point_id, ctx),
         _ => {}
     }
 }
@@ -32,23 +46,31 @@ fn handle_create_primitive(
     ctx: &ActionContext,
 ) {
     // Generate unique name
-    let existing_count = ctx.app_state.lock().project.parts().len();
-    let part_name =
-        name.unwrap_or_else(|| format!("{}_{}", primitive_type.name(), existing_count + 1));
-
-    // Generate mesh based on primitive type (default size: 0.1m)
-    let (vertices, normals, indices) = match primitive_type {
-        PrimitiveType::Box => generate_box_mesh([0.1, 0.1, 0.1]),
-        PrimitiveType::Cylinder => generate_cylinder_mesh(0.05, 0.1),
-        PrimitiveType::Sphere => generate_sphere_mesh(0.05),
+    let (part_name, segments) = {
+        let state = ctx.app_state.lock();
+        let name = state
+            .project
+            .unique_part_name(name.as_deref().unwrap_or(primitive_type.name()));
+        (name, state.primitive_segments)
     };
 
+    // Shape based on primitive type (default size: 0.1m)
+    let shape = match primitive_type {
+        PrimitiveType::Box => PrimitiveShape::Box { size: [0.1; 3] },
+        PrimitiveType::Cylinder => PrimitiveShape::Cylinder {
+            radius: 0.05,
+            length: 0.1,
+            segments,
+        },
+        PrimitiveType::Sphere => PrimitiveShape::Sphere {
+            radius: 0.05,
+            segments,
+        },
+    }
+    .with_segments(segments);
+
     // Create part
-    let mut part = Part::new(&part_name);
-    part.vertices = vertices;
-    part.normals = normals;
-    part.indices = indices;
-    part.calculate_bounding_box();
+    let mut part = Part::from_primitive(&part_name, shape);
 
     // Set material name based on primitive type
     part.material_name = Some(format!("{}_material", primitive_type.name().to_lowercase()));
@@ -74,10 +96,39 @@ fn handle_create_primitive(
     ctx.app_state.lock().add_part(part);
 }
 
+fn handle_set_primitive_segments(part_id: Uuid, segments: u32, ctx: &ActionContext) {
+    let part = {
+        let mut state = ctx.app_state.lock();
+        let Some(part) = state.get_part_mut(part_id) else {
+            return;
+        };
+        if !part.set_primitive_segments(segments) {
+            return;
+        }
+        let part = part.clone();
+        state.modified = true;
+        part
+    };
+
+    tracing::info!(
+        "Re-tessellated {} with {} segments ({} triangles)",
+        part.name,
+        segments,
+        part.indices.len() / 3
+    );
+
+    if let Some(viewport_state) = ctx.viewport_state {
+        viewport_state.lock().update_part_mesh(&part);
+    }
+}
+
 fn handle_create_empty(name: Option<String>, ctx: &ActionContext) {
     // Generate unique name
-    let existing_count = ctx.app_state.lock().project.parts().len();
-    let part_name = name.unwrap_or_else(|| format!("Empty_{}", existing_count + 1));
+    let part_name = ctx
+        .app_state
+        .lock()
+        .project
+        .unique_part_name(name.as_deref().unwrap_or("Empty"));
 
     // Create empty part (no geometry)
     let part = Part::new(&part_name);
@@ -98,6 +149,33 @@ fn handle_select_part(part_id: Option<Uuid>, ctx: &ActionContext) {
     // Overlays are updated in update_overlays() called after process_actions
 }
 
+fn handle_focus_part(part_id: Uuid, ctx: &ActionContext) {
+    let Some(viewport_state) = ctx.viewport_state else {
+        return;
+    };
+    let mut vp = viewport_state.lock();
+    // Bounds at the pose the part is drawn with, not its rest pose
+    let Some(bounds) = vp.renderer.part_world_bounds(part_id) else {
+        return;
+    };
+
+    // The camera target is the orbit pivot
+    vp.renderer
+        .camera_mut()
+        .fit_all(bounds.center(), bounds.radius());
+}
+
+fn handle_select_joint(joint_id: Uuid, ctx: &ActionContext) {
+    let Some(part_id) = ctx.app_state.lock().select_joint(joint_id) else {
+        tracing::warn!("Joint {} has no parent part to edit", joint_id);
+        return;
+    };
+
+    if let Some(viewport_state) = ctx.viewport_state {
+        viewport_state.lock().set_selected_part(Some(part_id));
+    }
+}
+
 fn handle_delete_selected_part(ctx: &ActionContext) {
     let selected = ctx.app_state.lock().selected_part;
     if let Some(id) = selected {
@@ -110,6 +188,51 @@ fn handle_delete_selected_part(ctx: &ActionContext) {
     }
 }
 
+fn handle_set_part_locked(part_id: Uuid, locked: bool, ctx: &ActionContext) {
+    let mut state = ctx.app_state.lock();
+    let Some(part) = state.get_part_mut(part_id) else {
+        tracing::warn!("Part {} not found", part_id);
+        return;
+    };
+    part.locked = locked;
+    tracing::info!(
+        "{} part: {}",
+        if locked { "Locked" } else { "Unlocked" },
+        part.name
+    );
+    drop(state);
+
+    // The gizmo caches the flag when shown; keep it in sync while it is
+    if let Some(viewport_state) = ctx.viewport_state {
+        viewport_state.lock().set_gizmo_part_locked(part_id, locked);
+    }
+}
+
+fn handle_mate_part(
+    part_id: Uuid,
+    point_id: Uuid,
+    target_part_id: Uuid,
+    target_point_id: Uuid,
+    ctx: &ActionContext,
+) {
+    let mut state = ctx.app_state.lock();
+    let transform = state
+        .project
+        .mate_part(part_id, point_id, target_part_id, target_point_id);
+    let Some(transform) = transform else {
+        tracing::warn!("Cannot mate part {} (missing point or locked)", part_id);
+        return;
+    };
+    state.modified = true;
+    drop(state);
+
+    if let Some(viewport_state) = ctx.viewport_state {
+        viewport_state
+            .lock()
+            .update_part_transform(part_id, transform);
+    }
+}
+
 fn handle_update_part_transform(part_id: Uuid, transform: Mat4, ctx: &ActionContext) {
     if let Some(part) = ctx.app_state.lock().get_part_mut(part_id) {
         part.origin_transform = transform;
@@ -2,6 +2,7 @@
 //!
 //! Handles actions related to sketch editing and CAD operations.
 
+use rk_cad::Sketch;
 use tracing::info;
 
 use crate::state::{AppAction, SketchAction};
@@ -22,11 +23,15 @@ pub fn handle_sketch_action(action: AppAction, ctx: &ActionContext) {
             info!("Created sketch: {}", sketch_id);
             // Automatically enter sketch mode for the new sketch
             state.cad.enter_sketch_mode(sketch_id);
+            if let Some(sketch) = state.cad.get_sketch(sketch_id) {
+                frame_sketch(sketch, ctx);
+            }
         }
 
         SketchAction::EditSketch { sketch_id } => {
             let mut state = ctx.app_state.lock();
-            if state.cad.get_sketch(sketch_id).is_some() {
+            if let Some(sketch) = state.cad.get_sketch(sketch_id) {
+                frame_sketch(sketch, ctx);
                 state.cad.enter_sketch_mode(sketch_id);
                 info!("Entered sketch mode for: {}", sketch_id);
             } else {
@@ -96,6 +101,7 @@ pub fn handle_sketch_action(action: AppAction, ctx: &ActionContext) {
                         tracing::warn!("Failed to add constraint: {}", e);
                     } else {
                         info!("Added constraint: {}", constraint_id);
+                        state.cad.request_solve(sketch_id);
                     }
                 }
             }
@@ -105,9 +111,32 @@ pub fn handle_sketch_action(action: AppAction, ctx: &ActionContext) {
             let mut state = ctx.app_state.lock();
             if let Some(sketch_state) = state.cad.editor_mode.sketch() {
                 let sketch_id = sketch_state.active_sketch;
-                if let Some(sketch) = state.cad.get_sketch_mut(sketch_id) {
-                    sketch.remove_constraint(constraint_id);
+                if let Some(sketch) = state.cad.get_sketch_mut(sketch_id)
+                    && sketch.remove_constraint(constraint_id).is_some()
+                {
+                    // Removing a constraint frees geometry, so re-solve this frame
                     info!("Deleted constraint: {}", constraint_id);
+                    state.cad.request_solve(sketch_id);
+                }
+            }
+        }
+
+        SketchAction::ToggleFixed { point } => {
+            let mut state = ctx.app_state.lock();
+            if let Some(sketch_state) = state.cad.editor_mode.sketch() {
+                let sketch_id = sketch_state.active_sketch;
+                if let Some(sketch) = state.cad.get_sketch_mut(sketch_id) {
+                    match sketch.toggle_fixed(point) {
+                        Ok(fixed) => {
+                            info!(
+                                "{} point: {}",
+                                if fixed { "Fixed" } else { "Unfixed" },
+                                point
+                            );
+                            state.cad.request_solve(sketch_id);
+                        }
+                        Err(e) => tracing::warn!("Failed to toggle fixed point: {}", e),
+                    }
                 }
             }
         }
@@ -136,5 +165,93 @@ pub fn handle_sketch_action(action: AppAction, ctx: &ActionContext) {
                 sketch_state.grid_spacing = spacing;
             }
         }
+
+        SketchAction::ToggleGrid => {
+            let mut state = ctx.app_state.lock();
+            if let Some(sketch_state) = state.cad.editor_mode.sketch_mut() {
+                sketch_state.show_grid = !sketch_state.show_grid;
+            }
+        }
+
+        SketchAction::FlipSketchPlane { sketch_id } => {
+            let mut state = ctx.app_state.lock();
+            let Some(sketch) = state.cad.get_sketch_mut(sketch_id) else {
+                tracing::warn!("Sketch not found: {}", sketch_id);
+                return;
+            };
+            sketch.plane.flip();
+            info!("Flipped sketch plane: {}", sketch_id);
+
+            // Features extruded from this sketch now point the other way
+            let kernel = rk_cad::default_kernel();
+            if let Err(e) = state.cad.data.history.rebuild(kernel.as_ref()) {
+                tracing::warn!("Rebuild after plane flip failed: {}", e);
+            }
+        }
+
+        SketchAction::SetFeatureSuppressed {
+            feature_id,
+            suppressed,
+        } => {
+            let kernel = rk_cad::default_kernel();
+            let mut state = ctx.app_state.lock();
+            match state
+                .cad
+                .data
+                .history
+                .set_suppressed(feature_id, suppressed, kernel.as_ref())
+            {
+                Ok(()) => info!(
+                    "{} feature: {}",
+                    if suppressed {
+                        "Suppressed"
+                    } else {
+                        "Unsuppressed"
+                    },
+                    feature_id
+                ),
+                Err(e) => tracing::warn!("Failed to toggle suppression: {}", e),
+            }
+        }
+
+        SketchAction::SetKeepBodiesSeparate {
+            feature_id,
+            keep_separate,
+        } => {
+            let kernel = rk_cad::default_kernel();
+            let mut state = ctx.app_state.lock();
+            match state.cad.data.history.set_keep_bodies_separate(
+                feature_id,
+                keep_separate,
+                kernel.as_ref(),
+            ) {
+                Ok(()) => info!(
+                    "{} bodies of feature: {}",
+                    if keep_separate { "Separated" } else { "Merged" },
+                    feature_id
+                ),
+                Err(e) => tracing::warn!("Failed to change body separation: {}", e),
+            }
+        }
     }
 }
+
+/// Look straight at the sketch plane and fit the sketch's extents
+fn frame_sketch(sketch: &Sketch, ctx: &ActionContext) {
+    let Some(viewport_state) = ctx.viewport_state else {
+        return;
+    };
+    let (min, max) = sketch.bounds_2d();
+    let center = sketch.plane.to_world((min + max) / 2.0);
+    let radius = (max - min).length() / 2.0;
+
+    // Orbit angles that put the camera on the normal side of the plane
+    let normal = sketch.plane.normal.normalize();
+    let yaw = normal.y.atan2(normal.x);
+    let pitch = normal.z.clamp(-1.0, 1.0).asin();
+
+    let mut vp = viewport_state.lock();
+    let camera = vp.renderer.camera_mut();
+    camera.set_orbit_angles(yaw, pitch);
+    camera.fit_all(center, radius);
+}
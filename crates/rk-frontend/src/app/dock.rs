@@ -3,7 +3,10 @@
 use egui_dock::{DockState, NodeIndex, TabViewer};
 
 use crate::config::SharedConfig;
-use crate::panels::{JointListPanel, Panel, PartListPanel, PropertiesPanel, ViewportPanel};
+use crate::panels::{
+    ConstraintListPanel, JointListPanel, Panel, PartListPanel, PropertiesPanel, RobotInfoPanel,
+    ViewportPanel,
+};
 use crate::state::{SharedAppState, SharedViewportState};
 
 /// Panel types for the dock system
@@ -12,6 +15,8 @@ pub enum PanelType {
     PartList(PartListPanel),
     JointList(JointListPanel),
     Properties(PropertiesPanel),
+    Constraints(ConstraintListPanel),
+    RobotInfo(RobotInfoPanel),
 }
 
 impl PanelType {
@@ -21,6 +26,8 @@ impl PanelType {
             PanelType::PartList(p) => p.name(),
             PanelType::JointList(p) => p.name(),
             PanelType::Properties(p) => p.name(),
+            PanelType::Constraints(p) => p.name(),
+            PanelType::RobotInfo(p) => p.name(),
         }
     }
 }
@@ -74,6 +81,8 @@ impl TabViewer for UrdfTabViewer<'_> {
                     panel.ui(ui, self.app_state);
                 }
             }
+            PanelType::Constraints(panel) => panel.ui(ui, self.app_state),
+            PanelType::RobotInfo(panel) => panel.ui(ui, self.app_state),
         }
     }
 }
@@ -89,7 +98,10 @@ pub fn create_dock_layout() -> DockState<PanelType> {
     let [viewport_area, _properties] = surface.split_right(
         NodeIndex::root(),
         0.75,
-        vec![PanelType::Properties(PropertiesPanel::new())],
+        vec![
+            PanelType::Properties(PropertiesPanel::new()),
+            PanelType::Constraints(ConstraintListPanel::new()),
+        ],
     );
 
     // 2. Split left from viewport area for parts list
@@ -103,7 +115,10 @@ pub fn create_dock_layout() -> DockState<PanelType> {
     let [_parts, _joints] = surface.split_below(
         left,
         0.6, // Parts gets 60%, Joints gets 40%
-        vec![PanelType::JointList(JointListPanel::new())],
+        vec![
+            PanelType::JointList(JointListPanel::new()),
+            PanelType::RobotInfo(RobotInfoPanel::new()),
+        ],
     );
 
     dock_state
@@ -68,6 +68,15 @@ pub fn render_menu_bar(ctx: &egui::Context, app_state: &SharedAppState) -> Optio
                             }
                             ui.close();
                         }
+                        if ui.button("glTF...").clicked() {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .add_filter("glTF files", &["gltf", "glb", "GLTF", "GLB"])
+                                .pick_file()
+                            {
+                                app_state.lock().queue_action(AppAction::ImportMesh(path));
+                            }
+                            ui.close();
+                        }
                     });
                     if ui.button("Import URDF...").clicked() {
                         if let Some(path) = rfd::FileDialog::new()
@@ -104,6 +113,155 @@ pub fn render_menu_bar(ctx: &egui::Context, app_state: &SharedAppState) -> Optio
                         }
                         ui.close();
                     }
+                    let has_selection = app_state.lock().selected_part.is_some();
+                    if ui
+                        .add_enabled(
+                            has_selection,
+                            egui::Button::new("Export Selected Subtree..."),
+                        )
+                        .on_hover_text("Export the selected link and its children as a URDF")
+                        .clicked()
+                    {
+                        let default_name = {
+                            let state = app_state.lock();
+                            state
+                                .selected_part
+                                .and_then(|id| state.get_part(id))
+                                .map(|part| part.name.clone())
+                                .unwrap_or_else(|| "subtree".to_string())
+                        };
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("URDF", &["urdf"])
+                            .set_file_name(format!("{}.urdf", default_name))
+                            .save_file()
+                        {
+                            let robot_name = path
+                                .file_stem()
+                                .and_then(|s| s.to_str())
+                                .unwrap_or("robot")
+                                .to_string();
+                            app_state
+                                .lock()
+                                .queue_action(AppAction::ExportUrdfSubtree { path, robot_name });
+                        }
+                        ui.close();
+                    }
+                    ui.separator();
+                    if ui
+                        .button("Save Joint State...")
+                        .on_hover_text("Save joint positions keyed by joint name")
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("JSON", &["json"])
+                            .set_file_name("joint_state.json")
+                            .save_file()
+                        {
+                            app_state
+                                .lock()
+                                .queue_action(AppAction::SaveJointState(path));
+                        }
+                        ui.close();
+                    }
+                    if ui
+                        .button("Load Joint State...")
+                        .on_hover_text("Apply joint positions keyed by joint name")
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Joint state", &["json"])
+                            .pick_file()
+                        {
+                            app_state
+                                .lock()
+                                .queue_action(AppAction::LoadJointState(path));
+                        }
+                        ui.close();
+                    }
+                    ui.separator();
+                    if ui
+                        .button("Export BOM...")
+                        .on_hover_text("Export unique parts with quantity, mass and size as CSV")
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("CSV", &["csv"])
+                            .set_file_name("bom.csv")
+                            .save_file()
+                        {
+                            app_state.lock().queue_action(AppAction::ExportBom(path));
+                        }
+                        ui.close();
+                    }
+                    if ui
+                        .button("Export View as SVG...")
+                        .on_hover_text(
+                            "Export part outlines at the current camera as a line drawing",
+                        )
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("SVG", &["svg"])
+                            .set_file_name("view.svg")
+                            .save_file()
+                        {
+                            app_state
+                                .lock()
+                                .queue_action(AppAction::ExportViewSvg(path));
+                        }
+                        ui.close();
+                    }
+                    ui.menu_button("Export All Parts as STL", |ui| {
+                        let mut state = app_state.lock();
+                        egui::ComboBox::from_label("Unit")
+                            .selected_text(state.stl_export_unit.name())
+                            .show_ui(ui, |ui| {
+                                for unit in rk_core::StlUnit::ALL {
+                                    ui.selectable_value(
+                                        &mut state.stl_export_unit,
+                                        *unit,
+                                        unit.name(),
+                                    );
+                                }
+                            });
+                        egui::ComboBox::from_label("Format")
+                            .selected_text(state.stl_export_format.name())
+                            .show_ui(ui, |ui| {
+                                for format in rk_core::StlFormat::ALL {
+                                    ui.selectable_value(
+                                        &mut state.stl_export_format,
+                                        *format,
+                                        format.name(),
+                                    );
+                                }
+                            });
+                        egui::ComboBox::from_label("Axes")
+                            .selected_text(state.stl_export_convention.name())
+                            .show_ui(ui, |ui| {
+                                for convention in rk_core::TargetConvention::ALL {
+                                    ui.selectable_value(
+                                        &mut state.stl_export_convention,
+                                        *convention,
+                                        convention.name(),
+                                    );
+                                }
+                            });
+                        drop(state);
+                        if ui
+                            .button("Choose Folder...")
+                            .on_hover_text(
+                                "Write each part to <name>.stl; identical meshes share a file",
+                            )
+                            .clicked()
+                        {
+                            if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                                app_state
+                                    .lock()
+                                    .queue_action(AppAction::ExportAllPartsStl(dir));
+                            }
+                            ui.close();
+                        }
+                    });
                     ui.separator();
                     if ui.button("Exit").clicked() {
                         ctx.send_viewport_cmd(egui::ViewportCommand::Close);
@@ -244,6 +402,34 @@ pub fn render_menu_bar(ctx: &egui::Context, app_state: &SharedAppState) -> Optio
                     menu_action = Some(MenuAction::ResetLayout);
                     ui.close();
                 }
+                if ui
+                    .button("Reset Layout & View")
+                    .on_hover_text("Restore the default panels, camera and display toggles")
+                    .clicked()
+                {
+                    menu_action = Some(MenuAction::ResetLayoutAndView);
+                    ui.close();
+                }
+                ui.separator();
+                ui.menu_button("Colorize", |ui| {
+                    if ui.button("By Depth").clicked() {
+                        app_state.lock().queue_action(AppAction::ColorizeByDepth);
+                        ui.close();
+                    }
+                    if ui.button("Distinct Colors").clicked() {
+                        app_state.lock().queue_action(AppAction::ColorizeDistinct);
+                        ui.close();
+                    }
+                    ui.separator();
+                    let has_originals = !app_state.lock().original_part_colors.is_empty();
+                    if ui
+                        .add_enabled(has_originals, egui::Button::new("Restore Colors"))
+                        .clicked()
+                    {
+                        app_state.lock().queue_action(AppAction::RestorePartColors);
+                        ui.close();
+                    }
+                });
             });
         });
     });
@@ -254,5 +440,7 @@ pub fn render_menu_bar(ctx: &egui::Context, app_state: &SharedAppState) -> Optio
 /// Actions triggered by the menu
 pub enum MenuAction {
     ResetLayout,
+    /// Reset the dock layout, camera and display toggles
+    ResetLayoutAndView,
     OpenPreferences,
 }
@@ -9,11 +9,14 @@ use std::sync::Arc;
 
 use egui_dock::{DockArea, DockState, Style};
 use parking_lot::Mutex;
+use rk_renderer::{DisplayOptions, RenderLayer};
 
 use crate::actions::{ActionContext, dispatch_action};
-use crate::config::{SharedConfig, create_shared_config};
+use crate::config::{EditorConfig, SharedConfig, create_shared_config};
 use crate::panels::PreferencesPanel;
-use crate::state::{SharedAppState, SharedViewportState, ViewportState, create_shared_state};
+use crate::state::{
+    AppState, SharedAppState, SharedViewportState, ViewportState, create_shared_state,
+};
 use crate::update::{SharedUpdateStatus, UpdateStatus, check_for_updates, create_update_status};
 use welcome::WelcomeDialog;
 
@@ -60,7 +63,7 @@ impl UrdfEditorApp {
             {
                 let cfg = config.read();
                 vp_state.renderer.apply_config(
-                    &cfg.config().renderer,
+                    &cfg.config().effective_renderer_config(),
                     &vp_state.device,
                     &vp_state.queue,
                 );
@@ -69,6 +72,9 @@ impl UrdfEditorApp {
             Arc::new(Mutex::new(vp_state))
         });
 
+        cc.egui_ctx
+            .set_visuals(config.read().config().ui.theme.visuals());
+
         // Create app state and apply editor config
         let app_state = create_shared_state();
         {
@@ -77,7 +83,16 @@ impl UrdfEditorApp {
             state.show_part_axes = cfg.config().editor.show_part_axes;
             state.show_joint_markers = cfg.config().editor.show_joint_markers;
             state.angle_display_mode = cfg.config().editor.angle_display_mode;
+            state.rotation_display_mode = cfg.config().editor.rotation_display_mode;
+            state.length_unit = cfg.config().editor.length_unit;
+            state.rotation_snap = cfg.config().editor.rotation_snap;
             state.stl_import_unit = cfg.config().editor.stl_import_unit;
+            state.flip_normals_on_import = cfg.config().editor.flip_normals_on_import;
+            state.recompute_normals_on_import = cfg.config().editor.recompute_normals_on_import;
+            state.double_click_focus = cfg.config().editor.double_click_focus;
+            state.primitive_segments = cfg.config().editor.primitive_segments;
+            state.joint_defaults = cfg.config().joint_defaults.clone();
+            state.default_viewport = cfg.config().viewport_defaults.clone();
         }
 
         // Create dock layout
@@ -116,6 +131,42 @@ impl UrdfEditorApp {
         }
     }
 
+    /// Run the sketch re-solves queued by this frame's actions
+    fn solve_pending_sketches(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        let mut state = self.app_state.lock();
+        for (sketch_id, result) in state.cad.solve_pending(now) {
+            tracing::debug!("Re-solved sketch {}: {:?}", sketch_id, result);
+        }
+        // Throttled heavy sketches need another frame once the interval passes
+        if state.cad.has_pending_solves() {
+            ctx.request_repaint_after(std::time::Duration::from_secs_f64(
+                crate::state::HEAVY_SOLVE_INTERVAL,
+            ));
+        }
+    }
+
+    /// Restore the default dock layout, fit the camera and reset display toggles
+    fn reset_layout_and_view(&mut self) {
+        let cfg = self.config.read();
+        let mut state = self.app_state.lock();
+        let mut display = DisplayOptions::default();
+        let mut vp = self.viewport_state.as_ref().map(|vp| vp.lock());
+        reset_layout_and_display(
+            &mut self.dock_state,
+            &mut state,
+            &cfg.config().editor,
+            vp.as_mut()
+                .map_or(&mut display, |vp| vp.renderer.display_options_mut()),
+        );
+
+        if let Some(vp) = vp.as_mut() {
+            vp.renderer
+                .apply_viewport_settings(&cfg.config().viewport_defaults);
+            vp.renderer.camera_mut().fit_all(glam::Vec3::ZERO, 2.0);
+        }
+    }
+
     /// Show update notification banner
     fn show_update_banner(&mut self, ctx: &egui::Context) {
         let status = self.update_status.lock().clone();
@@ -154,12 +205,33 @@ impl UrdfEditorApp {
             });
         }
     }
+
+    /// Show why the last export failed until the user dismisses it
+    fn show_export_error(&mut self, ctx: &egui::Context) {
+        let Some(message) = self.app_state.lock().export_error.clone() else {
+            return;
+        };
+
+        let mut dismissed = false;
+        egui::Window::new("Export Failed")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(message);
+                dismissed = ui.button("OK").clicked();
+            });
+        if dismissed {
+            self.app_state.lock().export_error = None;
+        }
+    }
 }
 
 impl eframe::App for UrdfEditorApp {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         // Process pending actions
         self.process_actions();
+        self.solve_pending_sketches(ctx);
 
         // Menu bar
         if let Some(menu_action) = render_menu_bar(ctx, &self.app_state) {
@@ -167,6 +239,9 @@ impl eframe::App for UrdfEditorApp {
                 MenuAction::ResetLayout => {
                     self.dock_state = create_dock_layout();
                 }
+                MenuAction::ResetLayoutAndView => {
+                    self.reset_layout_and_view();
+                }
                 MenuAction::OpenPreferences => {
                     self.preferences_open = true;
                 }
@@ -199,6 +274,8 @@ impl eframe::App for UrdfEditorApp {
         // Welcome dialog (shown on first launch)
         self.welcome_dialog.show(ctx);
 
+        self.show_export_error(ctx);
+
         // Preferences window
         if self.preferences_open {
             self.preferences_panel.show(
@@ -221,3 +298,79 @@ impl eframe::App for UrdfEditorApp {
         }
     }
 }
+
+/// Restore the default dock layout and display toggles
+///
+/// Shows every render layer again and drops the per-part visibility and
+/// shadow overrides. The camera and the viewport defaults are applied by the
+/// caller, which owns the renderer.
+fn reset_layout_and_display(
+    dock_state: &mut DockState<PanelType>,
+    state: &mut AppState,
+    editor: &EditorConfig,
+    display: &mut DisplayOptions,
+) {
+    *dock_state = create_dock_layout();
+
+    let defaults = AppState::default();
+    state.show_part_axes = editor.show_part_axes;
+    state.show_joint_markers = editor.show_joint_markers;
+    state.highlight_chain = defaults.highlight_chain;
+    state.show_name_labels = defaults.show_name_labels;
+    state.hidden_visuals.clear();
+    state.hidden_collisions.clear();
+    state.non_casting_shadows.clear();
+    state.non_receiving_shadows.clear();
+
+    display.layers = RenderLayer::ALL_MASK;
+    display.set_hidden_visuals(&[]);
+    display.set_hidden_collisions(&[]);
+    display.set_shadow_overrides(&[], &[]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn panel_names(dock_state: &DockState<PanelType>) -> Vec<String> {
+        let mut names: Vec<String> = dock_state
+            .iter_all_tabs()
+            .map(|(_, tab)| tab.name().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_reset_restores_default_layout_and_display() {
+        let default_names = panel_names(&create_dock_layout());
+
+        // Close a panel and hide things, as a user with a broken view would
+        let mut dock_state = create_dock_layout();
+        let tab = dock_state
+            .find_tab_from(|tab| matches!(tab, PanelType::Properties(_)))
+            .unwrap();
+        dock_state.remove_tab(tab).unwrap();
+        assert_ne!(panel_names(&dock_state), default_names);
+
+        let part = uuid::Uuid::new_v4();
+        let mut state = AppState::default();
+        state.show_part_axes = false;
+        state.show_name_labels = true;
+        state.hidden_visuals.insert(part);
+        state.non_casting_shadows.insert(part);
+        let mut display = DisplayOptions::default();
+        display.set_layer_visible(RenderLayer::Annotations, false);
+        display.set_hidden_collisions(&[part]);
+
+        let editor = EditorConfig::default();
+        reset_layout_and_display(&mut dock_state, &mut state, &editor, &mut display);
+
+        assert_eq!(panel_names(&dock_state), default_names);
+        assert_eq!(state.show_part_axes, editor.show_part_axes);
+        assert!(!state.show_name_labels);
+        assert!(state.hidden_visuals.is_empty() && state.non_casting_shadows.is_empty());
+        assert_eq!(display.layers, RenderLayer::ALL_MASK);
+        assert!(display.is_collision_visible(part));
+    }
+}
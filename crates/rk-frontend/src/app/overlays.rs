@@ -1,5 +1,8 @@
 //! Overlay update logic
 
+use glam::Vec3;
+use uuid::Uuid;
+
 use crate::state::{SharedAppState, SharedViewportState};
 
 /// Update overlays based on current selection
@@ -10,6 +13,44 @@ pub fn update_overlays(app_state: &SharedAppState, viewport_state: &Option<Share
 
     let state = app_state.lock();
 
+    // Highlight the kinematic chain from the selected part to the root
+    let chain_parts = state.selected_chain_parts();
+    viewport_state.lock().set_highlighted_parts(&chain_parts);
+
+    // Visual/collision visibility and collision shapes
+    {
+        let hidden_visuals: Vec<Uuid> = state.hidden_visuals.iter().copied().collect();
+        let hidden_collisions: Vec<Uuid> = state.hidden_collisions.iter().copied().collect();
+        let non_casting: Vec<Uuid> = state.non_casting_shadows.iter().copied().collect();
+        let non_receiving: Vec<Uuid> = state.non_receiving_shadows.iter().copied().collect();
+        let mut vp = viewport_state.lock();
+        vp.set_hidden_parts(&hidden_visuals, &hidden_collisions);
+        vp.set_shadow_overrides(&non_casting, &non_receiving);
+        vp.update_collision_shapes(&state.project.assembly, state.selected_collision);
+        vp.update_inertial_frames(&state.project.assembly);
+    }
+
+    // Joint origins as pickable markers
+    {
+        let assembly = &state.project.assembly;
+        let markers: Vec<(Uuid, Vec3)> = if state.show_joint_markers {
+            assembly
+                .joints
+                .iter()
+                .filter_map(|(joint_id, joint)| {
+                    let parent = assembly.get_link(joint.parent_link)?;
+                    let frame = parent.world_transform * joint.origin.to_mat4();
+                    Some((*joint_id, frame.w_axis.truncate()))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        viewport_state
+            .lock()
+            .set_joint_markers(markers, state.editing_joint_id);
+    }
+
     // First check if a collision is selected (takes priority over part selection)
     if let Some((link_id, collision_index)) = state.selected_collision
         && let Some(link) = state.project.assembly.get_link(link_id)
@@ -47,7 +88,7 @@ pub fn update_overlays(app_state: &SharedAppState, viewport_state: &Option<Share
         return;
     }
 
-    // No selection - clear overlays
+    // No selection - clear selection overlays
     drop(state);
-    viewport_state.lock().clear_overlays();
+    viewport_state.lock().clear_selection_overlays();
 }
@@ -7,11 +7,13 @@ mod manager;
 
 pub use manager::{ConfigError, ConfigManager, SharedConfig, create_shared_config};
 
-use rk_core::StlUnit;
-use rk_renderer::config::RendererConfig;
+use glam::Vec3;
+use rk_core::{Joint, JointLimits, JointType, Pose, StlUnit, ViewportSettings};
+use rk_renderer::config::{RendererConfig, ViewportConfig};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use crate::state::AngleDisplayMode;
+use crate::state::{AngleDisplayMode, RotationDisplayMode, RotationSnapIncrement};
 
 /// Editor preferences
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,8 +24,41 @@ pub struct EditorConfig {
     pub show_joint_markers: bool,
     /// Angle display mode for joint sliders
     pub angle_display_mode: AngleDisplayMode,
+    /// Orientation display mode for pose editors
+    #[serde(default)]
+    pub rotation_display_mode: RotationDisplayMode,
+    /// Unit for displaying and entering lengths
+    #[serde(default = "default_length_unit")]
+    pub length_unit: StlUnit,
+    /// Increment for rotate-gizmo snapping (hold Ctrl while dragging)
+    #[serde(default)]
+    pub rotation_snap: RotationSnapIncrement,
     /// Default unit for STL import
     pub stl_import_unit: StlUnit,
+    /// Reverse triangle winding of imported meshes
+    #[serde(default)]
+    pub flip_normals_on_import: bool,
+    /// Recompute normals of imported meshes
+    #[serde(default)]
+    pub recompute_normals_on_import: bool,
+    /// Double-clicking a part frames it and orbits around it
+    #[serde(default = "default_double_click_focus")]
+    pub double_click_focus: bool,
+    /// Segments around round primitives (cylinders, spheres) when created
+    #[serde(default = "default_primitive_segments")]
+    pub primitive_segments: u32,
+}
+
+fn default_length_unit() -> StlUnit {
+    StlUnit::Meters
+}
+
+fn default_double_click_focus() -> bool {
+    true
+}
+
+fn default_primitive_segments() -> u32 {
+    rk_core::CYLINDER_SEGMENTS
 }
 
 impl Default for EditorConfig {
@@ -32,7 +67,67 @@ impl Default for EditorConfig {
             show_part_axes: true,
             show_joint_markers: true,
             angle_display_mode: AngleDisplayMode::Degrees,
+            rotation_display_mode: RotationDisplayMode::Euler,
+            length_unit: default_length_unit(),
+            rotation_snap: RotationSnapIncrement::Deg15,
             stl_import_unit: StlUnit::Millimeters,
+            flip_normals_on_import: false,
+            recompute_normals_on_import: false,
+            double_click_focus: true,
+            primitive_segments: rk_core::CYLINDER_SEGMENTS,
+        }
+    }
+}
+
+/// Defaults used when creating new joints
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JointDefaultsConfig {
+    /// Joint type for newly connected parts
+    pub joint_type: JointType,
+    /// Joint axis (normalized when applied)
+    pub axis: [f32; 3],
+    /// Limits applied to new revolute joints
+    pub limits: JointLimits,
+    /// Limits applied to new prismatic joints
+    #[serde(default = "JointLimits::default_prismatic")]
+    pub prismatic_limits: JointLimits,
+}
+
+impl Default for JointDefaultsConfig {
+    fn default() -> Self {
+        Self {
+            joint_type: JointType::Fixed,
+            axis: [0.0, 0.0, 1.0],
+            limits: JointLimits::default_revolute(),
+            prismatic_limits: JointLimits::default_prismatic(),
+        }
+    }
+}
+
+impl JointDefaultsConfig {
+    /// Build a new joint using these defaults
+    pub fn build_joint(&self, name: impl Into<String>, parent: Uuid, child: Uuid) -> Joint {
+        let axis = Vec3::from_array(self.axis);
+        let mut builder = Joint::builder(name, parent, child)
+            .joint_type(self.joint_type)
+            .origin(Pose::default())
+            .axis(if axis.length_squared() > f32::EPSILON {
+                axis
+            } else {
+                Vec3::Z
+            });
+        if let Some(limits) = self.limits_for(self.joint_type) {
+            builder = builder.limits(limits);
+        }
+        builder.build()
+    }
+
+    /// Default limits for a joint of `joint_type`, if that type has limits
+    pub fn limits_for(&self, joint_type: JointType) -> Option<JointLimits> {
+        match joint_type {
+            JointType::Revolute => Some(self.limits),
+            JointType::Prismatic => Some(self.prismatic_limits),
+            _ => None,
         }
     }
 }
@@ -45,6 +140,24 @@ pub enum UiTheme {
     Light,
 }
 
+impl UiTheme {
+    /// egui visuals for this theme
+    pub fn visuals(self) -> egui::Visuals {
+        match self {
+            UiTheme::Dark => egui::Visuals::dark(),
+            UiTheme::Light => egui::Visuals::light(),
+        }
+    }
+
+    /// Viewport background used when the background follows the theme
+    pub fn viewport_background(self) -> [f32; 4] {
+        match self {
+            UiTheme::Dark => ViewportConfig::default().background_color,
+            UiTheme::Light => [0.82, 0.83, 0.86, 1.0],
+        }
+    }
+}
+
 /// UI preferences
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct UiConfig {
@@ -78,6 +191,12 @@ pub struct AppConfig {
     /// UI settings
     #[serde(default)]
     pub ui: UiConfig,
+    /// Defaults for newly created joints
+    #[serde(default)]
+    pub joint_defaults: JointDefaultsConfig,
+    /// Viewport for projects saved without their own viewport settings
+    #[serde(default)]
+    pub viewport_defaults: ViewportSettings,
 }
 
 impl AppConfig {
@@ -91,4 +210,104 @@ impl AppConfig {
             ..Default::default()
         }
     }
+
+    /// Renderer settings with the viewport background resolved against the UI theme
+    pub fn effective_renderer_config(&self) -> RendererConfig {
+        let mut renderer = self.renderer.clone();
+        if renderer.viewport.background_follows_theme {
+            renderer.viewport.background_color = self.ui.theme.viewport_background();
+        }
+        renderer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_joint_defaults_roundtrip() {
+        let mut config = AppConfig::new();
+        config.joint_defaults = JointDefaultsConfig {
+            joint_type: JointType::Revolute,
+            axis: [0.0, 1.0, 0.0],
+            limits: JointLimits::with_range(-1.0, 2.0),
+            prismatic_limits: JointLimits::with_range(0.0, 0.3),
+        };
+
+        let text = ron::ser::to_string_pretty(&config, ron::ser::PrettyConfig::default()).unwrap();
+        let loaded: AppConfig = ron::from_str(&text).unwrap();
+        assert_eq!(loaded, config);
+    }
+
+    #[test]
+    fn test_joint_defaults_missing_section() {
+        // Configs written before joint defaults existed still load
+        let loaded: AppConfig = ron::from_str("(version: 1)").unwrap();
+        assert_eq!(loaded.joint_defaults, JointDefaultsConfig::default());
+    }
+
+    #[test]
+    fn test_length_unit_missing_defaults_to_meters() {
+        // Configs written before the length unit existed keep showing meters
+        let text = ron::ser::to_string_pretty(&AppConfig::new(), Default::default()).unwrap();
+        assert!(text.contains("length_unit"));
+        let text: String = text
+            .lines()
+            .filter(|line| !line.contains("length_unit"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let loaded: AppConfig = ron::from_str(&text).unwrap();
+        assert_eq!(loaded.editor.length_unit, StlUnit::Meters);
+    }
+
+    #[test]
+    fn test_new_joint_uses_configured_defaults() {
+        let defaults = JointDefaultsConfig {
+            joint_type: JointType::Revolute,
+            axis: [0.0, 0.0, 2.0],
+            limits: JointLimits::with_range(-std::f32::consts::PI, std::f32::consts::PI),
+            prismatic_limits: JointLimits::with_range(0.0, 0.3),
+        };
+        let (parent, child) = (Uuid::new_v4(), Uuid::new_v4());
+        let joint = defaults.build_joint("j", parent, child);
+
+        assert_eq!(joint.joint_type, JointType::Revolute);
+        assert_eq!(joint.axis, Vec3::Z);
+        assert_eq!(joint.limits, Some(defaults.limits));
+        assert_eq!(joint.parent_link, parent);
+        assert_eq!(joint.child_link, child);
+    }
+
+    #[test]
+    fn test_new_joint_without_limits() {
+        let defaults = JointDefaultsConfig {
+            joint_type: JointType::Continuous,
+            ..Default::default()
+        };
+        let joint = defaults.build_joint("j", Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(joint.joint_type, JointType::Continuous);
+        assert!(joint.limits.is_none());
+    }
+
+    #[test]
+    fn test_background_follows_theme() {
+        let mut config = AppConfig::new();
+        config.renderer.viewport.background_color = [1.0, 0.0, 0.0, 1.0];
+
+        // Flag off: the configured color is used regardless of theme
+        config.ui.theme = UiTheme::Light;
+        let background = |c: &AppConfig| c.effective_renderer_config().viewport.background_color;
+        assert_eq!(background(&config), [1.0, 0.0, 0.0, 1.0]);
+
+        // Flag on: toggling the theme switches the background
+        config.renderer.viewport.background_follows_theme = true;
+        assert_eq!(background(&config), UiTheme::Light.viewport_background());
+        config.ui.theme = UiTheme::Dark;
+        assert_eq!(background(&config), UiTheme::Dark.viewport_background());
+        assert_ne!(
+            UiTheme::Dark.viewport_background(),
+            UiTheme::Light.viewport_background()
+        );
+    }
 }
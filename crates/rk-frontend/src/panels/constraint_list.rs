@@ -0,0 +1,134 @@
+//! Constraint list panel for the active sketch
+
+use egui::Ui;
+use uuid::Uuid;
+
+use crate::panels::Panel;
+use crate::state::{AppAction, SharedAppState, SketchAction};
+
+/// Lists the constraints of the sketch being edited
+pub struct ConstraintListPanel {
+    // Panel has no persistent state - the active sketch is in AppState
+}
+
+/// Snapshot of constraint data for rendering
+struct ConstraintInfo {
+    id: Uuid,
+    type_name: &'static str,
+    value: Option<f32>,
+    entities: Vec<&'static str>,
+}
+
+impl ConstraintListPanel {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for ConstraintListPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Panel for ConstraintListPanel {
+    fn name(&self) -> &str {
+        "Constraints"
+    }
+
+    fn ui(&mut self, ui: &mut Ui, app_state: &SharedAppState) {
+        let (constraints, is_solved, solving, dof) = {
+            let state = app_state.lock();
+            let Some(sketch) = state
+                .cad
+                .editor_mode
+                .sketch()
+                .and_then(|s| state.cad.get_sketch(s.active_sketch))
+            else {
+                drop(state);
+                ui.weak("Not editing a sketch.\nDouble-click a sketch to edit it.");
+                return;
+            };
+
+            let constraints: Vec<ConstraintInfo> = sketch
+                .constraints()
+                .iter()
+                .map(|c| ConstraintInfo {
+                    id: c.id(),
+                    type_name: c.type_name(),
+                    value: c.value(),
+                    entities: c
+                        .referenced_entities()
+                        .iter()
+                        .map(|id| sketch.get_entity(*id).map_or("?", |e| e.type_name()))
+                        .collect(),
+                })
+                .collect();
+
+            (
+                constraints,
+                sketch.is_solved(),
+                state.cad.is_solve_pending(sketch.id),
+                sketch.degrees_of_freedom(),
+            )
+        };
+
+        // Solve status header
+        ui.horizontal(|ui| {
+            if solving {
+                ui.spinner();
+                ui.label("Solving…");
+            } else if is_solved {
+                ui.label(format!("DOF: {}", dof));
+            } else {
+                ui.colored_label(egui::Color32::from_rgb(255, 180, 50), "Unsolved");
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Solve").clicked() {
+                    app_state
+                        .lock()
+                        .queue_action(AppAction::SketchAction(SketchAction::SolveSketch));
+                }
+            });
+        });
+
+        ui.separator();
+
+        if constraints.is_empty() {
+            ui.weak("No constraints in this sketch.");
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_salt("constraint_list_scroll")
+            .show(ui, |ui| {
+                for (index, constraint) in constraints.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let label = match constraint.value {
+                            Some(value) => {
+                                format!("{}. {} = {:.3}", index + 1, constraint.type_name, value)
+                            }
+                            None => format!("{}. {}", index + 1, constraint.type_name),
+                        };
+                        ui.label(label)
+                            .on_hover_text(constraint.entities.join(", "));
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .small_button("x")
+                                .on_hover_text("Delete constraint")
+                                .clicked()
+                            {
+                                app_state.lock().queue_action(AppAction::SketchAction(
+                                    SketchAction::DeleteConstraint {
+                                        constraint_id: constraint.id,
+                                    },
+                                ));
+                            }
+                        });
+                    });
+                }
+            });
+    }
+}
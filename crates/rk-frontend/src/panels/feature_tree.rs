@@ -6,7 +6,7 @@
 use egui::{CollapsingHeader, Ui};
 use uuid::Uuid;
 
-use rk_cad::SketchPlane;
+use rk_cad::{Feature, SketchPlane};
 
 use crate::panels::Panel;
 use crate::state::{AppAction, SharedAppState, SketchAction};
@@ -35,12 +35,26 @@ struct SketchInfo {
     dof: u32,
 }
 
+/// Snapshot of the rollback/build state for rendering
+struct RollbackInfo {
+    /// Index before which the rollback indicator is drawn
+    stop_position: usize,
+    /// Number of features included in the build
+    effective_len: usize,
+    /// Whether the last rebuild failed
+    failed: bool,
+}
+
 /// Snapshot of feature data for rendering
 struct FeatureInfo {
     id: Uuid,
     name: String,
     type_name: &'static str,
     is_suppressed: bool,
+    /// Whether each profile becomes its own body; `None` for non-sketch features
+    keep_bodies_separate: Option<bool>,
+    /// Error from the last rebuild
+    error: Option<String>,
 }
 
 impl FeatureTreePanel {
@@ -65,7 +79,7 @@ impl Panel for FeatureTreePanel {
 
     fn ui(&mut self, ui: &mut Ui, app_state: &SharedAppState) {
         // Collect data from state
-        let (has_sketches, is_sketch_mode, active_sketch, sketches, features) = {
+        let (has_sketches, is_sketch_mode, active_sketch, sketches, features, rollback) = {
             let state = app_state.lock();
             let cad = &state.cad;
 
@@ -85,15 +99,29 @@ impl Panel for FeatureTreePanel {
             let features: Vec<FeatureInfo> = cad
                 .data
                 .history
-                .features()
-                .map(|f| FeatureInfo {
-                    id: f.id(),
-                    name: f.name().to_string(),
-                    type_name: f.type_name(),
-                    is_suppressed: f.is_suppressed(),
+                .entries()
+                .iter()
+                .map(|e| FeatureInfo {
+                    id: e.feature.id(),
+                    name: e.feature.name().to_string(),
+                    type_name: e.feature.type_name(),
+                    is_suppressed: e.feature.is_suppressed(),
+                    keep_bodies_separate: matches!(
+                        e.feature,
+                        Feature::Extrude { .. } | Feature::Revolve { .. }
+                    )
+                    .then(|| e.feature.keeps_bodies_separate()),
+                    error: e.error.clone(),
                 })
                 .collect();
 
+            let history = &cad.data.history;
+            let rollback = RollbackInfo {
+                stop_position: history.build_stop_position(),
+                effective_len: history.effective_len(),
+                failed: history.first_failure().is_some(),
+            };
+
             let has_sketches = !sketches.is_empty();
             let is_sketch_mode = cad.is_sketch_mode();
             let active_sketch = cad.editor_mode.sketch().map(|s| s.active_sketch);
@@ -104,6 +132,7 @@ impl Panel for FeatureTreePanel {
                 active_sketch,
                 sketches,
                 features,
+                rollback,
             )
         };
 
@@ -185,6 +214,16 @@ impl Panel for FeatureTreePanel {
                                         ));
                                         ui.close();
                                     }
+                                    if ui
+                                        .button("Flip Plane")
+                                        .on_hover_text("Reverse the sketch normal")
+                                        .clicked()
+                                    {
+                                        app_state.lock().queue_action(AppAction::SketchAction(
+                                            SketchAction::FlipSketchPlane { sketch_id },
+                                        ));
+                                        ui.close();
+                                    }
                                     if ui.button("Delete").clicked() {
                                         // TODO: Delete sketch
                                         ui.close();
@@ -201,18 +240,49 @@ impl Panel for FeatureTreePanel {
                         if features.is_empty() {
                             ui.weak("No features yet.");
                         } else {
-                            for feature in &features {
+                            for (index, feature) in features.iter().enumerate() {
+                                if index == rollback.stop_position {
+                                    rollback_indicator(ui, &rollback);
+                                }
+
                                 let is_selected =
                                     self.selected == Some(TreeItem::Feature(feature.id));
                                 let is_suppressed = feature.is_suppressed;
 
                                 let label = if is_suppressed {
                                     format!("  {} [suppressed]", feature.name)
+                                } else if feature.error.is_some() {
+                                    format!("\u{26a0} {} ({})", feature.name, feature.type_name)
                                 } else {
                                     format!("  {} ({})", feature.name, feature.type_name)
                                 };
 
-                                let response = ui.selectable_label(is_selected, label);
+                                let mut text = egui::RichText::new(label);
+                                if is_suppressed {
+                                    text = text.color(ui.visuals().weak_text_color()).italics();
+                                } else if feature.error.is_some() {
+                                    text = text.color(ui.visuals().error_fg_color);
+                                } else if index >= rollback.effective_len {
+                                    text = text.weak();
+                                }
+
+                                let feature_id = feature.id;
+                                let mut response = ui
+                                    .horizontal(|ui| {
+                                        let mut active = !is_suppressed;
+                                        if ui
+                                            .checkbox(&mut active, "")
+                                            .on_hover_text("Include this feature in the build")
+                                            .changed()
+                                        {
+                                            queue_suppression(app_state, feature_id, !active);
+                                        }
+                                        ui.selectable_label(is_selected, text)
+                                    })
+                                    .inner;
+                                if let Some(error) = &feature.error {
+                                    response = response.on_hover_text(error);
+                                }
 
                                 if response.clicked() {
                                     self.selected = Some(TreeItem::Feature(feature.id));
@@ -232,7 +302,24 @@ impl Panel for FeatureTreePanel {
                                         })
                                         .clicked()
                                     {
-                                        // TODO: Toggle suppression
+                                        queue_suppression(app_state, feature_id, !is_suppressed);
+                                        ui.close();
+                                    }
+                                    if let Some(mut keep_separate) = feature.keep_bodies_separate
+                                        && ui
+                                            .checkbox(&mut keep_separate, "Keep Bodies Separate")
+                                            .on_hover_text(
+                                                "Create one body per closed profile instead of \
+                                                 merging them",
+                                            )
+                                            .changed()
+                                    {
+                                        app_state.lock().queue_action(AppAction::SketchAction(
+                                            SketchAction::SetKeepBodiesSeparate {
+                                                feature_id,
+                                                keep_separate,
+                                            },
+                                        ));
                                         ui.close();
                                     }
                                     if ui.button("Delete").clicked() {
@@ -241,6 +328,12 @@ impl Panel for FeatureTreePanel {
                                     }
                                 });
                             }
+
+                            if rollback.stop_position >= features.len()
+                                && (rollback.failed || rollback.effective_len < features.len())
+                            {
+                                rollback_indicator(ui, &rollback);
+                            }
                         }
                     });
             });
@@ -256,3 +349,29 @@ impl Panel for FeatureTreePanel {
         }
     }
 }
+
+/// Queue a suppression change for a feature
+fn queue_suppression(app_state: &SharedAppState, feature_id: Uuid, suppressed: bool) {
+    app_state.lock().queue_action(AppAction::SketchAction(
+        SketchAction::SetFeatureSuppressed {
+            feature_id,
+            suppressed,
+        },
+    ));
+}
+
+/// Draw the rollback bar marking the rollback position or the first failure
+///
+/// A failed feature doesn't stop the rebuild, so the bar says where the build
+/// first failed rather than claiming it stopped there.
+fn rollback_indicator(ui: &mut Ui, rollback: &RollbackInfo) {
+    let (text, color) = if rollback.failed {
+        ("Build failed above", ui.visuals().error_fg_color)
+    } else {
+        ("Rolled back", ui.visuals().weak_text_color())
+    };
+    let response = ui.colored_label(color, format!("\u{2500}\u{2500} {} \u{2500}\u{2500}", text));
+    if rollback.failed {
+        response.on_hover_text("Later features were still rebuilt");
+    }
+}
@@ -2,7 +2,7 @@
 
 use egui::Ui;
 
-use rk_core::JointType;
+use rk_core::{Joint, JointType};
 
 use crate::panels::Panel;
 use crate::state::{AngleDisplayMode, AppAction, SharedAppState};
@@ -34,6 +34,7 @@ impl Panel for JointListPanel {
         let joints: Vec<_> = state.project.assembly.joints.values().cloned().collect();
         let joint_positions = state.project.assembly.joint_positions.clone();
         let angle_mode = state.angle_display_mode;
+        let chain_joints = state.selected_chain_joints();
         drop(state);
 
         if joints.is_empty() {
@@ -70,7 +71,15 @@ impl Panel for JointListPanel {
             .id_salt("joint_list_scroll")
             .show(ui, |ui| {
                 for joint in &joints {
-                    self.render_joint_control(ui, joint, &joint_positions, angle_mode, app_state);
+                    let in_chain = chain_joints.contains(&joint.id);
+                    self.render_joint_control(
+                        ui,
+                        joint,
+                        &joint_positions,
+                        angle_mode,
+                        in_chain,
+                        app_state,
+                    );
                 }
             });
     }
@@ -83,6 +92,7 @@ impl JointListPanel {
         joint: &rk_core::Joint,
         joint_positions: &std::collections::HashMap<uuid::Uuid, f32>,
         angle_mode: AngleDisplayMode,
+        in_chain: bool,
         app_state: &SharedAppState,
     ) {
         let current_value_rad = joint_positions.get(&joint.id).copied().unwrap_or(0.0);
@@ -98,7 +108,13 @@ impl JointListPanel {
                     JointType::Floating => "[Float]",
                     JointType::Planar => "[Planar]",
                 };
-                ui.label(format!("{} {}", type_label, joint.name));
+                let label = format!("{} {}", type_label, joint.name);
+                if in_chain {
+                    // Joint lies on the selected part's chain to the root
+                    ui.colored_label(egui::Color32::from_rgb(80, 200, 255), label);
+                } else {
+                    ui.label(label);
+                }
             });
 
             match joint.joint_type {
@@ -112,12 +128,14 @@ impl JointListPanel {
                     });
                 }
                 JointType::Continuous => {
-                    // Continuous: full rotation, use +/- 180 degrees or PI
+                    // Continuous: show the wrapped angle, but step the raw position
+                    // by the wrapped change so dragging past +/-180 keeps turning
                     let (min_display, max_display) = match angle_mode {
                         AngleDisplayMode::Degrees => (-180.0, 180.0),
                         AngleDisplayMode::Radians => (-std::f32::consts::PI, std::f32::consts::PI),
                     };
-                    let mut display_value = angle_mode.from_radians(current_value_rad);
+                    let wrapped_rad = Joint::normalize_continuous(current_value_rad);
+                    let mut display_value = angle_mode.from_radians(wrapped_rad);
 
                     ui.horizontal(|ui| {
                         let slider =
@@ -125,7 +143,8 @@ impl JointListPanel {
                                 .suffix(angle_mode.suffix())
                                 .clamping(egui::SliderClamping::Never);
                         if ui.add(slider).changed() {
-                            let new_rad = angle_mode.to_radians(display_value);
+                            let delta = angle_mode.to_radians(display_value) - wrapped_rad;
+                            let new_rad = current_value_rad + Joint::normalize_continuous(delta);
                             app_state
                                 .lock()
                                 .queue_action(AppAction::UpdateJointPosition {
@@ -1,17 +1,21 @@
 //! UI panels
 
+mod constraint_list;
 mod feature_tree;
 mod joint_list;
 mod part_list;
 mod preferences;
 mod properties;
+mod robot_info;
 mod viewport;
 
+pub use constraint_list::ConstraintListPanel;
 pub use feature_tree::FeatureTreePanel;
 pub use joint_list::JointListPanel;
 pub use part_list::PartListPanel;
 pub use preferences::PreferencesPanel;
 pub use properties::PropertiesPanel;
+pub use robot_info::RobotInfoPanel;
 pub use viewport::ViewportPanel;
 
 use crate::config::SharedConfig;
@@ -22,6 +22,18 @@ pub struct PartListPanel {
     editing_project_name: bool,
     /// Temporary buffer for editing project name
     project_name_buffer: String,
+    /// Parts with hidden visuals (snapshot for the current frame)
+    hidden_visuals: HashSet<Uuid>,
+    /// Parts with hidden collisions (snapshot for the current frame)
+    hidden_collisions: HashSet<Uuid>,
+    /// Parts that do not cast shadows (snapshot for the current frame)
+    non_casting_shadows: HashSet<Uuid>,
+    /// Parts that do not receive shadows (snapshot for the current frame)
+    non_receiving_shadows: HashSet<Uuid>,
+    /// Parts locked against gizmo edits (snapshot for the current frame)
+    locked_parts: HashSet<Uuid>,
+    /// Color picked in the "Apply Color to Subtree" menu
+    subtree_color: egui::Color32,
 }
 
 impl PartListPanel {
@@ -31,6 +43,12 @@ impl PartListPanel {
             drop_target: None,
             editing_project_name: false,
             project_name_buffer: String::new(),
+            hidden_visuals: HashSet::new(),
+            hidden_collisions: HashSet::new(),
+            non_casting_shadows: HashSet::new(),
+            non_receiving_shadows: HashSet::new(),
+            locked_parts: HashSet::new(),
+            subtree_color: egui::Color32::GRAY,
         }
     }
 
@@ -46,6 +64,26 @@ impl PartListPanel {
     ) {
         let is_being_dragged = self.dragging_part == Some(part_id);
         let is_drop_target = self.drop_target == Some(part_id);
+        let is_locked = self.locked_parts.contains(&part_id);
+
+        // Lock toggle
+        let (icon, hover) = if is_locked {
+            ("🔒", "Locked: click to allow gizmo edits")
+        } else {
+            ("🔓", "Click to lock against gizmo edits")
+        };
+        let lock_text = if is_locked {
+            egui::RichText::new(icon)
+        } else {
+            egui::RichText::new(icon).weak()
+        };
+        if ui
+            .add(egui::Button::new(lock_text).frame(false).small())
+            .on_hover_text(hover)
+            .clicked()
+        {
+            actions.push(TreeAction::SetLocked(part_id, !is_locked));
+        }
 
         // Visual style based on drag state
         let text = if is_being_dragged {
@@ -81,6 +119,46 @@ impl PartListPanel {
                 actions.push(TreeAction::Delete(part_id));
                 ui.close();
             }
+            ui.separator();
+            let mut show_visual = !self.hidden_visuals.contains(&part_id);
+            if ui.checkbox(&mut show_visual, "Show Visual").clicked() {
+                actions.push(TreeAction::ToggleVisual(part_id));
+                ui.close();
+            }
+            let mut show_collision = !self.hidden_collisions.contains(&part_id);
+            if ui.checkbox(&mut show_collision, "Show Collision").clicked() {
+                actions.push(TreeAction::ToggleCollision(part_id));
+                ui.close();
+            }
+            let mut casts_shadow = !self.non_casting_shadows.contains(&part_id);
+            if ui.checkbox(&mut casts_shadow, "Cast Shadows").clicked() {
+                actions.push(TreeAction::ToggleCastShadow(part_id));
+                ui.close();
+            }
+            let mut receives_shadow = !self.non_receiving_shadows.contains(&part_id);
+            if ui
+                .checkbox(&mut receives_shadow, "Receive Shadows")
+                .clicked()
+            {
+                actions.push(TreeAction::ToggleReceiveShadow(part_id));
+                ui.close();
+            }
+            ui.separator();
+            ui.menu_button("Apply Color to Subtree", |ui| {
+                egui::color_picker::color_picker_color32(
+                    ui,
+                    &mut self.subtree_color,
+                    egui::color_picker::Alpha::OnlyBlend,
+                );
+                if ui.button("Apply").clicked() {
+                    let color = egui::Rgba::from(self.subtree_color).to_srgba_unmultiplied();
+                    actions.push(TreeAction::ApplyColorToSubtree(
+                        part_id,
+                        color.map(|c| c as f32 / 255.0),
+                    ));
+                    ui.close();
+                }
+            });
         });
 
         // Handle drag start
@@ -270,8 +348,21 @@ impl Panel for PartListPanel {
             .iter()
             .map(|(id, p)| (*id, p.name.clone()))
             .collect();
+        self.locked_parts = state
+            .project
+            .parts()
+            .iter()
+            .filter(|(_, p)| p.locked)
+            .map(|(id, _)| *id)
+            .collect();
 
         let is_empty = state.project.parts().is_empty();
+        self.hidden_visuals.clone_from(&state.hidden_visuals);
+        self.hidden_collisions.clone_from(&state.hidden_collisions);
+        self.non_casting_shadows
+            .clone_from(&state.non_casting_shadows);
+        self.non_receiving_shadows
+            .clone_from(&state.non_receiving_shadows);
         drop(state);
 
         // Reset drop targets each frame
@@ -402,6 +493,41 @@ impl Panel for PartListPanel {
                         .lock()
                         .queue_action(AppAction::ConnectParts { parent, child });
                 }
+                TreeAction::ToggleVisual(id) => {
+                    let mut state = app_state.lock();
+                    if !state.hidden_visuals.remove(&id) {
+                        state.hidden_visuals.insert(id);
+                    }
+                }
+                TreeAction::ToggleCollision(id) => {
+                    let mut state = app_state.lock();
+                    if !state.hidden_collisions.remove(&id) {
+                        state.hidden_collisions.insert(id);
+                    }
+                }
+                TreeAction::ToggleCastShadow(id) => {
+                    let mut state = app_state.lock();
+                    if !state.non_casting_shadows.remove(&id) {
+                        state.non_casting_shadows.insert(id);
+                    }
+                }
+                TreeAction::ToggleReceiveShadow(id) => {
+                    let mut state = app_state.lock();
+                    if !state.non_receiving_shadows.remove(&id) {
+                        state.non_receiving_shadows.insert(id);
+                    }
+                }
+                TreeAction::SetLocked(id, locked) => {
+                    app_state.lock().queue_action(AppAction::SetPartLocked {
+                        part_id: id,
+                        locked,
+                    });
+                }
+                TreeAction::ApplyColorToSubtree(id, color) => {
+                    app_state
+                        .lock()
+                        .queue_action(AppAction::ApplyColorToSubtree { part_id: id, color });
+                }
             }
         }
 
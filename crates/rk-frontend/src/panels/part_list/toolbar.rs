@@ -52,6 +52,15 @@ pub fn show_tree_context_menu(ui: &mut egui::Ui, app_state: &SharedAppState) {
             }
             ui.close();
         }
+        if ui.button("glTF...").clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("glTF files", &["gltf", "glb", "GLTF", "GLB"])
+                .pick_file()
+            {
+                app_state.lock().queue_action(AppAction::ImportMesh(path));
+            }
+            ui.close();
+        }
     });
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -10,6 +10,12 @@ pub enum TreeAction {
     Select(Uuid),
     Delete(Uuid),
     Disconnect(Uuid),
+    ToggleVisual(Uuid),
+    ToggleCollision(Uuid),
+    ToggleCastShadow(Uuid),
+    ToggleReceiveShadow(Uuid),
+    SetLocked(Uuid, bool),
+    ApplyColorToSubtree(Uuid, [f32; 4]),
     Connect { parent: Uuid, child: Uuid },
 }
 
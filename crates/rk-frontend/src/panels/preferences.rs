@@ -1,10 +1,13 @@
 //! Preferences window for application settings
 
-use rk_core::StlUnit;
+use rk_core::{JointLimits, JointType, StlUnit, ViewportSettings};
 use rk_renderer::config::RendererConfig;
 
-use crate::config::{EditorConfig, SharedConfig, UiConfig, UiTheme};
-use crate::state::{AngleDisplayMode, SharedAppState, SharedViewportState};
+use crate::config::{AppConfig, EditorConfig, SharedConfig, UiConfig, UiTheme};
+use crate::state::{
+    AngleDisplayMode, RotationDisplayMode, RotationSnapIncrement, SharedAppState,
+    SharedViewportState,
+};
 
 /// Current tab in the preferences window
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
@@ -71,10 +74,10 @@ impl PreferencesPanel {
                         self.renderer_tab(ui, config, viewport_state);
                     }
                     PreferencesTab::Editor => {
-                        self.editor_tab(ui, config, app_state);
+                        self.editor_tab(ui, config, app_state, viewport_state);
                     }
                     PreferencesTab::Interface => {
-                        self.interface_tab(ui, config);
+                        self.interface_tab(ui, config, viewport_state);
                     }
                 });
 
@@ -84,18 +87,11 @@ impl PreferencesPanel {
                 ui.horizontal(|ui| {
                     if ui.button("Reset to Defaults").clicked() {
                         config.write().reset_to_defaults();
-                        // Apply defaults to renderer
-                        if let Some(vp) = viewport_state {
+                        // Apply defaults to renderer and theme
+                        {
                             let cfg = config.read();
-                            let vp_lock = vp.lock();
-                            let device = vp_lock.device.clone();
-                            let queue = vp_lock.queue.clone();
-                            drop(vp_lock);
-                            vp.lock().renderer.apply_config(
-                                &cfg.config().renderer,
-                                &device,
-                                &queue,
-                            );
+                            ui.ctx().set_visuals(cfg.config().ui.theme.visuals());
+                            apply_renderer_config(cfg.config(), viewport_state);
                         }
                         // Apply defaults to app state
                         {
@@ -104,7 +100,18 @@ impl PreferencesPanel {
                             state.show_part_axes = cfg.config().editor.show_part_axes;
                             state.show_joint_markers = cfg.config().editor.show_joint_markers;
                             state.angle_display_mode = cfg.config().editor.angle_display_mode;
+                            state.rotation_display_mode = cfg.config().editor.rotation_display_mode;
+                            state.length_unit = cfg.config().editor.length_unit;
+                            state.rotation_snap = cfg.config().editor.rotation_snap;
                             state.stl_import_unit = cfg.config().editor.stl_import_unit;
+                            state.flip_normals_on_import =
+                                cfg.config().editor.flip_normals_on_import;
+                            state.recompute_normals_on_import =
+                                cfg.config().editor.recompute_normals_on_import;
+                            state.double_click_focus = cfg.config().editor.double_click_focus;
+                            state.primitive_segments = cfg.config().editor.primitive_segments;
+                            state.joint_defaults = cfg.config().joint_defaults.clone();
+                            state.default_viewport = cfg.config().viewport_defaults.clone();
                         }
                     }
 
@@ -139,6 +146,10 @@ impl PreferencesPanel {
             changed |= ui
                 .add(egui::Slider::new(&mut grid.spacing, 0.1..=10.0).text("Spacing"))
                 .changed();
+            changed |= ui
+                .add(egui::Slider::new(&mut grid.fade_radius, 0.0..=200.0).text("Fade Radius"))
+                .on_hover_text("Distance at which the grid fades out (0 = no fade)")
+                .changed();
 
             ui.horizontal(|ui| {
                 ui.label("Line Color:");
@@ -161,23 +172,42 @@ impl PreferencesPanel {
         // Viewport settings
         let mut viewport = renderer_cfg.viewport.clone();
         ui.collapsing("Viewport", |ui| {
+            changed |= ui
+                .checkbox(
+                    &mut viewport.background_follows_theme,
+                    "Background Follows Theme",
+                )
+                .on_hover_text("Use a background matching the light or dark UI theme")
+                .changed();
+            changed |= ui
+                .checkbox(
+                    &mut viewport.pick_prefer_front_faces,
+                    "Pick Front Faces First",
+                )
+                .on_hover_text(
+                    "Select the nearest front-facing surface; back faces are \
+                     picked only when nothing else is under the cursor",
+                )
+                .changed();
             ui.horizontal(|ui| {
-                ui.label("Background Color:");
-                let mut color = egui::Color32::from_rgba_unmultiplied(
-                    (viewport.background_color[0] * 255.0) as u8,
-                    (viewport.background_color[1] * 255.0) as u8,
-                    (viewport.background_color[2] * 255.0) as u8,
-                    (viewport.background_color[3] * 255.0) as u8,
-                );
-                if ui.color_edit_button_srgba(&mut color).changed() {
-                    viewport.background_color = [
-                        color.r() as f32 / 255.0,
-                        color.g() as f32 / 255.0,
-                        color.b() as f32 / 255.0,
-                        color.a() as f32 / 255.0,
-                    ];
-                    changed = true;
-                }
+                ui.add_enabled_ui(!viewport.background_follows_theme, |ui| {
+                    ui.label("Background Color:");
+                    let mut color = egui::Color32::from_rgba_unmultiplied(
+                        (viewport.background_color[0] * 255.0) as u8,
+                        (viewport.background_color[1] * 255.0) as u8,
+                        (viewport.background_color[2] * 255.0) as u8,
+                        (viewport.background_color[3] * 255.0) as u8,
+                    );
+                    if ui.color_edit_button_srgba(&mut color).changed() {
+                        viewport.background_color = [
+                            color.r() as f32 / 255.0,
+                            color.g() as f32 / 255.0,
+                            color.b() as f32 / 255.0,
+                            color.a() as f32 / 255.0,
+                        ];
+                        changed = true;
+                    }
+                });
             });
 
             ui.horizontal(|ui| {
@@ -255,6 +285,10 @@ impl PreferencesPanel {
             changed |= ui
                 .add(egui::Slider::new(&mut shadow.softness, 0.0..=3.0).text("Softness"))
                 .changed();
+            changed |= ui
+                .checkbox(&mut shadow.ground_shadow_only, "Ground Shadow Only")
+                .on_hover_text("Cast shadows onto the ground only, without self-shadowing")
+                .changed();
         });
 
         // Lighting settings
@@ -266,6 +300,10 @@ impl PreferencesPanel {
             changed |= ui
                 .add(egui::Slider::new(&mut lighting.ambient_strength, 0.0..=1.0).text("Ambient"))
                 .changed();
+            changed |= ui
+                .checkbox(&mut lighting.two_sided, "Two-Sided Lighting")
+                .on_hover_text("Light back faces of thin meshes as if they faced the camera")
+                .changed();
 
             ui.horizontal(|ui| {
                 ui.label("Light Color:");
@@ -308,6 +346,49 @@ impl PreferencesPanel {
             changed |= ui
                 .add(egui::Slider::new(&mut camera.fov_degrees, 10.0..=120.0).text("FOV"))
                 .changed();
+            changed |= ui
+                .checkbox(&mut camera.dolly_zoom, "Dolly Zoom")
+                .on_hover_text("Keep the subject size constant when changing FOV")
+                .changed();
+            changed |= ui
+                .checkbox(&mut camera.inertia, "Inertia")
+                .on_hover_text("Keep orbiting and zooming briefly after input stops")
+                .changed();
+            ui.add_enabled_ui(camera.inertia, |ui| {
+                changed |= ui
+                    .add(egui::Slider::new(&mut camera.inertia_damping, 1.0..=20.0).text("Damping"))
+                    .on_hover_text("How quickly coasting slows down (per second)")
+                    .changed();
+            });
+            changed |= ui
+                .checkbox(&mut camera.clamp_pitch, "Limit Orbit Pitch")
+                .on_hover_text("Stop orbiting just short of straight up or down")
+                .changed();
+            ui.horizontal(|ui| {
+                ui.label("Zoom Distance:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut camera.min_zoom_distance)
+                            .speed(0.01)
+                            .range(0.001..=camera.max_zoom_distance)
+                            .prefix("min "),
+                    )
+                    .changed();
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut camera.max_zoom_distance)
+                            .speed(1.0)
+                            .range(camera.min_zoom_distance..=f32::MAX)
+                            .prefix("max "),
+                    )
+                    .changed();
+            });
+            changed |= ui
+                .checkbox(&mut camera.stop_zoom_at_target, "Stop Zoom at Target")
+                .on_hover_text(
+                    "Stop at the minimum distance instead of pushing the orbit target forward",
+                )
+                .changed();
             changed |= ui
                 .add(
                     egui::Slider::new(&mut camera.pan_sensitivity, 0.0005..=0.01)
@@ -337,6 +418,44 @@ impl PreferencesPanel {
                 .changed();
         });
 
+        // Selection settings
+        let mut selection = renderer_cfg.selection.clone();
+        ui.collapsing("Selection", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Highlight Color:");
+                let mut color = [
+                    (selection.color[0] * 255.0) as u8,
+                    (selection.color[1] * 255.0) as u8,
+                    (selection.color[2] * 255.0) as u8,
+                ];
+                if ui.color_edit_button_srgb(&mut color).changed() {
+                    selection.color = [
+                        color[0] as f32 / 255.0,
+                        color[1] as f32 / 255.0,
+                        color[2] as f32 / 255.0,
+                    ];
+                    changed = true;
+                }
+            });
+            changed |= ui
+                .checkbox(&mut selection.show_occluded, "Show Through Geometry")
+                .on_hover_text("Outline the selected part even where other links hide it")
+                .changed();
+        });
+
+        // Sketch settings
+        let mut sketch = renderer_cfg.sketch.clone();
+        ui.collapsing("Sketch", |ui| {
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut sketch.depth_offset, 0.0..=1e-3)
+                        .logarithmic(true)
+                        .text("Depth Offset"),
+                )
+                .on_hover_text("Pull sketches towards the camera so they draw over faces")
+                .changed();
+        });
+
         // Apply changes to config and renderer
         if changed {
             let new_config = RendererConfig {
@@ -346,23 +465,23 @@ impl PreferencesPanel {
                 lighting,
                 camera,
                 gizmo,
+                sketch,
+                selection,
             };
-            cfg.config_mut().renderer = new_config.clone();
+            cfg.config_mut().renderer = new_config;
 
             // Apply to renderer immediately
-            if let Some(vp) = viewport_state {
-                let vp_lock = vp.lock();
-                let device = vp_lock.device.clone();
-                let queue = vp_lock.queue.clone();
-                drop(vp_lock);
-                vp.lock()
-                    .renderer
-                    .apply_config(&new_config, &device, &queue);
-            }
+            apply_renderer_config(cfg.config(), viewport_state);
         }
     }
 
-    fn editor_tab(&mut self, ui: &mut egui::Ui, config: &SharedConfig, app_state: &SharedAppState) {
+    fn editor_tab(
+        &mut self,
+        ui: &mut egui::Ui,
+        config: &SharedConfig,
+        app_state: &SharedAppState,
+        viewport_state: &Option<SharedViewportState>,
+    ) {
         let mut cfg = config.write();
         let editor_cfg = cfg.config_mut().editor.clone();
         let mut changed = false;
@@ -370,12 +489,23 @@ impl PreferencesPanel {
         let mut show_part_axes = editor_cfg.show_part_axes;
         let mut show_joint_markers = editor_cfg.show_joint_markers;
         let mut angle_display_mode = editor_cfg.angle_display_mode;
+        let mut rotation_display_mode = editor_cfg.rotation_display_mode;
+        let mut length_unit = editor_cfg.length_unit;
+        let mut rotation_snap = editor_cfg.rotation_snap;
         let mut stl_import_unit = editor_cfg.stl_import_unit;
+        let mut flip_normals_on_import = editor_cfg.flip_normals_on_import;
+        let mut recompute_normals_on_import = editor_cfg.recompute_normals_on_import;
+        let mut double_click_focus = editor_cfg.double_click_focus;
+        let mut primitive_segments = editor_cfg.primitive_segments;
 
         changed |= ui.checkbox(&mut show_part_axes, "Show Part Axes").changed();
         changed |= ui
             .checkbox(&mut show_joint_markers, "Show Joint Markers")
             .changed();
+        changed |= ui
+            .checkbox(&mut double_click_focus, "Double-Click to Focus")
+            .on_hover_text("Double-clicking a part frames it and orbits around it")
+            .changed();
 
         ui.horizontal(|ui| {
             ui.label("Angle Display:");
@@ -408,6 +538,74 @@ impl PreferencesPanel {
                 });
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Orientation Display:");
+            egui::ComboBox::from_id_salt("rotation_mode")
+                .selected_text(match rotation_display_mode {
+                    RotationDisplayMode::Euler => "Euler (RPY)",
+                    RotationDisplayMode::Quaternion => "Quaternion",
+                })
+                .show_ui(ui, |ui| {
+                    if ui
+                        .selectable_value(
+                            &mut rotation_display_mode,
+                            RotationDisplayMode::Euler,
+                            "Euler (RPY)",
+                        )
+                        .changed()
+                    {
+                        changed = true;
+                    }
+                    if ui
+                        .selectable_value(
+                            &mut rotation_display_mode,
+                            RotationDisplayMode::Quaternion,
+                            "Quaternion",
+                        )
+                        .changed()
+                    {
+                        changed = true;
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Length Unit:");
+            egui::ComboBox::from_id_salt("length_unit")
+                .selected_text(length_unit.name())
+                .show_ui(ui, |ui| {
+                    for unit in StlUnit::ALL {
+                        if ui
+                            .selectable_value(&mut length_unit, *unit, unit.name())
+                            .changed()
+                        {
+                            changed = true;
+                        }
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Rotation Snap:");
+            egui::ComboBox::from_id_salt("rotation_snap")
+                .selected_text(rotation_snap.display_name())
+                .show_ui(ui, |ui| {
+                    for increment in RotationSnapIncrement::all() {
+                        if ui
+                            .selectable_value(
+                                &mut rotation_snap,
+                                *increment,
+                                increment.display_name(),
+                            )
+                            .changed()
+                        {
+                            changed = true;
+                        }
+                    }
+                });
+            ui.weak("(hold Ctrl while rotating)");
+        });
+
         ui.horizontal(|ui| {
             ui.label("STL Import Unit:");
             egui::ComboBox::from_id_salt("stl_unit")
@@ -445,12 +643,42 @@ impl PreferencesPanel {
                 });
         });
 
+        changed |= ui
+            .checkbox(&mut flip_normals_on_import, "Flip Normals on Import")
+            .on_hover_text("Reverse triangle winding of imported meshes (inside-out CAD exports)")
+            .changed();
+        changed |= ui
+            .checkbox(
+                &mut recompute_normals_on_import,
+                "Recompute Normals on Import",
+            )
+            .on_hover_text("Rebuild normals from triangle winding instead of using the file's")
+            .changed();
+
+        ui.horizontal(|ui| {
+            ui.label("Primitive Segments:");
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut primitive_segments)
+                        .range(rk_core::MIN_PRIMITIVE_SEGMENTS..=rk_core::MAX_PRIMITIVE_SEGMENTS),
+                )
+                .on_hover_text("Segments around new cylinders and spheres")
+                .changed();
+        });
+
         if changed {
             cfg.config_mut().editor = EditorConfig {
                 show_part_axes,
                 show_joint_markers,
                 angle_display_mode,
+                rotation_display_mode,
+                length_unit,
+                rotation_snap,
                 stl_import_unit,
+                flip_normals_on_import,
+                recompute_normals_on_import,
+                double_click_focus,
+                primitive_segments,
             };
 
             // Apply to app state immediately
@@ -458,11 +686,113 @@ impl PreferencesPanel {
             state.show_part_axes = show_part_axes;
             state.show_joint_markers = show_joint_markers;
             state.angle_display_mode = angle_display_mode;
+            state.rotation_display_mode = rotation_display_mode;
+            state.length_unit = length_unit;
+            state.rotation_snap = rotation_snap;
             state.stl_import_unit = stl_import_unit;
+            state.flip_normals_on_import = flip_normals_on_import;
+            state.recompute_normals_on_import = recompute_normals_on_import;
+            state.double_click_focus = double_click_focus;
+            state.primitive_segments = primitive_segments;
+        }
+
+        ui.separator();
+        drop(cfg);
+        self.joint_defaults_section(ui, config, app_state);
+        self.viewport_defaults_section(ui, config, app_state, viewport_state);
+    }
+
+    fn viewport_defaults_section(
+        &mut self,
+        ui: &mut egui::Ui,
+        config: &SharedConfig,
+        app_state: &SharedAppState,
+        viewport_state: &Option<SharedViewportState>,
+    ) {
+        let mut defaults = None;
+
+        ui.collapsing("Project Viewport Defaults", |ui| {
+            ui.weak("Used for projects saved without their own view");
+            ui.horizontal(|ui| {
+                let current_view = ui
+                    .add_enabled(
+                        viewport_state.is_some(),
+                        egui::Button::new("Use Current View"),
+                    )
+                    .on_hover_text("Camera, display toggles and gizmo mode of the viewport");
+                if current_view.clicked()
+                    && let Some(vp) = viewport_state
+                {
+                    defaults = Some(vp.lock().renderer.viewport_settings());
+                }
+                if ui.button("Reset").clicked() {
+                    defaults = Some(ViewportSettings::default());
+                }
+            });
+        });
+
+        if let Some(defaults) = defaults {
+            app_state.lock().default_viewport = defaults.clone();
+            config.write().config_mut().viewport_defaults = defaults;
+        }
+    }
+
+    fn joint_defaults_section(
+        &mut self,
+        ui: &mut egui::Ui,
+        config: &SharedConfig,
+        app_state: &SharedAppState,
+    ) {
+        let mut cfg = config.write();
+        let mut defaults = cfg.config().joint_defaults.clone();
+        let mut changed = false;
+
+        ui.collapsing("New Joint Defaults", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Type:");
+                egui::ComboBox::from_id_salt("default_joint_type")
+                    .selected_text(defaults.joint_type.display_name())
+                    .show_ui(ui, |ui| {
+                        for joint_type in JointType::all() {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut defaults.joint_type,
+                                    *joint_type,
+                                    joint_type.display_name(),
+                                )
+                                .changed();
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Axis:");
+                for (value, label) in defaults.axis.iter_mut().zip(["X", "Y", "Z"]) {
+                    ui.label(label);
+                    changed |= ui
+                        .add(egui::DragValue::new(value).speed(0.01).range(-1.0..=1.0))
+                        .changed();
+                }
+            });
+
+            ui.label("Revolute limits (rad):");
+            changed |= joint_limits_rows(ui, &mut defaults.limits);
+            ui.label("Prismatic limits (m):");
+            changed |= joint_limits_rows(ui, &mut defaults.prismatic_limits);
+        });
+
+        if changed {
+            app_state.lock().joint_defaults = defaults.clone();
+            cfg.config_mut().joint_defaults = defaults;
         }
     }
 
-    fn interface_tab(&mut self, ui: &mut egui::Ui, config: &SharedConfig) {
+    fn interface_tab(
+        &mut self,
+        ui: &mut egui::Ui,
+        config: &SharedConfig,
+        viewport_state: &Option<SharedViewportState>,
+    ) {
         let mut cfg = config.write();
         let ui_cfg = cfg.config_mut().ui.clone();
         let mut changed = false;
@@ -498,9 +828,59 @@ impl PreferencesPanel {
             .changed();
 
         if changed {
+            let theme_changed = theme != ui_cfg.theme;
             cfg.config_mut().ui = UiConfig { theme, font_size };
+            if theme_changed {
+                ui.ctx().set_visuals(theme.visuals());
+                apply_renderer_config(cfg.config(), viewport_state);
+            }
         }
 
-        ui.label("(Theme and font changes require restart)");
+        ui.label("(Font changes require restart)");
+    }
+}
+
+/// Push the renderer settings, with the theme-resolved background, to the viewport
+fn apply_renderer_config(config: &AppConfig, viewport_state: &Option<SharedViewportState>) {
+    if let Some(vp) = viewport_state {
+        let mut vp = vp.lock();
+        let device = vp.device.clone();
+        let queue = vp.queue.clone();
+        vp.renderer
+            .apply_config(&config.effective_renderer_config(), &device, &queue);
     }
 }
+
+/// Edit rows for lower/upper/effort/velocity; returns true if any changed
+fn joint_limits_rows(ui: &mut egui::Ui, limits: &mut JointLimits) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label("Lower:");
+        changed |= ui
+            .add(egui::DragValue::new(&mut limits.lower).speed(0.01))
+            .changed();
+        ui.label("Upper:");
+        changed |= ui
+            .add(egui::DragValue::new(&mut limits.upper).speed(0.01))
+            .changed();
+    });
+    ui.horizontal(|ui| {
+        ui.label("Effort:");
+        changed |= ui
+            .add(
+                egui::DragValue::new(&mut limits.effort)
+                    .speed(1.0)
+                    .range(0.0..=f32::MAX),
+            )
+            .changed();
+        ui.label("Velocity:");
+        changed |= ui
+            .add(
+                egui::DragValue::new(&mut limits.velocity)
+                    .speed(0.1)
+                    .range(0.0..=f32::MAX),
+            )
+            .changed();
+    });
+    changed
+}
@@ -2,10 +2,11 @@
 
 use egui::Ui;
 use glam::Mat4;
-use rk_core::{CollisionElement, Joint, Part};
+use rk_core::{CollisionElement, Joint, Part, StlUnit};
 use uuid::Uuid;
 
-use crate::state::AppAction;
+use crate::config::JointDefaultsConfig;
+use crate::state::{AppAction, RotationDisplayMode};
 
 /// Information about a child joint (for display in properties panel)
 #[derive(Debug, Clone)]
@@ -18,6 +19,17 @@ pub struct ChildJointInfo {
     pub child_part_name: String,
 }
 
+/// A joint point on another part that the edited part can mate to
+#[derive(Debug, Clone)]
+pub struct MateTarget {
+    /// Part owning the point
+    pub part_id: Uuid,
+    /// Joint point ID
+    pub point_id: Uuid,
+    /// "part / point" label for display
+    pub label: String,
+}
+
 /// Context passed to property components for rendering
 pub struct PropertyContext<'a> {
     /// The part being edited
@@ -32,6 +44,16 @@ pub struct PropertyContext<'a> {
     pub selected_collision_index: Option<usize>,
     /// Joints connecting this part to its children
     pub child_joints: Vec<ChildJointInfo>,
+    /// Child joint opened for editing (e.g. picked in the viewport)
+    pub editing_joint: Option<Uuid>,
+    /// Joint points on other parts available as mate targets
+    pub mate_targets: Vec<MateTarget>,
+    /// How orientations are displayed and edited
+    pub rotation_display_mode: RotationDisplayMode,
+    /// Unit lengths are displayed and entered in
+    pub length_unit: StlUnit,
+    /// Defaults shown for joints that have no limits yet
+    pub joint_defaults: JointDefaultsConfig,
     /// Queue for actions to be processed
     pub pending_actions: &'a mut Vec<AppAction>,
 }
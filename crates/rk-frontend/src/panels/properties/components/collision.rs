@@ -1,10 +1,10 @@
 //! Collision component - collision element editing
 
-use egui::{DragValue, Ui};
+use egui::Ui;
 
-use rk_core::{GeometryType, Pose};
+use rk_core::{GeometryType, Pose, StlUnit};
 
-use crate::panels::properties::helpers::{rotation_row, vector3_row};
+use crate::panels::properties::helpers::{length_value, length3_row, orientation_row};
 use crate::panels::properties::{PropertyComponent, PropertyContext};
 use crate::state::AppAction;
 
@@ -88,7 +88,7 @@ impl PropertyComponent for CollisionComponent {
                 ui.indent(format!("collision_{}", index), |ui| {
                     // Origin position
                     let mut pos = collision.origin.xyz;
-                    if vector3_row(ui, "Position", &mut pos, 0.01) {
+                    if length3_row(ui, "Position", &mut pos, 0.01, ctx.length_unit) {
                         let origin = Pose::new(pos, collision.origin.rpy);
                         ctx.pending_actions.push(AppAction::UpdateCollisionOrigin {
                             link_id,
@@ -99,18 +99,12 @@ impl PropertyComponent for CollisionComponent {
                     }
 
                     // Origin rotation
-                    let mut rot_deg = [
-                        collision.origin.rpy[0].to_degrees(),
-                        collision.origin.rpy[1].to_degrees(),
-                        collision.origin.rpy[2].to_degrees(),
-                    ];
-                    if rotation_row(ui, "Rotation", &mut rot_deg, 1.0) {
-                        let rpy = [
-                            rot_deg[0].to_radians(),
-                            rot_deg[1].to_radians(),
-                            rot_deg[2].to_radians(),
-                        ];
-                        let origin = Pose::new(collision.origin.xyz, rpy);
+                    if let Some(origin) = orientation_row(
+                        ui,
+                        "Rotation",
+                        &collision.origin,
+                        ctx.rotation_display_mode,
+                    ) {
                         ctx.pending_actions.push(AppAction::UpdateCollisionOrigin {
                             link_id,
                             index,
@@ -121,7 +115,9 @@ impl PropertyComponent for CollisionComponent {
 
                     // Geometry type selector and parameters
                     ui.add_space(4.0);
-                    if let Some(new_geometry) = render_geometry_editor(ui, &collision.geometry) {
+                    if let Some(new_geometry) =
+                        render_geometry_editor(ui, &collision.geometry, ctx.length_unit)
+                    {
                         ctx.pending_actions
                             .push(AppAction::UpdateCollisionGeometry {
                                 link_id,
@@ -152,7 +148,11 @@ impl PropertyComponent for CollisionComponent {
 }
 
 /// Render geometry editor and return new geometry if changed
-fn render_geometry_editor(ui: &mut Ui, geometry: &GeometryType) -> Option<GeometryType> {
+fn render_geometry_editor(
+    ui: &mut Ui,
+    geometry: &GeometryType,
+    unit: StlUnit,
+) -> Option<GeometryType> {
     let mut changed = false;
     let mut new_geometry = geometry.clone();
 
@@ -220,15 +220,15 @@ fn render_geometry_editor(ui: &mut Ui, geometry: &GeometryType) -> Option<Geomet
             });
             ui.horizontal(|ui| {
                 ui.label("X");
-                if ui.add(DragValue::new(&mut size[0]).speed(0.01)).changed() {
+                if length_value(ui, &mut size[0], 0.01, unit) {
                     changed = true;
                 }
                 ui.label("Y");
-                if ui.add(DragValue::new(&mut size[1]).speed(0.01)).changed() {
+                if length_value(ui, &mut size[1], 0.01, unit) {
                     changed = true;
                 }
                 ui.label("Z");
-                if ui.add(DragValue::new(&mut size[2]).speed(0.01)).changed() {
+                if length_value(ui, &mut size[2], 0.01, unit) {
                     changed = true;
                 }
             });
@@ -236,13 +236,13 @@ fn render_geometry_editor(ui: &mut Ui, geometry: &GeometryType) -> Option<Geomet
         GeometryType::Cylinder { radius, length } => {
             ui.horizontal(|ui| {
                 ui.label("Radius:");
-                if ui.add(DragValue::new(radius).speed(0.01)).changed() {
+                if length_value(ui, radius, 0.01, unit) {
                     changed = true;
                 }
             });
             ui.horizontal(|ui| {
                 ui.label("Length:");
-                if ui.add(DragValue::new(length).speed(0.01)).changed() {
+                if length_value(ui, length, 0.01, unit) {
                     changed = true;
                 }
             });
@@ -250,7 +250,7 @@ fn render_geometry_editor(ui: &mut Ui, geometry: &GeometryType) -> Option<Geomet
         GeometryType::Sphere { radius } => {
             ui.horizontal(|ui| {
                 ui.label("Radius:");
-                if ui.add(DragValue::new(radius).speed(0.01)).changed() {
+                if length_value(ui, radius, 0.01, unit) {
                     changed = true;
                 }
             });
@@ -258,13 +258,13 @@ fn render_geometry_editor(ui: &mut Ui, geometry: &GeometryType) -> Option<Geomet
         GeometryType::Capsule { radius, length } => {
             ui.horizontal(|ui| {
                 ui.label("Radius:");
-                if ui.add(DragValue::new(radius).speed(0.01)).changed() {
+                if length_value(ui, radius, 0.01, unit) {
                     changed = true;
                 }
             });
             ui.horizontal(|ui| {
                 ui.label("Length:");
-                if ui.add(DragValue::new(length).speed(0.01)).changed() {
+                if length_value(ui, length, 0.01, unit) {
                     changed = true;
                 }
             });
@@ -1,15 +1,33 @@
-//! Geometry component - read-only mesh information
+//! Geometry component - mesh information and primitive resolution
 
 use egui::Ui;
+use rk_core::MeshReport;
+use uuid::Uuid;
 
 use crate::panels::properties::{PropertyComponent, PropertyContext};
+use crate::state::AppAction;
 
-/// Read-only geometry information component
-pub struct GeometryComponent;
+/// Geometry information component
+pub struct GeometryComponent {
+    /// Last mesh report, keyed by part and mesh size to skip re-analysis each frame
+    report_cache: Option<(Uuid, usize, usize, MeshReport)>,
+}
 
 impl GeometryComponent {
     pub fn new() -> Self {
-        Self
+        Self { report_cache: None }
+    }
+
+    fn report(&mut self, part: &rk_core::Part) -> MeshReport {
+        let key = (part.id, part.vertices.len(), part.indices.len());
+        match self.report_cache {
+            Some((id, vertices, indices, report)) if (id, vertices, indices) == key => report,
+            _ => {
+                let report = part.analyze_mesh();
+                self.report_cache = Some((key.0, key.1, key.2, report));
+                report
+            }
+        }
     }
 }
 
@@ -25,34 +43,83 @@ impl PropertyComponent for GeometryComponent {
     }
 
     fn default_open(&self) -> bool {
-        false // Collapsed by default since it's read-only info
+        false // Collapsed by default since it's mostly read-only info
     }
 
     fn ui(&mut self, ui: &mut Ui, ctx: &mut PropertyContext) -> bool {
         let part = &ctx.part;
+        let unit = ctx.length_unit;
 
         ui.label(format!("Vertices: {}", part.vertices.len()));
         ui.label(format!("Triangles: {}", part.indices.len() / 3));
         ui.label(format!(
-            "Bounding Box: [{:.3}, {:.3}, {:.3}] to [{:.3}, {:.3}, {:.3}]",
-            part.bbox_min[0],
-            part.bbox_min[1],
-            part.bbox_min[2],
-            part.bbox_max[0],
-            part.bbox_max[1],
-            part.bbox_max[2]
+            "Bounding Box: [{}, {}, {}] to [{}, {}, {}]",
+            unit.format(part.bbox_min[0]),
+            unit.format(part.bbox_min[1]),
+            unit.format(part.bbox_min[2]),
+            unit.format(part.bbox_max[0]),
+            unit.format(part.bbox_max[1]),
+            unit.format(part.bbox_max[2])
         ));
 
         let size = part.size();
         ui.label(format!(
-            "Size: {:.3} x {:.3} x {:.3}",
-            size.x, size.y, size.z
+            "Size: {} x {} x {}",
+            unit.format(size.x),
+            unit.format(size.y),
+            unit.format(size.z)
         ));
 
         if let Some(ref path) = part.stl_path {
             ui.label(format!("STL: {}", path));
         }
 
-        false // Read-only, never changes
+        // Round primitives regenerate their mesh when the resolution changes
+        if let Some(mut segments) = part.primitive.and_then(|shape| shape.segments()) {
+            ui.horizontal(|ui| {
+                ui.label("Segments:");
+                let response = ui.add(
+                    egui::DragValue::new(&mut segments)
+                        .range(rk_core::MIN_PRIMITIVE_SEGMENTS..=rk_core::MAX_PRIMITIVE_SEGMENTS),
+                );
+                if response.changed() {
+                    ctx.pending_actions.push(AppAction::SetPrimitiveSegments {
+                        part_id: part.id,
+                        segments,
+                    });
+                }
+            });
+        }
+
+        let report = self.report(part);
+        ui.separator();
+        if report.is_clean() {
+            ui.label("Mesh Health: OK");
+        } else {
+            let warn = ui.visuals().warn_fg_color;
+            ui.colored_label(
+                warn,
+                format!("\u{26a0} Mesh Health: {} issue(s)", report.issue_count()),
+            );
+        }
+        egui::Grid::new("mesh_health_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (label, count) in [
+                    ("Degenerate triangles", report.degenerate_triangles),
+                    ("Duplicate triangles", report.duplicate_triangles),
+                    ("Non-manifold edges", report.non_manifold_edges),
+                ] {
+                    ui.label(label);
+                    if count > 0 {
+                        ui.colored_label(ui.visuals().warn_fg_color, count.to_string());
+                    } else {
+                        ui.label("0");
+                    }
+                    ui.end_row();
+                }
+            });
+
+        false // Segment changes go through an action
     }
 }
@@ -2,10 +2,11 @@
 
 use egui::{DragValue, Ui};
 use glam::Vec3;
+use uuid::Uuid;
 
 use rk_core::{JointLimits, JointType, Pose};
 
-use crate::panels::properties::helpers::{rotation_row, vector3_row};
+use crate::panels::properties::helpers::{length3_row, orientation_row, vector3_row};
 use crate::panels::properties::{PropertyComponent, PropertyContext};
 use crate::state::AppAction;
 
@@ -13,12 +14,15 @@ use crate::state::AppAction;
 pub struct JointComponent {
     /// Currently expanded joint index (if any)
     expanded_index: Option<usize>,
+    /// Editing joint last applied to `expanded_index`
+    last_editing_joint: Option<Uuid>,
 }
 
 impl JointComponent {
     pub fn new() -> Self {
         Self {
             expanded_index: None,
+            last_editing_joint: None,
         }
     }
 }
@@ -39,6 +43,17 @@ impl PropertyComponent for JointComponent {
     }
 
     fn ui(&mut self, ui: &mut Ui, ctx: &mut PropertyContext) -> bool {
+        // Expand a newly picked joint; afterwards the user can collapse it
+        if ctx.editing_joint != self.last_editing_joint {
+            self.last_editing_joint = ctx.editing_joint;
+            if let Some(joint_id) = ctx.editing_joint {
+                self.expanded_index = ctx
+                    .child_joints
+                    .iter()
+                    .position(|info| info.joint_id == joint_id);
+            }
+        }
+
         if ctx.child_joints.is_empty() {
             ui.weak("No child joints");
             return false;
@@ -91,7 +106,7 @@ impl PropertyComponent for JointComponent {
 
                     // Origin position
                     let mut pos = info.joint.origin.xyz;
-                    if vector3_row(ui, "Position", &mut pos, 0.01) {
+                    if length3_row(ui, "Position", &mut pos, 0.01, ctx.length_unit) {
                         let origin = Pose::new(pos, info.joint.origin.rpy);
                         ctx.pending_actions.push(AppAction::UpdateJointOrigin {
                             joint_id: info.joint_id,
@@ -101,18 +116,12 @@ impl PropertyComponent for JointComponent {
                     }
 
                     // Origin rotation
-                    let mut rot_deg = [
-                        info.joint.origin.rpy[0].to_degrees(),
-                        info.joint.origin.rpy[1].to_degrees(),
-                        info.joint.origin.rpy[2].to_degrees(),
-                    ];
-                    if rotation_row(ui, "Rotation", &mut rot_deg, 1.0) {
-                        let rpy = [
-                            rot_deg[0].to_radians(),
-                            rot_deg[1].to_radians(),
-                            rot_deg[2].to_radians(),
-                        ];
-                        let origin = Pose::new(info.joint.origin.xyz, rpy);
+                    if let Some(origin) = orientation_row(
+                        ui,
+                        "Rotation",
+                        &info.joint.origin,
+                        ctx.rotation_display_mode,
+                    ) {
                         ctx.pending_actions.push(AppAction::UpdateJointOrigin {
                             joint_id: info.joint_id,
                             origin,
@@ -145,28 +154,34 @@ impl PropertyComponent for JointComponent {
                         ui.add_space(4.0);
                         ui.label("Limits:");
 
-                        let limits = info.joint.limits.unwrap_or_else(|| {
-                            if info.joint.joint_type == JointType::Prismatic {
-                                JointLimits::default_prismatic()
-                            } else {
-                                JointLimits::default_revolute()
-                            }
-                        });
+                        let limits = info
+                            .joint
+                            .limits
+                            .or_else(|| ctx.joint_defaults.limits_for(info.joint.joint_type))
+                            .unwrap_or_default();
 
                         let mut lower = limits.lower;
                         let mut upper = limits.upper;
                         let mut effort = limits.effort;
                         let mut velocity = limits.velocity;
 
-                        // Convert to degrees for revolute joints
+                        // Convert to degrees for revolute joints, display length otherwise
                         let is_revolute = info.joint.joint_type == JointType::Revolute;
+                        let unit = ctx.length_unit;
                         if is_revolute {
                             lower = lower.to_degrees();
                             upper = upper.to_degrees();
+                        } else {
+                            lower = unit.from_meters(lower);
+                            upper = unit.from_meters(upper);
                         }
 
-                        let suffix = if is_revolute { "°" } else { " m" };
-                        let speed = if is_revolute { 1.0 } else { 0.01 };
+                        let suffix = if is_revolute { "°" } else { unit.suffix() };
+                        let speed = if is_revolute {
+                            1.0
+                        } else {
+                            unit.from_meters(0.01)
+                        };
 
                         let mut limits_changed = false;
 
@@ -205,10 +220,13 @@ impl PropertyComponent for JointComponent {
                         });
 
                         if limits_changed {
-                            // Convert back to radians for revolute joints
+                            // Convert back to radians or meters for storage
                             if is_revolute {
                                 lower = lower.to_radians();
                                 upper = upper.to_radians();
+                            } else {
+                                lower = unit.to_meters(lower);
+                                upper = unit.to_meters(upper);
                             }
                             ctx.pending_actions.push(AppAction::UpdateJointLimits {
                                 joint_id: info.joint_id,
@@ -221,6 +239,55 @@ impl PropertyComponent for JointComponent {
                             });
                             changed = true;
                         }
+
+                        if is_revolute
+                            && ui
+                                .button("Find Limits")
+                                .on_hover_text(
+                                    "Sweep the joint and stop where the child parts collide",
+                                )
+                                .clicked()
+                        {
+                            ctx.pending_actions.push(AppAction::FindJointLimits {
+                                joint_id: info.joint_id,
+                            });
+                            changed = true;
+                        }
+                    }
+
+                    // Dynamics (for movable joints)
+                    if info.joint.joint_type.has_axis() {
+                        ui.add_space(4.0);
+                        ui.label("Dynamics:");
+
+                        let dynamics = info.joint.dynamics.unwrap_or_default();
+                        let mut damping = dynamics.damping;
+                        let mut friction = dynamics.friction;
+                        let mut dynamics_changed = false;
+
+                        ui.horizontal(|ui| {
+                            ui.label("Damping:");
+                            dynamics_changed |= ui
+                                .add(DragValue::new(&mut damping).speed(0.01).range(0.0..=1000.0))
+                                .changed();
+                            ui.label("Friction:");
+                            dynamics_changed |= ui
+                                .add(
+                                    DragValue::new(&mut friction)
+                                        .speed(0.01)
+                                        .range(0.0..=1000.0),
+                                )
+                                .changed();
+                        });
+
+                        if dynamics_changed {
+                            ctx.pending_actions.push(AppAction::UpdateJointDynamics {
+                                joint_id: info.joint_id,
+                                damping,
+                                friction,
+                            });
+                            changed = true;
+                        }
                     }
                 });
             }
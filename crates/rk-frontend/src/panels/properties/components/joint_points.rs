@@ -0,0 +1,119 @@
+//! Joint points component - connector frames used to mate parts
+
+use egui::{DragValue, Ui};
+use glam::{EulerRot, Quat, Vec3};
+use rk_core::JointPoint;
+
+use crate::panels::properties::helpers::length_value;
+use crate::panels::properties::{PropertyComponent, PropertyContext};
+use crate::state::AppAction;
+
+/// Joint point (connector) editing component
+pub struct JointPointsComponent;
+
+impl JointPointsComponent {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for JointPointsComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PropertyComponent for JointPointsComponent {
+    fn name(&self) -> &str {
+        "Joint Points"
+    }
+
+    fn default_open(&self) -> bool {
+        false
+    }
+
+    fn ui(&mut self, ui: &mut Ui, ctx: &mut PropertyContext) -> bool {
+        let mut changed = false;
+        let part_id = ctx.part.id;
+        let locked = ctx.part.locked;
+        let unit = ctx.length_unit;
+        let mut remove = None;
+
+        if ctx.part.joint_points.is_empty() {
+            ui.weak("No joint points");
+        }
+
+        for (index, point) in ctx.part.joint_points.iter_mut().enumerate() {
+            ui.push_id(point.id, |ui| {
+                ui.horizontal(|ui| {
+                    changed |= ui.text_edit_singleline(&mut point.name).changed();
+                    if ui.small_button("x").on_hover_text("Remove").clicked() {
+                        remove = Some(index);
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Pos:");
+                    for value in point.position.as_mut() {
+                        changed |= length_value(ui, value, 0.001, unit);
+                    }
+                });
+
+                let (roll, pitch, yaw) = point.orientation.to_euler(EulerRot::XYZ);
+                let mut rpy = [roll, pitch, yaw].map(f32::to_degrees);
+                ui.horizontal(|ui| {
+                    ui.label("RPY:");
+                    let mut rpy_changed = false;
+                    for value in &mut rpy {
+                        rpy_changed |= ui
+                            .add(DragValue::new(value).speed(1.0).suffix("°"))
+                            .changed();
+                    }
+                    if rpy_changed {
+                        let [r, p, y] = rpy.map(f32::to_radians);
+                        point.orientation = Quat::from_euler(EulerRot::XYZ, r, p, y);
+                        changed = true;
+                    }
+                });
+
+                ui.add_enabled_ui(!locked && !ctx.mate_targets.is_empty(), |ui| {
+                    ui.menu_button("Mate to", |ui| {
+                        for target in &ctx.mate_targets {
+                            if ui.button(&target.label).clicked() {
+                                ctx.pending_actions.push(AppAction::MatePart {
+                                    part_id,
+                                    point_id: point.id,
+                                    target_part_id: target.part_id,
+                                    target_point_id: target.point_id,
+                                });
+                                ui.close();
+                            }
+                        }
+                    })
+                    .response
+                    .on_disabled_hover_text(if locked {
+                        "Part is locked"
+                    } else {
+                        "No joint points on other parts"
+                    });
+                });
+            });
+            ui.separator();
+        }
+
+        if let Some(index) = remove {
+            ctx.part.joint_points.remove(index);
+            changed = true;
+        }
+
+        if ui.button("+ Add Joint Point").clicked() {
+            let name = format!("point_{}", ctx.part.joint_points.len() + 1);
+            ctx.part
+                .joint_points
+                .push(JointPoint::new(name, part_id, Vec3::ZERO));
+            changed = true;
+        }
+
+        changed
+    }
+}
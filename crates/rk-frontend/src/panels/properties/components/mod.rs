@@ -3,6 +3,7 @@
 mod collision;
 mod geometry;
 mod joint;
+mod joint_points;
 mod physical;
 mod transform;
 mod visual;
@@ -10,6 +11,7 @@ mod visual;
 pub use collision::CollisionComponent;
 pub use geometry::GeometryComponent;
 pub use joint::JointComponent;
+pub use joint_points::JointPointsComponent;
 pub use physical::PhysicalComponent;
 pub use transform::TransformComponent;
 pub use visual::VisualComponent;
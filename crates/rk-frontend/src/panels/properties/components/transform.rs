@@ -1,9 +1,10 @@
 //! Transform component - position, rotation, and scale editing
 
 use egui::Ui;
-use glam::{EulerRot, Mat4, Quat, Vec3};
+use glam::{Mat4, Vec3};
+use rk_core::Pose;
 
-use crate::panels::properties::helpers::{rotation_row, vector3_row};
+use crate::panels::properties::helpers::{length3_row, orientation_row, vector3_row};
 use crate::panels::properties::{PropertyComponent, PropertyContext};
 
 /// Transform component (position, rotation, scale)
@@ -32,44 +33,7 @@ impl PropertyComponent for TransformComponent {
     fn ui(&mut self, ui: &mut Ui, ctx: &mut PropertyContext) -> bool {
         let part = &mut ctx.part;
         let parent_transform = ctx.parent_world_transform;
-
-        // Extract position, rotation, and scale from the transform matrix (world coordinates)
-        let (scale, rotation, translation) = part.origin_transform.to_scale_rotation_translation();
-        let euler = rotation.to_euler(EulerRot::XYZ);
-
-        let mut pos = [translation.x, translation.y, translation.z];
-        let mut rot_deg = [
-            euler.0.to_degrees(),
-            euler.1.to_degrees(),
-            euler.2.to_degrees(),
-        ];
-        let mut scl = [scale.x, scale.y, scale.z];
-
-        // Local transform variables
-        let mut local_pos = [0.0f32; 3];
-        let mut local_rot_deg = [0.0f32; 3];
-        let mut local_scl = [1.0f32; 3];
-
-        // Calculate local transform if parent exists
-        if let Some(parent) = parent_transform {
-            let parent_inverse = parent.inverse();
-            let local = parent_inverse * part.origin_transform;
-            let (local_scale, local_rotation, local_translation) =
-                local.to_scale_rotation_translation();
-            let local_euler = local_rotation.to_euler(EulerRot::XYZ);
-
-            local_pos = [
-                local_translation.x,
-                local_translation.y,
-                local_translation.z,
-            ];
-            local_rot_deg = [
-                local_euler.0.to_degrees(),
-                local_euler.1.to_degrees(),
-                local_euler.2.to_degrees(),
-            ];
-            local_scl = [local_scale.x, local_scale.y, local_scale.z];
-        }
+        let unit = ctx.length_unit;
 
         // Show toggle switch only if parent exists
         if parent_transform.is_some() {
@@ -77,62 +41,34 @@ impl PropertyComponent for TransformComponent {
             ui.add_space(4.0);
         }
 
-        let (pos_changed, rot_changed, scale_changed);
-        let (local_pos_changed, local_rot_changed, local_scale_changed);
-
-        if self.show_local && parent_transform.is_some() {
-            // Show local coordinates
-            local_pos_changed = vector3_row(ui, "Position", &mut local_pos, 0.01);
-            local_rot_changed = rotation_row(ui, "Rotation", &mut local_rot_deg, 1.0);
-            local_scale_changed = vector3_row(ui, "Scale", &mut local_scl, 0.01);
-            pos_changed = false;
-            rot_changed = false;
-            scale_changed = false;
-        } else {
-            // Show world coordinates
-            pos_changed = vector3_row(ui, "Position", &mut pos, 0.01);
-            rot_changed = rotation_row(ui, "Rotation", &mut rot_deg, 1.0);
-            scale_changed = vector3_row(ui, "Scale", &mut scl, 0.01);
-            local_pos_changed = false;
-            local_rot_changed = false;
-            local_scale_changed = false;
+        // Local coordinates are edited in the parent's frame, world ones as-is
+        let frame = match parent_transform {
+            Some(parent) if self.show_local => parent,
+            _ => Mat4::IDENTITY,
+        };
+
+        // Extract position, rotation, and scale from the transform matrix
+        let (scale, rotation, translation) =
+            (frame.inverse() * part.origin_transform).to_scale_rotation_translation();
+        let mut pose = Pose::from_quat(translation.to_array(), rotation);
+        let mut scl = scale.to_array();
+
+        let mut changed = length3_row(ui, "Position", &mut pose.xyz, 0.01, unit);
+        if let Some(rotated) = orientation_row(ui, "Rotation", &pose, ctx.rotation_display_mode) {
+            pose = rotated;
+            changed = true;
         }
-
-        // Update transform if changed
-        if local_pos_changed || local_rot_changed || local_scale_changed {
-            // Local transform was edited - compute new world transform
-            let parent = parent_transform.unwrap();
-            let new_local_rotation = Quat::from_euler(
-                EulerRot::XYZ,
-                local_rot_deg[0].to_radians(),
-                local_rot_deg[1].to_radians(),
-                local_rot_deg[2].to_radians(),
-            );
-            let new_local_translation = Vec3::new(local_pos[0], local_pos[1], local_pos[2]);
-            let new_local_scale = Vec3::new(local_scl[0], local_scl[1], local_scl[2]);
-            let new_local_transform = Mat4::from_scale_rotation_translation(
-                new_local_scale,
-                new_local_rotation,
-                new_local_translation,
-            );
-            // world = parent * local
-            part.origin_transform = parent * new_local_transform;
-            true
-        } else if pos_changed || rot_changed || scale_changed {
-            // World transform was edited
-            let new_rotation = Quat::from_euler(
-                EulerRot::XYZ,
-                rot_deg[0].to_radians(),
-                rot_deg[1].to_radians(),
-                rot_deg[2].to_radians(),
-            );
-            let new_translation = Vec3::new(pos[0], pos[1], pos[2]);
-            let new_scale = Vec3::new(scl[0], scl[1], scl[2]);
-            part.origin_transform =
-                Mat4::from_scale_rotation_translation(new_scale, new_rotation, new_translation);
-            true
-        } else {
-            false
+        changed |= vector3_row(ui, "Scale", &mut scl, 0.01);
+
+        // Update transform if changed (world = parent * local)
+        if changed {
+            part.origin_transform = frame
+                * Mat4::from_scale_rotation_translation(
+                    Vec3::from(scl),
+                    pose.to_quat(),
+                    Vec3::from(pose.xyz),
+                );
         }
+        changed
     }
 }
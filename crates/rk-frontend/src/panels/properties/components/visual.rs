@@ -38,12 +38,12 @@ impl PropertyComponent for VisualComponent {
                 (part.color[3] * 255.0) as u8,
             );
             if ui.color_edit_button_srgba(&mut color).changed() {
-                part.color = [
+                part.set_color([
                     color.r() as f32 / 255.0,
                     color.g() as f32 / 255.0,
                     color.b() as f32 / 255.0,
                     color.a() as f32 / 255.0,
-                ];
+                ]);
                 changed = true;
             }
         });
@@ -1,6 +1,10 @@
 //! Common UI helper functions for property components
 
 use egui::{DragValue, Ui};
+use glam::Quat;
+use rk_core::{Pose, StlUnit};
+
+use crate::state::RotationDisplayMode;
 
 /// Render a labeled XYZ vector3 row with drag values
 /// Returns true if any value was changed
@@ -27,6 +31,47 @@ pub fn vector3_row(ui: &mut Ui, label: &str, values: &mut [f32; 3], speed: f32)
     .inner
 }
 
+/// Render a drag value for a length stored in meters, shown in `unit`
+/// `speed` is in meters per pixel. Returns true if the value was changed
+pub fn length_value(ui: &mut Ui, meters: &mut f32, speed: f32, unit: StlUnit) -> bool {
+    let mut value = unit.from_meters(*meters);
+    let changed = ui
+        .add(
+            DragValue::new(&mut value)
+                .speed(unit.from_meters(speed))
+                .max_decimals(unit.decimals() + 2)
+                .suffix(unit.suffix()),
+        )
+        .changed();
+    if changed {
+        *meters = unit.to_meters(value);
+    }
+    changed
+}
+
+/// Render a labeled XYZ row of lengths stored in meters, shown in `unit`
+/// Returns true if any value was changed
+pub fn length3_row(
+    ui: &mut Ui,
+    label: &str,
+    values: &mut [f32; 3],
+    speed: f32,
+    unit: StlUnit,
+) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+    });
+    ui.horizontal(|ui| {
+        let mut changed = false;
+        for (name, value) in ["X", "Y", "Z"].iter().zip(values.iter_mut()) {
+            ui.label(*name);
+            changed |= length_value(ui, value, speed, unit);
+        }
+        changed
+    })
+    .inner
+}
+
 /// Render a labeled XYZ vector3 row (read-only)
 #[allow(dead_code)]
 pub fn vector3_readonly_row(ui: &mut Ui, label: &str, values: &[f32; 3]) {
@@ -68,6 +113,56 @@ pub fn rotation_row(ui: &mut Ui, label: &str, rot_deg: &mut [f32; 3], speed: f32
     .inner
 }
 
+/// Render an editable quaternion row (x, y, z, w)
+/// Returns true if any value was changed
+pub fn quaternion_row(ui: &mut Ui, label: &str, quat: &mut [f32; 4], speed: f32) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+    });
+    ui.horizontal(|ui| {
+        let mut changed = false;
+        for (name, value) in ["X", "Y", "Z", "W"].iter().zip(quat.iter_mut()) {
+            ui.label(*name);
+            changed |= ui
+                .add(
+                    DragValue::new(value)
+                        .speed(speed)
+                        .range(-1.0..=1.0)
+                        .max_decimals(4),
+                )
+                .changed();
+        }
+        changed
+    })
+    .inner
+}
+
+/// Render the orientation of a pose as RPY degrees or a quaternion
+/// Returns the updated pose if the orientation was changed
+pub fn orientation_row(
+    ui: &mut Ui,
+    label: &str,
+    pose: &Pose,
+    mode: RotationDisplayMode,
+) -> Option<Pose> {
+    match mode {
+        RotationDisplayMode::Euler => {
+            let mut rot_deg = pose.rpy.map(f32::to_degrees);
+            rotation_row(ui, label, &mut rot_deg, 1.0)
+                .then(|| Pose::new(pose.xyz, rot_deg.map(f32::to_radians)))
+        }
+        RotationDisplayMode::Quaternion => {
+            let mut quat = pose.to_quat().to_array();
+            if !quaternion_row(ui, label, &mut quat, 0.01) {
+                return None;
+            }
+            // Ignore edits that collapse the quaternion; it can't be normalized
+            let rotation = Quat::from_array(quat);
+            (rotation.length_squared() > 1e-6).then(|| Pose::from_quat(pose.xyz, rotation))
+        }
+    }
+}
+
 /// Render rotation row (read-only) with degree suffix
 #[allow(dead_code)]
 pub fn rotation_readonly_row(ui: &mut Ui, label: &str, rot_deg: &[f32; 3]) {
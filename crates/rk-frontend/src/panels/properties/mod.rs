@@ -4,11 +4,11 @@ mod component;
 mod components;
 mod helpers;
 
-pub use component::{ChildJointInfo, PropertyComponent, PropertyContext};
+pub use component::{ChildJointInfo, MateTarget, PropertyComponent, PropertyContext};
 
 use components::{
-    CollisionComponent, GeometryComponent, JointComponent, PhysicalComponent, TransformComponent,
-    VisualComponent,
+    CollisionComponent, GeometryComponent, JointComponent, JointPointsComponent, PhysicalComponent,
+    TransformComponent, VisualComponent,
 };
 
 use crate::config::SharedConfig;
@@ -23,6 +23,7 @@ pub struct PropertiesPanel {
     geometry: GeometryComponent,
     collision: CollisionComponent,
     joint: JointComponent,
+    joint_points: JointPointsComponent,
 }
 
 impl PropertiesPanel {
@@ -34,6 +35,7 @@ impl PropertiesPanel {
             geometry: GeometryComponent::new(),
             collision: CollisionComponent::new(),
             joint: JointComponent::new(),
+            joint_points: JointPointsComponent::new(),
         }
     }
 }
@@ -123,6 +125,26 @@ impl Panel for PropertiesPanel {
             }
         });
 
+        let rotation_display_mode = state.rotation_display_mode;
+        let length_unit = state.length_unit;
+        let joint_defaults = state.joint_defaults.clone();
+        let editing_joint = state.editing_joint_id;
+
+        let mut mate_targets: Vec<MateTarget> = state
+            .project
+            .parts()
+            .values()
+            .filter(|p| p.id != selected_id)
+            .flat_map(|p| {
+                p.joint_points.iter().map(|point| MateTarget {
+                    part_id: p.id,
+                    point_id: point.id,
+                    label: format!("{} / {}", p.name, point.name),
+                })
+            })
+            .collect();
+        mate_targets.sort_by(|a, b| a.label.cmp(&b.label));
+
         let Some(part) = state.get_part_mut(selected_id) else {
             ui.weak("Selected part not found");
             return;
@@ -139,6 +161,8 @@ impl Panel for PropertiesPanel {
 
         ui.separator();
 
+        let old_color = part.color;
+
         // Pending actions to queue after rendering
         let mut pending_actions: Vec<AppAction> = Vec::new();
 
@@ -150,6 +174,11 @@ impl Panel for PropertiesPanel {
             collisions,
             selected_collision_index,
             child_joints,
+            editing_joint,
+            mate_targets,
+            rotation_display_mode,
+            length_unit,
+            joint_defaults,
             pending_actions: &mut pending_actions,
         };
 
@@ -161,6 +190,7 @@ impl Panel for PropertiesPanel {
         render_component(ui, &mut self.geometry, &mut ctx);
         render_component(ui, &mut self.collision, &mut ctx);
         render_component(ui, &mut self.joint, &mut ctx);
+        render_component(ui, &mut self.joint_points, &mut ctx);
 
         // If transform changed, update the renderer
         let new_transform = if transform_changed {
@@ -169,6 +199,12 @@ impl Panel for PropertiesPanel {
             None
         };
 
+        let new_color = (ctx.part.color != old_color).then_some(ctx.part.color);
+        if new_color.is_some() {
+            // The edited color is the real one; restoring must not undo it
+            state.original_part_colors.remove(&selected_id);
+        }
+
         // Queue any pending actions from components
         for action in pending_actions {
             state.queue_action(action);
@@ -176,12 +212,15 @@ impl Panel for PropertiesPanel {
 
         drop(state);
 
-        // Update renderer with new transform
+        // Update renderer with new transform and color
         if let Some(transform) = new_transform {
             viewport_state
                 .lock()
                 .update_part_transform(selected_id, transform);
         }
+        if let Some(color) = new_color {
+            viewport_state.lock().update_part_color(selected_id, color);
+        }
     }
 }
 
@@ -0,0 +1,120 @@
+//! Robot info panel with summary statistics of the model
+
+use egui::Ui;
+
+use crate::panels::Panel;
+use crate::state::SharedAppState;
+
+/// Shows link/joint counts, DOF, mass and mesh statistics
+pub struct RobotInfoPanel {
+    /// Colliding link name pairs from the last "Check Current Pose" run
+    pose_check: Option<Vec<(String, String)>>,
+}
+
+impl RobotInfoPanel {
+    pub fn new() -> Self {
+        Self { pose_check: None }
+    }
+
+    /// Run a self-collision check at the current joint positions
+    fn check_current_pose(app_state: &SharedAppState) -> Vec<(String, String)> {
+        let state = app_state.lock();
+        let assembly = &state.project.assembly;
+        let link_name = |id| {
+            assembly
+                .links
+                .get(&id)
+                .map_or_else(|| id.to_string(), |l| l.name.clone())
+        };
+        assembly
+            .self_collisions(state.project.parts(), &assembly.joint_positions)
+            .into_iter()
+            .map(|(a, b)| (link_name(a), link_name(b)))
+            .collect()
+    }
+}
+
+impl Default for RobotInfoPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Panel for RobotInfoPanel {
+    fn name(&self) -> &str {
+        "Robot Info"
+    }
+
+    fn ui(&mut self, ui: &mut Ui, app_state: &SharedAppState) {
+        let stats = app_state.lock().project.statistics();
+
+        egui::Grid::new("robot_info_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Links");
+                ui.label(stats.link_count.to_string());
+                ui.end_row();
+
+                ui.label("Joints");
+                ui.label(stats.joint_count.to_string());
+                ui.end_row();
+
+                ui.label("Degrees of freedom");
+                ui.label(stats.movable_dof.to_string());
+                ui.end_row();
+
+                ui.label("Total mass");
+                ui.label(format!("{:.3} kg", stats.total_mass));
+                ui.end_row();
+
+                ui.label("Triangles");
+                ui.label(stats.triangle_count.to_string());
+                ui.end_row();
+
+                ui.label("Size");
+                match stats.bounding_box {
+                    Some((min, max)) => ui.label(format!(
+                        "{:.3} x {:.3} x {:.3} m",
+                        max[0] - min[0],
+                        max[1] - min[1],
+                        max[2] - min[2]
+                    )),
+                    None => ui.weak("No geometry"),
+                };
+                ui.end_row();
+            });
+
+        if stats.link_count > 0 && stats.total_mass <= 0.0 {
+            ui.add_space(4.0);
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "Total mass is zero; set link masses before simulation.",
+            );
+        }
+
+        ui.separator();
+        if ui
+            .button("Check Current Pose")
+            .on_hover_text("Test the current joint positions for self-collision")
+            .clicked()
+        {
+            self.pose_check = Some(Self::check_current_pose(app_state));
+        }
+        match &self.pose_check {
+            Some(pairs) if pairs.is_empty() => {
+                ui.label("\u{2714} No self-collisions");
+            }
+            Some(pairs) => {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    format!("\u{26a0} {} colliding link pair(s)", pairs.len()),
+                );
+                for (a, b) in pairs {
+                    ui.label(format!("{a} \u{2194} {b}"));
+                }
+            }
+            None => {}
+        }
+    }
+}
@@ -1,8 +1,9 @@
 //! Camera settings overlay for the 3D viewport
 
 use glam::Vec3;
-use rk_renderer::{GizmoMode, GizmoSpace};
+use rk_renderer::{AxisView, GizmoMode, GizmoSpace};
 
+use crate::config::SharedConfig;
 use crate::state::SharedViewportState;
 
 /// Render camera settings overlay in the top-right corner (Unity-style)
@@ -10,6 +11,7 @@ pub fn render_camera_settings(
     ui: &mut egui::Ui,
     rect: egui::Rect,
     viewport_state: &SharedViewportState,
+    config: &SharedConfig,
     show_camera_settings: &mut bool,
 ) {
     let panel_width = 180.0;
@@ -70,7 +72,7 @@ pub fn render_camera_settings(
 
                     let mut vp = viewport_state.lock();
 
-                    // FOV slider
+                    // FOV slider, dollying here but kept in the config as well
                     ui.horizontal(|ui| {
                         ui.label("FOV");
                         ui.add_space(ui.available_width() - 100.0);
@@ -84,7 +86,21 @@ pub fn render_camera_settings(
                             )
                             .changed()
                         {
-                            vp.renderer.camera_mut().set_fov_degrees(fov);
+                            vp.renderer.camera_mut().set_fov(fov);
+                            config.write().config_mut().renderer.camera.fov_degrees =
+                                vp.renderer.camera().fov_degrees();
+                        }
+                    });
+
+                    // Dolly zoom toggle, kept in the config so Preferences don't undo it
+                    ui.horizontal(|ui| {
+                        if ui
+                            .checkbox(&mut vp.renderer.camera_mut().dolly_zoom, "Dolly Zoom")
+                            .on_hover_text("Keep the subject size constant when changing FOV")
+                            .changed()
+                        {
+                            config.write().config_mut().renderer.camera.dolly_zoom =
+                                vp.renderer.camera().dolly_zoom;
                         }
                     });
 
@@ -208,54 +224,282 @@ pub fn render_gizmo_toggle(
         });
 }
 
-/// Render axes indicator in the bottom-right corner
-pub fn render_axes_indicator(ui: &mut egui::Ui, rect: egui::Rect, yaw: f32, pitch: f32) {
+/// Status line naming the active gizmo mode and coordinate space
+pub fn gizmo_status_text(mode: GizmoMode, space: GizmoSpace) -> String {
+    let mode = match mode {
+        GizmoMode::Translate => "Move",
+        GizmoMode::Rotate => "Rotate",
+        GizmoMode::Scale => "Scale",
+    };
+    let space = match space {
+        GizmoSpace::Global => "Global",
+        GizmoSpace::Local => "Local",
+    };
+    format!("{mode} \u{00b7} {space}")
+}
+
+/// Render the active gizmo mode and space below the gizmo toggle
+pub fn render_gizmo_status(ui: &egui::Ui, rect: egui::Rect, mode: GizmoMode, space: GizmoSpace) {
     let painter = ui.painter();
-    let axes_center = rect.right_bottom() - egui::vec2(50.0, 50.0);
-    let axis_len = 30.0;
+    let galley = painter.layout_no_wrap(
+        gizmo_status_text(mode, space),
+        egui::FontId::proportional(12.0),
+        egui::Color32::from_gray(220),
+    );
+    // Below the 24pt toggle buttons and their frame
+    let pos = egui::pos2(rect.left() + 10.0, rect.top() + 46.0);
+    let background = egui::Rect::from_min_size(pos, galley.size()).expand2(egui::vec2(6.0, 3.0));
+    painter.rect_filled(
+        background,
+        4.0,
+        egui::Color32::from_rgba_unmultiplied(30, 30, 30, 220),
+    );
+    painter.galley(pos, galley, egui::Color32::from_gray(220));
+}
+
+/// Offset of the axes indicator center from the bottom-right corner
+const AXES_INDICATOR_INSET: f32 = 50.0;
+
+/// Projected length of a unit axis in the indicator, in points
+const AXES_INDICATOR_LEN: f32 = 30.0;
+
+/// Click radius around each axis tip, in points
+const AXIS_TIP_RADIUS: f32 = 8.0;
 
-    // Calculate camera basis vectors
-    let cos_yaw = yaw.cos();
-    let sin_yaw = yaw.sin();
-    let cos_pitch = pitch.cos();
-    let sin_pitch = pitch.sin();
+/// Click radius of the isometric region at the indicator center, in points
+const AXES_CENTER_RADIUS: f32 = 6.0;
+
+/// Project a world axis into the indicator as (screen offset, depth)
+///
+/// Depth is measured along the camera's view direction, so axes pointing at
+/// the viewer are negative.
+fn project_indicator_axis(axis: Vec3, yaw: f32, pitch: f32) -> (egui::Vec2, f32) {
+    let (cos_yaw, sin_yaw) = (yaw.cos(), yaw.sin());
+    let (cos_pitch, sin_pitch) = (pitch.cos(), pitch.sin());
 
     let forward = Vec3::new(-cos_pitch * cos_yaw, -cos_pitch * sin_yaw, -sin_pitch);
     let world_up = Vec3::Z;
-    let right = forward.cross(world_up).normalize();
+    let right = forward
+        .cross(world_up)
+        .normalize_or(Vec3::new(-sin_yaw, cos_yaw, 0.0));
     let up = right.cross(forward).normalize();
 
-    let project_axis = |world_axis: Vec3| -> (egui::Vec2, f32) {
-        let x = world_axis.dot(right);
-        let y = world_axis.dot(up);
-        let z = world_axis.dot(forward);
-        (egui::vec2(x * axis_len, -y * axis_len), z)
-    };
+    let offset = egui::vec2(axis.dot(right), -axis.dot(up)) * AXES_INDICATOR_LEN;
+    (offset, axis.dot(forward))
+}
 
-    let (x_dir, x_depth) = project_axis(Vec3::X);
-    let (y_dir, y_depth) = project_axis(Vec3::Y);
-    let (z_dir, z_depth) = project_axis(Vec3::Z);
+/// View under `offset` from the indicator center, if any
+///
+/// When tips overlap the one nearest the viewer wins.
+pub fn axes_indicator_hit(offset: egui::Vec2, yaw: f32, pitch: f32) -> Option<AxisView> {
+    AxisView::AXES
+        .into_iter()
+        .filter_map(|view| {
+            let (tip, depth) = project_indicator_axis(view.direction()?, yaw, pitch);
+            ((offset - tip).length() <= AXIS_TIP_RADIUS).then_some((depth, view))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, view)| view)
+        .or_else(|| (offset.length() <= AXES_CENTER_RADIUS).then_some(AxisView::Isometric))
+}
+
+/// Render the clickable axes indicator in the bottom-right corner
+///
+/// Returns the view whose tip (or the isometric center) was clicked.
+pub fn render_axes_indicator(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    yaw: f32,
+    pitch: f32,
+) -> Option<AxisView> {
+    let axes_center = rect.right_bottom() - egui::vec2(AXES_INDICATOR_INSET, AXES_INDICATOR_INSET);
+    let hit_rect = egui::Rect::from_center_size(
+        axes_center,
+        egui::Vec2::splat(2.0 * (AXES_INDICATOR_LEN + AXIS_TIP_RADIUS)),
+    );
+    let response = ui.interact(
+        hit_rect,
+        ui.id().with("axes_indicator"),
+        egui::Sense::click(),
+    );
+    let hovered = response
+        .hover_pos()
+        .and_then(|pos| axes_indicator_hit(pos - axes_center, yaw, pitch));
 
+    let painter = ui.painter();
+    let highlight = egui::Color32::from_white_alpha(60);
     let mut axes = [
-        (x_depth, x_dir, "X", egui::Color32::from_rgb(255, 68, 68)),
-        (y_depth, y_dir, "Y", egui::Color32::from_rgb(68, 255, 68)),
-        (z_depth, z_dir, "Z", egui::Color32::from_rgb(68, 68, 255)),
-    ];
+        (
+            Vec3::X,
+            AxisView::PosX,
+            AxisView::NegX,
+            "X",
+            egui::Color32::from_rgb(255, 68, 68),
+        ),
+        (
+            Vec3::Y,
+            AxisView::PosY,
+            AxisView::NegY,
+            "Y",
+            egui::Color32::from_rgb(68, 255, 68),
+        ),
+        (
+            Vec3::Z,
+            AxisView::PosZ,
+            AxisView::NegZ,
+            "Z",
+            egui::Color32::from_rgb(68, 68, 255),
+        ),
+    ]
+    .map(|(axis, pos, neg, label, color)| {
+        let (dir, depth) = project_indicator_axis(axis, yaw, pitch);
+        (depth, dir, pos, neg, label, color)
+    });
     axes.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
 
-    for (_depth, dir, label, color) in axes {
-        painter.line_segment(
-            [axes_center, axes_center + dir],
-            egui::Stroke::new(2.0, color),
+    if hovered == Some(AxisView::Isometric) {
+        painter.circle_filled(axes_center, AXES_CENTER_RADIUS, highlight);
+    }
+
+    for (_depth, dir, pos, neg, label, color) in axes {
+        // Negative axis tips are faded rings
+        let neg_tip = axes_center - dir;
+        if hovered == Some(neg) {
+            painter.circle_filled(neg_tip, AXIS_TIP_RADIUS, highlight);
+        }
+        painter.circle_stroke(
+            neg_tip,
+            3.0,
+            egui::Stroke::new(1.5, color.gamma_multiply(0.5)),
         );
 
+        let tip = axes_center + dir;
+        if hovered == Some(pos) {
+            painter.circle_filled(tip, AXIS_TIP_RADIUS, highlight);
+        }
+        painter.line_segment([axes_center, tip], egui::Stroke::new(2.0, color));
+
         let label_offset = dir.normalized() * 8.0;
         painter.text(
-            axes_center + dir + label_offset,
+            tip + label_offset,
             egui::Align2::CENTER_CENTER,
             label,
             egui::FontId::default(),
             color,
         );
     }
+
+    if response.clicked() { hovered } else { None }
+}
+
+/// Longest the scale bar may grow, in points
+const SCALE_BAR_MAX_LEN: f32 = 120.0;
+
+/// Largest 1/2/5 x 10^n length not exceeding `max`
+fn nice_scale_length(max: f32) -> f32 {
+    let magnitude = 10f32.powf(max.log10().floor());
+    [5.0, 2.0, 1.0]
+        .into_iter()
+        .map(|step| step * magnitude)
+        .find(|&len| len <= max)
+        .unwrap_or(magnitude)
+}
+
+/// Format a length in meters with a unit suited to its size
+fn format_scale_length(meters: f32) -> String {
+    if meters >= 1.0 {
+        format!("{:.0} m", meters)
+    } else if meters >= 0.01 {
+        format!("{} cm", (meters * 100.0).round())
+    } else {
+        format!("{} mm", (meters * 1000.0 * 100.0).round() / 100.0)
+    }
+}
+
+/// Render a scale bar in the bottom-left corner
+///
+/// `world_per_point` is the world length covered by one screen point at the
+/// camera's target plane.
+pub fn render_scale_bar(ui: &mut egui::Ui, rect: egui::Rect, world_per_point: f32) {
+    if !(world_per_point.is_finite() && world_per_point > 0.0) {
+        return;
+    }
+
+    let length = nice_scale_length(world_per_point * SCALE_BAR_MAX_LEN);
+    let bar_len = length / world_per_point;
+
+    let painter = ui.painter();
+    let left = rect.left_bottom() + egui::vec2(16.0, -20.0);
+    let right = left + egui::vec2(bar_len, 0.0);
+    let stroke = egui::Stroke::new(2.0, egui::Color32::from_gray(220));
+    let tick = egui::vec2(0.0, 5.0);
+
+    painter.line_segment([left, right], stroke);
+    painter.line_segment([left - tick, left + tick], stroke);
+    painter.line_segment([right - tick, right + tick], stroke);
+    painter.text(
+        left + egui::vec2(bar_len * 0.5, -6.0),
+        egui::Align2::CENTER_BOTTOM,
+        format_scale_length(length),
+        egui::FontId::proportional(12.0),
+        egui::Color32::from_gray(220),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axes_indicator_click_maps_to_view() {
+        let (yaw, pitch) = (30f32.to_radians(), 20f32.to_radians());
+
+        // Clicking the projected +X tip selects the view looking from +X
+        let (tip, _) = project_indicator_axis(Vec3::X, yaw, pitch);
+        let view = axes_indicator_hit(tip + egui::vec2(2.0, -1.0), yaw, pitch);
+        assert_eq!(view, Some(AxisView::PosX));
+        assert_eq!(view.unwrap().orbit_angles(yaw), (0.0, 0.0));
+
+        // The opposite tip selects -X, and the center the nearest isometric view
+        assert_eq!(axes_indicator_hit(-tip, yaw, pitch), Some(AxisView::NegX));
+        let (iso_yaw, iso_pitch) = axes_indicator_hit(egui::Vec2::ZERO, yaw, pitch)
+            .unwrap()
+            .orbit_angles(yaw);
+        assert!((iso_yaw - 45f32.to_radians()).abs() < 1e-6);
+        assert!((iso_pitch - 35.264f32.to_radians()).abs() < 1e-4);
+
+        // Looking straight down, +Z overlaps -Z at the center and wins
+        let top = axes_indicator_hit(egui::Vec2::ZERO, yaw, 89.9f32.to_radians());
+        assert_eq!(top, Some(AxisView::PosZ));
+
+        // Away from every region nothing is hit
+        assert_eq!(axes_indicator_hit(egui::vec2(0.0, 60.0), yaw, pitch), None);
+    }
+
+    #[test]
+    fn test_gizmo_status_is_painted_below_the_toggle() {
+        let ctx = egui::Context::default();
+        let rect = egui::Rect::from_min_size(egui::pos2(100.0, 50.0), egui::vec2(400.0, 300.0));
+        let output = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                render_gizmo_status(ui, rect, GizmoMode::Rotate, GizmoSpace::Local);
+            });
+        });
+
+        let texts: Vec<_> = output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                egui::Shape::Text(text) => Some((text.galley.text().to_owned(), text.pos)),
+                _ => None,
+            })
+            .collect();
+        let (_, pos) = texts
+            .iter()
+            .find(|(text, _)| text == "Rotate \u{00b7} Local")
+            .unwrap_or_else(|| panic!("status not painted: {texts:?}"));
+        // Left-aligned with the toggle and clear of its 24pt buttons
+        assert_eq!(pos.x, rect.left() + 10.0);
+        assert!(pos.y >= rect.top() + 10.0 + 24.0);
+    }
 }
@@ -0,0 +1,134 @@
+//! Part hover picking and tooltip contents
+
+use glam::{Mat4, Vec3};
+use rk_core::{Part, StlUnit};
+use uuid::Uuid;
+
+/// Minimum time between hover picks, in seconds
+const HOVER_PICK_INTERVAL: f64 = 0.05;
+
+/// Pointer movement (points) below which the last hover pick is reused
+const HOVER_PICK_MOVE_THRESHOLD: f32 = 2.0;
+
+/// Part metadata shown when hovering it in the viewport
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartTooltip {
+    pub name: String,
+    pub mass: f32,
+    pub triangle_count: usize,
+    pub size: Vec3,
+}
+
+impl PartTooltip {
+    pub fn from_part(part: &Part) -> Self {
+        Self {
+            name: part.name.clone(),
+            mass: part.mass,
+            triangle_count: part.indices.len() / 3,
+            size: part.size(),
+        }
+    }
+
+    /// Draw the tooltip body
+    pub fn ui(&self, ui: &mut egui::Ui, unit: StlUnit) {
+        ui.strong(&self.name);
+        ui.label(format!("Mass: {:.3} kg", self.mass));
+        ui.label(format!("Triangles: {}", self.triangle_count));
+        ui.label(format!(
+            "Size: {} x {} x {}",
+            unit.format(self.size.x),
+            unit.format(self.size.y),
+            unit.format(self.size.z)
+        ));
+    }
+}
+
+/// Throttled part picking under the pointer
+///
+/// Ray casting every frame is wasteful while the pointer rests, so a new pick
+/// runs only after the pointer or the camera `view` moves and
+/// [`HOVER_PICK_INTERVAL`] has passed.
+#[derive(Debug, Default)]
+pub struct HoverPicker {
+    last_pick: Option<(egui::Pos2, Mat4, f64)>,
+    hovered: Option<Uuid>,
+}
+
+impl HoverPicker {
+    /// Part under `pos`, calling `pick` only when a fresh pick is due
+    pub fn update(
+        &mut self,
+        pos: egui::Pos2,
+        view: Mat4,
+        now: f64,
+        pick: impl FnOnce() -> Option<Uuid>,
+    ) -> Option<Uuid> {
+        let due = self
+            .last_pick
+            .is_none_or(|(last_pos, last_view, last_time)| {
+                (last_pos.distance(pos) >= HOVER_PICK_MOVE_THRESHOLD || last_view != view)
+                    && now - last_time >= HOVER_PICK_INTERVAL
+            });
+        if due {
+            self.hovered = pick();
+            self.last_pick = Some((pos, view, now));
+        }
+        self.hovered
+    }
+
+    /// Forget the hovered part, e.g. when the pointer leaves the viewport
+    pub fn clear(&mut self) {
+        self.last_pick = None;
+        self.hovered = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rk_core::PrimitiveShape;
+
+    #[test]
+    fn test_tooltip_reports_part_metadata() {
+        let mut part = Part::from_primitive(
+            "bracket",
+            PrimitiveShape::Box {
+                size: [0.2, 0.4, 0.6],
+            },
+        );
+        part.mass = 2.5;
+
+        let tooltip = PartTooltip::from_part(&part);
+        assert_eq!(tooltip.name, "bracket");
+        assert_eq!(tooltip.mass, 2.5);
+        assert_eq!(tooltip.triangle_count, 12);
+        assert!(tooltip.size.abs_diff_eq(Vec3::new(0.2, 0.4, 0.6), 1e-6));
+    }
+
+    #[test]
+    fn test_hover_pick_is_throttled() {
+        let part_id = Uuid::new_v4();
+        let mut picker = HoverPicker::default();
+        let picks = std::cell::Cell::new(0);
+        let pick = || {
+            picks.set(picks.get() + 1);
+            Some(part_id)
+        };
+
+        let pos = egui::pos2(10.0, 10.0);
+        let view = Mat4::IDENTITY;
+        assert_eq!(picker.update(pos, view, 0.0, pick), Some(part_id));
+        // Resting pointer, or a move too soon after the last pick, reuses it
+        assert_eq!(picker.update(pos, view, 1.0, pick), Some(part_id));
+        picker.update(pos + egui::vec2(5.0, 0.0), view, 0.01, pick);
+        assert_eq!(picks.get(), 1);
+        // A move after the interval picks again
+        picker.update(pos + egui::vec2(5.0, 0.0), view, 1.0, pick);
+        assert_eq!(picks.get(), 2);
+
+        // A camera move under a resting pointer picks again too
+        let moved = Mat4::from_translation(Vec3::X);
+        picker.update(pos + egui::vec2(5.0, 0.0), moved, 2.0, pick);
+        assert_eq!(picks.get(), 3);
+    }
+}
@@ -0,0 +1,230 @@
+//! Billboarded link and joint name labels
+
+use glam::{Mat4, Vec3};
+use rk_core::Assembly;
+use rk_renderer::Renderer;
+use uuid::Uuid;
+
+/// Minimum time between occlusion refreshes while the view changes, in seconds
+const OCCLUSION_INTERVAL: f64 = 0.1;
+
+/// Time after which occlusion is refreshed even for a still view, in seconds
+const OCCLUSION_MAX_AGE: f64 = 1.0;
+
+/// Screen distance (points) below which labels count as coinciding
+const STACK_RADIUS: f32 = 4.0;
+
+/// Name to draw at a world position
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameLabel {
+    pub text: String,
+    pub position: Vec3,
+    /// Part the label sits inside, ignored when testing occlusion
+    pub owner: Option<Uuid>,
+    pub is_joint: bool,
+}
+
+/// Labels for every link origin and joint origin
+pub fn collect_name_labels(assembly: &Assembly) -> Vec<NameLabel> {
+    let links = assembly.links.values().map(|link| NameLabel {
+        text: link.name.clone(),
+        position: link.world_transform.w_axis.truncate(),
+        owner: link.part_id,
+        is_joint: false,
+    });
+    let joints = assembly.joints.values().filter_map(|joint| {
+        let parent = assembly.get_link(joint.parent_link)?;
+        let frame = parent.world_transform * joint.origin.to_mat4();
+        Some(NameLabel {
+            text: joint.name.clone(),
+            position: frame.w_axis.truncate(),
+            owner: assembly.get_link(joint.child_link)?.part_id,
+            is_joint: true,
+        })
+    });
+    links.chain(joints).collect()
+}
+
+/// Throttled occlusion tests for name labels
+///
+/// Each test ray casts against every mesh, so results are reused until the
+/// view or the labels move and [`OCCLUSION_INTERVAL`] has passed.
+#[derive(Debug, Default)]
+pub struct LabelOcclusion {
+    last_refresh: Option<(Mat4, f64)>,
+    positions: Vec<Vec3>,
+    occluded: Vec<bool>,
+}
+
+impl LabelOcclusion {
+    /// Whether each label is hidden, calling `is_occluded` only when a refresh is due
+    pub fn update(
+        &mut self,
+        labels: &[NameLabel],
+        view: Mat4,
+        now: f64,
+        is_occluded: impl Fn(&NameLabel) -> bool,
+    ) -> &[bool] {
+        let moved = self.positions.len() != labels.len()
+            || self
+                .positions
+                .iter()
+                .zip(labels)
+                .any(|(position, label)| *position != label.position);
+        let due = self.occluded.len() != labels.len()
+            || self.last_refresh.is_none_or(|(last_view, last_time)| {
+                let age = now - last_time;
+                ((moved || last_view != view) && age >= OCCLUSION_INTERVAL)
+                    || age >= OCCLUSION_MAX_AGE
+            });
+        if due {
+            self.occluded = labels.iter().map(is_occluded).collect();
+            self.positions = labels.iter().map(|label| label.position).collect();
+            self.last_refresh = Some((view, now));
+        }
+        &self.occluded
+    }
+}
+
+/// Shift anchors that coincide with an earlier one upward by `step` each
+pub fn stack_coincident(anchors: &mut [egui::Pos2], step: f32) {
+    let original = anchors.to_vec();
+    for (i, anchor) in anchors.iter_mut().enumerate() {
+        let below = original[..i]
+            .iter()
+            .filter(|other| other.distance(original[i]) < STACK_RADIUS)
+            .count();
+        anchor.y -= below as f32 * step;
+    }
+}
+
+/// Draw labels that are in front of the camera and not hidden by a part
+///
+/// `occluded` holds one flag per label, from [`LabelOcclusion::update`].
+/// Labels at the same spot, like a child link and its joint, are stacked.
+pub fn render_name_labels(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    renderer: &Renderer,
+    labels: &[NameLabel],
+    occluded: &[bool],
+) {
+    let painter = ui.painter_at(rect);
+    let font = egui::FontId::proportional(12.0);
+    let (visible, mut anchors): (Vec<&NameLabel>, Vec<egui::Pos2>) = labels
+        .iter()
+        .zip(occluded)
+        .filter(|(_, hidden)| !**hidden)
+        .filter_map(|(label, _)| {
+            let (screen, _) =
+                renderer
+                    .camera()
+                    .world_to_screen(label.position, rect.width(), rect.height())?;
+            let pos = rect.min + egui::vec2(screen.x, screen.y);
+            rect.contains(pos).then_some((label, pos))
+        })
+        .unzip();
+    stack_coincident(&mut anchors, font.size + 8.0);
+
+    for (label, pos) in visible.into_iter().zip(anchors) {
+        let color = if label.is_joint {
+            egui::Color32::from_rgb(255, 210, 120)
+        } else {
+            egui::Color32::WHITE
+        };
+        let galley = painter.layout_no_wrap(label.text.clone(), font.clone(), color);
+        let text_rect =
+            egui::Align2::CENTER_BOTTOM.anchor_size(pos - egui::vec2(0.0, 6.0), galley.size());
+        painter.rect_filled(
+            text_rect.expand(3.0),
+            3.0,
+            egui::Color32::from_black_alpha(160),
+        );
+        painter.galley(text_rect.min, galley, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rk_core::{Joint, Link, Pose};
+
+    #[test]
+    fn test_labels_at_link_and_joint_origins() {
+        let mut assembly = Assembly::new("robot");
+        let base = assembly.add_link(Link::empty("base"));
+        let mut arm = Link::empty("arm");
+        let arm_part = Uuid::new_v4();
+        arm.part_id = Some(arm_part);
+        let arm = assembly.add_link(arm);
+        let origin = Pose::from_position([0.0, 0.0, 0.5]);
+        assembly
+            .connect(base, arm, Joint::fixed("shoulder", base, arm, origin))
+            .unwrap();
+        assembly.update_world_transforms();
+
+        let labels = collect_name_labels(&assembly);
+        assert_eq!(labels.len(), 3);
+        let find = |name: &str| labels.iter().find(|l| l.text == name).unwrap();
+        assert_eq!(find("base").position, Vec3::ZERO);
+        assert_eq!(find("arm").position, Vec3::new(0.0, 0.0, 0.5));
+        let joint = find("shoulder");
+        assert!(joint.is_joint);
+        assert_eq!(joint.position, Vec3::new(0.0, 0.0, 0.5));
+        assert_eq!(joint.owner, Some(arm_part));
+    }
+
+    #[test]
+    fn test_coinciding_labels_are_stacked() {
+        let mut anchors = [
+            egui::pos2(10.0, 50.0),
+            egui::pos2(40.0, 50.0),
+            egui::pos2(11.0, 51.0),
+            egui::pos2(10.0, 50.0),
+        ];
+        stack_coincident(&mut anchors, 20.0);
+        assert_eq!(
+            anchors,
+            [
+                egui::pos2(10.0, 50.0),
+                egui::pos2(40.0, 50.0),
+                egui::pos2(11.0, 31.0),
+                egui::pos2(10.0, 10.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_label_occlusion_is_cached() {
+        let label = |z: f32| NameLabel {
+            text: "arm".to_string(),
+            position: Vec3::new(0.0, 0.0, z),
+            owner: None,
+            is_joint: false,
+        };
+        let tests = std::cell::Cell::new(0);
+        let is_occluded = |_: &NameLabel| {
+            tests.set(tests.get() + 1);
+            true
+        };
+        let mut occlusion = LabelOcclusion::default();
+        let view = Mat4::IDENTITY;
+        let moved_view = Mat4::from_translation(Vec3::X);
+
+        assert_eq!(
+            occlusion.update(&[label(0.0)], view, 0.0, is_occluded),
+            [true]
+        );
+        // A still view reuses the result; a change waits for the interval
+        occlusion.update(&[label(0.0)], view, 0.5, is_occluded);
+        occlusion.update(&[label(0.0)], moved_view, 0.05, is_occluded);
+        assert_eq!(tests.get(), 1);
+        occlusion.update(&[label(0.0)], moved_view, 0.2, is_occluded);
+        assert_eq!(tests.get(), 2);
+        // Moved labels and new labels refresh too
+        occlusion.update(&[label(1.0)], moved_view, 0.4, is_occluded);
+        assert_eq!(tests.get(), 3);
+        occlusion.update(&[label(1.0), label(2.0)], moved_view, 0.41, is_occluded);
+        assert_eq!(tests.get(), 5);
+    }
+}
@@ -1,23 +1,42 @@
 //! 3D Viewport panel
 
 mod camera_overlay;
+mod hover;
+mod labels;
+mod navigation;
 
-use glam::Vec3;
-use rk_renderer::{GizmoAxis, GizmoMode, GizmoSpace};
+use glam::{Vec2, Vec3};
+use rk_cad::SketchPlane;
+use rk_core::Pose;
+use rk_renderer::{CameraController, GizmoAxis, GizmoMode, GizmoSpace, RenderLayer};
 
 use crate::config::SharedConfig;
 use crate::panels::Panel;
 use crate::state::{
-    AppAction, GizmoTransform, PickablePartData, SharedAppState, SharedViewportState, pick_object,
+    AppAction, AppState, CadState, GizmoTransform, SKETCH_PICK_RADIUS, SharedAppState,
+    SharedViewportState, SketchAction, SketchTool, ViewportState, apply_collision_rotation,
+    apply_gizmo_transform, sketches_to_render_data,
 };
 
-use camera_overlay::{render_axes_indicator, render_camera_settings, render_gizmo_toggle};
+use camera_overlay::{
+    render_axes_indicator, render_camera_settings, render_gizmo_status, render_gizmo_toggle,
+    render_scale_bar,
+};
+use hover::{HoverPicker, PartTooltip};
+use labels::{LabelOcclusion, collect_name_labels, render_name_labels};
+use navigation::{CameraDrag, camera_drag, double_click_focus, fit_all_pressed};
 
 /// 3D viewport panel
 pub struct ViewportPanel {
     last_size: egui::Vec2,
     hovered_axis: GizmoAxis,
     show_camera_settings: bool,
+    /// Inertial orbit/zoom state
+    camera_controller: CameraController,
+    /// Part under the pointer, for the metadata tooltip
+    hover_picker: HoverPicker,
+    /// Which name labels are hidden behind parts
+    label_occlusion: LabelOcclusion,
 }
 
 impl ViewportPanel {
@@ -26,6 +45,9 @@ impl ViewportPanel {
             last_size: egui::Vec2::ZERO,
             hovered_axis: GizmoAxis::None,
             show_camera_settings: false,
+            camera_controller: CameraController::default(),
+            hover_picker: HoverPicker::default(),
+            label_occlusion: LabelOcclusion::default(),
         }
     }
 }
@@ -75,15 +97,23 @@ impl Panel for ViewportPanel {
         ui.horizontal(|ui| {
             ui.label("View:");
             if ui.button("Top").clicked() {
+                self.camera_controller.stop();
                 viewport_state.lock().renderer.camera_mut().set_top_view();
             }
             if ui.button("Front").clicked() {
+                self.camera_controller.stop();
                 viewport_state.lock().renderer.camera_mut().set_front_view();
             }
             if ui.button("Side").clicked() {
+                self.camera_controller.stop();
                 viewport_state.lock().renderer.camera_mut().set_side_view();
             }
-            if ui.button("Fit All").clicked() {
+            if ui
+                .button("Fit All")
+                .on_hover_text("Frame the whole scene (Home)")
+                .clicked()
+            {
+                self.camera_controller.stop();
                 viewport_state
                     .lock()
                     .renderer
@@ -106,8 +136,125 @@ impl Panel for ViewportPanel {
             if ui.checkbox(&mut show_markers, "Markers").changed() {
                 state.renderer.set_show_markers(show_markers);
             }
+            let mut show_inertial = state.renderer.show_inertial_frames();
+            if ui
+                .checkbox(&mut show_inertial, "Inertia")
+                .on_hover_text("Show each link's inertial frame")
+                .changed()
+            {
+                state.renderer.set_show_inertial_frames(show_inertial);
+            }
+            let mut show_visuals = state.renderer.show_visuals();
+            let mut show_collisions = state.renderer.show_collisions();
+            if ui.checkbox(&mut show_visuals, "Visuals").changed() {
+                state.renderer.set_show_visuals(show_visuals);
+            }
+            if ui.checkbox(&mut show_collisions, "Collisions").changed() {
+                state.renderer.set_show_collisions(show_collisions);
+            }
+            ui.menu_button("Layers", |ui| {
+                let mut layers = state.renderer.visible_layers();
+                for (layer, label) in [
+                    (RenderLayer::Default, "Geometry"),
+                    (RenderLayer::Collision, "Collision"),
+                    (RenderLayer::Annotations, "Annotations"),
+                    (RenderLayer::Debug, "Debug"),
+                ] {
+                    let mut visible = layer.is_visible_in(layers);
+                    if ui.checkbox(&mut visible, label).changed() {
+                        layers ^= layer.mask();
+                        state.renderer.set_visible_layers(layers);
+                    }
+                }
+            });
+            drop(state);
+
+            let mut highlight_chain = app_state.lock().highlight_chain;
+            if ui
+                .checkbox(&mut highlight_chain, "Chain")
+                .on_hover_text("Highlight the path from the selected part to the root")
+                .changed()
+            {
+                app_state.lock().highlight_chain = highlight_chain;
+            }
+            let mut show_name_labels = app_state.lock().show_name_labels;
+            if ui
+                .checkbox(&mut show_name_labels, "Names")
+                .on_hover_text("Show link and joint names in the viewport")
+                .changed()
+            {
+                app_state.lock().show_name_labels = show_name_labels;
+            }
         });
 
+        // Sketch toolbar (only while editing a sketch)
+        let sketch_settings = app_state.lock().cad.editor_mode.sketch().map(|s| {
+            (
+                s.active_sketch,
+                s.current_tool,
+                s.show_grid,
+                s.snap_to_grid,
+                s.grid_spacing,
+            )
+        });
+        if let Some((sketch_id, current_tool, mut show_grid, mut snap, mut spacing)) =
+            sketch_settings
+        {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Tool:");
+                for &tool in SketchTool::ALL {
+                    // Separate select, drawing and constraint tools
+                    if tool == SketchTool::Line || tool == SketchTool::ConstrainCoincident {
+                        ui.separator();
+                    }
+                    if ui
+                        .selectable_label(current_tool == tool, tool.name())
+                        .clicked()
+                        && current_tool != tool
+                    {
+                        app_state
+                            .lock()
+                            .queue_action(AppAction::SketchAction(SketchAction::SetTool { tool }));
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Sketch:");
+                if ui.checkbox(&mut show_grid, "Grid").changed() {
+                    app_state
+                        .lock()
+                        .queue_action(AppAction::SketchAction(SketchAction::ToggleGrid));
+                }
+                if ui.checkbox(&mut snap, "Snap").changed() {
+                    app_state
+                        .lock()
+                        .queue_action(AppAction::SketchAction(SketchAction::ToggleSnap));
+                }
+                ui.label("Spacing:");
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut spacing)
+                            .speed(0.01)
+                            .range(0.001..=100.0),
+                    )
+                    .changed()
+                {
+                    app_state.lock().queue_action(AppAction::SketchAction(
+                        SketchAction::SetGridSpacing { spacing },
+                    ));
+                }
+                if ui
+                    .button("Flip Plane")
+                    .on_hover_text("Reverse the sketch normal (and extrude direction)")
+                    .clicked()
+                {
+                    app_state.lock().queue_action(AppAction::SketchAction(
+                        SketchAction::FlipSketchPlane { sketch_id },
+                    ));
+                }
+            });
+        }
+
         // Main viewport area
         let available_size = ui.available_size();
         let width = available_size.x as u32;
@@ -118,8 +265,10 @@ impl Panel for ViewportPanel {
         }
 
         // Ensure texture and render
+        let sketches = sketches_to_render_data(&app_state.lock().cad);
         let texture_id = {
             let mut state = viewport_state.lock();
+            state.set_sketches(sketches);
             let mut egui_renderer = render_state.renderer.write();
             let tex_id = state.ensure_texture(width, height, &mut egui_renderer);
             state.render();
@@ -139,8 +288,26 @@ impl Panel for ViewportPanel {
         let mouse_pos = response.hover_pos().or(response.interact_pointer_pos());
         let local_mouse = mouse_pos.map(|p| p - response.rect.min);
 
+        // Rotate-gizmo snapping while Ctrl is held
+        let rotation_snap = ui
+            .input(|i| i.modifiers.command)
+            .then(|| app_state.lock().rotation_snap.radians());
+
+        // Space + left drag navigates instead of interacting with the scene
+        let space_held = response.hovered() && ui.input(|i| i.key_down(egui::Key::Space));
+
         // Handle camera input
         let mut vp_state = viewport_state.lock();
+        vp_state.gizmo.rotation_snap = rotation_snap;
+
+        // Sketch hover, and entity picks for the active constraint tool
+        let sketch_clicked = handle_sketch_pointer(
+            app_state,
+            &vp_state,
+            local_mouse,
+            available_size,
+            response.clicked_by(egui::PointerButton::Primary) && !space_held,
+        );
 
         // Gizmo interaction (left mouse button)
         let mut gizmo_delta: Option<GizmoTransform> = None;
@@ -159,6 +326,7 @@ impl Panel for ViewportPanel {
 
             // Start drag on left click
             if response.drag_started_by(egui::PointerButton::Primary)
+                && !space_held
                 && self.hovered_axis != GizmoAxis::None
             {
                 vp_state.start_gizmo_drag(
@@ -184,40 +352,41 @@ impl Panel for ViewportPanel {
             // Object picking on click (only if not interacting with gizmo)
             if response.clicked_by(egui::PointerButton::Primary)
                 && self.hovered_axis == GizmoAxis::None
+                && !sketch_clicked
             {
-                // Gather pickable part data from app_state
-                let pickable_parts: Vec<PickablePartData> = {
-                    let app = app_state.lock();
-                    app.project
-                        .parts()
-                        .values()
-                        .map(|part| PickablePartData {
-                            id: part.id,
-                            vertices: part.vertices.clone(),
-                            indices: part.indices.clone(),
-                            transform: part.origin_transform,
-                            bbox_min: part.bbox_min,
-                            bbox_max: part.bbox_max,
-                        })
-                        .collect()
+                // Joint markers sit on part surfaces, so they take priority
+                let action = if let Some(joint_id) =
+                    vp_state.pick_joint_marker(pos.x, pos.y, available_size.x, available_size.y)
+                {
+                    AppAction::SelectJoint(joint_id)
+                } else {
+                    let hit =
+                        vp_state
+                            .renderer
+                            .raycast(pos.x, pos.y, available_size.x, available_size.y);
+                    AppAction::SelectPart(hit.map(|hit| hit.part_id))
                 };
 
-                // Perform picking
-                let camera = vp_state.renderer.camera();
-                let hit = pick_object(
-                    camera,
-                    pos.x,
-                    pos.y,
-                    available_size.x,
-                    available_size.y,
-                    &pickable_parts,
-                );
-
                 // Queue selection action
-                let selected_id = hit.map(|(id, _)| id);
-                app_state
-                    .lock()
-                    .queue_action(AppAction::SelectPart(selected_id));
+                app_state.lock().queue_action(action);
+            }
+
+            // Double-click frames the part under the cursor
+            let double_clicked = response.double_clicked_by(egui::PointerButton::Primary)
+                && self.hovered_axis == GizmoAxis::None
+                && !sketch_clicked;
+            if double_clicked {
+                let hit = vp_state
+                    .renderer
+                    .raycast(pos.x, pos.y, available_size.x, available_size.y)
+                    .map(|hit| hit.part_id);
+                let mut app = app_state.lock();
+                if let Some(action) =
+                    double_click_focus(double_clicked, app.double_click_focus, hit)
+                {
+                    self.camera_controller.stop();
+                    app.queue_action(action);
+                }
             }
         }
 
@@ -226,6 +395,8 @@ impl Panel for ViewportPanel {
             && let Some((link_id, collision_index)) = vp_state.gizmo.editing_collision
         {
             let link_world_transform = vp_state.gizmo.link_world_transform;
+            let drag_rotation = vp_state.gizmo.collision_drag_rotation;
+            let mut new_drag_rotation = None;
             drop(vp_state);
 
             // Calculate the delta in link-local space
@@ -247,17 +418,17 @@ impl Panel for ViewportPanel {
                     }
                 }
                 GizmoTransform::Rotation(rotation) => {
-                    // For rotation, we need to update the RPY angles
                     if let Some(link) = app.project.assembly.get_link_mut(link_id)
                         && let Some(collision) = link.collisions.get_mut(collision_index)
                     {
-                        // Current rotation as quaternion
-                        let current_quat = collision.origin.to_quat();
-                        // Apply the rotation delta
-                        let new_quat = rotation * current_quat;
-                        // Convert back to euler angles (XYZ order)
-                        let (x, y, z) = new_quat.to_euler(glam::EulerRot::XYZ);
-                        collision.origin.rpy = [x, y, z];
+                        // Accumulate on the drag's quaternion rather than re-reading
+                        // RPY, which drifts and flips near gimbal lock
+                        let current_quat =
+                            drag_rotation.unwrap_or_else(|| collision.origin.to_quat());
+                        let new_quat =
+                            apply_collision_rotation(current_quat, rotation, link_world_transform);
+                        collision.origin = Pose::from_quat(collision.origin.xyz, new_quat);
+                        new_drag_rotation = Some(new_quat);
                     }
                 }
                 GizmoTransform::Scale(_) => {
@@ -270,6 +441,9 @@ impl Panel for ViewportPanel {
 
             // Re-lock viewport state for rest of handling
             vp_state = viewport_state.lock();
+            if new_drag_rotation.is_some() {
+                vp_state.gizmo.collision_drag_rotation = new_drag_rotation;
+            }
         }
         // Apply gizmo transform to part
         else if let Some(transform) = gizmo_delta
@@ -278,142 +452,120 @@ impl Panel for ViewportPanel {
             let queue = vp_state.queue.clone();
             drop(vp_state);
 
-            let mut app = app_state.lock();
+            // Locked parts are left untouched
+            let new_transform = app_state
+                .lock()
+                .get_part_mut(part_id)
+                .and_then(|part| apply_gizmo_transform(part, transform));
 
-            match transform {
-                GizmoTransform::Translation(delta) => {
-                    // Moving the whole part - update part transform
-                    let new_transform = if let Some(part) = app.get_part_mut(part_id) {
-                        let (scale, rotation, translation) =
-                            part.origin_transform.to_scale_rotation_translation();
-                        let new_translation = translation + delta;
-                        part.origin_transform = glam::Mat4::from_scale_rotation_translation(
-                            scale,
-                            rotation,
-                            new_translation,
-                        );
-                        Some(part.origin_transform)
-                    } else {
-                        None
-                    };
-                    drop(app);
-
-                    // Update mesh renderer transform
-                    if let Some(transform) = new_transform {
-                        let mut vp = viewport_state.lock();
-                        vp.renderer
-                            .update_part_transform(&queue, part_id, transform);
-                        drop(vp);
-                    }
-                }
-                GizmoTransform::Rotation(rotation) => {
-                    // Rotating the whole part
-                    let new_transform = if let Some(part) = app.get_part_mut(part_id) {
-                        let (scale, old_rotation, translation) =
-                            part.origin_transform.to_scale_rotation_translation();
-                        let new_rotation = rotation * old_rotation;
-                        part.origin_transform = glam::Mat4::from_scale_rotation_translation(
-                            scale,
-                            new_rotation,
-                            translation,
-                        );
-                        Some(part.origin_transform)
-                    } else {
-                        None
-                    };
-                    drop(app);
-
-                    // Update mesh renderer transform
-                    if let Some(transform) = new_transform {
-                        let mut vp = viewport_state.lock();
-                        vp.renderer
-                            .update_part_transform(&queue, part_id, transform);
-                        drop(vp);
-                    }
-                }
-                GizmoTransform::Scale(scale_delta) => {
-                    // Scaling the whole part
-                    let new_transform = if let Some(part) = app.get_part_mut(part_id) {
-                        let (old_scale, rotation, translation) =
-                            part.origin_transform.to_scale_rotation_translation();
-                        let new_scale = old_scale * scale_delta;
-                        part.origin_transform = glam::Mat4::from_scale_rotation_translation(
-                            new_scale,
-                            rotation,
-                            translation,
-                        );
-                        Some(part.origin_transform)
-                    } else {
-                        None
-                    };
-                    drop(app);
-
-                    // Update mesh renderer transform
-                    if let Some(transform) = new_transform {
-                        let mut vp = viewport_state.lock();
-                        vp.renderer
-                            .update_part_transform(&queue, part_id, transform);
-                        drop(vp);
-                    }
-                }
+            // Update mesh renderer transform
+            if let Some(transform) = new_transform {
+                viewport_state
+                    .lock()
+                    .renderer
+                    .update_part_transform(&queue, part_id, transform);
             }
 
             // Re-lock viewport state for rest of handling
             vp_state = viewport_state.lock();
         }
 
-        // Get camera sensitivity from config
+        // Get camera sensitivity and inertia from config
         let (orbit_sens, pan_sens, zoom_sens) = {
             let cfg = config.read();
             let cam = &cfg.config().renderer.camera;
+            self.camera_controller.inertia = cam.inertia;
+            self.camera_controller.damping = cam.inertia_damping;
             (
                 cam.orbit_sensitivity,
                 cam.pan_sensitivity,
                 cam.zoom_sensitivity,
             )
         };
-
-        // Middle mouse button for orbit/pan (only if not dragging gizmo)
-        if !vp_state.is_dragging_gizmo() && response.dragged_by(egui::PointerButton::Middle) {
-            let delta = response.drag_delta();
-            if ui.input(|i| i.modifiers.shift) {
-                // Pan
-                vp_state
-                    .renderer
-                    .camera_mut()
-                    .pan_with_sensitivity(delta.x, delta.y, pan_sens);
-            } else {
-                // Orbit
-                vp_state
-                    .renderer
-                    .camera_mut()
-                    .orbit(-delta.x * orbit_sens, delta.y * orbit_sens);
+        let dt = ui.input(|i| i.stable_dt);
+
+        // Orbit/pan drags (only if not dragging gizmo)
+        let mut orbit_dragged = false;
+        if !vp_state.is_dragging_gizmo() {
+            let modifiers = ui.input(|i| i.modifiers);
+            for button in [
+                egui::PointerButton::Primary,
+                egui::PointerButton::Middle,
+                egui::PointerButton::Secondary,
+            ] {
+                if !response.dragged_by(button) {
+                    continue;
+                }
+                let delta = response.drag_delta();
+                match camera_drag(button, modifiers, space_held) {
+                    Some(CameraDrag::Pan) => {
+                        self.camera_controller.stop();
+                        vp_state
+                            .renderer
+                            .camera_mut()
+                            .pan_with_sensitivity(delta.x, delta.y, pan_sens);
+                    }
+                    Some(CameraDrag::Orbit) => {
+                        orbit_dragged = true;
+                        self.camera_controller.track_orbit(
+                            vp_state.renderer.camera_mut(),
+                            -delta.x * orbit_sens,
+                            delta.y * orbit_sens,
+                            dt,
+                        );
+                    }
+                    None => {}
+                }
             }
         }
 
-        // Right mouse button for orbit as well
-        if !vp_state.is_dragging_gizmo() && response.dragged_by(egui::PointerButton::Secondary) {
-            let delta = response.drag_delta();
-            vp_state
-                .renderer
-                .camera_mut()
-                .orbit(-delta.x * orbit_sens, delta.y * orbit_sens);
-        }
+        // Part under a resting pointer, for the metadata tooltip
+        let hovered_part = match mouse_pos.zip(local_mouse) {
+            Some((pos, local))
+                if response.hovered()
+                    && !ui.input(|i| i.pointer.any_down())
+                    && self.hovered_axis == GizmoAxis::None =>
+            {
+                let now = ui.input(|i| i.time);
+                let view = vp_state.renderer.camera().view_matrix();
+                self.hover_picker.update(pos, view, now, || {
+                    vp_state
+                        .renderer
+                        .raycast(local.x, local.y, available_size.x, available_size.y)
+                        .map(|hit| hit.part_id)
+                })
+            }
+            _ => {
+                self.hover_picker.clear();
+                None
+            }
+        };
 
         // Zoom with scroll
         if response.hovered() {
             let scroll_delta = ui.input(|i| i.smooth_scroll_delta.y);
             if scroll_delta != 0.0 {
-                vp_state
-                    .renderer
-                    .camera_mut()
-                    .zoom(scroll_delta * zoom_sens);
+                self.camera_controller
+                    .zoom(vp_state.renderer.camera_mut(), scroll_delta * zoom_sens);
             }
         }
 
-        // Gizmo mode keyboard shortcuts
+        // Coast after an orbit drag or scroll when inertia is enabled
+        if !orbit_dragged
+            && self
+                .camera_controller
+                .advance(vp_state.renderer.camera_mut(), dt)
+        {
+            ui.ctx().request_repaint();
+        }
+
+        // Gizmo mode and navigation keyboard shortcuts
         if response.hovered() {
             ui.input(|i| {
+                if fit_all_pressed(i) {
+                    self.camera_controller.stop();
+                    vp_state.renderer.camera_mut().fit_all(Vec3::ZERO, 2.0);
+                }
                 if i.key_pressed(egui::Key::T) {
                     vp_state.renderer.set_gizmo_mode(GizmoMode::Translate);
                 }
@@ -457,25 +609,200 @@ impl Panel for ViewportPanel {
             }
         });
 
+        // Link and joint names
+        let labels = {
+            let state = app_state.lock();
+            state
+                .show_name_labels
+                .then(|| collect_name_labels(&state.project.assembly))
+        };
+        if let Some(labels) = labels {
+            let renderer = &vp_state.renderer;
+            let occluded = self.label_occlusion.update(
+                &labels,
+                renderer.camera().view_matrix(),
+                ui.input(|i| i.time),
+                |label| renderer.is_point_occluded(label.position, label.owner),
+            );
+            render_name_labels(ui, response.rect, renderer, &labels, occluded);
+        }
+
         // Get camera state for axes indicator
         let yaw = vp_state.renderer.camera().yaw;
         let pitch = vp_state.renderer.camera().pitch;
+        let world_per_point = vp_state
+            .renderer
+            .camera()
+            .world_per_pixel(response.rect.height());
+        let rotate_angle = vp_state.rotate_drag_angle();
+        let gizmo_mode = vp_state.renderer.gizmo_mode();
+        let gizmo_space = vp_state.renderer.gizmo_space();
         drop(vp_state);
 
-        // Draw axes indicator overlay
-        render_axes_indicator(ui, response.rect, yaw, pitch);
+        // Rotation readout next to the cursor while rotating
+        if let (Some(angle), Some(pos)) = (rotate_angle, mouse_pos) {
+            let snapped = if rotation_snap.is_some() {
+                " (snap)"
+            } else {
+                ""
+            };
+            ui.painter().text(
+                pos + egui::vec2(16.0, -16.0),
+                egui::Align2::LEFT_BOTTOM,
+                format!("{:.1}\u{00b0}{}", angle.to_degrees(), snapped),
+                egui::FontId::proportional(14.0),
+                egui::Color32::WHITE,
+            );
+        }
+
+        // Metadata tooltip for the hovered part
+        let tooltip = hovered_part.and_then(|part_id| {
+            let state = app_state.lock();
+            let part = state.get_part(part_id)?;
+            Some((PartTooltip::from_part(part), state.length_unit))
+        });
+        if let Some((tooltip, unit)) = tooltip {
+            response
+                .clone()
+                .on_hover_ui_at_pointer(|ui| tooltip.ui(ui, unit));
+        }
 
-        // Draw gizmo mode toggle overlay (top-left)
+        // Draw axes indicator overlay; clicking a tip eases to that view
+        if let Some(view) = render_axes_indicator(ui, response.rect, yaw, pitch) {
+            let (view_yaw, view_pitch) = view.orbit_angles(yaw);
+            let vp_state = viewport_state.lock();
+            self.camera_controller.animate_to_view(
+                vp_state.renderer.camera(),
+                view_yaw,
+                view_pitch,
+            );
+            ui.ctx().request_repaint();
+        }
+
+        // Draw scale bar overlay (bottom-left)
+        render_scale_bar(ui, response.rect, world_per_point);
+
+        // Draw gizmo mode toggle overlay (top-left) with the active mode and space below
         render_gizmo_toggle(ui, response.rect, viewport_state);
+        render_gizmo_status(ui, response.rect, gizmo_mode, gizmo_space);
 
         // Draw camera settings overlay (top-right, Unity-style)
         render_camera_settings(
             ui,
             response.rect,
             viewport_state,
+            config,
             &mut self.show_camera_settings,
         );
 
         self.last_size = available_size;
     }
 }
+
+/// Track the hovered sketch entity and feed clicks to the constraint tools
+///
+/// Returns true if the click was taken by an active constraint tool.
+fn handle_sketch_pointer(
+    app_state: &SharedAppState,
+    vp_state: &ViewportState,
+    mouse: Option<egui::Vec2>,
+    size: egui::Vec2,
+    clicked: bool,
+) -> bool {
+    let tolerance = SKETCH_PICK_RADIUS * vp_state.renderer.camera().world_per_pixel(size.y);
+    sketch_pointer(
+        &mut app_state.lock(),
+        |plane| {
+            mouse.and_then(|pos| vp_state.screen_to_sketch(plane, pos.x, pos.y, size.x, size.y))
+        },
+        tolerance,
+        clicked,
+    )
+}
+
+/// Sketch pointer handling once the pointer is mapped onto the sketch plane
+fn sketch_pointer(
+    app: &mut AppState,
+    to_sketch: impl FnOnce(&SketchPlane) -> Option<Vec2>,
+    tolerance: f32,
+    clicked: bool,
+) -> bool {
+    let CadState {
+        data, editor_mode, ..
+    } = &mut app.cad;
+    let Some(mode) = editor_mode.sketch_mut() else {
+        return false;
+    };
+    let Some(sketch) = data.history.get_sketch(mode.active_sketch) else {
+        return false;
+    };
+
+    mode.update_hover(sketch, to_sketch(&sketch.plane), tolerance);
+
+    if !clicked || !mode.current_tool.is_constraint() {
+        return false;
+    }
+    let action = mode
+        .hovered_entity
+        .and_then(|id| mode.constraint_pick(sketch, id));
+    if let Some(action) = action {
+        app.queue_action(AppAction::SketchAction(action));
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{ActionContext, handle_sketch_action};
+    use crate::state::create_shared_state;
+
+    #[test]
+    fn test_selected_constraint_tool_previews_and_picks_candidates() {
+        let app_state = create_shared_state();
+        let lines = {
+            let mut app = app_state.lock();
+            let sketch_id = app.cad.create_sketch("pointer", SketchPlane::xy());
+            let sketch = app.cad.get_sketch_mut(sketch_id).unwrap();
+            let (_, lines) = sketch.add_rectangle(Vec2::ZERO, Vec2::new(2.0, 1.0));
+            app.cad.enter_sketch_mode(sketch_id);
+            lines
+        };
+
+        // Pick the tool the way the sketch toolbar does
+        let ctx = ActionContext::new(&app_state, &None);
+        handle_sketch_action(
+            AppAction::SketchAction(SketchAction::SetTool {
+                tool: SketchTool::ConstrainParallel,
+            }),
+            &ctx,
+        );
+
+        let mut app = app_state.lock();
+        let hovered = |app: &AppState| app.cad.editor_mode.sketch().unwrap().hovered_entity;
+        let at = |point: Vec2| move |_: &SketchPlane| Some(point);
+
+        // A corner is not a parallel candidate, so it gets no preview
+        assert!(sketch_pointer(&mut app, at(Vec2::ZERO), 0.05, true));
+        assert_eq!(hovered(&app), None);
+
+        // Clicking the bottom then the top edge queues the constraint
+        assert!(sketch_pointer(
+            &mut app,
+            at(Vec2::new(1.0, 0.0)),
+            0.05,
+            true
+        ));
+        assert_eq!(hovered(&app), Some(lines[0]));
+        assert!(sketch_pointer(
+            &mut app,
+            at(Vec2::new(1.0, 1.0)),
+            0.05,
+            true
+        ));
+        assert!(matches!(
+            app.take_pending_actions().as_slice(),
+            [AppAction::SketchAction(SketchAction::AddConstraint { .. })]
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+//! Mapping from pointer and keyboard input to camera navigation
+
+use uuid::Uuid;
+
+use crate::state::AppAction;
+
+/// Camera motion driven by a pointer drag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraDrag {
+    Orbit,
+    Pan,
+}
+
+/// Key that frames the whole scene
+pub const FIT_ALL_KEY: egui::Key = egui::Key::Home;
+
+/// Decide how a drag with `button` moves the camera
+///
+/// - Space + left drag pans
+/// - Shift + middle drag pans, plain middle drag orbits
+/// - Right drag orbits
+pub fn camera_drag(
+    button: egui::PointerButton,
+    modifiers: egui::Modifiers,
+    space_held: bool,
+) -> Option<CameraDrag> {
+    match button {
+        egui::PointerButton::Primary if space_held => Some(CameraDrag::Pan),
+        egui::PointerButton::Middle if modifiers.shift => Some(CameraDrag::Pan),
+        egui::PointerButton::Middle | egui::PointerButton::Secondary => Some(CameraDrag::Orbit),
+        _ => None,
+    }
+}
+
+/// Whether the fit-all shortcut was pressed this frame
+pub fn fit_all_pressed(input: &egui::InputState) -> bool {
+    input.key_pressed(FIT_ALL_KEY) && !input.modifiers.any()
+}
+
+/// Action for a primary double-click on the viewport
+///
+/// Double-clicking a part frames it and orbits around it when `enabled`;
+/// double-clicking empty space does nothing.
+pub fn double_click_focus(
+    double_clicked: bool,
+    enabled: bool,
+    hit: Option<Uuid>,
+) -> Option<AppAction> {
+    (double_clicked && enabled)
+        .then_some(hit)
+        .flatten()
+        .map(AppAction::FocusPart)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{Modifiers, PointerButton};
+
+    #[test]
+    fn test_drag_mapping() {
+        let none = Modifiers::NONE;
+        let shift = Modifiers::SHIFT;
+
+        assert_eq!(camera_drag(PointerButton::Primary, none, false), None);
+        assert_eq!(
+            camera_drag(PointerButton::Primary, none, true),
+            Some(CameraDrag::Pan)
+        );
+        assert_eq!(
+            camera_drag(PointerButton::Middle, shift, false),
+            Some(CameraDrag::Pan)
+        );
+        assert_eq!(
+            camera_drag(PointerButton::Middle, none, false),
+            Some(CameraDrag::Orbit)
+        );
+        assert_eq!(
+            camera_drag(PointerButton::Secondary, none, true),
+            Some(CameraDrag::Orbit)
+        );
+    }
+
+    #[test]
+    fn test_fit_all_key() {
+        let press = |modifiers| {
+            let ctx = egui::Context::default();
+            let mut raw = egui::RawInput::default();
+            raw.events.push(egui::Event::Key {
+                key: FIT_ALL_KEY,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers,
+            });
+            raw.modifiers = modifiers;
+            let mut pressed = false;
+            let _ = ctx.run(raw, |ctx| pressed = ctx.input(fit_all_pressed));
+            pressed
+        };
+
+        assert!(press(egui::Modifiers::NONE));
+        assert!(!press(egui::Modifiers::CTRL));
+    }
+
+    #[test]
+    fn test_double_click_on_part_focuses_it() {
+        // Two quick clicks on a sensed area register as a double-click
+        let ctx = egui::Context::default();
+        let pos = egui::pos2(50.0, 50.0);
+        let mut double_clicked = false;
+        for (time, pressed) in [(0.0, true), (0.05, false), (0.1, true), (0.15, false)] {
+            let mut raw = egui::RawInput {
+                time: Some(time),
+                ..Default::default()
+            };
+            raw.events.push(egui::Event::PointerMoved(pos));
+            raw.events.push(egui::Event::PointerButton {
+                pos,
+                button: PointerButton::Primary,
+                pressed,
+                modifiers: Modifiers::NONE,
+            });
+            let _ = ctx.run(raw, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    let response = ui.allocate_response(ui.available_size(), egui::Sense::click());
+                    double_clicked |= response.double_clicked();
+                });
+            });
+        }
+        assert!(double_clicked);
+
+        let part_id = Uuid::new_v4();
+        assert!(matches!(
+            double_click_focus(double_clicked, true, Some(part_id)),
+            Some(AppAction::FocusPart(id)) if id == part_id
+        ));
+        assert!(double_click_focus(double_clicked, true, None).is_none());
+        assert!(double_click_focus(double_clicked, false, Some(part_id)).is_none());
+        assert!(double_click_focus(false, true, Some(part_id)).is_none());
+    }
+}
@@ -2,17 +2,22 @@
 
 mod editor;
 mod sketch_mode;
+mod sketch_render;
 mod viewport;
 
 pub use editor::{EditorTool, PrimitiveType};
 pub use sketch_mode::{
-    CadState, EditorMode, InProgressEntity, SketchAction, SketchModeState, SketchTool,
+    CadState, EditorMode, HEAVY_SOLVE_INTERVAL, InProgressEntity, SketchAction, SketchModeState,
+    SketchTool, pick_sketch_entity,
 };
+pub use sketch_render::{sketch_to_render_data, sketches_to_render_data};
 pub use viewport::{
-    GizmoInteraction, GizmoTransform, PickablePartData, SharedViewportState, ViewportState,
-    pick_object,
+    GizmoInteraction, GizmoTransform, RotationSnapIncrement, SKETCH_PICK_RADIUS,
+    SharedViewportState, ViewportState, apply_collision_rotation, apply_gizmo_transform,
+    snap_angle,
 };
 
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -20,7 +25,12 @@ use glam::Mat4;
 use parking_lot::Mutex;
 use uuid::Uuid;
 
-use rk_core::{GeometryType, JointLimits, JointType, Part, Pose, Project, StlUnit};
+use crate::config::JointDefaultsConfig;
+
+use rk_core::{
+    GeometryType, ImportOptions, JointLimits, JointType, Part, Pose, Project, StlFormat, StlUnit,
+    TargetConvention, ViewportSettings,
+};
 
 /// Actions that can be performed on the app state
 #[derive(Debug, Clone)]
@@ -36,6 +46,18 @@ pub enum AppAction {
     LoadProject(PathBuf),
     /// Export URDF with path and robot name
     ExportUrdf { path: PathBuf, robot_name: String },
+    /// Export the selected link and its descendants as a standalone URDF file
+    ExportUrdfSubtree { path: PathBuf, robot_name: String },
+    /// Save current joint positions keyed by joint name (JSON)
+    SaveJointState(PathBuf),
+    /// Load joint positions keyed by joint name (JSON)
+    LoadJointState(PathBuf),
+    /// Export a bill of materials of unique parts as CSV
+    ExportBom(PathBuf),
+    /// Export part outlines at the current camera as an SVG line drawing
+    ExportViewSvg(PathBuf),
+    /// Export every part as an STL file in a directory
+    ExportAllPartsStl(PathBuf),
     /// New project
     NewProject,
 
@@ -51,14 +73,29 @@ pub enum AppAction {
         primitive_type: PrimitiveType,
         name: Option<String>,
     },
+    /// Re-tessellate a round primitive part
+    SetPrimitiveSegments { part_id: Uuid, segments: u32 },
     /// Create an empty part (no geometry)
     CreateEmpty { name: Option<String> },
     /// Select a part
     SelectPart(Option<Uuid>),
+    /// Frame a part and orbit around its center
+    FocusPart(Uuid),
+    /// Select a joint for editing (selects its parent part)
+    SelectJoint(Uuid),
     /// Delete selected part
     DeleteSelectedPart,
     /// Update part transform
     UpdatePartTransform { part_id: Uuid, transform: Mat4 },
+    /// Lock or unlock a part against gizmo edits
+    SetPartLocked { part_id: Uuid, locked: bool },
+    /// Move a part so its joint point mates with a joint point on another part
+    MatePart {
+        part_id: Uuid,
+        point_id: Uuid,
+        target_part_id: Uuid,
+        target_point_id: Uuid,
+    },
 
     // Assembly actions
     /// Connect two parts
@@ -89,6 +126,14 @@ pub enum AppAction {
         joint_id: Uuid,
         limits: Option<JointLimits>,
     },
+    /// Set revolute limits to where the child subtree first collides
+    FindJointLimits { joint_id: Uuid },
+    /// Update joint damping and friction (both zero removes the dynamics)
+    UpdateJointDynamics {
+        joint_id: Uuid,
+        damping: f32,
+        friction: f32,
+    },
 
     // Collision actions
     /// Select a collision element (link_id, collision_index)
@@ -113,6 +158,16 @@ pub enum AppAction {
         geometry: GeometryType,
     },
 
+    // Appearance actions
+    /// Color parts by the depth of their link in the tree (display only)
+    ColorizeByDepth,
+    /// Give every link a distinct color (display only)
+    ColorizeDistinct,
+    /// Restore the colors shown before colorizing
+    RestorePartColors,
+    /// Set the color of a part and of all parts below its link
+    ApplyColorToSubtree { part_id: Uuid, color: [f32; 4] },
+
     // Sketch/CAD actions
     /// Execute a sketch action
     SketchAction(SketchAction),
@@ -126,6 +181,16 @@ pub enum AngleDisplayMode {
     Radians,
 }
 
+/// How orientations are shown in the property editors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RotationDisplayMode {
+    /// Roll/pitch/yaw in degrees
+    #[default]
+    Euler,
+    /// Normalized quaternion (x, y, z, w)
+    Quaternion,
+}
+
 /// Application state
 pub struct AppState {
     /// Current project (contains parts, assembly, materials)
@@ -136,6 +201,8 @@ pub struct AppState {
     pub selected_part: Option<Uuid>,
     /// Currently selected collision element (link_id, collision_index)
     pub selected_collision: Option<(Uuid, usize)>,
+    /// Joint opened for editing in the parent part's properties
+    pub editing_joint_id: Option<Uuid>,
     /// Hovered part
     pub hovered_part: Option<Uuid>,
     /// Current editor tool
@@ -152,10 +219,50 @@ pub struct AppState {
     pub show_part_axes: bool,
     /// Show joint markers
     pub show_joint_markers: bool,
+    /// Highlight the kinematic chain from the selected part to the root
+    pub highlight_chain: bool,
+    /// Float link and joint names over the viewport
+    pub show_name_labels: bool,
     /// Global unit setting for STL import and other operations
     pub stl_import_unit: StlUnit,
+    /// Reverse triangle winding of imported meshes
+    pub flip_normals_on_import: bool,
+    /// Recompute normals of imported meshes
+    pub recompute_normals_on_import: bool,
+    /// Double-clicking a part frames it and orbits around it
+    pub double_click_focus: bool,
+    /// Segments around newly created round primitives
+    pub primitive_segments: u32,
+    /// Reason the last URDF export failed, shown until dismissed
+    pub export_error: Option<String>,
+    /// Unit for "Export All Parts as STL"
+    pub stl_export_unit: StlUnit,
+    /// Encoding for "Export All Parts as STL"
+    pub stl_export_format: StlFormat,
+    /// Axis convention for "Export All Parts as STL"
+    pub stl_export_convention: TargetConvention,
     /// Angle display mode for joint sliders
     pub angle_display_mode: AngleDisplayMode,
+    /// Orientation display mode for pose editors
+    pub rotation_display_mode: RotationDisplayMode,
+    /// Unit for displaying and entering lengths
+    pub length_unit: StlUnit,
+    /// Increment used when snapping rotate-gizmo drags
+    pub rotation_snap: RotationSnapIncrement,
+    /// Defaults used when creating new joints
+    pub joint_defaults: JointDefaultsConfig,
+    /// Viewport applied to projects without saved viewport settings
+    pub default_viewport: ViewportSettings,
+    /// Colors replaced by a colorize action (part_id -> original color)
+    pub original_part_colors: HashMap<Uuid, [f32; 4]>,
+    /// Parts whose visual mesh is hidden individually
+    pub hidden_visuals: HashSet<Uuid>,
+    /// Parts whose link collision shapes are hidden individually
+    pub hidden_collisions: HashSet<Uuid>,
+    /// Parts that do not cast shadows
+    pub non_casting_shadows: HashSet<Uuid>,
+    /// Parts that do not receive shadows
+    pub non_receiving_shadows: HashSet<Uuid>,
 }
 
 impl Default for AppState {
@@ -165,6 +272,7 @@ impl Default for AppState {
             cad: CadState::default(),
             selected_part: None,
             selected_collision: None,
+            editing_joint_id: None,
             hovered_part: None,
             current_tool: EditorTool::default(),
             symmetry_mode: false,
@@ -173,8 +281,28 @@ impl Default for AppState {
             pending_actions: Vec::new(),
             show_part_axes: true,
             show_joint_markers: true,
+            highlight_chain: true,
+            show_name_labels: false,
             stl_import_unit: StlUnit::Millimeters,
+            flip_normals_on_import: false,
+            recompute_normals_on_import: false,
+            double_click_focus: true,
+            primitive_segments: rk_core::CYLINDER_SEGMENTS,
+            export_error: None,
+            stl_export_unit: StlUnit::Meters,
+            stl_export_convention: TargetConvention::NATIVE,
+            stl_export_format: StlFormat::Binary,
             angle_display_mode: AngleDisplayMode::default(),
+            rotation_display_mode: RotationDisplayMode::default(),
+            length_unit: StlUnit::Meters,
+            rotation_snap: RotationSnapIncrement::default(),
+            joint_defaults: JointDefaultsConfig::default(),
+            default_viewport: ViewportSettings::default(),
+            original_part_colors: HashMap::new(),
+            hidden_visuals: HashSet::new(),
+            hidden_collisions: HashSet::new(),
+            non_casting_shadows: HashSet::new(),
+            non_receiving_shadows: HashSet::new(),
         }
     }
 }
@@ -219,6 +347,16 @@ impl AppState {
         Self::default()
     }
 
+    /// Import options reflecting the current import preferences
+    pub fn import_options(&self) -> ImportOptions {
+        ImportOptions {
+            stl_unit: self.stl_import_unit,
+            flip_normals: self.flip_normals_on_import,
+            recompute_normals: self.recompute_normals_on_import,
+            ..ImportOptions::default()
+        }
+    }
+
     /// Add a part (delegates to project)
     pub fn add_part(&mut self, part: Part) {
         self.project.add_part(part);
@@ -245,6 +383,52 @@ impl AppState {
     /// Select a part
     pub fn select_part(&mut self, id: Option<Uuid>) {
         self.selected_part = id;
+        self.editing_joint_id = None;
+    }
+
+    /// Select a joint for editing, selecting the part of its parent link
+    ///
+    /// Returns the selected part, or None if the joint's parent has no part.
+    pub fn select_joint(&mut self, joint_id: Uuid) -> Option<Uuid> {
+        let assembly = &self.project.assembly;
+        let part_id = assembly
+            .get_joint(joint_id)
+            .and_then(|joint| assembly.get_link(joint.parent_link))
+            .and_then(|link| link.part_id)?;
+        self.select_part(Some(part_id));
+        self.editing_joint_id = Some(joint_id);
+        Some(part_id)
+    }
+
+    /// Get the ancestor parts of the selected part, ordered parent → … → root
+    ///
+    /// Empty when nothing is selected or chain highlighting is disabled.
+    pub fn selected_chain_parts(&self) -> Vec<Uuid> {
+        let assembly = &self.project.assembly;
+        self.selected_chain_links()
+            .iter()
+            .skip(1)
+            .filter_map(|link_id| assembly.get_link(*link_id).and_then(|l| l.part_id))
+            .collect()
+    }
+
+    /// Get the joints connecting the selected part to the root
+    pub fn selected_chain_joints(&self) -> Vec<Uuid> {
+        let assembly = &self.project.assembly;
+        self.selected_chain_links()
+            .first()
+            .map(|link_id| assembly.joint_path_to_root(*link_id))
+            .unwrap_or_default()
+    }
+
+    fn selected_chain_links(&self) -> Vec<Uuid> {
+        if !self.highlight_chain {
+            return Vec::new();
+        }
+        self.selected_part
+            .and_then(|part_id| self.project.assembly.find_link_by_part(part_id))
+            .map(|link| self.project.assembly.path_to_root(link.id))
+            .unwrap_or_default()
     }
 
     /// Queue an action
@@ -264,6 +448,11 @@ impl AppState {
         self.selected_part = None;
         self.selected_collision = None;
         self.project_path = None;
+        self.original_part_colors.clear();
+        self.hidden_visuals.clear();
+        self.hidden_collisions.clear();
+        self.non_casting_shadows.clear();
+        self.non_receiving_shadows.clear();
         self.modified = false;
     }
 
@@ -274,6 +463,11 @@ impl AppState {
         self.project_path = Some(path);
         self.selected_part = None;
         self.selected_collision = None;
+        self.original_part_colors.clear();
+        self.hidden_visuals.clear();
+        self.hidden_collisions.clear();
+        self.non_casting_shadows.clear();
+        self.non_receiving_shadows.clear();
         self.modified = false;
     }
 }
@@ -1,9 +1,12 @@
 //! Sketch mode state types
 
+use std::collections::HashSet;
+use std::f32::consts::TAU;
+
 use glam::Vec2;
 use uuid::Uuid;
 
-use rk_cad::{CadData, Sketch, SketchConstraint, SketchEntity, SketchPlane};
+use rk_cad::{CadData, Sketch, SketchConstraint, SketchEntity, SketchPlane, SolveResult};
 
 /// Tool for sketch editing
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -29,6 +32,8 @@ pub enum SketchTool {
     ConstrainParallel,
     /// Add perpendicular constraint
     ConstrainPerpendicular,
+    /// Fix a point in place (click again to unfix)
+    ConstrainFixed,
     /// Add distance dimension
     DimensionDistance,
     /// Add angle dimension
@@ -38,6 +43,24 @@ pub enum SketchTool {
 }
 
 impl SketchTool {
+    /// Every tool, in toolbar order
+    pub const ALL: &'static [SketchTool] = &[
+        SketchTool::Select,
+        SketchTool::Line,
+        SketchTool::Circle,
+        SketchTool::Arc,
+        SketchTool::Rectangle,
+        SketchTool::ConstrainCoincident,
+        SketchTool::ConstrainHorizontal,
+        SketchTool::ConstrainVertical,
+        SketchTool::ConstrainParallel,
+        SketchTool::ConstrainPerpendicular,
+        SketchTool::ConstrainFixed,
+        SketchTool::DimensionDistance,
+        SketchTool::DimensionAngle,
+        SketchTool::DimensionRadius,
+    ];
+
     /// Get the display name of the tool
     pub fn name(&self) -> &'static str {
         match self {
@@ -51,6 +74,7 @@ impl SketchTool {
             SketchTool::ConstrainVertical => "Vertical",
             SketchTool::ConstrainParallel => "Parallel",
             SketchTool::ConstrainPerpendicular => "Perpendicular",
+            SketchTool::ConstrainFixed => "Fixed",
             SketchTool::DimensionDistance => "Distance",
             SketchTool::DimensionAngle => "Angle",
             SketchTool::DimensionRadius => "Radius",
@@ -74,11 +98,49 @@ impl SketchTool {
                 | SketchTool::ConstrainVertical
                 | SketchTool::ConstrainParallel
                 | SketchTool::ConstrainPerpendicular
+                | SketchTool::ConstrainFixed
                 | SketchTool::DimensionDistance
                 | SketchTool::DimensionAngle
                 | SketchTool::DimensionRadius
         )
     }
+
+    /// Number of entities a constraint tool picks (0 for other tools)
+    pub fn constraint_arity(&self) -> usize {
+        match self {
+            SketchTool::ConstrainHorizontal
+            | SketchTool::ConstrainVertical
+            | SketchTool::ConstrainFixed
+            | SketchTool::DimensionRadius => 1,
+            SketchTool::ConstrainCoincident
+            | SketchTool::ConstrainParallel
+            | SketchTool::ConstrainPerpendicular
+            | SketchTool::DimensionDistance
+            | SketchTool::DimensionAngle => 2,
+            _ => 0,
+        }
+    }
+
+    /// Check whether the tool accepts this kind of entity
+    fn accepts(&self, entity: &SketchEntity) -> bool {
+        match self {
+            SketchTool::ConstrainCoincident
+            | SketchTool::ConstrainFixed
+            | SketchTool::DimensionDistance => entity.is_point(),
+            SketchTool::ConstrainHorizontal
+            | SketchTool::ConstrainVertical
+            | SketchTool::ConstrainParallel
+            | SketchTool::ConstrainPerpendicular
+            | SketchTool::DimensionAngle => matches!(entity, SketchEntity::Line { .. }),
+            SketchTool::DimensionRadius => {
+                matches!(
+                    entity,
+                    SketchEntity::Circle { .. } | SketchEntity::Arc { .. }
+                )
+            }
+            _ => false,
+        }
+    }
 }
 
 /// Entity being drawn (in progress)
@@ -124,6 +186,8 @@ pub struct SketchModeState {
     pub snap_to_grid: bool,
     /// Grid spacing for snapping
     pub grid_spacing: f32,
+    /// Draw the sketch grid at the snap spacing
+    pub show_grid: bool,
 }
 
 impl Default for SketchModeState {
@@ -136,6 +200,7 @@ impl Default for SketchModeState {
             hovered_entity: None,
             snap_to_grid: true,
             grid_spacing: 1.0,
+            show_grid: true,
         }
     }
 }
@@ -180,6 +245,114 @@ impl SketchModeState {
         self.in_progress = None;
     }
 
+    /// Translate a click on an entity into a constraint action for the current tool
+    ///
+    /// Handles the single-entity constraint tools; returns `None` if the tool
+    /// does not apply to the clicked entity.
+    pub fn constraint_click(&self, sketch: &Sketch, entity_id: Uuid) -> Option<SketchAction> {
+        let entity = sketch.get_entity(entity_id)?;
+        match (self.current_tool, entity) {
+            // Clicking an already-fixed point unfixes it
+            (SketchTool::ConstrainFixed, SketchEntity::Point { .. }) => {
+                Some(SketchAction::ToggleFixed { point: entity_id })
+            }
+            (SketchTool::ConstrainHorizontal, SketchEntity::Line { .. }) => {
+                Some(SketchAction::AddConstraint {
+                    constraint: SketchConstraint::horizontal(entity_id),
+                })
+            }
+            (SketchTool::ConstrainVertical, SketchEntity::Line { .. }) => {
+                Some(SketchAction::AddConstraint {
+                    constraint: SketchConstraint::vertical(entity_id),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Check whether clicking an entity would continue the current constraint
+    ///
+    /// Once the first entity of a two-entity constraint is picked, only
+    /// entities that can complete it (of the right kind and not already
+    /// picked) remain candidates.
+    pub fn is_constraint_candidate(&self, sketch: &Sketch, entity_id: Uuid) -> bool {
+        let tool = self.current_tool;
+        if self.selected_entities.len() >= tool.constraint_arity()
+            || self.selected_entities.contains(&entity_id)
+        {
+            return false;
+        }
+        sketch
+            .get_entity(entity_id)
+            .is_some_and(|entity| tool.accepts(entity))
+    }
+
+    /// Update the hovered entity from the cursor position in sketch space
+    ///
+    /// With a constraint tool active only valid candidates are hovered, so
+    /// the highlight previews what a click would pick.
+    pub fn update_hover(&mut self, sketch: &Sketch, point: Option<Vec2>, tolerance: f32) {
+        self.hovered_entity = point
+            .and_then(|p| pick_sketch_entity(sketch, p, tolerance))
+            .filter(|&id| {
+                !self.current_tool.is_constraint() || self.is_constraint_candidate(sketch, id)
+            });
+    }
+
+    /// Pick an entity with the current constraint tool
+    ///
+    /// Single-entity constraints are returned right away. The first entity of
+    /// a two-entity constraint is added to the selection and the constraint
+    /// is returned once the second one is picked. Dimensions take the
+    /// current measurement as their value.
+    pub fn constraint_pick(&mut self, sketch: &Sketch, entity_id: Uuid) -> Option<SketchAction> {
+        if !self.is_constraint_candidate(sketch, entity_id) {
+            return None;
+        }
+        let tool = self.current_tool;
+        if tool.constraint_arity() == 1 {
+            if tool == SketchTool::DimensionRadius {
+                let radius = match sketch.get_entity(entity_id)? {
+                    SketchEntity::Circle { radius, .. } | SketchEntity::Arc { radius, .. } => {
+                        *radius
+                    }
+                    _ => return None,
+                };
+                return Some(SketchAction::AddConstraint {
+                    constraint: SketchConstraint::radius(entity_id, radius),
+                });
+            }
+            return self.constraint_click(sketch, entity_id);
+        }
+
+        let Some(&first) = self.selected_entities.first() else {
+            self.select_entity(entity_id);
+            return None;
+        };
+        self.clear_selection();
+        let position = |id| sketch.get_entity(id).and_then(|e| e.position());
+        let direction = |id| match sketch.get_entity(id)? {
+            SketchEntity::Line { start, end, .. } => Some(position(*end)? - position(*start)?),
+            _ => None,
+        };
+        let constraint = match tool {
+            SketchTool::ConstrainCoincident => SketchConstraint::coincident(first, entity_id),
+            SketchTool::ConstrainParallel => SketchConstraint::parallel(first, entity_id),
+            SketchTool::ConstrainPerpendicular => SketchConstraint::perpendicular(first, entity_id),
+            SketchTool::DimensionDistance => SketchConstraint::distance(
+                first,
+                entity_id,
+                position(first)?.distance(position(entity_id)?),
+            ),
+            SketchTool::DimensionAngle => {
+                let (d1, d2) = (direction(first)?, direction(entity_id)?);
+                SketchConstraint::angle(first, entity_id, d1.to_angle() - d2.to_angle())
+            }
+            _ => return None,
+        };
+        Some(SketchAction::AddConstraint { constraint })
+    }
+
     /// Snap a point to grid if enabled
     pub fn snap_point(&self, point: Vec2) -> Vec2 {
         if self.snap_to_grid {
@@ -193,6 +366,59 @@ impl SketchModeState {
     }
 }
 
+/// Find the entity under a point in sketch space
+///
+/// Points within `tolerance` win over curves so that endpoints stay
+/// pickable where curves meet; otherwise the nearest curve within
+/// `tolerance` is returned.
+pub fn pick_sketch_entity(sketch: &Sketch, point: Vec2, tolerance: f32) -> Option<Uuid> {
+    let position = |id| sketch.get_entity(id).and_then(|e| e.position());
+    let distance = |entity: &SketchEntity| -> Option<f32> {
+        match entity {
+            SketchEntity::Point { position, .. } => Some(position.distance(point)),
+            SketchEntity::Line { start, end, .. } => {
+                let (a, b) = (position(*start)?, position(*end)?);
+                let ab = b - a;
+                let t =
+                    ((point - a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+                Some((a + ab * t).distance(point))
+            }
+            SketchEntity::Circle { center, radius, .. } => {
+                Some((position(*center)?.distance(point) - radius).abs())
+            }
+            SketchEntity::Arc {
+                center,
+                start,
+                end,
+                radius,
+                direction,
+                ..
+            } => {
+                let (c, a, b) = (position(*center)?, position(*start)?, position(*end)?);
+                let (start_angle, sweep) = direction.sweep_angles(c, a, b);
+                let offset = ((point - c).to_angle() - start_angle) * sweep.signum();
+                if offset.rem_euclid(TAU) <= sweep.abs() {
+                    Some((c.distance(point) - radius).abs())
+                } else {
+                    Some(a.distance(point).min(b.distance(point)))
+                }
+            }
+            SketchEntity::Ellipse { .. } | SketchEntity::Spline { .. } => None,
+        }
+    };
+
+    let nearest = |points: bool| {
+        sketch
+            .entities_iter()
+            .filter(|e| e.is_point() == points)
+            .filter_map(|e| Some((e.id(), distance(e)?)))
+            .filter(|(_, d)| *d <= tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    };
+    nearest(true).or_else(|| nearest(false))
+}
+
 /// Editor mode (3D assembly or 2D sketch)
 #[derive(Debug, Clone, Default)]
 pub enum EditorMode {
@@ -245,14 +471,35 @@ pub enum SketchAction {
     AddConstraint { constraint: SketchConstraint },
     /// Delete a constraint
     DeleteConstraint { constraint_id: Uuid },
+    /// Fix a point at its current position, or unfix it if already fixed
+    ToggleFixed { point: Uuid },
     /// Solve the sketch
     SolveSketch,
     /// Toggle grid snapping
     ToggleSnap,
     /// Set grid spacing
     SetGridSpacing { spacing: f32 },
+    /// Toggle sketch grid visibility
+    ToggleGrid,
+    /// Reverse a sketch plane's normal and rebuild the model
+    FlipSketchPlane { sketch_id: Uuid },
+    /// Suppress or unsuppress a feature and rebuild the model
+    SetFeatureSuppressed { feature_id: Uuid, suppressed: bool },
+    /// Make a sketch feature create one body per profile (or one merged body)
+    /// and rebuild the model
+    SetKeepBodiesSeparate {
+        feature_id: Uuid,
+        keep_separate: bool,
+    },
 }
 
+/// Sketches with more entities plus constraints than this are re-solved at most
+/// once per [`HEAVY_SOLVE_INTERVAL`]
+pub const HEAVY_SKETCH_SIZE: usize = 200;
+
+/// Minimum time between re-solves of a heavy sketch, in seconds
+pub const HEAVY_SOLVE_INTERVAL: f64 = 0.1;
+
 /// Extended CAD state for the application
 #[derive(Debug, Clone, Default)]
 pub struct CadState {
@@ -260,6 +507,10 @@ pub struct CadState {
     pub data: CadData,
     /// Current editor mode
     pub editor_mode: EditorMode,
+    /// Sketches whose constraints changed since they were last solved
+    pending_solves: HashSet<Uuid>,
+    /// Time of the last heavy-sketch solve (seconds, UI clock)
+    last_heavy_solve: Option<f64>,
 }
 
 impl CadState {
@@ -306,4 +557,164 @@ impl CadState {
     pub fn is_sketch_mode(&self) -> bool {
         self.editor_mode.is_sketch()
     }
+
+    /// Queue a re-solve of a sketch; repeated requests before the next
+    /// [`solve_pending`](Self::solve_pending) coalesce into one solve
+    pub fn request_solve(&mut self, sketch_id: Uuid) {
+        self.pending_solves.insert(sketch_id);
+    }
+
+    /// Whether a sketch is waiting to be re-solved
+    pub fn is_solve_pending(&self, sketch_id: Uuid) -> bool {
+        self.pending_solves.contains(&sketch_id)
+    }
+
+    /// Whether any sketch is waiting to be re-solved
+    pub fn has_pending_solves(&self) -> bool {
+        !self.pending_solves.is_empty()
+    }
+
+    /// Solve each sketch with queued changes once; call once per frame
+    ///
+    /// Heavy sketches (see [`HEAVY_SKETCH_SIZE`]) stay queued until
+    /// [`HEAVY_SOLVE_INTERVAL`] has passed since the last heavy solve, so
+    /// a burst of edits does not stall every frame. `now` is the UI clock in seconds.
+    pub fn solve_pending(&mut self, now: f64) -> Vec<(Uuid, SolveResult)> {
+        let heavy_ready = self
+            .last_heavy_solve
+            .is_none_or(|last| now - last >= HEAVY_SOLVE_INTERVAL);
+
+        let mut results = Vec::new();
+        for sketch_id in std::mem::take(&mut self.pending_solves) {
+            let Some(sketch) = self.data.history.get_sketch_mut(sketch_id) else {
+                continue;
+            };
+            let heavy = sketch.entities().len() + sketch.constraints().len() > HEAVY_SKETCH_SIZE;
+            if heavy && !heavy_ready {
+                self.pending_solves.insert(sketch_id);
+                continue;
+            }
+            if heavy {
+                self.last_heavy_solve = Some(now);
+            }
+            results.push((sketch_id, sketch.solve()));
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_prefers_points_over_curves() {
+        let mut sketch = Sketch::new("pick", SketchPlane::xy());
+        let (points, lines) = sketch.add_rectangle(Vec2::ZERO, Vec2::new(2.0, 1.0));
+
+        // Near a corner the endpoint wins over both edges
+        let corner = pick_sketch_entity(&sketch, Vec2::new(0.02, 0.01), 0.05);
+        assert!(corner.is_some_and(|id| points.contains(&id)));
+
+        let edge = pick_sketch_entity(&sketch, Vec2::new(1.0, 0.03), 0.05).unwrap();
+        assert!(lines.contains(&edge));
+        assert!(pick_sketch_entity(&sketch, Vec2::new(1.0, 0.5), 0.05).is_none());
+    }
+
+    #[test]
+    fn test_candidates_filtered_mid_selection() {
+        let mut sketch = Sketch::new("candidates", SketchPlane::xy());
+        let (points, lines) = sketch.add_rectangle(Vec2::ZERO, Vec2::new(2.0, 1.0));
+        let center = sketch.add_point(Vec2::new(5.0, 0.0));
+        let circle = sketch.add_circle(center, 1.0);
+
+        let mut mode = SketchModeState::new(sketch.id);
+        mode.current_tool = SketchTool::ConstrainParallel;
+        assert!(mode.is_constraint_candidate(&sketch, lines[0]));
+        assert!(!mode.is_constraint_candidate(&sketch, points[0]));
+        assert!(!mode.is_constraint_candidate(&sketch, circle));
+
+        // First pick: the picked line is no longer a candidate, other lines are
+        assert!(mode.constraint_pick(&sketch, lines[0]).is_none());
+        assert_eq!(mode.selected_entities, vec![lines[0]]);
+        assert!(!mode.is_constraint_candidate(&sketch, lines[0]));
+        assert!(mode.is_constraint_candidate(&sketch, lines[2]));
+        assert!(!mode.is_constraint_candidate(&sketch, points[1]));
+
+        // Hovering shows only valid candidates
+        mode.update_hover(&sketch, Some(Vec2::new(1.0, 0.01)), 0.05);
+        assert_eq!(mode.hovered_entity, None);
+        mode.update_hover(&sketch, Some(Vec2::new(1.0, 0.99)), 0.05);
+        assert_eq!(mode.hovered_entity, Some(lines[2]));
+
+        // Second pick completes the constraint and resets the selection
+        let action = mode.constraint_pick(&sketch, lines[2]);
+        assert!(matches!(
+            action,
+            Some(SketchAction::AddConstraint {
+                constraint: SketchConstraint::Parallel { line1, line2, .. }
+            }) if line1 == lines[0] && line2 == lines[2]
+        ));
+        assert!(mode.selected_entities.is_empty());
+    }
+
+    #[test]
+    fn test_rapid_constraint_edits_coalesce_into_one_solve() {
+        use crate::actions::{ActionContext, handle_sketch_action};
+        use crate::state::{AppAction, create_shared_state};
+
+        let app_state = create_shared_state();
+        let (sketch_id, lines) = {
+            let mut app = app_state.lock();
+            let sketch_id = app.cad.create_sketch("coalesce", SketchPlane::xy());
+            let sketch = app.cad.get_sketch_mut(sketch_id).unwrap();
+            let (_, lines) = sketch.add_rectangle(Vec2::ZERO, Vec2::new(2.0, 1.0));
+            app.cad.enter_sketch_mode(sketch_id);
+            (sketch_id, lines)
+        };
+
+        // Several constraints added within one frame only queue the sketch
+        let ctx = ActionContext::new(&app_state, &None);
+        for line in &lines {
+            handle_sketch_action(
+                AppAction::SketchAction(SketchAction::AddConstraint {
+                    constraint: SketchConstraint::horizontal(*line),
+                }),
+                &ctx,
+            );
+        }
+        let mut app = app_state.lock();
+        let cad = &mut app.cad;
+        assert_eq!(cad.get_sketch(sketch_id).unwrap().constraints().len(), 4);
+        assert!(cad.is_solve_pending(sketch_id));
+        assert!(!cad.get_sketch(sketch_id).unwrap().is_solved());
+
+        let results = cad.solve_pending(0.0);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, sketch_id);
+        assert!(!cad.has_pending_solves());
+        assert!(cad.solve_pending(0.0).is_empty());
+    }
+
+    #[test]
+    fn test_heavy_sketch_solves_are_throttled() {
+        let mut cad = CadState::new();
+        let sketch_id = cad.create_sketch("heavy", SketchPlane::xy());
+        let sketch = cad.get_sketch_mut(sketch_id).unwrap();
+        for i in 0..=HEAVY_SKETCH_SIZE {
+            sketch.add_point(Vec2::new(i as f32, 0.0));
+        }
+
+        cad.request_solve(sketch_id);
+        assert_eq!(cad.solve_pending(1.0).len(), 1);
+
+        // Edits right after a heavy solve wait for the interval
+        cad.request_solve(sketch_id);
+        assert!(
+            cad.solve_pending(1.0 + HEAVY_SOLVE_INTERVAL / 2.0)
+                .is_empty()
+        );
+        assert!(cad.is_solve_pending(sketch_id));
+        assert_eq!(cad.solve_pending(1.0 + HEAVY_SOLVE_INTERVAL).len(), 1);
+    }
 }
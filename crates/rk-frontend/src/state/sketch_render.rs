@@ -0,0 +1,372 @@
+//! Conversion of sketches into renderer geometry
+
+use glam::{Vec2, Vec4};
+
+use rk_cad::sketch::arc_segment_count;
+use rk_cad::{Sketch, SketchEntity};
+use rk_renderer::SketchRenderData;
+use rk_renderer::sub_renderers::sketch::flags;
+
+use super::{CadState, SketchModeState};
+
+/// Default color for sketch curves
+const CURVE_COLOR: Vec4 = Vec4::new(0.9, 0.9, 0.9, 1.0);
+/// Default color for sketch points
+const POINT_COLOR: Vec4 = Vec4::new(1.0, 1.0, 1.0, 1.0);
+/// Color of the fixed-point glyph
+const FIXED_COLOR: Vec4 = Vec4::new(0.95, 0.35, 0.3, 1.0);
+/// Fixed glyph half-size relative to the grid spacing
+const FIXED_MARKER_SCALE: f32 = 0.2;
+/// Segments used to tessellate circles and arcs
+const CURVE_SEGMENTS: u32 = 64;
+/// Minor sketch grid line color
+const GRID_MINOR_COLOR: Vec4 = Vec4::new(0.5, 0.5, 0.55, 0.25);
+/// Major sketch grid line color
+const GRID_MAJOR_COLOR: Vec4 = Vec4::new(0.6, 0.6, 0.65, 0.5);
+/// Every n-th grid line is drawn as a major line
+const GRID_MAJOR_EVERY: u32 = 5;
+/// Minimum grid half-extent, in grid cells
+const GRID_MIN_CELLS: f32 = 10.0;
+/// Maximum grid half-extent, in grid cells (keeps vertex counts bounded)
+const GRID_MAX_CELLS: f32 = 100.0;
+/// Origin X axis color on the active sketch
+const ORIGIN_X_COLOR: Vec4 = Vec4::new(0.9, 0.25, 0.25, 1.0);
+/// Origin Y axis color on the active sketch
+const ORIGIN_Y_COLOR: Vec4 = Vec4::new(0.25, 0.85, 0.3, 1.0);
+/// Origin point color on the active sketch
+const ORIGIN_POINT_COLOR: Vec4 = Vec4::new(1.0, 0.85, 0.2, 1.0);
+/// Alpha of the origin axes on inactive sketches
+const ORIGIN_INACTIVE_ALPHA: f32 = 0.3;
+/// Axis length relative to the farthest sketch geometry from the origin
+const ORIGIN_AXIS_FRACTION: f32 = 0.5;
+/// Minimum axis length, in grid cells
+const ORIGIN_AXIS_MIN_CELLS: f32 = 2.0;
+
+/// Build render data for every sketch
+///
+/// The sketch being edited, if any, reflects the sketch mode state.
+pub fn sketches_to_render_data(cad: &CadState) -> Vec<SketchRenderData> {
+    let mode = cad.editor_mode.sketch();
+    cad.data
+        .history
+        .sketches()
+        .values()
+        .map(|sketch| sketch_to_render_data(sketch, mode))
+        .collect()
+}
+
+/// Build render data for a sketch
+///
+/// Pass the sketch mode state when the sketch is being edited so that
+/// selection and hover state are reflected in the vertex flags.
+pub fn sketch_to_render_data(sketch: &Sketch, mode: Option<&SketchModeState>) -> SketchRenderData {
+    let mut data = SketchRenderData::new(sketch.id, sketch.plane.transform());
+    data.is_active = mode.is_some_and(|m| m.active_sketch == sketch.id);
+
+    // Grid first so sketch geometry draws over it
+    if let Some(mode) = mode
+        && data.is_active
+        && mode.show_grid
+    {
+        add_sketch_grid(&mut data, sketch, mode.grid_spacing);
+    }
+
+    let spacing = mode.map_or(1.0, |m| m.grid_spacing);
+    add_sketch_origin(&mut data, sketch, spacing);
+
+    let entity_flags = |id| {
+        let mut f = 0;
+        if let Some(mode) = mode {
+            if mode.selected_entities.contains(&id) {
+                f |= flags::SELECTED;
+            }
+            if mode.hovered_entity == Some(id) {
+                f |= flags::HOVERED;
+            }
+            // Preview what can complete a half-picked constraint
+            if !mode.selected_entities.is_empty()
+                && mode.current_tool.is_constraint()
+                && mode.is_constraint_candidate(sketch, id)
+            {
+                f |= flags::CANDIDATE;
+            }
+        }
+        if sketch.is_construction(id) {
+            f |= flags::CONSTRUCTION;
+        }
+        f
+    };
+    let point_position = |id| sketch.get_entity(id).and_then(|e| e.position());
+
+    for entity in sketch.entities_iter() {
+        let id = entity.id();
+        let f = entity_flags(id);
+        match entity {
+            SketchEntity::Point { position, .. } => {
+                if sketch.is_point_fixed(id) {
+                    data.add_point(*position, FIXED_COLOR, f | flags::FIXED);
+                    let half_size = mode.map_or(1.0, |m| m.grid_spacing) * FIXED_MARKER_SCALE;
+                    data.add_fixed_marker(*position, half_size, FIXED_COLOR, f);
+                } else {
+                    data.add_point(*position, POINT_COLOR, f);
+                }
+            }
+            SketchEntity::Line { start, end, .. } => {
+                if let (Some(a), Some(b)) = (point_position(*start), point_position(*end)) {
+                    data.add_line(a, b, CURVE_COLOR, f);
+                }
+            }
+            SketchEntity::Circle { center, radius, .. } => {
+                if let Some(c) = point_position(*center) {
+                    data.add_circle(c, *radius, CURVE_COLOR, f, CURVE_SEGMENTS);
+                }
+            }
+            SketchEntity::Arc {
+                center,
+                start,
+                end,
+                radius,
+                direction,
+                ..
+            } => {
+                if let (Some(c), Some(a), Some(b)) = (
+                    point_position(*center),
+                    point_position(*start),
+                    point_position(*end),
+                ) {
+                    let (start_angle, sweep) = direction.sweep_angles(c, a, b);
+                    data.add_arc(
+                        c,
+                        *radius,
+                        start_angle,
+                        start_angle + sweep,
+                        CURVE_COLOR,
+                        f,
+                        arc_segment_count(sweep, CURVE_SEGMENTS),
+                    );
+                }
+            }
+            // Ellipses and splines are not drawn yet
+            SketchEntity::Ellipse { .. } | SketchEntity::Spline { .. } => {}
+        }
+    }
+
+    data
+}
+
+/// Add the sketch origin axes, sized to the sketch geometry
+///
+/// Only the active sketch gets bright axes and an origin point; other
+/// sketches show dimmed axes so their planes stay distinguishable.
+fn add_sketch_origin(data: &mut SketchRenderData, sketch: &Sketch, spacing: f32) {
+    let extent = sketch
+        .entities_iter()
+        .filter_map(|e| match e {
+            SketchEntity::Circle { center, radius, .. }
+            | SketchEntity::Arc { center, radius, .. } => sketch
+                .get_entity(*center)
+                .and_then(|c| c.position())
+                .map(|c| c.length() + radius),
+            _ => e.position().map(Vec2::length),
+        })
+        .fold(0.0_f32, f32::max);
+    let length = (extent * ORIGIN_AXIS_FRACTION).max(ORIGIN_AXIS_MIN_CELLS * spacing.max(0.0));
+    if length <= 0.0 {
+        return;
+    }
+
+    let (x_color, y_color) = if data.is_active {
+        (ORIGIN_X_COLOR, ORIGIN_Y_COLOR)
+    } else {
+        (
+            ORIGIN_X_COLOR.truncate().extend(ORIGIN_INACTIVE_ALPHA),
+            ORIGIN_Y_COLOR.truncate().extend(ORIGIN_INACTIVE_ALPHA),
+        )
+    };
+    data.add_line(Vec2::ZERO, Vec2::X * length, x_color, flags::ORIGIN);
+    data.add_line(Vec2::ZERO, Vec2::Y * length, y_color, flags::ORIGIN);
+    if data.is_active {
+        data.add_point(Vec2::ZERO, ORIGIN_POINT_COLOR, flags::ORIGIN);
+    }
+}
+
+/// Add a grid at the snap spacing that covers the sketch geometry
+fn add_sketch_grid(data: &mut SketchRenderData, sketch: &Sketch, spacing: f32) {
+    if spacing <= 0.0 {
+        return;
+    }
+
+    let (min, max) = sketch
+        .entities_iter()
+        .filter_map(|e| e.position())
+        .fold((Vec2::ZERO, Vec2::ZERO), |(min, max), p| {
+            (min.min(p), max.max(p))
+        });
+    let center = (min + max) * 0.5;
+    let half_extent = ((max - min).max_element() * 0.5 + 2.0 * spacing)
+        .clamp(GRID_MIN_CELLS * spacing, GRID_MAX_CELLS * spacing);
+
+    data.add_grid(
+        center,
+        half_extent,
+        spacing,
+        GRID_MAJOR_EVERY,
+        GRID_MINOR_COLOR,
+        GRID_MAJOR_COLOR,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rk_cad::{ArcDirection, SketchPlane};
+
+    fn arc_sketch(direction: ArcDirection) -> Sketch {
+        let mut sketch = Sketch::new("arc", SketchPlane::xy());
+        let center = sketch.add_point(Vec2::ZERO);
+        let start = sketch.add_point(Vec2::new(1.0, 0.0));
+        let end = sketch.add_point(Vec2::new(0.0, -1.0));
+        sketch.add_arc_with_direction(center, start, end, 1.0, direction);
+        sketch
+    }
+
+    /// Line vertices of the sketch geometry, without the origin glyph
+    fn curve_vertices(data: &SketchRenderData) -> Vec<Vec2> {
+        data.line_vertices
+            .iter()
+            .filter(|v| v.flags & flags::ORIGIN == 0)
+            .map(|v| Vec2::new(v.position[0], v.position[1]))
+            .collect()
+    }
+
+    #[test]
+    fn test_reflex_arc_segments_and_orientation() {
+        let data = sketch_to_render_data(&arc_sketch(ArcDirection::CounterClockwise), None);
+        let vertices = curve_vertices(&data);
+
+        // 270° of a 64-segment circle, two vertices per segment
+        assert_eq!(vertices.len(), 48 * 2);
+
+        assert!((vertices[0] - Vec2::X).length() < 1e-5);
+        // Counter-clockwise: first step heads towards +Y
+        assert!(vertices[1].y > 0.0);
+        // Sweep passes through the top and left of the circle
+        assert!(vertices.iter().any(|v| v.x < -0.99 && v.y.abs() < 0.1));
+    }
+
+    #[test]
+    fn test_clockwise_arc_short_sweep() {
+        let data = sketch_to_render_data(&arc_sketch(ArcDirection::Clockwise), None);
+        let vertices = curve_vertices(&data);
+
+        // 90° of a 64-segment circle
+        assert_eq!(vertices.len(), 16 * 2);
+        assert!(vertices.iter().all(|v| v.y <= 1e-5));
+        assert!(vertices.iter().all(|v| v.x >= -1e-5));
+    }
+
+    #[test]
+    fn test_grid_lines_align_to_snap_spacing() {
+        let mut sketch = Sketch::new("grid", SketchPlane::xy());
+        sketch.add_point(Vec2::new(3.3, -7.1));
+        let mode = SketchModeState {
+            grid_spacing: 0.25,
+            ..SketchModeState::new(sketch.id)
+        };
+
+        let data = sketch_to_render_data(&sketch, Some(&mode));
+        let grid: Vec<_> = data
+            .line_vertices
+            .chunks(2)
+            .filter(|l| l[0].flags & flags::GRID != 0)
+            .collect();
+        assert!(!grid.is_empty());
+
+        let on_grid = |v: f32| {
+            let cells = v / mode.grid_spacing;
+            (cells - cells.round()).abs() < 1e-3
+        };
+        for line in &grid {
+            let (a, b) = (line[0].position, line[1].position);
+            // Lines are axis aligned and sit on multiples of the spacing
+            if a[0] == b[0] {
+                assert!(on_grid(a[0]), "x = {}", a[0]);
+            } else {
+                assert_eq!(a[1], b[1]);
+                assert!(on_grid(a[1]), "y = {}", a[1]);
+            }
+        }
+
+        // A snapped point lies on a grid intersection
+        let snapped = mode.snap_point(Vec2::new(1.13, -0.61));
+        assert!(on_grid(snapped.x) && on_grid(snapped.y));
+    }
+
+    #[test]
+    fn test_grid_hidden_when_disabled() {
+        let sketch = Sketch::new("grid", SketchPlane::xy());
+        let mut mode = SketchModeState::new(sketch.id);
+        mode.show_grid = false;
+
+        let data = sketch_to_render_data(&sketch, Some(&mode));
+        assert!(
+            data.line_vertices
+                .iter()
+                .all(|v| v.flags & flags::GRID == 0)
+        );
+    }
+
+    #[test]
+    fn test_origin_glyph_only_on_active_sketch() {
+        let mut sketch = Sketch::new("origin", SketchPlane::xy());
+        sketch.add_point(Vec2::new(40.0, 0.0));
+        let origin_lines = |data: &SketchRenderData| -> Vec<f32> {
+            data.line_vertices
+                .chunks(2)
+                .filter(|l| l[0].flags & flags::ORIGIN != 0)
+                .map(|l| Vec2::new(l[1].position[0], l[1].position[1]).length())
+                .collect()
+        };
+        let origin_points = |data: &SketchRenderData| {
+            data.point_vertices
+                .iter()
+                .filter(|v| v.flags & flags::ORIGIN != 0)
+                .count()
+        };
+
+        let mode = SketchModeState::new(sketch.id);
+        let active = sketch_to_render_data(&sketch, Some(&mode));
+        assert_eq!(origin_points(&active), 1);
+        // Axes scale with the geometry instead of a fixed length
+        assert_eq!(origin_lines(&active), vec![20.0, 20.0]);
+
+        let other = SketchModeState::new(uuid::Uuid::new_v4());
+        for data in [
+            sketch_to_render_data(&sketch, None),
+            sketch_to_render_data(&sketch, Some(&other)),
+        ] {
+            assert_eq!(origin_points(&data), 0);
+            assert_eq!(origin_lines(&data).len(), 2);
+            assert!(
+                data.line_vertices
+                    .iter()
+                    .filter(|v| v.flags & flags::ORIGIN != 0)
+                    .all(|v| v.color[3] <= ORIGIN_INACTIVE_ALPHA)
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_sketch_is_drawn_and_only_the_edited_one_is_active() {
+        let mut cad = CadState::new();
+        let edited = cad.create_sketch("edited", SketchPlane::xy());
+        let other = cad.create_sketch("other", SketchPlane::xy());
+        cad.enter_sketch_mode(edited);
+
+        let data = sketches_to_render_data(&cad);
+        assert_eq!(data.len(), 2);
+        let active = |id| data.iter().find(|d| d.id == id).unwrap().is_active;
+        assert!(active(edited));
+        assert!(!active(other));
+    }
+}
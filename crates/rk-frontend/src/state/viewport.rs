@@ -1,13 +1,32 @@
 //! Viewport rendering state
 
+use std::f32::consts::{PI, TAU};
 use std::sync::Arc;
 
-use glam::{Mat4, Quat, Vec3};
+use glam::{Mat4, Quat, Vec2, Vec3};
 use parking_lot::Mutex;
 use uuid::Uuid;
 
-use rk_core::Part;
-use rk_renderer::{GizmoAxis, GizmoMode, Renderer, axis::AxisInstance};
+use rk_cad::SketchPlane;
+use rk_core::{Assembly, GeometryType, Part};
+use rk_renderer::constants::collision::{DEFAULT_COLOR, SELECTED_COLOR};
+use rk_renderer::{
+    Camera, GizmoAxis, GizmoMode, RenderContext, Renderer, SketchRenderData, SketchRenderer,
+    ViewScale, axis::AxisInstance, marker::MarkerInstance, scene_marker_scale, world_per_pixel,
+};
+
+/// Screen-space radius in pixels within which a click picks a joint marker
+pub const JOINT_MARKER_PICK_RADIUS: f32 = 8.0;
+/// Screen-space pick radius for sketch entities, in pixels
+pub const SKETCH_PICK_RADIUS: f32 = 6.0;
+/// World-space radius of joint markers before the global marker scale
+const JOINT_MARKER_SIZE: f32 = 0.015;
+/// Joint marker color
+const JOINT_MARKER_COLOR: [f32; 4] = [1.0, 0.55, 0.1, 1.0];
+/// Color of the marker of the joint being edited
+const EDITING_JOINT_MARKER_COLOR: [f32; 4] = [1.0, 0.9, 0.2, 1.0];
+/// Axis length of the inertial frame triads
+const INERTIAL_AXIS_SCALE: f32 = 0.1;
 
 /// Render texture for viewport
 struct RenderTexture {
@@ -27,6 +46,60 @@ pub enum GizmoTransform {
     Scale(Vec3),
 }
 
+/// Angle increment used when snapping rotate-gizmo drags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RotationSnapIncrement {
+    #[default]
+    Deg15,
+    Deg45,
+    Deg90,
+}
+
+impl RotationSnapIncrement {
+    /// All increments (for UI)
+    pub fn all() -> &'static [RotationSnapIncrement] {
+        &[
+            RotationSnapIncrement::Deg15,
+            RotationSnapIncrement::Deg45,
+            RotationSnapIncrement::Deg90,
+        ]
+    }
+
+    /// Increment in degrees
+    pub fn degrees(&self) -> f32 {
+        match self {
+            RotationSnapIncrement::Deg15 => 15.0,
+            RotationSnapIncrement::Deg45 => 45.0,
+            RotationSnapIncrement::Deg90 => 90.0,
+        }
+    }
+
+    /// Increment in radians
+    pub fn radians(&self) -> f32 {
+        self.degrees().to_radians()
+    }
+
+    /// Display label
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RotationSnapIncrement::Deg15 => "15\u{00b0}",
+            RotationSnapIncrement::Deg45 => "45\u{00b0}",
+            RotationSnapIncrement::Deg90 => "90\u{00b0}",
+        }
+    }
+}
+
+/// Round an angle to the nearest multiple of `increment`
+///
+/// Angles exactly halfway between two multiples round away from zero.
+/// A non-positive increment leaves the angle unchanged.
+pub fn snap_angle(angle: f32, increment: f32) -> f32 {
+    if increment <= 0.0 {
+        return angle;
+    }
+    (angle / increment).round() * increment
+}
+
 /// Gizmo interaction state
 #[derive(Default)]
 pub struct GizmoInteraction {
@@ -34,12 +107,24 @@ pub struct GizmoInteraction {
     pub drag_axis: GizmoAxis,
     pub drag_start_pos: Vec3,
     pub drag_start_angle: f32,
+    /// Unsnapped rotation accumulated over the current rotate drag (radians)
+    pub drag_total_angle: f32,
+    /// Rotation already applied during the current rotate drag (radians)
+    pub drag_applied_angle: f32,
+    /// Snap increment for rotate drags (radians), if snapping is active
+    pub rotation_snap: Option<f32>,
     pub part_start_transform: Mat4,
     pub part_id: Option<Uuid>,
+    /// Whether the part under the gizmo is locked against drags
+    pub part_locked: bool,
     /// Collision being edited: (link_id, collision_index)
     pub editing_collision: Option<(Uuid, usize)>,
     /// Link world transform for collision editing
     pub link_world_transform: Mat4,
+    /// Collision rotation accumulated over the current rotate drag
+    ///
+    /// Kept as a quaternion so repeated deltas don't round-trip through RPY.
+    pub collision_drag_rotation: Option<Quat>,
     pub gizmo_position: Vec3,
     pub gizmo_scale: f32,
 }
@@ -49,8 +134,12 @@ pub struct ViewportState {
     pub renderer: Renderer,
     pub device: Arc<wgpu::Device>,
     pub queue: Arc<wgpu::Queue>,
+    /// Context for the sub-renderers registered with the renderer
+    render_context: RenderContext,
     render_texture: Option<RenderTexture>,
     pub gizmo: GizmoInteraction,
+    /// Joint origins currently drawn as markers, for picking
+    joint_markers: Vec<(Uuid, Vec3)>,
 }
 
 impl ViewportState {
@@ -60,13 +149,18 @@ impl ViewportState {
         queue: Arc<wgpu::Queue>,
         format: wgpu::TextureFormat,
     ) -> Self {
-        let renderer = Renderer::new(&device, format, 800, 600);
+        let mut renderer = Renderer::new(&device, format, 800, 600);
+        let render_context = renderer.create_context(&device, &queue);
+        renderer.registry_mut().register(SketchRenderer::new());
+        renderer.registry_mut().init_all(&render_context);
         Self {
             renderer,
             device,
             queue,
+            render_context,
             render_texture: None,
             gizmo: GizmoInteraction::default(),
+            joint_markers: Vec::new(),
         }
     }
 
@@ -119,6 +213,10 @@ impl ViewportState {
 
             // Resize renderer
             self.renderer.resize(&self.device, width, height);
+            self.render_context.resize(width, height);
+            self.renderer
+                .registry_mut()
+                .resize_all(&self.render_context, width, height);
 
             self.render_texture = Some(RenderTexture {
                 texture,
@@ -132,12 +230,21 @@ impl ViewportState {
         self.render_texture.as_ref().unwrap().egui_texture_id
     }
 
+    /// Size of the viewport render texture (1x1 before the first frame)
+    pub fn view_size(&self) -> (u32, u32) {
+        self.render_texture
+            .as_ref()
+            .map_or((1, 1), |rt| (rt.width, rt.height))
+    }
+
     /// Render the 3D scene to the texture
     pub fn render(&mut self) {
         let Some(ref rt) = self.render_texture else {
             return;
         };
 
+        self.renderer.prepare_plugins(&self.render_context);
+
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -149,11 +256,23 @@ impl ViewportState {
         self.queue.submit(std::iter::once(encoder.finish()));
     }
 
+    /// Replace the sketches drawn in the viewport
+    pub fn set_sketches(&mut self, sketches: Vec<SketchRenderData>) {
+        if let Some(renderer) = self.renderer.registry_mut().find_mut::<SketchRenderer>() {
+            renderer.set_sketches(sketches);
+        }
+    }
+
     /// Add a part to the viewport
     pub fn add_part(&mut self, part: &Part) -> Uuid {
         self.renderer.add_part(&self.device, part)
     }
 
+    /// Replace a part's geometry, keeping how it is posed and highlighted
+    pub fn update_part_mesh(&mut self, part: &Part) {
+        self.renderer.update_part_mesh(&self.device, part);
+    }
+
     /// Update a part's transform
     pub fn update_part_transform(&mut self, part_id: Uuid, transform: Mat4) {
         self.renderer
@@ -170,6 +289,11 @@ impl ViewportState {
         self.renderer.set_selected_part(&self.queue, part_id);
     }
 
+    /// Set the parts highlighted as the selected kinematic chain
+    pub fn set_highlighted_parts(&mut self, part_ids: &[Uuid]) {
+        self.renderer.set_highlighted_parts(&self.queue, part_ids);
+    }
+
     /// Remove a part
     pub fn remove_part(&mut self, part_id: Uuid) {
         self.renderer.remove_part(part_id);
@@ -190,14 +314,158 @@ impl ViewportState {
         self.renderer.update_axes(&self.queue, &[instance]);
     }
 
+    /// Update the inertial frame triads for every link
+    pub fn update_inertial_frames(&mut self, assembly: &Assembly) {
+        let instances = if self.renderer.show_inertial_frames() {
+            inertial_axis_instances(assembly)
+        } else {
+            Vec::new()
+        };
+        self.renderer.update_inertial_axes(&self.queue, &instances);
+    }
+
+    /// Apply per-part visual/collision visibility overrides
+    pub fn set_hidden_parts(&mut self, hidden_visuals: &[Uuid], hidden_collisions: &[Uuid]) {
+        let display = self.renderer.display_options_mut();
+        display.set_hidden_visuals(hidden_visuals);
+        display.set_hidden_collisions(hidden_collisions);
+    }
+
+    /// Apply per-part shadow casting/receiving overrides
+    pub fn set_shadow_overrides(&mut self, non_casting: &[Uuid], non_receiving: &[Uuid]) {
+        let queue = self.queue.clone();
+        self.renderer
+            .set_shadow_overrides(&queue, non_casting, non_receiving);
+    }
+
+    /// Rebuild collision shape instances from the assembly
+    ///
+    /// Links are identified by their part ID for visibility overrides.
+    /// Mesh collisions are not drawn.
+    pub fn update_collision_shapes(
+        &mut self,
+        assembly: &Assembly,
+        selected: Option<(Uuid, usize)>,
+    ) {
+        let display = self.renderer.display_options().clone();
+        let collisions = self.renderer.collision_renderer_mut();
+        collisions.clear();
+
+        if display.show_collisions {
+            for (link_id, link) in &assembly.links {
+                if !display.is_collision_visible(link.part_id.unwrap_or(*link_id)) {
+                    continue;
+                }
+                for (index, collision) in link.collisions.iter().enumerate() {
+                    let transform = link.world_transform * collision.origin.to_mat4();
+                    let color = if selected == Some((*link_id, index)) {
+                        SELECTED_COLOR
+                    } else {
+                        DEFAULT_COLOR
+                    };
+                    match &collision.geometry {
+                        GeometryType::Box { size } => collisions.add_box(transform, *size, color),
+                        GeometryType::Sphere { radius } => {
+                            collisions.add_sphere(transform, *radius, color)
+                        }
+                        GeometryType::Cylinder { radius, length } => {
+                            collisions.add_cylinder(transform, *radius, *length, color)
+                        }
+                        GeometryType::Capsule { radius, length } => {
+                            collisions.add_capsule(transform, *radius, *length, color)
+                        }
+                        GeometryType::Mesh { .. } => {}
+                    }
+                }
+            }
+        }
+
+        self.renderer.collision_renderer().upload(&self.queue);
+    }
+
     /// Clear axes and markers
     pub fn clear_overlays(&mut self) {
+        self.clear_selection_overlays();
+        self.set_joint_markers(Vec::new(), None);
+    }
+
+    /// Clear the axes and gizmo shown for the selection, keeping joint markers
+    pub fn clear_selection_overlays(&mut self) {
         self.renderer.update_axes(&self.queue, &[]);
-        self.renderer.update_markers(&self.queue, &[]);
-        self.renderer.update_selected_markers(&self.queue, &[]);
         self.renderer.hide_gizmo();
     }
 
+    /// Draw joint origins as markers, highlighting the joint being edited
+    ///
+    /// Markers are scaled to the size of the scene so they stay readable on
+    /// both small and large robots.
+    pub fn set_joint_markers(&mut self, markers: Vec<(Uuid, Vec3)>, editing: Option<Uuid>) {
+        let scale = self
+            .renderer
+            .scene_bounds()
+            .map_or(1.0, |bounds| scene_marker_scale(bounds.radius()));
+        self.renderer.set_marker_scale(scale);
+
+        let (selected, normal): (Vec<_>, Vec<_>) = markers
+            .iter()
+            .partition(|(joint_id, _)| Some(*joint_id) == editing);
+        let instances = |markers: Vec<&(Uuid, Vec3)>, color| -> Vec<MarkerInstance> {
+            markers
+                .into_iter()
+                .map(|(_, position)| MarkerInstance::new(*position, JOINT_MARKER_SIZE, color))
+                .collect()
+        };
+        self.renderer
+            .update_markers(&self.queue, &instances(normal, JOINT_MARKER_COLOR));
+        self.renderer.update_selected_markers(
+            &self.queue,
+            &instances(selected, EDITING_JOINT_MARKER_COLOR),
+        );
+        self.joint_markers = markers;
+    }
+
+    /// Project a screen position onto a sketch plane, in sketch coordinates
+    pub fn screen_to_sketch(
+        &self,
+        plane: &SketchPlane,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+    ) -> Option<Vec2> {
+        let (origin, direction) = self
+            .renderer
+            .camera()
+            .screen_to_ray(screen_x, screen_y, width, height);
+        ray_plane_intersection(origin, direction, plane.origin, plane.normal)
+            .map(|hit| plane.to_local(hit))
+    }
+
+    /// Joint whose marker is under the given screen position, if any
+    pub fn pick_joint_marker(
+        &self,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+    ) -> Option<Uuid> {
+        if !self.renderer.show_markers() {
+            return None;
+        }
+        let (origin, direction) = self
+            .renderer
+            .camera()
+            .screen_to_ray(screen_x, screen_y, width, height);
+        pick_marker(
+            self.renderer.camera(),
+            &self.joint_markers,
+            origin,
+            direction,
+            height,
+            JOINT_MARKER_PICK_RADIUS,
+        )
+    }
+
     /// Show gizmo for a part
     pub fn show_gizmo_for_part(&mut self, part: &Part) {
         // Calculate center from bounding box
@@ -219,6 +487,7 @@ impl ViewportState {
         self.gizmo.gizmo_position = world_center;
         self.gizmo.gizmo_scale = scale;
         self.gizmo.part_id = Some(part.id);
+        self.gizmo.part_locked = part.locked;
         self.gizmo.part_start_transform = part.origin_transform;
 
         // Set object rotation for local coordinate space
@@ -227,10 +496,18 @@ impl ViewportState {
         self.renderer.show_gizmo(&self.queue, world_center, scale);
     }
 
+    /// Update the lock flag if the gizmo is shown for `part_id`
+    pub fn set_gizmo_part_locked(&mut self, part_id: Uuid, locked: bool) {
+        if self.gizmo.part_id == Some(part_id) {
+            self.gizmo.part_locked = locked;
+        }
+    }
+
     /// Hide gizmo
     pub fn hide_gizmo(&mut self) {
         self.renderer.hide_gizmo();
         self.gizmo.part_id = None;
+        self.gizmo.part_locked = false;
         self.gizmo.editing_collision = None;
     }
 
@@ -261,6 +538,7 @@ impl ViewportState {
         self.gizmo.gizmo_position = translation;
         self.gizmo.gizmo_scale = scale;
         self.gizmo.part_id = None;
+        self.gizmo.part_locked = false;
         self.gizmo.editing_collision = Some((link_id, collision_index));
         self.gizmo.link_world_transform = link_world_transform;
         self.gizmo.part_start_transform = collision_origin;
@@ -309,7 +587,7 @@ impl ViewportState {
         width: f32,
         height: f32,
     ) {
-        if axis == GizmoAxis::None {
+        if axis == GizmoAxis::None || self.gizmo.part_locked {
             return;
         }
 
@@ -354,6 +632,9 @@ impl ViewportState {
                     // Calculate initial angle from gizmo center
                     let offset = point - self.gizmo.gizmo_position;
                     self.gizmo.drag_start_angle = self.angle_on_plane(offset, rotation_axis);
+                    self.gizmo.drag_total_angle = 0.0;
+                    self.gizmo.drag_applied_angle = 0.0;
+                    self.gizmo.collision_drag_rotation = None;
                     self.renderer.set_gizmo_highlight(&self.queue, axis);
                 }
             }
@@ -442,13 +723,24 @@ impl ViewportState {
         ) {
             let offset = current_point - self.gizmo.gizmo_position;
             let current_angle = self.angle_on_plane(offset, rotation_axis);
-            let angle_delta = self.gizmo.drag_start_angle - current_angle;
+            // Wrap so crossing the atan2 seam doesn't produce a full turn
+            let angle_delta =
+                (self.gizmo.drag_start_angle - current_angle + PI).rem_euclid(TAU) - PI;
 
             // Update start angle for next frame
             self.gizmo.drag_start_angle = current_angle;
+            self.gizmo.drag_total_angle += angle_delta;
+
+            // Apply only the change in (possibly snapped) total angle
+            let target_angle = match self.gizmo.rotation_snap {
+                Some(increment) => snap_angle(self.gizmo.drag_total_angle, increment),
+                None => self.gizmo.drag_total_angle,
+            };
+            let step = target_angle - self.gizmo.drag_applied_angle;
+            self.gizmo.drag_applied_angle = target_angle;
 
             // Create rotation quaternion around the axis
-            let rotation = Quat::from_axis_angle(rotation_axis, angle_delta);
+            let rotation = Quat::from_axis_angle(rotation_axis, step);
 
             return Some(GizmoTransform::Rotation(rotation));
         }
@@ -521,44 +813,130 @@ impl ViewportState {
     pub fn end_gizmo_drag(&mut self) {
         self.gizmo.dragging = false;
         self.gizmo.drag_axis = GizmoAxis::None;
+        self.gizmo.collision_drag_rotation = None;
         self.renderer
             .set_gizmo_highlight(&self.queue, GizmoAxis::None);
     }
 
     /// Get the plane normal for dragging on an axis
+    ///
+    /// The plane contains the drag axis and is one of the two gizmo planes
+    /// through it, whichever faces the camera more.
     fn get_drag_plane_normal(&self, axis: GizmoAxis) -> Vec3 {
-        if axis == GizmoAxis::None {
-            let camera = self.renderer.camera();
-            return (camera.target - camera.position).normalize();
-        }
-
         let camera = self.renderer.camera();
         let camera_forward = (camera.target - camera.position).normalize();
 
-        // Get the axis direction in current coordinate space
-        let axis_dir = self.renderer.gizmo_axis_direction(axis);
-
-        // Create two candidate normals perpendicular to the axis
-        let up = if axis_dir.y.abs() < 0.9 {
-            Vec3::Y
-        } else {
-            Vec3::X
-        };
-        let candidate1 = axis_dir.cross(up).normalize();
-        let candidate2 = axis_dir.cross(candidate1).normalize();
-
-        // Choose the candidate that is most facing the camera
-        if camera_forward.dot(candidate1).abs() > camera_forward.dot(candidate2).abs() {
-            candidate1
-        } else {
-            candidate2
+        // Normals of the gizmo planes containing the axis, in current coordinate space
+        let candidates = match axis {
+            GizmoAxis::None => return camera_forward,
+            GizmoAxis::X => [GizmoAxis::Y, GizmoAxis::Z],
+            GizmoAxis::Y => [GizmoAxis::X, GizmoAxis::Z],
+            GizmoAxis::Z => [GizmoAxis::X, GizmoAxis::Y],
         }
+        .map(|other| self.renderer.gizmo_axis_direction(other));
+
+        most_view_facing_normal(camera_forward, candidates)
     }
 
     /// Check if currently dragging
     pub fn is_dragging_gizmo(&self) -> bool {
         self.gizmo.dragging
     }
+
+    /// Rotation applied by the current rotate drag, in radians
+    pub fn rotate_drag_angle(&self) -> Option<f32> {
+        (self.gizmo.dragging && self.renderer.gizmo_mode() == GizmoMode::Rotate)
+            .then_some(self.gizmo.drag_applied_angle)
+    }
+}
+
+/// Apply a gizmo drag delta to a part's origin transform
+///
+/// Returns the new transform, or `None` (leaving the part untouched) when the
+/// part is locked.
+pub fn apply_gizmo_transform(part: &mut Part, transform: GizmoTransform) -> Option<Mat4> {
+    if part.locked {
+        return None;
+    }
+    let (scale, rotation, translation) = part.origin_transform.to_scale_rotation_translation();
+    let (scale, rotation, translation) = match transform {
+        GizmoTransform::Translation(delta) => (scale, rotation, translation + delta),
+        GizmoTransform::Rotation(delta) => (scale, delta * rotation, translation),
+        GizmoTransform::Scale(delta) => (scale * delta, rotation, translation),
+    };
+    part.origin_transform = Mat4::from_scale_rotation_translation(scale, rotation, translation);
+    Some(part.origin_transform)
+}
+
+/// Axis triads at each link's inertial origin, in world space
+///
+/// Massless links carry no inertial element and are skipped.
+pub fn inertial_axis_instances(assembly: &Assembly) -> Vec<AxisInstance> {
+    assembly
+        .links
+        .values()
+        .filter(|link| link.inertial.mass > 0.0)
+        .map(|link| AxisInstance {
+            transform: link.inertial_world_transform().to_cols_array_2d(),
+            scale: INERTIAL_AXIS_SCALE,
+            _pad: [0.0; 3],
+        })
+        .collect()
+}
+
+/// Apply a world-space gizmo rotation to a collision rotation in link space
+///
+/// The delta is conjugated into the link frame so the collision turns about
+/// the world axis the gizmo shows, regardless of how the link is oriented.
+pub fn apply_collision_rotation(current: Quat, world_delta: Quat, link_world: Mat4) -> Quat {
+    let (_, link_rotation, _) = link_world.to_scale_rotation_translation();
+    let local_delta = link_rotation.inverse() * world_delta * link_rotation;
+    (local_delta * current).normalize()
+}
+
+/// Pick the marker closest to the camera that lies within `radius_px` of a ray
+///
+/// The pixel radius is converted to a world distance at each marker's view
+/// depth, so markers stay equally easy to hit regardless of distance.
+pub fn pick_marker(
+    camera: &Camera,
+    markers: &[(Uuid, Vec3)],
+    ray_origin: Vec3,
+    ray_dir: Vec3,
+    viewport_height: f32,
+    radius_px: f32,
+) -> Option<Uuid> {
+    let forward = (camera.target - camera.position).normalize();
+    markers
+        .iter()
+        .filter_map(|(id, position)| {
+            let t = (*position - ray_origin).dot(ray_dir);
+            let depth = (*position - camera.position).dot(forward);
+            if t <= 0.0 || depth <= 0.0 {
+                return None;
+            }
+            let miss = (ray_origin + ray_dir * t).distance(*position);
+            let scale = ViewScale::Perspective {
+                fov: camera.fov,
+                depth,
+            };
+            (miss <= radius_px * world_per_pixel(scale, viewport_height)).then_some((*id, t))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(id, _)| id)
+}
+
+/// Pick the candidate plane normal most parallel to the view direction
+///
+/// Rays hit that plane closest to head-on, so small pointer motions map to
+/// small, stable moves instead of jumping along a plane seen edge-on.
+fn most_view_facing_normal(camera_forward: Vec3, candidates: [Vec3; 2]) -> Vec3 {
+    let [first, second] = candidates;
+    if camera_forward.dot(first).abs() >= camera_forward.dot(second).abs() {
+        first
+    } else {
+        second
+    }
 }
 
 /// Ray-plane intersection
@@ -581,150 +959,188 @@ fn ray_plane_intersection(
     Some(ray_origin + ray_dir * t)
 }
 
-/// Ray-AABB (Axis-Aligned Bounding Box) intersection test
-/// Returns the distance to intersection if hit, None otherwise
-fn ray_aabb_intersection(
-    ray_origin: Vec3,
-    ray_dir: Vec3,
-    bbox_min: Vec3,
-    bbox_max: Vec3,
-) -> Option<f32> {
-    let inv_dir = Vec3::new(1.0 / ray_dir.x, 1.0 / ray_dir.y, 1.0 / ray_dir.z);
-
-    let t1 = (bbox_min.x - ray_origin.x) * inv_dir.x;
-    let t2 = (bbox_max.x - ray_origin.x) * inv_dir.x;
-    let t3 = (bbox_min.y - ray_origin.y) * inv_dir.y;
-    let t4 = (bbox_max.y - ray_origin.y) * inv_dir.y;
-    let t5 = (bbox_min.z - ray_origin.z) * inv_dir.z;
-    let t6 = (bbox_max.z - ray_origin.z) * inv_dir.z;
-
-    let tmin = t1.min(t2).max(t3.min(t4)).max(t5.min(t6));
-    let tmax = t1.max(t2).min(t3.max(t4)).min(t5.max(t6));
-
-    if tmax < 0.0 || tmin > tmax {
-        return None;
-    }
+pub type SharedViewportState = Arc<Mutex<ViewportState>>;
 
-    Some(if tmin < 0.0 { tmax } else { tmin })
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rk_core::{Link, Pose};
+
+    #[test]
+    fn test_inertial_triad_at_link_inertial_origin() {
+        let mut assembly = Assembly::new("robot");
+        let mut link = Link::empty("body");
+        link.world_transform = Mat4::from_rotation_translation(
+            Quat::from_rotation_z(PI / 2.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        );
+        link.inertial.mass = 2.0;
+        link.inertial.origin = Pose {
+            xyz: [0.5, 0.0, 0.25],
+            rpy: [0.0, 0.0, PI / 2.0],
+        };
+        assembly.add_link(link);
+        assembly.add_link(Link::empty("massless"));
+
+        let instances = inertial_axis_instances(&assembly);
+        assert_eq!(instances.len(), 1);
+        let transform = Mat4::from_cols_array_2d(&instances[0].transform);
+        // Local +X offset is rotated onto world +Y by the link
+        assert!(
+            transform
+                .w_axis
+                .truncate()
+                .abs_diff_eq(Vec3::new(1.0, 0.5, 0.25), 1e-5)
+        );
+        // Link and inertial yaw compose into a half turn
+        assert!(transform.x_axis.truncate().abs_diff_eq(-Vec3::X, 1e-5));
+        assert_eq!(instances[0].scale, INERTIAL_AXIS_SCALE);
+    }
 
-/// Ray-triangle intersection using Möller–Trumbore algorithm
-/// Returns the distance to intersection if hit, None otherwise
-fn ray_triangle_intersection(
-    ray_origin: Vec3,
-    ray_dir: Vec3,
-    v0: Vec3,
-    v1: Vec3,
-    v2: Vec3,
-) -> Option<f32> {
-    const EPSILON: f32 = 1e-6;
+    #[test]
+    fn test_gizmo_transform_skips_locked_part() {
+        let start = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        let mut part = Part::new("base");
+        part.origin_transform = start;
 
-    let edge1 = v1 - v0;
-    let edge2 = v2 - v0;
-    let h = ray_dir.cross(edge2);
-    let a = edge1.dot(h);
+        let moved = apply_gizmo_transform(&mut part, GizmoTransform::Translation(Vec3::X));
+        assert_eq!(
+            moved,
+            Some(Mat4::from_translation(Vec3::new(2.0, 2.0, 3.0)))
+        );
 
-    if a.abs() < EPSILON {
-        return None; // Ray is parallel to triangle
+        part.origin_transform = start;
+        part.locked = true;
+        for transform in [
+            GizmoTransform::Translation(Vec3::X),
+            GizmoTransform::Rotation(Quat::from_rotation_z(0.5)),
+            GizmoTransform::Scale(Vec3::splat(2.0)),
+        ] {
+            assert_eq!(apply_gizmo_transform(&mut part, transform), None);
+            assert_eq!(part.origin_transform, start);
+        }
     }
 
-    let f = 1.0 / a;
-    let s = ray_origin - v0;
-    let u = f * s.dot(h);
-
-    if !(0.0..=1.0).contains(&u) {
-        return None;
+    #[test]
+    fn test_snap_angle_increments() {
+        for increment in RotationSnapIncrement::all() {
+            let step = increment.radians();
+            assert_eq!(snap_angle(0.0, step), 0.0);
+            assert!((snap_angle(step * 2.2, step) - step * 2.0).abs() < 1e-5);
+            assert!((snap_angle(-step * 2.8, step) + step * 3.0).abs() < 1e-5);
+        }
+        assert!(
+            (snap_angle(100f32.to_radians(), 45f32.to_radians()) - 90f32.to_radians()).abs() < 1e-5
+        );
+        assert!(
+            (snap_angle(-50f32.to_radians(), 90f32.to_radians()) + 90f32.to_radians()).abs() < 1e-5
+        );
     }
 
-    let q = s.cross(edge1);
-    let v = f * ray_dir.dot(q);
-
-    if v < 0.0 || u + v > 1.0 {
-        return None;
+    #[test]
+    fn test_snap_angle_thresholds() {
+        let step = 15f32.to_radians();
+        let half = step * 0.5;
+        // Just below the midpoint stays on the lower multiple
+        assert_eq!(snap_angle(half - 1e-4, step), 0.0);
+        // Just above the midpoint moves to the next multiple
+        assert!((snap_angle(half + 1e-4, step) - step).abs() < 1e-6);
+        assert!((snap_angle(-half - 1e-4, step) + step).abs() < 1e-6);
+        // Small jitter around a multiple doesn't move the snapped value
+        assert!((snap_angle(step * 4.0 + 1e-3, step) - step * 4.0).abs() < 1e-5);
+        assert!((snap_angle(step * 4.0 - 1e-3, step) - step * 4.0).abs() < 1e-5);
     }
 
-    let t = f * edge2.dot(q);
-
-    if t > EPSILON { Some(t) } else { None }
-}
-
-/// Data needed for picking a single part
-pub struct PickablePartData {
-    pub id: Uuid,
-    pub vertices: Vec<[f32; 3]>,
-    pub indices: Vec<u32>,
-    pub transform: Mat4,
-    pub bbox_min: [f32; 3],
-    pub bbox_max: [f32; 3],
-}
-
-/// Pick the closest object at the given screen position
-/// Returns the UUID of the hit object and the distance, if any
-pub fn pick_object(
-    camera: &rk_renderer::Camera,
-    screen_x: f32,
-    screen_y: f32,
-    width: f32,
-    height: f32,
-    parts: &[PickablePartData],
-) -> Option<(Uuid, f32)> {
-    let (ray_origin, ray_dir) = camera.screen_to_ray(screen_x, screen_y, width, height);
-
-    let mut closest_hit: Option<(Uuid, f32)> = None;
-
-    for part in parts {
-        let transform = part.transform;
-
-        // Transform bounding box to world space (approximate with corners)
-        let bbox_min = Vec3::from(part.bbox_min);
-        let bbox_max = Vec3::from(part.bbox_max);
-
-        // Transform all 8 corners of the bounding box
-        let corners = [
-            transform.transform_point3(Vec3::new(bbox_min.x, bbox_min.y, bbox_min.z)),
-            transform.transform_point3(Vec3::new(bbox_max.x, bbox_min.y, bbox_min.z)),
-            transform.transform_point3(Vec3::new(bbox_min.x, bbox_max.y, bbox_min.z)),
-            transform.transform_point3(Vec3::new(bbox_max.x, bbox_max.y, bbox_min.z)),
-            transform.transform_point3(Vec3::new(bbox_min.x, bbox_min.y, bbox_max.z)),
-            transform.transform_point3(Vec3::new(bbox_max.x, bbox_min.y, bbox_max.z)),
-            transform.transform_point3(Vec3::new(bbox_min.x, bbox_max.y, bbox_max.z)),
-            transform.transform_point3(Vec3::new(bbox_max.x, bbox_max.y, bbox_max.z)),
-        ];
-
-        // Compute world-space AABB from transformed corners
-        let mut world_min = corners[0];
-        let mut world_max = corners[0];
-        for corner in &corners[1..] {
-            world_min = world_min.min(*corner);
-            world_max = world_max.max(*corner);
+    #[test]
+    fn test_collision_rotation_does_not_drift() {
+        let link_world = Mat4::from_rotation_translation(
+            Quat::from_euler(glam::EulerRot::XYZ, 0.4, -1.1, 2.0),
+            Vec3::new(0.3, 0.0, -0.2),
+        );
+        let start = Quat::from_euler(glam::EulerRot::XYZ, 0.2, 1.5, -0.7);
+        let axis = Vec3::new(1.0, 2.0, -0.5).normalize();
+        let total = 2.5;
+        let steps = 2000;
+
+        let step = Quat::from_axis_angle(axis, total / steps as f32);
+        let mut accumulated = start;
+        for _ in 0..steps {
+            accumulated = apply_collision_rotation(accumulated, step, link_world);
         }
+        let combined =
+            apply_collision_rotation(start, Quat::from_axis_angle(axis, total), link_world);
+
+        assert!(accumulated.angle_between(combined) < 1e-3);
+        // Converting to RPY once at the end preserves the rotation
+        let (x, y, z) = accumulated.to_euler(glam::EulerRot::XYZ);
+        let from_rpy = Quat::from_euler(glam::EulerRot::XYZ, x, y, z);
+        assert!(from_rpy.angle_between(combined) < 1e-3);
+    }
 
-        // First check AABB for early rejection
-        if ray_aabb_intersection(ray_origin, ray_dir, world_min, world_max).is_none() {
-            continue;
-        }
+    #[test]
+    fn test_snap_angle_non_positive_increment() {
+        assert_eq!(snap_angle(0.37, 0.0), 0.37);
+        assert_eq!(snap_angle(0.37, -1.0), 0.37);
+    }
 
-        // Test each triangle
-        for chunk in part.indices.chunks(3) {
-            if chunk.len() != 3 {
-                continue;
-            }
+    #[test]
+    fn test_drag_plane_faces_camera() {
+        // Looking mostly down at an X drag: the XY plane (normal Z) faces the view
+        let forward = Vec3::new(0.3, 0.2, -1.0).normalize();
+        assert_eq!(
+            most_view_facing_normal(forward, [Vec3::Y, Vec3::Z]),
+            Vec3::Z
+        );
+        // At a glancing angle from the side, the XZ plane (normal Y) wins
+        let forward = Vec3::new(0.2, -1.0, -0.3).normalize();
+        assert_eq!(
+            most_view_facing_normal(forward, [Vec3::Y, Vec3::Z]),
+            Vec3::Y
+        );
+        // Facing is independent of the normal's sign
+        assert_eq!(
+            most_view_facing_normal(forward, [Vec3::Z, -Vec3::Y]),
+            -Vec3::Y
+        );
+    }
 
-            let v0 = transform.transform_point3(Vec3::from(part.vertices[chunk[0] as usize]));
-            let v1 = transform.transform_point3(Vec3::from(part.vertices[chunk[1] as usize]));
-            let v2 = transform.transform_point3(Vec3::from(part.vertices[chunk[2] as usize]));
+    #[test]
+    fn test_pick_marker_screen_radius() {
+        let mut camera = Camera::new(1.0);
+        camera.target = Vec3::ZERO;
+        camera.position = Vec3::new(0.0, -2.0, 0.0);
+        camera.distance = 2.0;
+        let (width, height) = (400.0, 400.0);
+
+        let near = Uuid::new_v4();
+        let behind = Uuid::new_v4();
+        let beside = Uuid::new_v4();
+        let markers = [
+            (behind, Vec3::new(0.0, 1.0, 0.0)),
+            (near, Vec3::ZERO),
+            (beside, Vec3::new(0.3, 0.0, 0.0)),
+        ];
+        let pick = |x: f32, y: f32| {
+            let (origin, dir) = camera.screen_to_ray(x, y, width, height);
+            pick_marker(&camera, &markers, origin, dir, height, 8.0)
+        };
 
-            if let Some(t) = ray_triangle_intersection(ray_origin, ray_dir, v0, v1, v2) {
-                match closest_hit {
-                    None => closest_hit = Some((part.id, t)),
-                    Some((_, current_t)) if t < current_t => closest_hit = Some((part.id, t)),
-                    _ => {}
-                }
-            }
-        }
+        // Center hits both occluding markers; the nearer one wins
+        assert_eq!(pick(200.0, 200.0), Some(near));
+        // A few pixels off still hits, well outside the radius misses
+        assert_eq!(pick(205.0, 200.0), Some(near));
+        assert_eq!(pick(200.0, 230.0), None);
+
+        // 0.3 m to the right at 2 m depth with a 40 degree FOV
+        let px_per_m = height / (2.0 * 2.0 * (camera.fov / 2.0).tan());
+        assert_eq!(pick(200.0 + 0.3 * px_per_m + 3.0, 200.0), Some(beside));
+
+        // Markers behind the camera are never picked
+        let behind_camera = [(near, Vec3::new(0.0, -3.0, 0.0))];
+        let (origin, dir) = camera.screen_to_ray(200.0, 200.0, width, height);
+        assert_eq!(
+            pick_marker(&camera, &behind_camera, origin, dir, height, 8.0),
+            None
+        );
     }
-
-    closest_hit
 }
-
-pub type SharedViewportState = Arc<Mutex<ViewportState>>;
@@ -0,0 +1,165 @@
+//! Registering a custom sub-renderer with the main renderer
+//!
+//! Draws a colored triangle through the plugin registry into an offscreen
+//! target and prints the color at the center of the frame.
+//!
+//! Run with `cargo run -p rk-renderer --example custom_sub_renderer`.
+
+use rk_renderer::{PassType, RenderContext, Renderer, Scene, SubRenderer, read_texture};
+
+const SIZE: u32 = 64;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// Screen-space triangle, independent of the camera
+const SHADER: &str = r#"
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var positions = array<vec2<f32>, 3>(
+        vec2<f32>(-0.8, -0.8),
+        vec2<f32>(0.8, -0.8),
+        vec2<f32>(0.0, 0.8),
+    );
+    var colors = array<vec3<f32>, 3>(
+        vec3<f32>(1.0, 0.0, 0.0),
+        vec3<f32>(0.0, 1.0, 0.0),
+        vec3<f32>(0.0, 0.0, 1.0),
+    );
+    var out: VertexOutput;
+    out.position = vec4<f32>(positions[index], 0.0, 1.0);
+    out.color = colors[index];
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+"#;
+
+/// Draws a single triangle in the overlay pass
+#[derive(Default)]
+struct TriangleRenderer {
+    enabled: bool,
+    pipeline: Option<wgpu::RenderPipeline>,
+}
+
+impl SubRenderer for TriangleRenderer {
+    fn name(&self) -> &str {
+        "triangle"
+    }
+
+    fn priority(&self) -> i32 {
+        250
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn pass_type(&self) -> PassType {
+        PassType::Overlay
+    }
+
+    fn on_init(&mut self, ctx: &RenderContext) {
+        let shader = ctx.create_shader(SHADER, "Triangle Shader");
+        let layout = ctx.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Triangle Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+        // Must match the main pass: surface format, depth buffer and MSAA
+        self.pipeline = Some(ctx.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Triangle Pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(ctx.surface_format().into())],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: ctx.depth_format(),
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: ctx.sample_count(),
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        }));
+        self.enabled = true;
+    }
+
+    fn on_resize(&mut self, _ctx: &RenderContext, _width: u32, _height: u32) {}
+
+    fn prepare(&mut self, _ctx: &RenderContext, _scene: &Scene) {}
+
+    fn render<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>, _scene: &Scene) {
+        if let Some(pipeline) = &self.pipeline {
+            pass.set_pipeline(pipeline);
+            pass.draw(0..3, 0..1);
+        }
+    }
+}
+
+fn main() {
+    let Some((device, queue)) = pollster::block_on(rk_renderer::headless_device()) else {
+        eprintln!("No GPU adapter available");
+        return;
+    };
+
+    let mut renderer = Renderer::new(&device, FORMAT, SIZE, SIZE);
+    let ctx = renderer.create_context(&device, &queue);
+    renderer
+        .registry_mut()
+        .register(TriangleRenderer::default());
+    renderer.registry_mut().init_all(&ctx);
+
+    let target = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Example Target"),
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = target.create_view(&Default::default());
+
+    renderer.prepare_plugins(&ctx);
+    let mut encoder = device.create_command_encoder(&Default::default());
+    renderer.render(&mut encoder, &view, &queue);
+    queue.submit([encoder.finish()]);
+
+    let Some(pixels) = read_texture(&device, &queue, &target) else {
+        eprintln!("Reading the frame back failed");
+        return;
+    };
+    let center = ((SIZE / 2 * SIZE + SIZE / 2) * 4) as usize;
+    println!("Center pixel: {:?}", &pixels[center..center + 4]);
+}
@@ -0,0 +1,56 @@
+//! Rendering without egui or a window
+//!
+//! Builds a renderer on a plain `wgpu` device, adds a box, renders one frame
+//! into an offscreen texture and prints the color at the center of the frame.
+//!
+//! Run with `cargo run -p rk-renderer --example headless`.
+
+use glam::Vec3;
+use rk_core::{Part, PrimitiveShape};
+use rk_renderer::{Renderer, read_texture};
+
+const SIZE: u32 = 64;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+fn main() {
+    let Some((device, queue)) = pollster::block_on(rk_renderer::headless_device()) else {
+        eprintln!("No GPU adapter available");
+        return;
+    };
+
+    let mut renderer = Renderer::new(&device, FORMAT, SIZE, SIZE);
+    let ctx = renderer.create_context(&device, &queue);
+    renderer.registry_mut().init_all(&ctx);
+
+    let part = Part::from_primitive("box", PrimitiveShape::Box { size: [0.5; 3] });
+    renderer.add_part(&device, &part);
+    renderer.camera_mut().fit_all(Vec3::ZERO, 0.5);
+
+    let target = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Headless Target"),
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = target.create_view(&Default::default());
+
+    renderer.prepare_plugins(&ctx);
+    let mut encoder = device.create_command_encoder(&Default::default());
+    renderer.render(&mut encoder, &view, &queue);
+    queue.submit([encoder.finish()]);
+
+    let Some(pixels) = read_texture(&device, &queue, &target) else {
+        eprintln!("Reading the frame back failed");
+        return;
+    };
+    let center = ((SIZE / 2 * SIZE + SIZE / 2) * 4) as usize;
+    println!("Center pixel: {:?}", &pixels[center..center + 4]);
+}
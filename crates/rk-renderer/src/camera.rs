@@ -1,8 +1,12 @@
 //! Orbit camera for 3D viewport
 
+use std::f32::consts::{PI, TAU};
+
 use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, Vec3};
 
+use crate::constants::camera::{MAX_DISTANCE, MIN_DISTANCE, ZOOM_SCALE};
+
 /// Camera uniform buffer data
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
@@ -29,6 +33,36 @@ impl Default for CameraUniform {
     }
 }
 
+/// How a projection maps world lengths to screen lengths
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViewScale {
+    /// Perspective projection with vertical FOV (radians), measured at `depth`
+    /// in front of the eye.
+    Perspective {
+        /// Vertical field of view in radians.
+        fov: f32,
+        /// View-space depth of the plane being measured.
+        depth: f32,
+    },
+    /// Orthographic projection showing `height` world units vertically.
+    Orthographic {
+        /// Visible world height.
+        height: f32,
+    },
+}
+
+/// World-space length covered by one pixel in a viewport `viewport_height` pixels tall
+pub fn world_per_pixel(scale: ViewScale, viewport_height: f32) -> f32 {
+    if viewport_height <= 0.0 {
+        return 0.0;
+    }
+    let visible_height = match scale {
+        ViewScale::Perspective { fov, depth } => 2.0 * depth * (fov * 0.5).tan(),
+        ViewScale::Orthographic { height } => height,
+    };
+    visible_height / viewport_height
+}
+
 /// Orbit camera
 pub struct Camera {
     /// Camera position in world space.
@@ -51,6 +85,82 @@ pub struct Camera {
     pub pitch: f32,
     /// Distance from target.
     pub distance: f32,
+    /// Keep the subject size constant when the FOV changes by adjusting distance.
+    pub dolly_zoom: bool,
+    /// Limit pitch to +/-89 degrees; when off the camera can orbit over the top.
+    pub clamp_pitch: bool,
+    /// Closest the camera may zoom to the target.
+    pub min_distance: f32,
+    /// Farthest the camera may zoom from the target.
+    pub max_distance: f32,
+    /// Stop zooming in at `min_distance`; when off the target is pushed forward instead.
+    pub stop_at_target: bool,
+}
+
+/// Pitch limit used when `Camera::clamp_pitch` is enabled
+const MAX_PITCH_DEGREES: f32 = 89.0;
+
+/// Preset view looking at the target from along a world axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxisView {
+    /// Looking from +X
+    PosX,
+    /// Looking from -X
+    NegX,
+    /// Looking from +Y
+    PosY,
+    /// Looking from -Y
+    NegY,
+    /// Looking down from +Z
+    PosZ,
+    /// Looking up from -Z
+    NegZ,
+    /// Isometric view from the octant nearest the current yaw
+    Isometric,
+}
+
+impl AxisView {
+    /// The six axis-aligned views
+    pub const AXES: [AxisView; 6] = [
+        AxisView::PosX,
+        AxisView::NegX,
+        AxisView::PosY,
+        AxisView::NegY,
+        AxisView::PosZ,
+        AxisView::NegZ,
+    ];
+
+    /// World direction from the target toward the camera, for axis views
+    pub fn direction(self) -> Option<Vec3> {
+        match self {
+            AxisView::PosX => Some(Vec3::X),
+            AxisView::NegX => Some(Vec3::NEG_X),
+            AxisView::PosY => Some(Vec3::Y),
+            AxisView::NegY => Some(Vec3::NEG_Y),
+            AxisView::PosZ => Some(Vec3::Z),
+            AxisView::NegZ => Some(Vec3::NEG_Z),
+            AxisView::Isometric => None,
+        }
+    }
+
+    /// Camera (yaw, pitch) for this view
+    ///
+    /// Top and bottom views keep `current_yaw` so the scene doesn't spin.
+    pub fn orbit_angles(self, current_yaw: f32) -> (f32, f32) {
+        use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+        match self {
+            AxisView::PosX => (0.0, 0.0),
+            AxisView::NegX => (PI, 0.0),
+            AxisView::PosY => (FRAC_PI_2, 0.0),
+            AxisView::NegY => (-FRAC_PI_2, 0.0),
+            AxisView::PosZ => (current_yaw, FRAC_PI_2),
+            AxisView::NegZ => (current_yaw, -FRAC_PI_2),
+            AxisView::Isometric => {
+                let octant = ((current_yaw - FRAC_PI_4) / FRAC_PI_2).round();
+                (octant * FRAC_PI_2 + FRAC_PI_4, (0.5f32).sqrt().atan())
+            }
+        }
+    }
 }
 
 impl Camera {
@@ -78,6 +188,11 @@ impl Camera {
             yaw,
             pitch,
             distance,
+            dolly_zoom: false,
+            clamp_pitch: true,
+            min_distance: MIN_DISTANCE,
+            max_distance: MAX_DISTANCE,
+            stop_at_target: true,
         }
     }
 
@@ -88,12 +203,42 @@ impl Camera {
 
     /// Orbit the camera around the target
     pub fn orbit(&mut self, delta_yaw: f32, delta_pitch: f32) {
-        self.yaw += delta_yaw;
-        self.pitch =
-            (self.pitch + delta_pitch).clamp(-89.0_f32.to_radians(), 89.0_f32.to_radians());
+        self.set_orbit_angles(self.yaw + delta_yaw, self.pitch + delta_pitch);
+    }
+
+    /// Place the camera on its orbit around `target`
+    pub fn set_view(&mut self, target: Vec3, distance: f32, yaw: f32, pitch: f32) {
+        self.target = target;
+        self.distance = self.clamp_distance(distance);
+        self.set_orbit_angles(yaw, pitch);
+    }
+
+    /// Place the camera at the given yaw and pitch around the target
+    pub fn set_orbit_angles(&mut self, yaw: f32, pitch: f32) {
+        self.yaw = yaw;
+        self.pitch = self.limit_pitch(pitch);
         self.update_position_from_orbit();
     }
 
+    /// Enable or disable the pitch clamp, re-limiting the current pitch
+    pub fn set_clamp_pitch(&mut self, clamp: bool) {
+        if self.clamp_pitch != clamp {
+            self.clamp_pitch = clamp;
+            self.set_orbit_angles(self.yaw, self.pitch);
+        }
+    }
+
+    /// Apply the pitch clamp, or wrap to [-pi, pi) when unclamped
+    fn limit_pitch(&self, pitch: f32) -> f32 {
+        if self.clamp_pitch {
+            let limit = MAX_PITCH_DEGREES.to_radians();
+            pitch.clamp(-limit, limit)
+        } else {
+            // Wrap so repeated orbits don't grow without bound
+            (pitch + PI).rem_euclid(TAU) - PI
+        }
+    }
+
     /// Pan the camera (move target)
     pub fn pan(&mut self, delta_x: f32, delta_y: f32) {
         self.pan_with_sensitivity(delta_x, delta_y, 0.002);
@@ -111,12 +256,48 @@ impl Camera {
     }
 
     /// Zoom the camera
+    ///
+    /// The distance stays within `min_distance..=max_distance`. Zooming in past
+    /// the minimum either stops there or, without `stop_at_target`, moves the
+    /// target forward by the remainder so the camera keeps travelling.
     pub fn zoom(&mut self, delta: f32) {
-        self.distance = (self.distance * (1.0 - delta * 0.1)).clamp(0.1, 10000.0);
+        // A large inward step must not flip the camera behind the target
+        let wanted = self.distance * (1.0 - delta * ZOOM_SCALE).max(0.0);
+        if !self.stop_at_target && wanted < self.min_distance {
+            let forward = (self.target - self.position).normalize_or_zero();
+            self.target += forward * (self.min_distance - wanted);
+        }
+        self.distance = self.clamp_distance(wanted);
         self.update_position_from_orbit();
     }
 
-    /// Set field of view in degrees
+    /// Set the zoom distance limits, keeping the current distance within them
+    pub fn set_distance_limits(&mut self, min: f32, max: f32) {
+        self.min_distance = min.max(0.001);
+        self.max_distance = max.max(self.min_distance);
+        self.distance = self.clamp_distance(self.distance);
+        self.update_position_from_orbit();
+    }
+
+    fn clamp_distance(&self, distance: f32) -> f32 {
+        distance.clamp(self.min_distance, self.max_distance)
+    }
+
+    /// Set field of view in degrees, dollying when `dolly_zoom` is enabled
+    ///
+    /// In dolly mode the orbit distance is scaled by `tan(old / 2) / tan(new / 2)`
+    /// so the target keeps the same on-screen size while perspective changes.
+    pub fn set_fov(&mut self, degrees: f32) {
+        let old_fov = self.fov;
+        self.set_fov_degrees(degrees);
+        if self.dolly_zoom {
+            let ratio = (old_fov * 0.5).tan() / (self.fov * 0.5).tan();
+            self.distance = self.clamp_distance(self.distance * ratio);
+            self.update_position_from_orbit();
+        }
+    }
+
+    /// Set field of view in degrees without adjusting distance
     pub fn set_fov_degrees(&mut self, fov_degrees: f32) {
         self.fov = fov_degrees.clamp(10.0, 120.0).to_radians();
     }
@@ -141,34 +322,41 @@ impl Camera {
         let y = self.distance * self.pitch.cos() * self.yaw.sin();
         let z = self.distance * self.pitch.sin();
         self.position = self.target + Vec3::new(x, y, z);
+        self.up = if self.clamp_pitch {
+            Vec3::Z
+        } else {
+            // Tangent of the orbit toward +pitch; stays valid over the poles
+            Vec3::new(
+                -self.pitch.sin() * self.yaw.cos(),
+                -self.pitch.sin() * self.yaw.sin(),
+                self.pitch.cos(),
+            )
+        };
     }
 
-    /// Fit camera to show the given bounding sphere
+    /// Fit camera to show the given bounding sphere, within the zoom limits
     pub fn fit_all(&mut self, center: Vec3, radius: f32) {
         self.target = center;
-        self.distance = (radius * 2.5).max(1.0);
+        self.distance = self.clamp_distance((radius * 2.5).max(1.0));
         self.update_position_from_orbit();
     }
 
     /// Set to top view
     pub fn set_top_view(&mut self) {
-        self.yaw = 0.0;
-        self.pitch = 89.0_f32.to_radians();
-        self.update_position_from_orbit();
+        let (yaw, pitch) = AxisView::PosZ.orbit_angles(0.0);
+        self.set_orbit_angles(yaw, pitch);
     }
 
     /// Set to front view
     pub fn set_front_view(&mut self) {
-        self.yaw = 0.0;
-        self.pitch = 0.0;
-        self.update_position_from_orbit();
+        let (yaw, pitch) = AxisView::PosX.orbit_angles(self.yaw);
+        self.set_orbit_angles(yaw, pitch);
     }
 
     /// Set to side view
     pub fn set_side_view(&mut self) {
-        self.yaw = 90.0_f32.to_radians();
-        self.pitch = 0.0;
-        self.update_position_from_orbit();
+        let (yaw, pitch) = AxisView::PosY.orbit_angles(self.yaw);
+        self.set_orbit_angles(yaw, pitch);
     }
 
     /// Get view matrix
@@ -181,6 +369,19 @@ impl Camera {
         Mat4::perspective_rh(self.fov, self.aspect, self.near, self.far)
     }
 
+    /// Screen scale at the orbit target plane
+    pub fn view_scale(&self) -> ViewScale {
+        ViewScale::Perspective {
+            fov: self.fov,
+            depth: self.distance,
+        }
+    }
+
+    /// World-space length of one pixel at the orbit target plane
+    pub fn world_per_pixel(&self, viewport_height: f32) -> f32 {
+        world_per_pixel(self.view_scale(), viewport_height)
+    }
+
     /// Get camera uniform data
     pub fn uniform(&self) -> CameraUniform {
         let view = self.view_matrix();
@@ -229,4 +430,394 @@ impl Camera {
 
         (ray_origin, ray_direction)
     }
+
+    /// Project a world point to screen coordinates
+    ///
+    /// Returns the pixel position (origin top-left) and the point's depth
+    /// along the view direction, or `None` if it lies behind the near plane.
+    pub fn world_to_screen(
+        &self,
+        point: Vec3,
+        screen_width: f32,
+        screen_height: f32,
+    ) -> Option<(glam::Vec2, f32)> {
+        let view = self.view_matrix().transform_point3(point);
+        let depth = -view.z;
+        if depth < self.near {
+            return None;
+        }
+        let clip = self.projection_matrix() * view.extend(1.0);
+        let ndc = clip.truncate() / clip.w;
+        let screen = glam::Vec2::new(
+            (ndc.x + 1.0) * 0.5 * screen_width,
+            (1.0 - ndc.y) * 0.5 * screen_height,
+        );
+        Some((screen, depth))
+    }
+}
+
+/// Speeds below this (rad/s for orbit, zoom units/s) count as stopped
+const MIN_CAMERA_VELOCITY: f32 = 1e-3;
+
+/// Rate per second at which view transitions close the remaining angle
+const VIEW_TRANSITION_RATE: f32 = 12.0;
+
+/// View transitions snap to the target within this angle (radians)
+const VIEW_TRANSITION_EPSILON: f32 = 1e-3;
+
+/// Signed shortest angle from `from` to `to`
+fn angle_delta(from: f32, to: f32) -> f32 {
+    (to - from + PI).rem_euclid(TAU) - PI
+}
+
+/// Scale a velocity by exponential decay over `dt` seconds
+///
+/// `damping` is the decay rate per second: the velocity falls to `1/e` of its
+/// value after `1 / damping` seconds, independent of the frame rate.
+pub fn decay_velocity(velocity: f32, damping: f32, dt: f32) -> f32 {
+    velocity * (-damping.max(0.0) * dt).exp()
+}
+
+/// Inertial orbit and zoom for a [`Camera`]
+///
+/// While dragging, [`CameraController::track_orbit`] measures the orbit
+/// speed; after release, [`CameraController::advance`] keeps orbiting with
+/// exponentially decaying velocity. Zoom steps are spread out the same way so
+/// that a scroll eases in instead of jumping. [`CameraController::animate_to_view`]
+/// eases the orbit angles to a preset view regardless of inertia.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraController {
+    /// Whether motion continues after input stops.
+    pub inertia: bool,
+    /// Velocity decay rate per second.
+    pub damping: f32,
+    /// Orbit velocity as (yaw, pitch) in radians per second.
+    pub orbit_velocity: glam::Vec2,
+    /// Zoom velocity in `Camera::zoom` units per second.
+    pub zoom_velocity: f32,
+    /// Orbit angles (yaw, pitch) of a view being eased toward.
+    pub view_target: Option<glam::Vec2>,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            inertia: false,
+            damping: 6.0,
+            orbit_velocity: glam::Vec2::ZERO,
+            zoom_velocity: 0.0,
+            view_target: None,
+        }
+    }
+}
+
+impl CameraController {
+    /// Orbit the camera from a drag and record the drag speed
+    pub fn track_orbit(&mut self, camera: &mut Camera, delta_yaw: f32, delta_pitch: f32, dt: f32) {
+        camera.orbit(delta_yaw, delta_pitch);
+        self.orbit_velocity = if self.inertia && dt > 0.0 {
+            glam::Vec2::new(delta_yaw, delta_pitch) / dt
+        } else {
+            glam::Vec2::ZERO
+        };
+    }
+
+    /// Zoom by `delta`, eased over the following frames when inertia is on
+    pub fn zoom(&mut self, camera: &mut Camera, delta: f32) {
+        if self.inertia && self.damping > 0.0 {
+            // The decaying velocity integrates to exactly `delta`
+            self.zoom_velocity += delta * self.damping;
+        } else {
+            camera.zoom(delta);
+        }
+    }
+
+    /// Ease the camera to the view at `yaw`/`pitch` over the next frames
+    pub fn animate_to_view(&mut self, camera: &Camera, yaw: f32, pitch: f32) {
+        self.stop();
+        self.view_target = Some(glam::Vec2::new(yaw, camera.limit_pitch(pitch)));
+    }
+
+    /// Drop any remaining motion
+    pub fn stop(&mut self) {
+        self.orbit_velocity = glam::Vec2::ZERO;
+        self.zoom_velocity = 0.0;
+        self.view_target = None;
+    }
+
+    /// Whether the camera is still coasting
+    pub fn is_moving(&self) -> bool {
+        self.orbit_velocity.length() > MIN_CAMERA_VELOCITY
+            || self.zoom_velocity.abs() > MIN_CAMERA_VELOCITY
+            || self.view_target.is_some()
+    }
+
+    /// Step a view transition; returns whether it is still in progress
+    fn advance_view(&mut self, camera: &mut Camera, target: glam::Vec2, dt: f32) -> bool {
+        let yaw_delta = angle_delta(camera.yaw, target.x);
+        let pitch_delta = target.y - camera.pitch;
+        if yaw_delta.abs().max(pitch_delta.abs()) < VIEW_TRANSITION_EPSILON {
+            camera.set_orbit_angles(camera.yaw + yaw_delta, target.y);
+            self.view_target = None;
+            return false;
+        }
+        let t = 1.0 - decay_velocity(1.0, VIEW_TRANSITION_RATE, dt);
+        camera.set_orbit_angles(camera.yaw + yaw_delta * t, camera.pitch + pitch_delta * t);
+        true
+    }
+
+    /// Advance coasting motion by `dt` seconds
+    ///
+    /// Applies the distance travelled over the step (the integral of the
+    /// decaying velocity) and returns whether the camera is still moving.
+    pub fn advance(&mut self, camera: &mut Camera, dt: f32) -> bool {
+        if let Some(target) = self.view_target {
+            return self.advance_view(camera, target, dt);
+        }
+        if !self.inertia || !self.is_moving() {
+            self.stop();
+            return false;
+        }
+
+        let decay = decay_velocity(1.0, self.damping, dt);
+        // Integral of v * exp(-k t) over [0, dt]
+        let travel = if self.damping > 0.0 {
+            (1.0 - decay) / self.damping
+        } else {
+            dt
+        };
+
+        let orbit = self.orbit_velocity * travel;
+        if orbit != glam::Vec2::ZERO {
+            camera.orbit(orbit.x, orbit.y);
+        }
+        if self.zoom_velocity != 0.0 {
+            camera.zoom(self.zoom_velocity * travel);
+        }
+
+        self.orbit_velocity *= decay;
+        self.zoom_velocity *= decay;
+        if !self.is_moving() {
+            self.stop();
+        }
+        self.is_moving()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_fov_updates_projection_and_dollies() {
+        let mut camera = Camera::new(1.0);
+        camera.set_fov(40.0);
+        let proj_40 = camera.projection_matrix();
+        let distance = camera.distance;
+
+        camera.set_fov(60.0);
+        assert!((camera.fov_degrees() - 60.0).abs() < 1e-4);
+        assert_ne!(camera.projection_matrix(), proj_40);
+        assert_eq!(camera.distance, distance);
+
+        camera.dolly_zoom = true;
+        camera.set_fov(30.0);
+        let expected = distance * 30.0_f32.to_radians().tan() / 15.0_f32.to_radians().tan();
+        assert!((camera.distance - expected).abs() < 1e-4);
+        assert!(((camera.position - camera.target).length() - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_preset_views_match_axis_views() {
+        let mut camera = Camera::new(1.0);
+        camera.orbit(1.0, 0.3);
+
+        camera.set_front_view();
+        assert!(
+            (camera.position - camera.target)
+                .normalize()
+                .abs_diff_eq(Vec3::X, 1e-5)
+        );
+        camera.set_side_view();
+        assert!(
+            (camera.position - camera.target)
+                .normalize()
+                .abs_diff_eq(Vec3::Y, 1e-5)
+        );
+        // Top view stops at the pitch clamp, just short of straight down
+        camera.set_top_view();
+        assert_eq!(camera.yaw, 0.0);
+        assert_eq!(camera.pitch, MAX_PITCH_DEGREES.to_radians());
+    }
+
+    #[test]
+    fn test_world_per_pixel() {
+        // 90 degree FOV at depth 5 shows 10 units over 500 px
+        let perspective = ViewScale::Perspective {
+            fov: 90f32.to_radians(),
+            depth: 5.0,
+        };
+        assert!((world_per_pixel(perspective, 500.0) - 0.02).abs() < 1e-6);
+
+        // Doubling depth doubles the length per pixel
+        let farther = ViewScale::Perspective {
+            fov: 90f32.to_radians(),
+            depth: 10.0,
+        };
+        assert!((world_per_pixel(farther, 500.0) - 0.04).abs() < 1e-6);
+
+        // Orthographic ignores depth: 3 units over 600 px
+        let ortho = ViewScale::Orthographic { height: 3.0 };
+        assert!((world_per_pixel(ortho, 600.0) - 0.005).abs() < 1e-7);
+
+        assert_eq!(world_per_pixel(ortho, 0.0), 0.0);
+
+        let mut camera = Camera::new(1.0);
+        camera.set_fov_degrees(60.0);
+        let expected = 2.0 * camera.distance * 30f32.to_radians().tan() / 400.0;
+        assert!((camera.world_per_pixel(400.0) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_orbit_pitch_clamp() {
+        let limit = MAX_PITCH_DEGREES.to_radians();
+        let mut camera = Camera::new(1.0);
+        for _ in 0..50 {
+            camera.orbit(0.0, 0.1);
+            assert!(camera.pitch <= limit + 1e-6);
+        }
+        assert!((camera.pitch - limit).abs() < 1e-6);
+        assert!(camera.position.z > camera.target.z);
+
+        // Without the clamp the camera goes over the top and comes down behind
+        camera.clamp_pitch = false;
+        let before = camera.position - camera.target;
+        for _ in 0..10 {
+            camera.orbit(0.0, 0.1);
+        }
+        let after = camera.position - camera.target;
+        assert!(camera.pitch > limit);
+        assert!(after.truncate().dot(before.truncate()) < 0.0);
+        assert!(camera.view_matrix().is_finite());
+        assert!(camera.up.dot(after).abs() < 1e-4);
+
+        // Turning the clamp back on brings the camera back within the limit
+        camera.set_clamp_pitch(true);
+        assert!(camera.pitch <= limit + 1e-6);
+        assert_eq!(camera.up, Vec3::Z);
+    }
+
+    #[test]
+    fn test_inertia_decays_to_rest() {
+        // e^(-6 * 1.5) is about 1e-4, so 2 rad/s stops within 1.5 s
+        assert!(decay_velocity(2.0, 6.0, 1.5) < MIN_CAMERA_VELOCITY);
+        assert!(decay_velocity(2.0, 6.0, 0.5) > MIN_CAMERA_VELOCITY);
+        // Frame-rate independent
+        let stepped = (0..60).fold(2.0, |v, _| decay_velocity(v, 6.0, 1.0 / 60.0));
+        assert!((stepped - decay_velocity(2.0, 6.0, 1.0)).abs() < 1e-5);
+
+        let mut camera = Camera::new(1.0);
+        let mut controller = CameraController {
+            inertia: true,
+            ..Default::default()
+        };
+        let start_yaw = camera.yaw;
+        controller.track_orbit(&mut camera, 0.05, 0.0, 1.0 / 60.0);
+        assert!((controller.orbit_velocity.x - 3.0).abs() < 1e-4);
+
+        let dt = 1.0 / 60.0;
+        let mut frames = 0;
+        while controller.advance(&mut camera, dt) {
+            frames += 1;
+            assert!(frames < 120, "still moving after 2 s");
+        }
+        assert!(frames > 30);
+        assert_eq!(controller.orbit_velocity, glam::Vec2::ZERO);
+        // Coasting covers v / k beyond the tracked step
+        let coasted = camera.yaw - start_yaw - 0.05;
+        assert!((coasted - 3.0 / 6.0).abs() < 1e-3, "coasted {coasted}");
+
+        // A scroll step eases in but lands where an instant zoom would
+        let mut eased = Camera::new(1.0);
+        let mut instant = Camera::new(1.0);
+        controller.zoom(&mut eased, 1.0);
+        instant.zoom(1.0);
+        assert_eq!(eased.distance, Camera::new(1.0).distance);
+        while controller.advance(&mut eased, dt) {}
+        assert!((eased.distance - instant.distance).abs() / instant.distance < 0.05);
+    }
+
+    #[test]
+    fn test_zoom_clamps_at_min_distance() {
+        let mut camera = Camera::new(1.0);
+        camera.set_distance_limits(0.5, 50.0);
+        let forward = (camera.target - camera.position).normalize();
+
+        // A step large enough to overshoot the target stops at the minimum
+        camera.zoom(25.0);
+        assert_eq!(camera.distance, 0.5);
+        assert_eq!(camera.target, Vec3::ZERO);
+        let still_forward = (camera.target - camera.position).normalize();
+        assert!(forward.dot(still_forward) > 0.999);
+
+        camera.zoom(-1000.0);
+        assert_eq!(camera.distance, 50.0);
+
+        // Without the stop, zooming past the minimum pushes the target ahead
+        camera.stop_at_target = false;
+        camera.set_distance_limits(0.5, 50.0);
+        camera.distance = 0.5;
+        camera.zoom(5.0);
+        assert_eq!(camera.distance, 0.5);
+        assert!(camera.target.dot(forward) > 0.0);
+
+        // Fitting a huge scene also respects the limits
+        camera.fit_all(Vec3::ZERO, 1000.0);
+        assert_eq!(camera.distance, 50.0);
+    }
+
+    #[test]
+    fn test_view_transition_takes_short_way_round() {
+        let mut camera = Camera::new(1.0);
+        camera.set_orbit_angles(170f32.to_radians(), 0.0);
+        let mut controller = CameraController::default();
+        controller.animate_to_view(&camera, -170f32.to_radians(), 90f32.to_radians());
+
+        // Pitch target is clamped, so the transition can finish
+        let mut frames = 0;
+        while controller.advance(&mut camera, 1.0 / 60.0) {
+            // Crossing +/-180 degrees, never back through 0
+            assert!(camera.yaw.cos() < 0.0);
+            frames += 1;
+            assert!(frames < 300, "transition did not settle");
+        }
+        assert!(angle_delta(camera.yaw, -170f32.to_radians()).abs() < 1e-3);
+        assert!((camera.pitch - MAX_PITCH_DEGREES.to_radians()).abs() < 1e-6);
+        assert!(!controller.is_moving());
+    }
+
+    #[test]
+    fn test_world_to_screen() {
+        let mut camera = Camera::new(1.0);
+        camera.set_view(Vec3::new(1.0, 2.0, 0.5), 4.0, 0.3, 0.4);
+        let (width, height) = (800.0, 600.0);
+        camera.aspect = width / height;
+
+        // The orbit target lands in the center at the orbit distance
+        let (center, depth) = camera
+            .world_to_screen(camera.target, width, height)
+            .unwrap();
+        assert!(center.abs_diff_eq(glam::Vec2::new(400.0, 300.0), 1e-3));
+        assert!((depth - 4.0).abs() < 1e-4);
+
+        // Round trip through screen_to_ray
+        let (origin, direction) = camera.screen_to_ray(120.0, 450.0, width, height);
+        let point = origin + direction * 3.0;
+        let (screen, _) = camera.world_to_screen(point, width, height).unwrap();
+        assert!(screen.abs_diff_eq(glam::Vec2::new(120.0, 450.0), 1e-2));
+
+        // Points behind the camera are not projected
+        let behind = camera.position + (camera.position - camera.target);
+        assert!(camera.world_to_screen(behind, width, height).is_none());
+    }
 }
@@ -0,0 +1,300 @@
+//! Offscreen capture of color, depth and world-normal buffers
+//!
+//! [`Renderer::capture_buffers`](crate::Renderer::capture_buffers) draws the
+//! visual meshes into single-sampled textures that can be sampled by other
+//! GPU passes (ambient occlusion, compositing) or read back to the CPU.
+
+use std::sync::mpsc;
+
+/// Format of the captured depth buffer.
+pub const CAPTURE_DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Format of the captured normal buffer: world-space normal in xyz, coverage in w.
+pub const CAPTURE_NORMAL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Color, depth and world-normal textures of one captured frame.
+pub struct CapturedBuffers {
+    /// Shaded color in the renderer's surface format.
+    pub color: wgpu::Texture,
+    /// Depth in `[0, 1]`; `1.0` where no mesh was drawn.
+    pub depth: wgpu::Texture,
+    /// World-space unit normals; `w` is `1.0` where a mesh was drawn, `0.0` elsewhere.
+    pub normal: wgpu::Texture,
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+}
+
+impl CapturedBuffers {
+    pub(crate) fn new(
+        device: &wgpu::Device,
+        color_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        let target = |label, format| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            })
+        };
+        Self {
+            color: target("Captured Color", color_format),
+            depth: target("Captured Depth", CAPTURE_DEPTH_FORMAT),
+            normal: target("Captured Normal", CAPTURE_NORMAL_FORMAT),
+            width,
+            height,
+        }
+    }
+
+    /// Read the depth buffer back to the CPU, row by row from the top.
+    ///
+    /// Returns `None` if the GPU readback failed.
+    pub fn read_depth(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Option<Vec<f32>> {
+        let linear = copy_depth(device, queue, &self.depth);
+        let bytes = read_texture(device, queue, &linear)?;
+        Some(bytemuck::cast_slice(&bytes).to_vec())
+    }
+
+    /// Read the normal buffer back to the CPU, row by row from the top.
+    ///
+    /// Returns `None` if the GPU readback failed.
+    pub fn read_normals(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Option<Vec<[f32; 4]>> {
+        let bytes = read_texture(device, queue, &self.normal)?;
+        let halves: &[[u16; 4]] = bytemuck::cast_slice(&bytes);
+        Some(halves.iter().map(|texel| texel.map(f16_to_f32)).collect())
+    }
+}
+
+/// Draw a depth texture into an `Rgba8Unorm` texture holding the raw f32 bytes
+///
+/// Downlevel backends can neither copy depth textures into buffers nor render
+/// to 32-bit float targets, so depth is read back bit-exact through bytes.
+fn copy_depth(device: &wgpu::Device, queue: &wgpu::Queue, depth: &wgpu::Texture) -> wgpu::Texture {
+    let target = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Captured Depth Copy"),
+        size: depth.size(),
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("Depth Copy Shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/depth_copy.wgsl").into()),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Depth Copy Bind Group Layout"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        }],
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("Depth Copy Pipeline Layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Depth Copy Pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+
+    let depth_view = depth.create_view(&wgpu::TextureViewDescriptor {
+        aspect: wgpu::TextureAspect::DepthOnly,
+        ..Default::default()
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Depth Copy Bind Group"),
+        layout: &bind_group_layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(&depth_view),
+        }],
+    });
+
+    let target_view = target.create_view(&wgpu::TextureViewDescriptor::default());
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Depth Copy Encoder"),
+    });
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Depth Copy Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target_view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+    queue.submit([encoder.finish()]);
+    target
+}
+
+/// Device for rendering without a window.
+///
+/// Prefers the software fallback adapter so results don't depend on the GPU,
+/// and uses any adapter when there is none. Returns `None` without an adapter.
+pub async fn headless_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::default();
+    let fallback = wgpu::RequestAdapterOptions {
+        force_fallback_adapter: true,
+        ..Default::default()
+    };
+    let adapter = match instance.request_adapter(&fallback).await {
+        Ok(adapter) => adapter,
+        Err(_) => instance.request_adapter(&Default::default()).await.ok()?,
+    };
+    adapter.request_device(&Default::default()).await.ok()
+}
+
+/// Copy a color texture back to the CPU and return its tightly packed texels,
+/// row by row from the top.
+///
+/// The texture needs `COPY_SRC` usage. Returns `None` for depth/stencil
+/// formats or if the GPU readback failed.
+pub fn read_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+) -> Option<Vec<u8>> {
+    let bytes_per_texel = texture.format().block_copy_size(None)?;
+    let size = texture.size();
+    let row_bytes = size.width * bytes_per_texel;
+    let padded_row_bytes =
+        row_bytes.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Capture Readback Buffer"),
+        size: (padded_row_bytes * size.height) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Capture Readback Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_bytes),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+    queue.submit([encoder.finish()]);
+
+    let (sender, receiver) = mpsc::channel();
+    buffer
+        .slice(..)
+        .map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+    device.poll(wgpu::PollType::wait_indefinitely()).ok()?;
+    receiver.try_recv().ok()?.ok()?;
+
+    let mapped = buffer.slice(..).get_mapped_range();
+    let texels = mapped
+        .chunks_exact(padded_row_bytes as usize)
+        .flat_map(|row| &row[..row_bytes as usize])
+        .copied()
+        .collect();
+    Some(texels)
+}
+
+/// Widen an IEEE 754 half-precision float.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f32::from(bits & 0x3ff);
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f16_to_f32() {
+        assert_eq!(f16_to_f32(0x0000), 0.0);
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xbc00), -1.0);
+        assert_eq!(f16_to_f32(0x3800), 0.5);
+        assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+        assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+        assert!(f16_to_f32(0x7e00).is_nan());
+    }
+}
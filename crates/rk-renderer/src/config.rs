@@ -20,6 +20,14 @@ pub struct GridConfig {
     pub x_axis_color: [f32; 3],
     /// Y-axis color (RGB)
     pub y_axis_color: [f32; 3],
+    /// Distance from the camera at which the grid has faded out; lines also
+    /// fade towards the grid edges. 0 disables fading.
+    #[serde(default = "default_grid_fade_radius")]
+    pub fade_radius: f32,
+}
+
+fn default_grid_fade_radius() -> f32 {
+    crate::constants::grid::DEFAULT_FADE_RADIUS
 }
 
 impl Default for GridConfig {
@@ -31,6 +39,7 @@ impl Default for GridConfig {
             line_color: [0.3, 0.3, 0.3],
             x_axis_color: [0.8, 0.2, 0.2],
             y_axis_color: [0.2, 0.8, 0.2],
+            fade_radius: default_grid_fade_radius(),
         }
     }
 }
@@ -42,6 +51,12 @@ pub struct ViewportConfig {
     pub background_color: [f32; 4],
     /// MSAA sample count (1 = disabled, 2, 4, 8)
     pub msaa_sample_count: u32,
+    /// Derive the background from the UI theme instead of `background_color`
+    #[serde(default)]
+    pub background_follows_theme: bool,
+    /// Pick front faces over nearer back faces (inverted meshes stay pickable)
+    #[serde(default)]
+    pub pick_prefer_front_faces: bool,
 }
 
 impl Default for ViewportConfig {
@@ -49,6 +64,8 @@ impl Default for ViewportConfig {
         Self {
             background_color: [0.15, 0.15, 0.18, 1.0],
             msaa_sample_count: 4,
+            background_follows_theme: false,
+            pick_prefer_front_faces: false,
         }
     }
 }
@@ -66,6 +83,9 @@ pub struct ShadowConfig {
     pub normal_bias: f32,
     /// Shadow softness (PCF filter size)
     pub softness: f32,
+    /// Only the ground receives shadows (no self-shadowing on parts)
+    #[serde(default)]
+    pub ground_shadow_only: bool,
 }
 
 impl Default for ShadowConfig {
@@ -76,6 +96,7 @@ impl Default for ShadowConfig {
             bias: 0.005,
             normal_bias: 0.01,
             softness: 1.0,
+            ground_shadow_only: false,
         }
     }
 }
@@ -93,6 +114,9 @@ pub struct LightingConfig {
     pub ambient_color: [f32; 3],
     /// Ambient light strength
     pub ambient_strength: f32,
+    /// Light back faces by flipping their normal toward the camera
+    #[serde(default)]
+    pub two_sided: bool,
 }
 
 impl Default for LightingConfig {
@@ -103,6 +127,7 @@ impl Default for LightingConfig {
             intensity: 1.0,
             ambient_color: [1.0, 1.0, 1.0],
             ambient_strength: 0.3,
+            two_sided: false,
         }
     }
 }
@@ -122,6 +147,47 @@ pub struct CameraConfig {
     pub zoom_sensitivity: f32,
     /// Orbit sensitivity multiplier
     pub orbit_sensitivity: f32,
+    /// Adjust orbit distance on FOV changes to keep the subject size constant
+    #[serde(default)]
+    pub dolly_zoom: bool,
+    /// Keep orbit pitch short of straight up/down so the view never flips
+    #[serde(default = "default_clamp_pitch")]
+    pub clamp_pitch: bool,
+    /// Keep orbiting and zooming briefly after input stops
+    #[serde(default)]
+    pub inertia: bool,
+    /// Inertia velocity decay rate per second
+    #[serde(default = "default_inertia_damping")]
+    pub inertia_damping: f32,
+    /// Closest zoom distance to the orbit target
+    #[serde(default = "default_min_zoom_distance")]
+    pub min_zoom_distance: f32,
+    /// Farthest zoom distance from the orbit target
+    #[serde(default = "default_max_zoom_distance")]
+    pub max_zoom_distance: f32,
+    /// Stop zooming in at the minimum distance instead of pushing the target forward
+    #[serde(default = "default_stop_zoom_at_target")]
+    pub stop_zoom_at_target: bool,
+}
+
+fn default_inertia_damping() -> f32 {
+    6.0
+}
+
+fn default_min_zoom_distance() -> f32 {
+    crate::constants::camera::MIN_DISTANCE
+}
+
+fn default_max_zoom_distance() -> f32 {
+    crate::constants::camera::MAX_DISTANCE
+}
+
+fn default_stop_zoom_at_target() -> bool {
+    true
+}
+
+fn default_clamp_pitch() -> bool {
+    true
 }
 
 impl Default for CameraConfig {
@@ -133,6 +199,13 @@ impl Default for CameraConfig {
             pan_sensitivity: 0.002,
             zoom_sensitivity: 0.1,
             orbit_sensitivity: 0.005,
+            dolly_zoom: false,
+            clamp_pitch: true,
+            inertia: false,
+            inertia_damping: default_inertia_damping(),
+            min_zoom_distance: default_min_zoom_distance(),
+            max_zoom_distance: default_max_zoom_distance(),
+            stop_zoom_at_target: default_stop_zoom_at_target(),
         }
     }
 }
@@ -164,6 +237,39 @@ impl Default for GizmoConfig {
     }
 }
 
+/// Sketch rendering configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SketchConfig {
+    /// Depth offset towards the camera so sketches on faces draw above them
+    pub depth_offset: f32,
+}
+
+impl Default for SketchConfig {
+    fn default() -> Self {
+        Self {
+            depth_offset: crate::constants::sketch::DEFAULT_DEPTH_OFFSET,
+        }
+    }
+}
+
+/// Selection highlight configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SelectionConfig {
+    /// Highlight color of the selected part (RGB)
+    pub color: [f32; 3],
+    /// Draw the selected part's outline through occluding geometry
+    pub show_occluded: bool,
+}
+
+impl Default for SelectionConfig {
+    fn default() -> Self {
+        Self {
+            color: crate::constants::selection::DEFAULT_COLOR,
+            show_occluded: true,
+        }
+    }
+}
+
 /// Complete renderer configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct RendererConfig {
@@ -185,6 +291,12 @@ pub struct RendererConfig {
     /// Gizmo settings
     #[serde(default)]
     pub gizmo: GizmoConfig,
+    /// Sketch settings
+    #[serde(default)]
+    pub sketch: SketchConfig,
+    /// Selection highlight settings
+    #[serde(default)]
+    pub selection: SelectionConfig,
 }
 
 impl RendererConfig {
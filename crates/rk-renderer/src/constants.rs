@@ -15,6 +15,12 @@ pub mod grid {
     pub const X_AXIS_COLOR: [f32; 3] = [0.8, 0.2, 0.2];
     /// Y-axis color (green)
     pub const Y_AXIS_COLOR: [f32; 3] = [0.2, 0.8, 0.2];
+    /// Default distance from the camera at which the grid has faded out
+    pub const DEFAULT_FADE_RADIUS: f32 = 15.0;
+    /// Fraction of the grid half-size over which lines fade towards the edge
+    pub const EDGE_FADE_FRACTION: f32 = 0.25;
+    /// Grid line opacity before fading
+    pub const LINE_ALPHA: f32 = 0.8;
 }
 
 /// Gizmo rendering constants
@@ -63,12 +69,32 @@ pub mod gizmo {
     }
 }
 
-/// Marker (sphere) rendering constants
+/// Marker rendering constants
 pub mod marker {
     /// Number of horizontal segments for sphere
     pub const SEGMENTS: u32 = 16;
     /// Number of vertical rings for sphere
     pub const RINGS: u32 = 12;
+    /// Cross bar half-thickness relative to the marker size
+    pub const CROSS_THICKNESS: f32 = 0.15;
+    /// Scene radius (m) at which markers are drawn at their own size
+    pub const REFERENCE_SCENE_RADIUS: f32 = 0.5;
+    /// Smallest scene-fitted marker scale
+    pub const MIN_SCENE_SCALE: f32 = 0.1;
+    /// Largest scene-fitted marker scale
+    pub const MAX_SCENE_SCALE: f32 = 20.0;
+}
+
+/// Sketch rendering constants
+pub mod sketch {
+    /// Default depth offset pulling sketches towards the camera (NDC depth units)
+    pub const DEFAULT_DEPTH_OFFSET: f32 = 1e-5;
+}
+
+/// Selection highlight constants
+pub mod selection {
+    /// Default selection highlight color (orange)
+    pub const DEFAULT_COLOR: [f32; 3] = [1.0, 0.6, 0.2];
 }
 
 /// Instance buffer limits
@@ -147,4 +173,11 @@ pub mod shadow {
     pub const DEFAULT_BIAS: f32 = 0.005;
     /// Default normal-based shadow bias for grazing angles
     pub const DEFAULT_NORMAL_BIAS: f32 = 0.01;
+    /// Half-extent of the ground shadow catcher plane (world units)
+    pub const GROUND_HALF_SIZE: f32 = 100.0;
+    /// Depth kept behind the fitted casters so receivers such as the ground
+    /// stay inside the shadow frustum (world units)
+    pub const FIT_RECEIVER_DEPTH: f32 = 2.0 * GROUND_HALF_SIZE;
+    /// Smallest light-space extent of a fitted shadow frustum (world units)
+    pub const FIT_MIN_EXTENT: f32 = 1e-3;
 }
@@ -28,7 +28,9 @@ pub struct RenderContext {
 }
 
 impl RenderContext {
-    /// Creates a new render context.
+    /// Creates a new render context from shared device and queue handles.
+    ///
+    /// See [`from_device`](Self::from_device) for borrowed handles.
     pub fn new(
         device: Arc<wgpu::Device>,
         queue: Arc<wgpu::Queue>,
@@ -86,6 +88,45 @@ impl RenderContext {
         }
     }
 
+    /// Creates a render context for an existing device and queue.
+    ///
+    /// This is the entry point for embedding the renderer outside egui: any
+    /// `wgpu` device works, including one without a surface. `surface_format`,
+    /// `width` and `height` must match the [`Renderer`](crate::Renderer) the
+    /// context is used with; [`Renderer::create_context`](crate::Renderer::create_context)
+    /// builds a matching one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rk_renderer::{RenderContext, Renderer};
+    ///
+    /// let instance = wgpu::Instance::default();
+    /// let adapter = pollster::block_on(instance.request_adapter(&Default::default())).unwrap();
+    /// let (device, queue) =
+    ///     pollster::block_on(adapter.request_device(&Default::default())).unwrap();
+    ///
+    /// let format = wgpu::TextureFormat::Rgba8Unorm;
+    /// let renderer = Renderer::new(&device, format, 640, 480);
+    /// let ctx = RenderContext::from_device(&device, &queue, format, 640, 480);
+    /// assert_eq!(ctx.surface_format(), renderer.format());
+    /// ```
+    pub fn from_device(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        surface_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        Self::new(
+            Arc::new(device.clone()),
+            Arc::new(queue.clone()),
+            surface_format,
+            width,
+            height,
+        )
+    }
+
     /// Returns the wgpu device.
     pub fn device(&self) -> &wgpu::Device {
         &self.device
@@ -0,0 +1,298 @@
+//! Display options controlling which scene elements are drawn
+//!
+//! Every draw belongs to a [`RenderLayer`], and a visibility bitmask hides whole
+//! layers at once. Within a layer, elements can be toggled globally, and visual meshes
+//! and collision shapes additionally have per-link overrides keyed by the part
+//! ID that represents the link. Shadow casting and receiving follow the same
+//! scheme.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::scene::RenderLayer;
+
+/// Visibility settings for the scene's draws.
+#[derive(Debug, Clone)]
+pub struct DisplayOptions {
+    /// Mask of layers drawn at all (see [`RenderLayer::mask`]); individual
+    /// toggles apply within a visible layer
+    pub layers: u32,
+    /// Draw the ground grid
+    pub show_grid: bool,
+    /// Draw part coordinate axes
+    pub show_axes: bool,
+    /// Draw joint markers
+    pub show_markers: bool,
+    /// Draw each link's inertial frame
+    pub show_inertial_frames: bool,
+    /// Draw visual meshes
+    pub show_visuals: bool,
+    /// Draw collision shapes
+    pub show_collisions: bool,
+    /// Parts whose visual mesh is hidden regardless of `show_visuals`
+    hidden_visuals: HashSet<Uuid>,
+    /// Parts whose collision shapes are hidden regardless of `show_collisions`
+    hidden_collisions: HashSet<Uuid>,
+    /// Only the ground receives shadows; parts are lit without self-shadowing
+    pub ground_shadow_only: bool,
+    /// Parts excluded from the shadow pass
+    non_casting: HashSet<Uuid>,
+    /// Parts drawn without sampling the shadow map
+    non_receiving: HashSet<Uuid>,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            layers: RenderLayer::ALL_MASK,
+            show_grid: true,
+            show_axes: true,
+            show_markers: true,
+            show_inertial_frames: false,
+            show_visuals: true,
+            show_collisions: true,
+            hidden_visuals: HashSet::new(),
+            hidden_collisions: HashSet::new(),
+            ground_shadow_only: false,
+            non_casting: HashSet::new(),
+            non_receiving: HashSet::new(),
+        }
+    }
+}
+
+/// Draws issued by the main render pass for a given set of display options.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MainPassDraws {
+    /// Visual meshes to draw
+    pub visuals: Vec<Uuid>,
+    /// Whether the collision shapes are drawn
+    pub collisions: bool,
+    /// Visual meshes rendered into the shadow map
+    pub shadow_casters: Vec<Uuid>,
+    /// Whether the grid is drawn
+    pub grid: bool,
+    /// Whether part axes are drawn
+    pub axes: bool,
+    /// Whether joint markers are drawn
+    pub markers: bool,
+    /// Whether inertial frames are drawn
+    pub inertial_frames: bool,
+}
+
+impl DisplayOptions {
+    /// Check whether a layer is drawn.
+    pub fn is_layer_visible(&self, layer: RenderLayer) -> bool {
+        layer.is_visible_in(self.layers)
+    }
+
+    /// Show or hide a layer.
+    pub fn set_layer_visible(&mut self, layer: RenderLayer, visible: bool) {
+        if visible {
+            self.layers |= layer.mask();
+        } else {
+            self.layers &= !layer.mask();
+        }
+    }
+
+    /// Check whether the visual mesh of a part is drawn.
+    pub fn is_visual_visible(&self, part_id: Uuid) -> bool {
+        self.is_layer_visible(RenderLayer::Default)
+            && self.show_visuals
+            && !self.hidden_visuals.contains(&part_id)
+    }
+
+    /// Check whether the collision shapes of a part's link are drawn.
+    pub fn is_collision_visible(&self, part_id: Uuid) -> bool {
+        self.is_layer_visible(RenderLayer::Collision)
+            && self.show_collisions
+            && !self.hidden_collisions.contains(&part_id)
+    }
+
+    /// Check whether a part is rendered into the shadow map.
+    pub fn casts_shadow(&self, part_id: Uuid) -> bool {
+        !self.non_casting.contains(&part_id)
+    }
+
+    /// Check whether a part is shaded with shadows from other geometry.
+    pub fn receives_shadow(&self, part_id: Uuid) -> bool {
+        !self.ground_shadow_only && !self.non_receiving.contains(&part_id)
+    }
+
+    /// Replace the per-part shadow overrides.
+    ///
+    /// Returns true if either set changed.
+    pub fn set_shadow_overrides(&mut self, non_casting: &[Uuid], non_receiving: &[Uuid]) -> bool {
+        let casting_changed = replace_if_changed(&mut self.non_casting, non_casting);
+        replace_if_changed(&mut self.non_receiving, non_receiving) || casting_changed
+    }
+
+    /// Replace the per-part visual overrides.
+    ///
+    /// Returns true if the set changed.
+    pub fn set_hidden_visuals(&mut self, part_ids: &[Uuid]) -> bool {
+        replace_if_changed(&mut self.hidden_visuals, part_ids)
+    }
+
+    /// Replace the per-part collision overrides.
+    ///
+    /// Returns true if the set changed.
+    pub fn set_hidden_collisions(&mut self, part_ids: &[Uuid]) -> bool {
+        replace_if_changed(&mut self.hidden_collisions, part_ids)
+    }
+
+    /// Parts whose visual mesh is hidden individually.
+    pub fn hidden_visuals(&self) -> &HashSet<Uuid> {
+        &self.hidden_visuals
+    }
+
+    /// Parts whose collision shapes are hidden individually.
+    pub fn hidden_collisions(&self) -> &HashSet<Uuid> {
+        &self.hidden_collisions
+    }
+
+    /// Decide which draws the main pass issues.
+    pub fn main_pass_draws(
+        &self,
+        mesh_ids: impl IntoIterator<Item = Uuid>,
+        has_collisions: bool,
+    ) -> MainPassDraws {
+        let visuals: Vec<Uuid> = mesh_ids
+            .into_iter()
+            .filter(|id| self.is_visual_visible(*id))
+            .collect();
+        let shadow_casters = visuals
+            .iter()
+            .copied()
+            .filter(|id| self.casts_shadow(*id))
+            .collect();
+        let annotations = self.is_layer_visible(RenderLayer::Annotations);
+        MainPassDraws {
+            visuals,
+            collisions: self.is_layer_visible(RenderLayer::Collision)
+                && self.show_collisions
+                && has_collisions,
+            shadow_casters,
+            grid: annotations && self.show_grid,
+            axes: annotations && self.show_axes,
+            markers: annotations && self.show_markers,
+            inertial_frames: annotations && self.show_inertial_frames,
+        }
+    }
+}
+
+fn replace_if_changed(set: &mut HashSet<Uuid>, ids: &[Uuid]) -> bool {
+    let new_set: HashSet<Uuid> = ids.iter().copied().collect();
+    if *set == new_set {
+        return false;
+    }
+    *set = new_set;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hiding_visuals_keeps_collisions() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let mut options = DisplayOptions::default();
+
+        let draws = options.main_pass_draws(ids, true);
+        assert_eq!(draws.visuals.len(), 2);
+        assert!(draws.collisions);
+
+        options.show_visuals = false;
+        let draws = options.main_pass_draws(ids, true);
+        assert!(draws.visuals.is_empty());
+        assert!(draws.collisions);
+
+        options.show_visuals = true;
+        options.show_collisions = false;
+        let draws = options.main_pass_draws(ids, true);
+        assert_eq!(draws.visuals.len(), 2);
+        assert!(!draws.collisions);
+    }
+
+    #[test]
+    fn test_per_part_overrides() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let mut options = DisplayOptions::default();
+
+        assert!(options.set_hidden_visuals(&[a]));
+        assert!(!options.set_hidden_visuals(&[a]));
+        assert!(options.set_hidden_collisions(&[b]));
+
+        assert_eq!(options.main_pass_draws([a, b], true).visuals, vec![b]);
+        assert!(options.is_collision_visible(a));
+        assert!(!options.is_collision_visible(b));
+        assert!(!options.is_visual_visible(a));
+    }
+
+    #[test]
+    fn test_non_casting_part_skipped_in_shadow_pass() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let mut options = DisplayOptions::default();
+        assert_eq!(
+            options.main_pass_draws([a, b], false).shadow_casters.len(),
+            2
+        );
+
+        assert!(options.set_shadow_overrides(&[a], &[]));
+        assert!(!options.set_shadow_overrides(&[a], &[]));
+        let draws = options.main_pass_draws([a, b], false);
+        assert_eq!(draws.visuals.len(), 2);
+        assert_eq!(draws.shadow_casters, vec![b]);
+
+        // Hidden parts never cast
+        options.set_hidden_visuals(&[b]);
+        assert!(
+            options
+                .main_pass_draws([a, b], false)
+                .shadow_casters
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_ground_shadow_only_disables_receivers() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let mut options = DisplayOptions::default();
+        options.set_shadow_overrides(&[], &[a]);
+        assert!(!options.receives_shadow(a));
+        assert!(options.receives_shadow(b));
+
+        options.ground_shadow_only = true;
+        assert!(!options.receives_shadow(b));
+        assert!(options.casts_shadow(b));
+    }
+
+    #[test]
+    fn test_hidden_annotations_layer_skips_axes_and_markers() {
+        let id = Uuid::new_v4();
+        let mut options = DisplayOptions::default();
+        let draws = options.main_pass_draws([id], true);
+        assert!(draws.grid && draws.axes && draws.markers);
+        // Inertial frames are opt-in
+        assert!(!draws.inertial_frames);
+        options.show_inertial_frames = true;
+        assert!(options.main_pass_draws([id], true).inertial_frames);
+
+        options.set_layer_visible(RenderLayer::Annotations, false);
+        let draws = options.main_pass_draws([id], true);
+        assert!(!draws.grid && !draws.axes && !draws.markers && !draws.inertial_frames);
+        // Other layers are unaffected
+        assert_eq!(draws.visuals, vec![id]);
+        assert!(draws.collisions);
+
+        options.layers = RenderLayer::Annotations.mask();
+        let draws = options.main_pass_draws([id], true);
+        assert!(draws.axes);
+        assert!(draws.visuals.is_empty() && draws.shadow_casters.is_empty());
+        assert!(!draws.collisions);
+    }
+}
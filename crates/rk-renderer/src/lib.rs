@@ -22,7 +22,9 @@
 //! ├── resources/       # Resource management (MeshManager)
 //! ├── plugin.rs        # Plugin system (RendererRegistry)
 //! ├── sub_renderers/   # Built-in renderers (Grid, Mesh, Axis, Marker, Gizmo)
+//! ├── display.rs       # Visual/collision display options
 //! ├── camera.rs        # Camera system
+//! ├── capture.rs       # Color/depth/normal buffer capture
 //! ├── pipeline.rs      # Pipeline utilities
 //! └── renderer.rs      # Main Renderer
 //! ```
@@ -37,7 +39,9 @@ pub mod traits;
 
 // Rendering infrastructure
 pub mod camera;
+pub mod capture;
 pub mod constants;
+pub mod display;
 pub mod instanced;
 pub mod light;
 pub mod pipeline;
@@ -69,18 +73,20 @@ pub mod mesh {
 
 // Re-exports for convenience
 pub use camera::*;
+pub use capture::{CapturedBuffers, headless_device, read_texture};
 pub use config::RendererConfig;
 pub use context::RenderContext;
+pub use display::DisplayOptions;
 pub use light::{DirectionalLight, LightUniform};
 pub use plugin::{RendererPlugin, RendererRegistry};
 pub use renderer::*;
 pub use resources::MeshData as ResourceMeshData;
 pub use resources::{GpuMesh, MeshHandle, MeshManager};
-pub use scene::{BoundingBox, Frustum, RenderLayer, RenderObject, Scene};
+pub use scene::{BoundingBox, Frustum, Ray, RayHit, RenderLayer, RenderObject, Scene};
 pub use sub_renderers::{
     AxisInstance, AxisRenderer, GizmoAxis, GizmoMode, GizmoRenderer, GizmoSpace, GridRenderer,
-    GridSubRenderer, MarkerInstance, MarkerRenderer, MeshRenderer, SketchRenderData,
-    SketchRenderer, SketchVertex,
+    GridSubRenderer, MarkerInstance, MarkerRenderer, MarkerStyle, MeshRenderer, PointStyle,
+    SketchPointVertex, SketchRenderData, SketchRenderer, SketchVertex, scene_marker_scale,
 };
 pub use traits::{PassType, SubRenderer};
 pub use vertex::MeshVertex;
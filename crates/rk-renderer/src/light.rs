@@ -3,7 +3,9 @@
 use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, Vec3};
 
+use crate::config::LightingConfig;
 use crate::constants::shadow;
+use crate::scene::BoundingBox;
 
 /// Light uniform buffer data sent to GPU (128 bytes)
 #[repr(C)]
@@ -11,7 +13,7 @@ use crate::constants::shadow;
 pub struct LightUniform {
     /// Light view-projection matrix for shadow mapping
     pub light_view_proj: [[f32; 4]; 4],
-    /// Light direction (normalized, world space) - xyz = direction, w = unused
+    /// Light direction (normalized, world space) - xyz = direction, w = two-sided (1.0 or 0.0)
     pub direction: [f32; 4],
     /// Light color (RGB) and intensity (A)
     pub color_intensity: [f32; 4],
@@ -25,7 +27,7 @@ impl Default for LightUniform {
     fn default() -> Self {
         Self {
             light_view_proj: Mat4::IDENTITY.to_cols_array_2d(),
-            direction: [0.5, 0.5, 1.0, 0.0],
+            direction: [0.5, 0.5, 1.0, 1.0],
             color_intensity: [1.0, 1.0, 1.0, 1.0],
             ambient: [1.0, 1.0, 1.0, 0.3],
             shadow_params: [shadow::DEFAULT_BIAS, shadow::DEFAULT_NORMAL_BIAS, 1.0, 1.0],
@@ -57,6 +59,8 @@ pub struct DirectionalLight {
     pub shadow_softness: f32,
     /// Enable/disable shadow rendering
     pub shadows_enabled: bool,
+    /// Light back faces as if they faced the camera
+    pub two_sided: bool,
     /// Orthographic projection half-size for shadow map (world units)
     pub ortho_size: f32,
     /// Near plane for shadow projection
@@ -84,6 +88,7 @@ impl DirectionalLight {
             shadow_normal_bias: shadow::DEFAULT_NORMAL_BIAS,
             shadow_softness: 1.0,
             shadows_enabled: true,
+            two_sided: false,
             ortho_size: 20.0,
             ortho_near: 0.1,
             ortho_far: 100.0,
@@ -95,6 +100,16 @@ impl DirectionalLight {
         self.direction = dir.normalize();
     }
 
+    /// Apply lighting settings from the renderer configuration
+    pub fn apply_config(&mut self, config: &LightingConfig) {
+        self.set_direction(Vec3::from_array(config.direction));
+        self.color = Vec3::from_array(config.color);
+        self.intensity = config.intensity;
+        self.ambient_color = Vec3::from_array(config.ambient_color);
+        self.ambient_strength = config.ambient_strength;
+        self.two_sided = config.two_sided;
+    }
+
     /// Set light direction from yaw and pitch angles (in radians)
     pub fn set_direction_from_angles(&mut self, yaw: f32, pitch: f32) {
         let x = pitch.cos() * yaw.cos();
@@ -122,15 +137,57 @@ impl DirectionalLight {
         )
     }
 
+    /// Shadow view-projection fitted tightly around a world-space box
+    ///
+    /// The orthographic frustum spans exactly the box's extent across the
+    /// light, so the whole shadow map covers the casters. Depth starts at the
+    /// front of the box and reaches `FIT_RECEIVER_DEPTH` past its back.
+    pub fn fitted_view_projection(&self, bounds: &BoundingBox) -> Mat4 {
+        let center = bounds.center();
+        let up = if self.direction.cross(Vec3::Z).length_squared() < 1e-6 {
+            Vec3::Y
+        } else {
+            Vec3::Z
+        };
+        let view = Mat4::look_at_rh(center + self.direction, center, up);
+
+        let light_bounds = bounds.transform(&view);
+        let min = light_bounds.min;
+        let max = light_bounds
+            .max
+            .max(min + Vec3::splat(shadow::FIT_MIN_EXTENT));
+        // The light looks down -Z, so the nearest caster has the largest z
+        let proj = Mat4::orthographic_rh(
+            min.x,
+            max.x,
+            min.y,
+            max.y,
+            -max.z,
+            -min.z + shadow::FIT_RECEIVER_DEPTH,
+        );
+        proj * view
+    }
+
     /// Get the uniform data for GPU
     pub fn uniform(&self, scene_center: Vec3) -> LightUniform {
         let view = self.view_matrix(scene_center);
-        let proj = self.projection_matrix();
-        let light_view_proj = proj * view;
+        self.uniform_with(self.projection_matrix() * view)
+    }
+
+    /// Get the uniform data with the shadow frustum fitted to `bounds`
+    pub fn fitted_uniform(&self, bounds: &BoundingBox) -> LightUniform {
+        self.uniform_with(self.fitted_view_projection(bounds))
+    }
 
+    fn uniform_with(&self, light_view_proj: Mat4) -> LightUniform {
         LightUniform {
             light_view_proj: light_view_proj.to_cols_array_2d(),
-            direction: [self.direction.x, self.direction.y, self.direction.z, 0.0],
+            direction: [
+                self.direction.x,
+                self.direction.y,
+                self.direction.z,
+                if self.two_sided { 1.0 } else { 0.0 },
+            ],
             color_intensity: [self.color.x, self.color.y, self.color.z, self.intensity],
             ambient: [
                 self.ambient_color.x,
@@ -154,3 +211,58 @@ impl DirectionalLight {
         let _ = center; // Center is used in view_matrix, not stored
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_sided_flag_reaches_uniform() {
+        let mut light = DirectionalLight::new();
+        let mut config = LightingConfig::default();
+        assert!(!config.two_sided);
+
+        light.apply_config(&config);
+        assert_eq!(light.uniform(Vec3::ZERO).direction[3], 0.0);
+
+        config.two_sided = true;
+        light.apply_config(&config);
+        let uniform = light.uniform(Vec3::ZERO);
+        assert_eq!(uniform.direction[3], 1.0);
+        // The flag must not disturb the light direction itself
+        let dir = Vec3::from_slice(&uniform.direction[..3]);
+        assert!((dir - Vec3::from_array(config.direction).normalize()).length() < 1e-6);
+    }
+
+    #[test]
+    fn test_fitted_shadow_frustum_bounds_scene() {
+        let bounds = BoundingBox::new(Vec3::new(-1.0, 2.0, 0.0), Vec3::new(3.0, 4.0, 1.5));
+        for direction in [Vec3::new(0.5, 0.5, 1.0), Vec3::Z, Vec3::new(-1.0, 0.2, 0.3)] {
+            let mut light = DirectionalLight::new();
+            light.set_direction(direction);
+            let view_proj = light.fitted_view_projection(&bounds);
+
+            let corners: Vec<Vec3> = (0..8)
+                .map(|i| {
+                    let pick = |bit, lo: f32, hi: f32| if i & bit == 0 { lo } else { hi };
+                    Vec3::new(
+                        pick(1, bounds.min.x, bounds.max.x),
+                        pick(2, bounds.min.y, bounds.max.y),
+                        pick(4, bounds.min.z, bounds.max.z),
+                    )
+                })
+                .map(|c| view_proj.project_point3(c))
+                .collect();
+            let ndc = BoundingBox::from_points(corners);
+
+            // Every corner is inside the frustum...
+            assert!(ndc.min.cmpge(Vec3::new(-1.0, -1.0, 0.0) - 1e-4).all());
+            assert!(ndc.max.cmple(Vec3::ONE + 1e-4).all());
+            // ...which spans exactly the box across the light and starts at its front
+            let tight = |v: f32, target: f32| (v - target).abs() < 1e-4;
+            assert!(tight(ndc.min.x, -1.0) && tight(ndc.max.x, 1.0), "{ndc:?}");
+            assert!(tight(ndc.min.y, -1.0) && tight(ndc.max.y, 1.0), "{ndc:?}");
+            assert!(tight(ndc.min.z, 0.0), "{ndc:?}");
+        }
+    }
+}
@@ -2,10 +2,33 @@
 //!
 //! This module provides a plugin-based architecture for extending the renderer
 //! with custom sub-renderers.
+//!
+//! Sub-renderers registered with [`Renderer::registry_mut`] are drawn by
+//! [`Renderer::render`] in the pass named by [`SubRenderer::pass_type`]. The
+//! renderer has no [`RenderContext`] of its own, so the application creates
+//! one and drives the lifecycle:
+//!
+//! ```ignore
+//! let ctx = RenderContext::new(device, queue, format, width, height);
+//! renderer.registry_mut().register(MyRenderer::default());
+//! renderer.registry_mut().init_all(&ctx);
+//!
+//! // Every frame
+//! renderer.prepare_plugins(&ctx);
+//! renderer.render(&mut encoder, &view, &queue);
+//! ```
+//!
+//! See `examples/custom_sub_renderer.rs` for a complete sub-renderer that
+//! draws a triangle.
+//!
+//! [`Renderer::registry_mut`]: crate::Renderer::registry_mut
+//! [`Renderer::render`]: crate::Renderer::render
+
+use std::any::Any;
 
 use crate::context::RenderContext;
 use crate::scene::Scene;
-use crate::traits::SubRenderer;
+use crate::traits::{PassType, SubRenderer};
 
 /// Registry for managing sub-renderers.
 ///
@@ -63,6 +86,13 @@ impl RendererRegistry {
         None
     }
 
+    /// Gets a mutable reference to the first sub-renderer of type `T`.
+    pub fn find_mut<T: SubRenderer>(&mut self) -> Option<&mut T> {
+        self.sub_renderers
+            .iter_mut()
+            .find_map(|r| (r.as_mut() as &mut dyn Any).downcast_mut::<T>())
+    }
+
     /// Returns true if the registry contains a sub-renderer with the given name.
     pub fn contains(&self, name: &str) -> bool {
         self.sub_renderers.iter().any(|r| r.name() == name)
@@ -130,6 +160,42 @@ impl RendererRegistry {
         }
     }
 
+    /// Renders enabled sub-renderers whose layer is visible in `mask`, in priority order.
+    pub fn render_layers<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>, scene: &Scene, mask: u32) {
+        for renderer in self.visible_in(mask) {
+            renderer.render(pass, scene);
+        }
+    }
+
+    /// Renders the visible sub-renderers that draw in `pass_type`, in priority order.
+    pub fn render_pass_type<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        scene: &Scene,
+        pass_type: PassType,
+        mask: u32,
+    ) {
+        for renderer in self.in_pass(pass_type, mask) {
+            renderer.render(pass, scene);
+        }
+    }
+
+    /// Iterates the visible sub-renderers that draw in `pass_type`.
+    pub fn in_pass(
+        &self,
+        pass_type: PassType,
+        mask: u32,
+    ) -> impl Iterator<Item = &dyn SubRenderer> {
+        self.visible_in(mask)
+            .filter(move |r| r.pass_type() == pass_type)
+    }
+
+    /// Iterates enabled sub-renderers whose layer is visible in `mask`.
+    pub fn visible_in(&self, mask: u32) -> impl Iterator<Item = &dyn SubRenderer> {
+        self.iter()
+            .filter(move |r| r.is_enabled() && r.layer().is_visible_in(mask))
+    }
+
     /// Destroys all sub-renderers.
     pub fn destroy_all(&mut self) {
         for renderer in &mut self.sub_renderers {
@@ -169,11 +235,13 @@ pub trait RendererPlugin: Send + Sync + 'static {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scene::RenderLayer;
 
     struct TestRenderer {
         name: String,
         priority: i32,
         enabled: bool,
+        layer: RenderLayer,
     }
 
     impl TestRenderer {
@@ -182,8 +250,14 @@ mod tests {
                 name: name.to_string(),
                 priority,
                 enabled: true,
+                layer: RenderLayer::Default,
             }
         }
+
+        fn on_layer(mut self, layer: RenderLayer) -> Self {
+            self.layer = layer;
+            self
+        }
     }
 
     impl SubRenderer for TestRenderer {
@@ -203,6 +277,10 @@ mod tests {
             self.enabled = enabled;
         }
 
+        fn layer(&self) -> RenderLayer {
+            self.layer
+        }
+
         fn on_init(&mut self, _ctx: &RenderContext) {}
         fn on_resize(&mut self, _ctx: &RenderContext, _width: u32, _height: u32) {}
         fn prepare(&mut self, _ctx: &RenderContext, _scene: &Scene) {}
@@ -223,6 +301,40 @@ mod tests {
         assert_eq!(names, vec!["first", "second", "third"]);
     }
 
+    #[test]
+    fn test_registry_filters_by_layer() {
+        let mut registry = RendererRegistry::new();
+        registry.register(TestRenderer::new("mesh", 100));
+        registry.register(TestRenderer::new("axes", 200).on_layer(RenderLayer::Annotations));
+        registry.register(TestRenderer::new("labels", 300).on_layer(RenderLayer::Debug));
+        registry.ensure_sorted();
+
+        let names = |mask| {
+            registry
+                .visible_in(mask)
+                .map(|r| r.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(RenderLayer::ALL_MASK), vec!["mesh", "axes", "labels"]);
+        assert_eq!(names(RenderLayer::Default.mask()), vec!["mesh"]);
+        assert_eq!(names(RenderLayer::Debug.mask()), vec!["labels"]);
+        assert_eq!(
+            names(RenderLayer::Annotations.mask() | RenderLayer::Debug.mask()),
+            vec!["axes", "labels"]
+        );
+        assert!(names(0).is_empty());
+    }
+
+    #[test]
+    fn test_registry_find_by_type() {
+        let mut registry = RendererRegistry::new();
+        registry.register(TestRenderer::new("test", 100));
+
+        let found = registry.find_mut::<TestRenderer>().expect("registered");
+        found.set_enabled(false);
+        assert!(!registry.get("test").unwrap().is_enabled());
+    }
+
     #[test]
     fn test_registry_unregister() {
         let mut registry = RendererRegistry::new();
@@ -12,29 +12,39 @@
 //! - [`MeshManager`]: Handles GPU mesh resources
 //! - [`RendererRegistry`]: Manages sub-renderer plugins
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use glam::{Mat4, Vec3};
 use uuid::Uuid;
 use wgpu::util::DeviceExt;
 
-use rk_core::Part;
+use rk_core::{Part, ViewportSettings};
 
 use crate::camera::Camera;
+use crate::capture::CapturedBuffers;
 use crate::config::{
-    CameraConfig, GizmoConfig, GridConfig, LightingConfig, RendererConfig, ShadowConfig,
-    ViewportConfig,
+    CameraConfig, GizmoConfig, GridConfig, LightingConfig, RendererConfig, SelectionConfig,
+    ShadowConfig, SketchConfig, ViewportConfig,
 };
-use crate::constants::shadow::{SHADOW_MAP_FORMAT, SHADOW_MAP_SIZE};
+use crate::constants::selection;
+use crate::constants::shadow::{GROUND_HALF_SIZE, SHADOW_MAP_FORMAT, SHADOW_MAP_SIZE};
 use crate::constants::viewport::{CLEAR_COLOR, SAMPLE_COUNT};
+use crate::context::RenderContext;
+use crate::display::DisplayOptions;
 use crate::light::DirectionalLight;
 use crate::plugin::RendererRegistry;
 use crate::resources::MeshManager;
-use crate::scene::Scene;
+use crate::scene::{
+    BoundingBox, PickFacing, PickGeometry, Ray, RayHit, RenderLayer, Scene, raycast_closest_with,
+};
 use crate::sub_renderers::{
     AxisInstance, AxisRenderer, CollisionRenderer, GizmoAxis, GizmoMode, GizmoRenderer, GizmoSpace,
-    GridRenderer, MarkerInstance, MarkerRenderer, MeshData, MeshRenderer,
+    GridRenderer, MarkerInstance, MarkerRenderer, MeshData, MeshRenderer, SketchRenderer,
 };
+use crate::traits::PassType;
+
+/// Hits within this fraction of a point's distance count as reaching it.
+const OCCLUSION_TOLERANCE: f32 = 0.01;
 
 /// Mesh entry with bind group
 pub struct MeshEntry {
@@ -42,6 +52,8 @@ pub struct MeshEntry {
     pub data: MeshData,
     /// Bind group for instance-specific uniforms.
     pub bind_group: wgpu::BindGroup,
+    /// CPU-side geometry used for ray casting.
+    pub pick: PickGeometry,
 }
 
 /// Main renderer combining all sub-renderers.
@@ -90,11 +102,15 @@ pub struct Renderer {
     light_bind_group: wgpu::BindGroup,
     /// Bind group for shadow pass (light uniform only)
     shadow_light_bind_group: wgpu::BindGroup,
+    /// Ground plane drawn in ground-shadow-only mode
+    ground_shadow_mesh: MeshData,
+    ground_shadow_bind_group: wgpu::BindGroup,
 
     // Sub-renderers (legacy - will migrate to registry)
     grid_renderer: GridRenderer,
     mesh_renderer: MeshRenderer,
     axis_renderer: AxisRenderer,
+    inertial_axis_renderer: AxisRenderer,
     marker_renderer: MarkerRenderer,
     gizmo_renderer: GizmoRenderer,
     collision_renderer: CollisionRenderer,
@@ -102,12 +118,14 @@ pub struct Renderer {
     // Data - UUID-keyed storage for O(1) lookup and removal
     meshes: HashMap<Uuid, MeshEntry>,
     selected_part: Option<Uuid>,
+    pick_facing: PickFacing,
+    highlighted_parts: HashSet<Uuid>,
+    selection_color: [f32; 3],
+    show_occluded_selection: bool,
 
     // Display options
-    show_grid: bool,
-    show_axes: bool,
-    show_markers: bool,
     show_gizmo: bool,
+    display: DisplayOptions,
 
     // Configurable rendering settings
     clear_color: wgpu::Color,
@@ -188,6 +206,10 @@ impl Renderer {
             &camera_buffer,
         );
 
+        let ground_shadow_mesh = MeshData::from_part(device, &Self::ground_plane_part());
+        let ground_shadow_bind_group =
+            mesh_renderer.create_instance_bind_group(device, &ground_shadow_mesh);
+
         // Create light bind groups after mesh_renderer is created
         let light_bind_group = Self::create_light_bind_group(
             device,
@@ -230,6 +252,14 @@ impl Renderer {
             &camera_buffer,
         );
 
+        let inertial_axis_renderer = AxisRenderer::new(
+            device,
+            format,
+            depth_format,
+            &camera_bind_group_layout,
+            &camera_buffer,
+        );
+
         let marker_renderer = MarkerRenderer::new(
             device,
             format,
@@ -259,7 +289,7 @@ impl Renderer {
         let mesh_manager = MeshManager::new();
         let registry = RendererRegistry::new();
 
-        Self {
+        let mut renderer = Self {
             // New components
             scene,
             mesh_manager,
@@ -282,25 +312,33 @@ impl Renderer {
             shadow_sampler,
             light_bind_group,
             shadow_light_bind_group,
+            ground_shadow_mesh,
+            ground_shadow_bind_group,
 
             grid_renderer,
             mesh_renderer,
             axis_renderer,
+            inertial_axis_renderer,
             marker_renderer,
             gizmo_renderer,
             collision_renderer,
             meshes: HashMap::new(),
             selected_part: None,
-            show_grid: true,
-            show_axes: true,
-            show_markers: true,
+            pick_facing: PickFacing::default(),
+            highlighted_parts: HashSet::new(),
+            selection_color: selection::DEFAULT_COLOR,
+            show_occluded_selection: true,
             show_gizmo: true,
+            display: DisplayOptions::default(),
             clear_color: CLEAR_COLOR,
             shadow_map_size: SHADOW_MAP_SIZE,
             format,
             width,
             height,
-        }
+        };
+        // The saved-settings default is the single source for the initial view
+        renderer.apply_viewport_settings(&ViewportSettings::default());
+        renderer
     }
 
     // ========== Camera accessors ==========
@@ -356,34 +394,121 @@ impl Renderer {
 
     // ========== Display option accessors ==========
 
+    /// Get the mask of render layers that are drawn (see [`RenderLayer::mask`]).
+    pub fn visible_layers(&self) -> u32 {
+        self.display.layers
+    }
+
+    /// Set the render layers that are drawn.
+    ///
+    /// Individual toggles such as [`set_show_axes`](Self::set_show_axes) only
+    /// take effect while their layer is visible.
+    pub fn set_visible_layers(&mut self, mask: u32) {
+        self.display.layers = mask;
+    }
+
     /// Get whether the grid is visible.
     pub fn show_grid(&self) -> bool {
-        self.show_grid
+        self.display.show_grid
     }
 
     /// Set whether the grid is visible.
     pub fn set_show_grid(&mut self, show: bool) {
-        self.show_grid = show;
+        self.display.show_grid = show;
     }
 
     /// Get whether axes are visible.
     pub fn show_axes(&self) -> bool {
-        self.show_axes
+        self.display.show_axes
     }
 
     /// Set whether axes are visible.
     pub fn set_show_axes(&mut self, show: bool) {
-        self.show_axes = show;
+        self.display.show_axes = show;
     }
 
     /// Get whether markers are visible.
     pub fn show_markers(&self) -> bool {
-        self.show_markers
+        self.display.show_markers
     }
 
     /// Set whether markers are visible.
     pub fn set_show_markers(&mut self, show: bool) {
-        self.show_markers = show;
+        self.display.show_markers = show;
+    }
+
+    /// Get whether inertial frames are visible.
+    pub fn show_inertial_frames(&self) -> bool {
+        self.display.show_inertial_frames
+    }
+
+    /// Set whether inertial frames are visible.
+    pub fn set_show_inertial_frames(&mut self, show: bool) {
+        self.display.show_inertial_frames = show;
+    }
+
+    /// Get whether visual meshes are visible.
+    pub fn show_visuals(&self) -> bool {
+        self.display.show_visuals
+    }
+
+    /// Set whether visual meshes are visible.
+    pub fn set_show_visuals(&mut self, show: bool) {
+        self.display.show_visuals = show;
+    }
+
+    /// Get whether collision shapes are visible.
+    pub fn show_collisions(&self) -> bool {
+        self.display.show_collisions
+    }
+
+    /// Set whether collision shapes are visible.
+    pub fn set_show_collisions(&mut self, show: bool) {
+        self.display.show_collisions = show;
+    }
+
+    /// Capture the camera, display toggles and gizmo mode for saving with a project.
+    pub fn viewport_settings(&self) -> ViewportSettings {
+        ViewportSettings {
+            camera_target: self.camera.target,
+            camera_distance: self.camera.distance,
+            camera_yaw: self.camera.yaw,
+            camera_pitch: self.camera.pitch,
+            show_grid: self.display.show_grid,
+            show_axes: self.display.show_axes,
+            show_markers: self.display.show_markers,
+            show_inertial_frames: self.display.show_inertial_frames,
+            show_visuals: self.display.show_visuals,
+            show_collisions: self.display.show_collisions,
+            gizmo_mode: self.gizmo_mode(),
+        }
+    }
+
+    /// Restore the camera, display toggles and gizmo mode saved with a project.
+    pub fn apply_viewport_settings(&mut self, settings: &ViewportSettings) {
+        self.camera.set_view(
+            settings.camera_target,
+            settings.camera_distance,
+            settings.camera_yaw,
+            settings.camera_pitch,
+        );
+        self.display.show_grid = settings.show_grid;
+        self.display.show_axes = settings.show_axes;
+        self.display.show_markers = settings.show_markers;
+        self.display.show_inertial_frames = settings.show_inertial_frames;
+        self.display.show_visuals = settings.show_visuals;
+        self.display.show_collisions = settings.show_collisions;
+        self.set_gizmo_mode(settings.gizmo_mode);
+    }
+
+    /// Get the visual/collision display options.
+    pub fn display_options(&self) -> &DisplayOptions {
+        &self.display
+    }
+
+    /// Get mutable display options (for per-link overrides).
+    pub fn display_options_mut(&mut self) -> &mut DisplayOptions {
+        &mut self.display
     }
 
     /// Get whether the gizmo rendering is enabled.
@@ -424,7 +549,8 @@ impl Renderer {
             sample_count: SAMPLE_COUNT,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Depth32Float,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            // Attachment-only: sampled multisampled depth breaks MSAA resolve on GL backends.
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             view_formats: &[],
         });
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -481,6 +607,15 @@ impl Renderer {
         (texture, view)
     }
 
+    fn ground_plane_part() -> Part {
+        let h = GROUND_HALF_SIZE;
+        let mut part = Part::new("Ground Shadow");
+        part.vertices = vec![[-h, -h, 0.0], [h, -h, 0.0], [h, h, 0.0], [-h, h, 0.0]];
+        part.indices = vec![0, 1, 2, 0, 2, 3];
+        part.normals = vec![[0.0, 0.0, 1.0]; 2];
+        part
+    }
+
     fn create_shadow_sampler(device: &wgpu::Device) -> wgpu::Sampler {
         device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("Shadow Sampler"),
@@ -549,6 +684,15 @@ impl Renderer {
         self.format
     }
 
+    /// Creates a [`RenderContext`] matching this renderer's format and size.
+    ///
+    /// Pass it to [`RendererRegistry::init_all`](crate::plugin::RendererRegistry::init_all)
+    /// and [`prepare_plugins`](Self::prepare_plugins) when driving the
+    /// renderer without egui. Create a new one after [`resize`](Self::resize).
+    pub fn create_context(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> RenderContext {
+        RenderContext::from_device(device, queue, self.format, self.width, self.height)
+    }
+
     fn update_camera(&self, queue: &wgpu::Queue) {
         let camera_uniform = self.camera.uniform();
         queue.write_buffer(
@@ -558,10 +702,18 @@ impl Renderer {
         );
     }
 
-    fn update_light(&self, queue: &wgpu::Queue) {
-        // Use camera target as scene center for shadow projection
-        let scene_center = self.camera.target;
-        let light_uniform = self.light.uniform(scene_center);
+    /// Upload the light, fitting the shadow frustum to the given casters
+    fn update_light(&self, queue: &wgpu::Queue, shadow_casters: &[Uuid]) {
+        let bounds = shadow_casters
+            .iter()
+            .filter_map(|id| self.part_world_bounds(*id))
+            .filter(BoundingBox::is_valid)
+            .reduce(|a, b| a.union(&b));
+        let light_uniform = match bounds {
+            Some(bounds) => self.light.fitted_uniform(&bounds),
+            // Nothing casts: keep a fixed frustum around the camera target
+            None => self.light.uniform(self.camera.target),
+        };
         queue.write_buffer(
             &self.light_buffer,
             0,
@@ -574,14 +726,58 @@ impl Renderer {
     /// Returns the part's UUID for reference.
     pub fn add_part(&mut self, device: &wgpu::Device, part: &Part) -> Uuid {
         tracing::info!("Renderer::add_part called for '{}'", part.name);
-        let data = MeshData::from_part(device, part);
+        let data = MeshData::from_part_with_selection_color(device, part, self.selection_color);
         let bind_group = self.mesh_renderer.create_instance_bind_group(device, &data);
 
-        self.meshes.insert(part.id, MeshEntry { data, bind_group });
+        let pick = PickGeometry::from_part(part);
+
+        self.meshes.insert(
+            part.id,
+            MeshEntry {
+                data,
+                bind_group,
+                pick,
+            },
+        );
         tracing::info!("Renderer now has {} meshes", self.meshes.len());
         part.id
     }
 
+    /// The model matrix a part is currently drawn with.
+    pub fn part_transform(&self, part_id: Uuid) -> Option<Mat4> {
+        self.meshes
+            .get(&part_id)
+            .map(|entry| Mat4::from_cols_array_2d(&entry.data.instance.model))
+    }
+
+    /// World-space bounds of a part's mesh at its current pose.
+    pub fn part_world_bounds(&self, part_id: Uuid) -> Option<BoundingBox> {
+        self.meshes.get(&part_id).map(|entry| {
+            let model = Mat4::from_cols_array_2d(&entry.data.instance.model);
+            entry.pick.bounds.transform(&model)
+        })
+    }
+
+    /// World-space bounds of every part at its current pose.
+    pub fn scene_bounds(&self) -> Option<BoundingBox> {
+        self.meshes
+            .keys()
+            .filter_map(|part_id| self.part_world_bounds(*part_id))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Replace a part's geometry, keeping its transform, color and selection.
+    ///
+    /// Parts that have no mesh yet are added instead.
+    pub fn update_part_mesh(&mut self, device: &wgpu::Device, part: &Part) {
+        let Some(entry) = self.meshes.get_mut(&part.id) else {
+            self.add_part(device, part);
+            return;
+        };
+        entry.data.set_geometry(device, part);
+        entry.pick = PickGeometry::from_part(part);
+    }
+
     /// Update a part's transform.
     pub fn update_part_transform(&mut self, queue: &wgpu::Queue, part_id: Uuid, transform: Mat4) {
         if let Some(entry) = self.meshes.get_mut(&part_id) {
@@ -619,9 +815,68 @@ impl Renderer {
         self.selected_part
     }
 
+    /// Set the parts highlighted as a kinematic chain.
+    ///
+    /// Only parts whose state actually changes are written to the GPU, so this
+    /// is cheap to call every frame.
+    pub fn set_highlighted_parts(&mut self, queue: &wgpu::Queue, part_ids: &[Uuid]) {
+        let new_set: HashSet<Uuid> = part_ids.iter().copied().collect();
+        if new_set == self.highlighted_parts {
+            return;
+        }
+
+        for id in self.highlighted_parts.difference(&new_set) {
+            if let Some(entry) = self.meshes.get_mut(id) {
+                entry.data.set_highlighted(queue, false);
+            }
+        }
+        for id in new_set.difference(&self.highlighted_parts) {
+            if let Some(entry) = self.meshes.get_mut(id) {
+                entry.data.set_highlighted(queue, true);
+            }
+        }
+        self.highlighted_parts = new_set;
+    }
+
+    /// Set the parts excluded from casting or receiving shadows.
+    ///
+    /// Receiver state is written to the GPU only for parts whose state changes,
+    /// so this is cheap to call every frame and picks up newly added parts.
+    pub fn set_shadow_overrides(
+        &mut self,
+        queue: &wgpu::Queue,
+        non_casting: &[Uuid],
+        non_receiving: &[Uuid],
+    ) {
+        self.display
+            .set_shadow_overrides(non_casting, non_receiving);
+        self.sync_shadow_receivers(queue);
+    }
+
+    /// Only shade the ground with shadows, leaving parts unshadowed.
+    pub fn set_ground_shadow_only(&mut self, queue: &wgpu::Queue, enabled: bool) {
+        self.display.ground_shadow_only = enabled;
+        self.sync_shadow_receivers(queue);
+    }
+
+    fn sync_shadow_receivers(&mut self, queue: &wgpu::Queue) {
+        for (id, entry) in &mut self.meshes {
+            let receives = self.display.receives_shadow(*id);
+            if entry.data.receives_shadow() != receives {
+                entry.data.set_receives_shadow(queue, receives);
+            }
+        }
+    }
+
+    /// Get the parts currently highlighted as a kinematic chain.
+    pub fn highlighted_parts(&self) -> &HashSet<Uuid> {
+        &self.highlighted_parts
+    }
+
     /// Remove a part - O(1) operation with UUID-based storage.
     pub fn remove_part(&mut self, part_id: Uuid) {
         self.meshes.remove(&part_id);
+        self.highlighted_parts.remove(&part_id);
         if self.selected_part == Some(part_id) {
             self.selected_part = None;
         }
@@ -631,6 +886,7 @@ impl Renderer {
     pub fn clear_parts(&mut self) {
         self.meshes.clear();
         self.selected_part = None;
+        self.highlighted_parts.clear();
     }
 
     /// Check if a part exists.
@@ -643,16 +899,88 @@ impl Renderer {
         self.meshes.len()
     }
 
+    /// Cast a ray from a screen position into the scene.
+    ///
+    /// Uses the current camera and each part's displayed transform, returning
+    /// the closest hit if any.
+    pub fn raycast(&self, screen_x: f32, screen_y: f32, width: f32, height: f32) -> Option<RayHit> {
+        let (origin, direction) = self.camera.screen_to_ray(screen_x, screen_y, width, height);
+        self.raycast_ray(&Ray::new(origin, direction))
+    }
+
+    /// Cast a world-space ray against all visible parts, returning the closest hit.
+    pub fn raycast_ray(&self, ray: &Ray) -> Option<RayHit> {
+        raycast_closest_with(ray, self.pick_targets(None), self.pick_facing)
+    }
+
+    /// Pick geometry and displayed transform of every visible part except `skip`.
+    fn pick_targets(
+        &self,
+        skip: Option<Uuid>,
+    ) -> impl Iterator<Item = (Uuid, &PickGeometry, Mat4)> {
+        self.meshes
+            .iter()
+            .filter(move |(id, _)| Some(**id) != skip && self.display.is_visual_visible(**id))
+            .map(|(id, entry)| {
+                (
+                    *id,
+                    &entry.pick,
+                    Mat4::from_cols_array_2d(&entry.data.instance.model),
+                )
+            })
+    }
+
+    /// Whether a part lies between the camera and a world point.
+    ///
+    /// `owner` is skipped so that a point inside its own part, such as a link
+    /// origin, is not hidden by that part.
+    pub fn is_point_occluded(&self, point: Vec3, owner: Option<Uuid>) -> bool {
+        let eye = self.camera.position;
+        let distance = eye.distance(point);
+        if distance <= f32::EPSILON {
+            return false;
+        }
+        let ray = Ray::new(eye, (point - eye) / distance);
+        raycast_closest_with(&ray, self.pick_targets(owner), self.pick_facing)
+            .is_some_and(|hit| hit.distance < distance * (1.0 - OCCLUSION_TOLERANCE))
+    }
+
+    /// How triangle winding affects picking.
+    pub fn pick_facing(&self) -> PickFacing {
+        self.pick_facing
+    }
+
+    /// Set how triangle winding affects picking.
+    pub fn set_pick_facing(&mut self, facing: PickFacing) {
+        self.pick_facing = facing;
+    }
+
     /// Update axis display
     pub fn update_axes(&mut self, queue: &wgpu::Queue, instances: &[AxisInstance]) {
         self.axis_renderer.update_instances(queue, instances);
     }
 
+    /// Update inertial frame display
+    pub fn update_inertial_axes(&mut self, queue: &wgpu::Queue, instances: &[AxisInstance]) {
+        self.inertial_axis_renderer
+            .update_instances(queue, instances);
+    }
+
     /// Update marker display
     pub fn update_markers(&mut self, queue: &wgpu::Queue, instances: &[MarkerInstance]) {
         self.marker_renderer.update_instances(queue, instances);
     }
 
+    /// Global marker size multiplier.
+    pub fn marker_scale(&self) -> f32 {
+        self.marker_renderer.scale()
+    }
+
+    /// Set the global marker size multiplier, applied on the next marker update.
+    pub fn set_marker_scale(&mut self, scale: f32) {
+        self.marker_renderer.set_scale(scale);
+    }
+
     /// Update selected marker display (rendered on top)
     pub fn update_selected_markers(&mut self, queue: &wgpu::Queue, instances: &[MarkerInstance]) {
         self.marker_renderer
@@ -745,11 +1073,21 @@ impl Renderer {
         queue: &wgpu::Queue,
     ) {
         self.update_camera(queue);
-        self.update_light(queue);
+
+        let draws = self.display.main_pass_draws(
+            self.meshes.keys().copied(),
+            !self.collision_renderer.is_empty(),
+        );
+        self.update_light(queue, &draws.shadow_casters);
 
         // === SHADOW PASS ===
         // Render scene from light's perspective to generate shadow map
-        if self.light.shadows_enabled && !self.meshes.is_empty() {
+        let plugin_casters = self
+            .registry
+            .in_pass(PassType::Shadow, self.display.layers)
+            .next()
+            .is_some();
+        if self.light.shadows_enabled && (!draws.shadow_casters.is_empty() || plugin_casters) {
             let mut shadow_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Shadow Pass"),
                 color_attachments: &[],
@@ -774,7 +1112,11 @@ impl Renderer {
                 1.0,
             );
 
-            for entry in self.meshes.values() {
+            for entry in draws
+                .shadow_casters
+                .iter()
+                .filter_map(|id| self.meshes.get(id))
+            {
                 self.mesh_renderer.render_shadow(
                     &mut shadow_pass,
                     &entry.data,
@@ -782,6 +1124,12 @@ impl Renderer {
                     &self.shadow_light_bind_group,
                 );
             }
+            self.registry.render_pass_type(
+                &mut shadow_pass,
+                &self.scene,
+                PassType::Shadow,
+                self.display.layers,
+            );
         }
 
         // === MAIN PASS ===
@@ -826,12 +1174,32 @@ impl Renderer {
         });
 
         // Render grid
-        if self.show_grid {
+        if draws.grid {
             self.grid_renderer.render(&mut render_pass);
         }
 
-        // Render meshes with lighting and shadows
-        for entry in self.meshes.values() {
+        // Ground shadow catcher (only meaningful while shadows are rendered)
+        if self.display.ground_shadow_only
+            && self.light.shadows_enabled
+            && self.display.is_layer_visible(RenderLayer::Default)
+        {
+            self.mesh_renderer.render_ground_shadow(
+                &mut render_pass,
+                &self.ground_shadow_mesh,
+                &self.ground_shadow_bind_group,
+                &self.light_bind_group,
+            );
+        }
+
+        // Render visual meshes with lighting and shadows. The selected part
+        // goes last so its outline is depth-tested against the others only.
+        let selected = self.selected_part.filter(|id| draws.visuals.contains(id));
+        for entry in draws
+            .visuals
+            .iter()
+            .filter(|id| Some(**id) != selected)
+            .filter_map(|id| self.meshes.get(id))
+        {
             self.mesh_renderer.render(
                 &mut render_pass,
                 &entry.data,
@@ -839,19 +1207,59 @@ impl Renderer {
                 &self.light_bind_group,
             );
         }
+        if let Some(entry) = selected.and_then(|id| self.meshes.get(&id)) {
+            // Outline where occluders hide the selection, so it can be found
+            if self.show_occluded_selection {
+                self.mesh_renderer.render_selection_outline(
+                    &mut render_pass,
+                    &entry.data,
+                    &entry.bind_group,
+                    &self.light_bind_group,
+                );
+            }
+            self.mesh_renderer.render(
+                &mut render_pass,
+                &entry.data,
+                &entry.bind_group,
+                &self.light_bind_group,
+            );
+        }
+        self.registry.render_pass_type(
+            &mut render_pass,
+            &self.scene,
+            PassType::Opaque,
+            self.display.layers,
+        );
 
         // Render axes
-        if self.show_axes {
+        if draws.axes {
             self.axis_renderer.render(&mut render_pass);
         }
+        if draws.inertial_frames {
+            self.inertial_axis_renderer.render(&mut render_pass);
+        }
 
         // Render markers
-        if self.show_markers {
+        if draws.markers {
             self.marker_renderer.render(&mut render_pass);
         }
 
         // Render collision shapes (semi-transparent, after markers)
-        self.collision_renderer.render(&mut render_pass);
+        if draws.collisions {
+            self.collision_renderer.render(&mut render_pass);
+        }
+        self.registry.render_pass_type(
+            &mut render_pass,
+            &self.scene,
+            PassType::Transparent,
+            self.display.layers,
+        );
+        self.registry.render_pass_type(
+            &mut render_pass,
+            &self.scene,
+            PassType::Overlay,
+            self.display.layers,
+        );
 
         // Render gizmo (always on top)
         if self.show_gizmo {
@@ -905,6 +1313,15 @@ impl Renderer {
         &mut self.registry
     }
 
+    /// Prepare registered sub-renderers for the next frame.
+    ///
+    /// Call once per frame before [`render`](Self::render). The context's
+    /// camera buffer is updated first so plugins draw from the current view.
+    pub fn prepare_plugins(&mut self, ctx: &RenderContext) {
+        ctx.update_camera(&self.camera.uniform());
+        self.registry.prepare_all(ctx, &self.scene);
+    }
+
     // ========== Configuration Methods ==========
 
     /// Apply a full renderer configuration.
@@ -923,11 +1340,20 @@ impl Renderer {
         self.apply_lighting_config(&config.lighting);
         self.apply_camera_config(&config.camera);
         self.apply_gizmo_config(&config.gizmo, queue);
+        self.apply_selection_config(&config.selection, queue);
+        self.apply_sketch_config(&config.sketch);
+    }
+
+    /// Apply sketch configuration to the registered sketch renderer, if any.
+    pub fn apply_sketch_config(&mut self, config: &SketchConfig) {
+        if let Some(sketch) = self.registry.find_mut::<SketchRenderer>() {
+            sketch.apply_config(config);
+        }
     }
 
     /// Apply grid configuration.
     pub fn apply_grid_config(&mut self, config: &GridConfig, device: &wgpu::Device) {
-        self.show_grid = config.enabled;
+        self.display.show_grid = config.enabled;
         // Rebuild grid with new parameters
         self.grid_renderer.rebuild(
             device,
@@ -937,6 +1363,8 @@ impl Renderer {
             config.x_axis_color,
             config.y_axis_color,
         );
+        self.grid_renderer
+            .set_fade_radius(device, config.fade_radius);
     }
 
     /// Apply shadow configuration.
@@ -945,6 +1373,8 @@ impl Renderer {
         self.light.shadow_bias = config.bias;
         self.light.shadow_normal_bias = config.normal_bias;
         self.light.shadow_softness = config.softness;
+        // Receivers are re-synced on the next `set_shadow_overrides` call
+        self.display.ground_shadow_only = config.ground_shadow_only;
 
         // Resize shadow map if size changed
         if config.map_size != self.shadow_map_size {
@@ -974,15 +1404,17 @@ impl Renderer {
 
     /// Apply lighting configuration.
     pub fn apply_lighting_config(&mut self, config: &LightingConfig) {
-        self.light.set_direction(Vec3::from_array(config.direction));
-        self.light.color = Vec3::from_array(config.color);
-        self.light.intensity = config.intensity;
-        self.light.ambient_color = Vec3::from_array(config.ambient_color);
-        self.light.ambient_strength = config.ambient_strength;
+        self.light.apply_config(config);
     }
 
     /// Apply camera configuration.
     pub fn apply_camera_config(&mut self, config: &CameraConfig) {
+        self.camera.dolly_zoom = config.dolly_zoom;
+        self.camera.set_clamp_pitch(config.clamp_pitch);
+        self.camera.stop_at_target = config.stop_zoom_at_target;
+        self.camera
+            .set_distance_limits(config.min_zoom_distance, config.max_zoom_distance);
+        // Applied without the dolly, since configs are re-applied on every change
         self.camera.set_fov_degrees(config.fov_degrees);
         self.camera.set_near(config.near_plane);
         self.camera.set_far(config.far_plane);
@@ -1002,6 +1434,17 @@ impl Renderer {
         // Note: gizmo scale is applied per-instance when showing the gizmo
     }
 
+    /// Apply selection highlight configuration.
+    pub fn apply_selection_config(&mut self, config: &SelectionConfig, queue: &wgpu::Queue) {
+        self.show_occluded_selection = config.show_occluded;
+        if config.color != self.selection_color {
+            self.selection_color = config.color;
+            for entry in self.meshes.values_mut() {
+                entry.data.set_selection_color(queue, config.color);
+            }
+        }
+    }
+
     /// Apply viewport configuration.
     ///
     /// Note: MSAA changes require renderer recreation and are not applied here.
@@ -1013,11 +1456,359 @@ impl Renderer {
             b: config.background_color[2] as f64,
             a: config.background_color[3] as f64,
         };
+        self.pick_facing = if config.pick_prefer_front_faces {
+            PickFacing::PreferFront
+        } else {
+            PickFacing::Nearest
+        };
         // Note: MSAA changes require recreation of pipelines and textures
     }
 
+    /// Render the visual meshes offscreen into color, depth and world-normal textures.
+    ///
+    /// Uses the lighting and shadow map from the last [`render`](Self::render);
+    /// grid, overlays and gizmo are left out so the buffers hold geometry only.
+    /// The capture is submitted before returning.
+    pub fn capture_buffers(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> CapturedBuffers {
+        self.update_camera(queue);
+        let buffers = CapturedBuffers::new(device, self.format, self.width, self.height);
+        let color_view = buffers.color.create_view(&Default::default());
+        let depth_view = buffers.depth.create_view(&Default::default());
+        let normal_view = buffers.normal.create_view(&Default::default());
+
+        let draws = self.display.main_pass_draws(
+            self.meshes.keys().copied(),
+            !self.collision_renderer.is_empty(),
+        );
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Capture Encoder"),
+        });
+        {
+            let attachment = |view, clear| {
+                Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })
+            };
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Capture Pass"),
+                color_attachments: &[
+                    attachment(&color_view, self.clear_color),
+                    attachment(&normal_view, wgpu::Color::TRANSPARENT),
+                ],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            for entry in draws.visuals.iter().filter_map(|id| self.meshes.get(id)) {
+                self.mesh_renderer.render_gbuffer(
+                    &mut pass,
+                    &entry.data,
+                    &entry.bind_group,
+                    &self.light_bind_group,
+                );
+            }
+        }
+        queue.submit([encoder.finish()]);
+
+        buffers
+    }
+
     /// Get the current MSAA sample count.
     pub fn sample_count(&self) -> u32 {
         SAMPLE_COUNT
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::traits::SubRenderer;
+
+    /// Device on the noop backend: commands are validated but not executed
+    fn noop_device() -> (wgpu::Device, wgpu::Queue) {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::NOOP,
+            backend_options: wgpu::BackendOptions {
+                noop: wgpu::NoopBackendOptions { enable: true },
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let adapter = pollster::block_on(instance.request_adapter(&Default::default())).unwrap();
+        pollster::block_on(adapter.request_device(&Default::default())).unwrap()
+    }
+
+    /// Counts how often it is drawn
+    struct CountingRenderer {
+        pass_type: PassType,
+        renders: Arc<AtomicUsize>,
+    }
+
+    impl SubRenderer for CountingRenderer {
+        fn name(&self) -> &str {
+            "counting"
+        }
+        fn priority(&self) -> i32 {
+            200
+        }
+        fn is_enabled(&self) -> bool {
+            true
+        }
+        fn set_enabled(&mut self, _enabled: bool) {}
+        fn pass_type(&self) -> PassType {
+            self.pass_type
+        }
+        fn on_init(&mut self, _ctx: &RenderContext) {}
+        fn on_resize(&mut self, _ctx: &RenderContext, _width: u32, _height: u32) {}
+        fn prepare(&mut self, _ctx: &RenderContext, _scene: &Scene) {}
+        fn render<'a>(&'a self, _pass: &mut wgpu::RenderPass<'a>, _scene: &Scene) {
+            self.renders.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_registered_sub_renderer_draws_in_frame() {
+        let (device, queue) = noop_device();
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut renderer = Renderer::new(&device, format, 64, 64);
+
+        let opaque = Arc::new(AtomicUsize::new(0));
+        let post = Arc::new(AtomicUsize::new(0));
+        for (pass_type, renders) in [(PassType::Opaque, &opaque), (PassType::PostProcess, &post)] {
+            renderer.registry_mut().register(CountingRenderer {
+                pass_type,
+                renders: renders.clone(),
+            });
+        }
+
+        let target = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Test Target"),
+            size: wgpu::Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = target.create_view(&Default::default());
+        let frame = |renderer: &Renderer| {
+            let mut encoder = device.create_command_encoder(&Default::default());
+            renderer.render(&mut encoder, &view, &queue);
+            queue.submit([encoder.finish()]);
+        };
+
+        frame(&renderer);
+        assert_eq!(opaque.load(Ordering::SeqCst), 1);
+        // Post-processing is not dispatched yet
+        assert_eq!(post.load(Ordering::SeqCst), 0);
+
+        // Hidden layers skip the sub-renderer
+        renderer
+            .display_options_mut()
+            .set_layer_visible(RenderLayer::Default, false);
+        frame(&renderer);
+        assert_eq!(opaque.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_selection_color_reaches_part_uniform() {
+        let (device, queue) = noop_device();
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut renderer = Renderer::new(&device, format, 64, 64);
+        let existing = renderer.add_part(&device, &Part::new("existing"));
+        let uniform = |renderer: &Renderer, id| renderer.meshes[&id].data.instance.selection_color;
+        assert_eq!(uniform(&renderer, existing)[..3], selection::DEFAULT_COLOR);
+
+        let mut config = RendererConfig::default();
+        config.selection.color = [0.1, 0.9, 0.3];
+        config.selection.show_occluded = false;
+        renderer.apply_config(&config, &device, &queue);
+        assert_eq!(uniform(&renderer, existing), [0.1, 0.9, 0.3, 1.0]);
+        assert!(!renderer.show_occluded_selection);
+
+        // Parts added later pick up the configured color as well
+        let added = renderer.add_part(&device, &Part::new("added"));
+        assert_eq!(uniform(&renderer, added), [0.1, 0.9, 0.3, 1.0]);
+        renderer.set_selected_part(&queue, Some(added));
+        assert_eq!(uniform(&renderer, added), [0.1, 0.9, 0.3, 1.0]);
+    }
+
+    #[test]
+    fn test_hidden_parts_are_not_picked_and_do_not_occlude() {
+        let (device, _queue) = noop_device();
+        let mut renderer = Renderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64);
+        let shape = rk_core::PrimitiveShape::Box { size: [1.0; 3] };
+        let part = Part::from_primitive("box", shape);
+        renderer.add_part(&device, &part);
+
+        let ray = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::NEG_Z);
+        let behind = Vec3::new(0.0, 0.0, -5.0);
+        renderer.camera_mut().position = ray.origin;
+        assert_eq!(
+            renderer.raycast_ray(&ray).map(|hit| hit.part_id),
+            Some(part.id)
+        );
+        assert!(renderer.is_point_occluded(behind, None));
+
+        renderer
+            .display_options_mut()
+            .set_hidden_visuals(&[part.id]);
+        assert!(renderer.raycast_ray(&ray).is_none());
+        assert!(!renderer.is_point_occluded(behind, None));
+    }
+
+    #[test]
+    fn test_mesh_update_keeps_pose_and_selection() {
+        let (device, queue) = noop_device();
+        let mut renderer = Renderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64);
+        let shape = rk_core::PrimitiveShape::Cylinder {
+            radius: 0.1,
+            length: 0.5,
+            segments: 8,
+        };
+        let mut part = Part::from_primitive("cylinder", shape);
+        renderer.add_part(&device, &part);
+        let pose = Mat4::from_translation(Vec3::new(0.3, -0.2, 0.5));
+        renderer.update_part_transform(&queue, part.id, pose);
+        renderer.set_selected_part(&queue, Some(part.id));
+        let index_count = renderer.meshes[&part.id].data.index_count;
+
+        assert!(part.set_primitive_segments(32));
+        renderer.update_part_mesh(&device, &part);
+
+        let data = &renderer.meshes[&part.id].data;
+        assert!(data.index_count > index_count);
+        assert_eq!(Mat4::from_cols_array_2d(&data.instance.model), pose);
+        assert_eq!(data.instance.selected, 1);
+    }
+
+    #[test]
+    fn test_new_renderer_starts_at_default_viewport() {
+        let (device, _queue) = noop_device();
+        let renderer = Renderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64);
+        assert_eq!(renderer.viewport_settings(), ViewportSettings::default());
+    }
+
+    #[test]
+    fn test_part_world_bounds_follow_pose() {
+        let (device, queue) = noop_device();
+        let mut renderer = Renderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64);
+        let shape = rk_core::PrimitiveShape::Box {
+            size: [0.2, 0.2, 0.2],
+        };
+        let part = Part::from_primitive("box", shape);
+        renderer.add_part(&device, &part);
+        assert!(renderer.part_world_bounds(Uuid::new_v4()).is_none());
+
+        let offset = Vec3::new(1.0, 2.0, 3.0);
+        renderer.update_part_transform(&queue, part.id, Mat4::from_translation(offset));
+        let bounds = renderer.part_world_bounds(part.id).unwrap();
+        assert!((bounds.center() - offset).length() < 1e-5);
+        assert!((bounds.size() - Vec3::splat(0.2)).length() < 1e-5);
+
+        let other = Part::from_primitive("other", shape);
+        renderer.add_part(&device, &other);
+        let scene = renderer.scene_bounds().unwrap();
+        assert!((scene.min - Vec3::splat(-0.1)).length() < 1e-5);
+        assert!((scene.max - (offset + 0.1)).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_configured_fov_change_does_not_dolly() {
+        let (device, queue) = noop_device();
+        let mut renderer = Renderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64);
+        let mut config = RendererConfig::default();
+        config.camera.dolly_zoom = true;
+        renderer.apply_config(&config, &device, &queue);
+        let distance = renderer.camera().distance;
+        let fov = renderer.camera().fov_degrees();
+
+        config.camera.fov_degrees = fov / 2.0;
+        renderer.apply_config(&config, &device, &queue);
+        assert!((renderer.camera().fov_degrees() - fov / 2.0).abs() < 1e-4);
+        assert_eq!(renderer.camera().distance, distance);
+
+        // Re-applying an unchanged FOV leaves the camera where it is
+        renderer.apply_config(&config, &device, &queue);
+        assert_eq!(renderer.camera().distance, distance);
+    }
+
+    #[test]
+    fn test_sketch_config_reaches_registered_sketch_renderer() {
+        let (device, queue) = noop_device();
+        let mut renderer = Renderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64);
+        renderer.registry_mut().register(SketchRenderer::new());
+
+        let mut config = RendererConfig::default();
+        config.sketch.depth_offset = 0.0125;
+        renderer.apply_config(&config, &device, &queue);
+
+        let sketch = renderer.registry_mut().find_mut::<SketchRenderer>();
+        assert_eq!(sketch.unwrap().depth_offset(), 0.0125);
+    }
+
+    #[test]
+    fn test_captured_depth_matches_scene() {
+        // Reading real depth values needs an adapter that executes commands
+        let Some((device, queue)) = pollster::block_on(crate::headless_device()) else {
+            eprintln!("No GPU adapter available; skipping");
+            return;
+        };
+
+        const SIZE: u32 = 64;
+        let mut renderer = Renderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, SIZE, SIZE);
+        let part = Part::from_primitive("cube", rk_core::PrimitiveShape::Box { size: [0.5; 3] });
+        renderer.add_part(&device, &part);
+        // Looking down -X at the cube's +X face, 1.75 in front of the eye
+        renderer.camera_mut().set_view(Vec3::ZERO, 2.0, 0.0, 0.0);
+
+        let buffers = renderer.capture_buffers(&device, &queue);
+        let depth = buffers.read_depth(&device, &queue).unwrap();
+        let normals = buffers.read_normals(&device, &queue).unwrap();
+        assert_eq!(depth.len(), (SIZE * SIZE) as usize);
+
+        let camera = renderer.camera();
+        let face = camera.projection_matrix()
+            * camera.view_matrix()
+            * Vec3::new(0.25, 0.0, 0.0).extend(1.0);
+        let expected = face.z / face.w;
+        let center = (SIZE / 2 * SIZE + SIZE / 2) as usize;
+        assert!(
+            (depth[center] - expected).abs() < 1e-4,
+            "{} != {}",
+            depth[center],
+            expected
+        );
+        assert!(depth[center] > 0.0 && depth[center] < 1.0);
+        // Nothing drawn in the corner: cleared to the far plane
+        assert_eq!(depth[0], 1.0);
+
+        let normal = normals[center];
+        assert!(Vec3::new(normal[0], normal[1], normal[2]).abs_diff_eq(Vec3::X, 1e-3));
+        assert_eq!(normal[3], 1.0);
+        assert_eq!(normals[0][3], 0.0);
+    }
+}
@@ -3,6 +3,8 @@
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use rk_core::{MeshReport, analyze_mesh};
+
 use crate::context::RenderContext;
 use crate::scene::BoundingBox;
 use crate::vertex::MeshVertex;
@@ -85,6 +87,20 @@ impl MeshData {
         self
     }
 
+    /// Analyzes the mesh for degenerate, duplicate, and non-manifold geometry.
+    ///
+    /// Non-indexed meshes are treated as consecutive vertex triples.
+    pub fn analyze(&self) -> MeshReport {
+        let positions: Vec<[f32; 3]> = self.vertices.iter().map(|v| v.position).collect();
+        match &self.indices {
+            Some(indices) => analyze_mesh(&positions, indices),
+            None => {
+                let indices: Vec<u32> = (0..positions.len() as u32).collect();
+                analyze_mesh(&positions, &indices)
+            }
+        }
+    }
+
     fn compute_bounds(vertices: &[MeshVertex]) -> BoundingBox {
         if vertices.is_empty() {
             return BoundingBox::empty();
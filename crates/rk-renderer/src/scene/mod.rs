@@ -4,9 +4,11 @@
 //! enabling cleaner architecture and better extensibility.
 
 mod bounds;
+mod raycast;
 mod render_object;
 
 pub use bounds::*;
+pub use raycast::*;
 pub use render_object::*;
 
 use std::collections::HashMap;
@@ -0,0 +1,366 @@
+//! CPU ray casting against part geometry.
+//!
+//! Shared by viewport picking, measurement tools and plugins so that all of
+//! them agree on what lies under the cursor.
+
+use glam::{Mat4, Vec3};
+use uuid::Uuid;
+
+use rk_core::Part;
+
+use super::BoundingBox;
+
+/// A ray in world space.
+#[derive(Debug, Clone, Copy)]
+pub struct Ray {
+    /// Ray origin.
+    pub origin: Vec3,
+    /// Normalized ray direction.
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Creates a new ray.
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Returns the point at distance `t` along the ray.
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+}
+
+/// Result of a successful ray cast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// Part that was hit.
+    pub part_id: Uuid,
+    /// World-space hit position.
+    pub position: Vec3,
+    /// World-space surface normal, facing the ray origin.
+    pub normal: Vec3,
+    /// Distance from the ray origin to the hit.
+    pub distance: f32,
+}
+
+/// How triangle winding affects which hit a ray cast reports.
+///
+/// Triangles are always hit from both sides, so inverted or inconsistently
+/// wound meshes stay pickable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PickFacing {
+    /// Report the nearest hit regardless of winding.
+    #[default]
+    Nearest,
+    /// Report the nearest front-facing hit, falling back to back faces only
+    /// when no front face is hit.
+    PreferFront,
+}
+
+/// Closest front- and back-facing hits of a ray cast
+#[derive(Debug, Clone, Copy, Default)]
+struct FacingHits {
+    front: Option<(f32, Vec3)>,
+    back: Option<(f32, Vec3)>,
+}
+
+impl PickFacing {
+    /// Choose between the closest front- and back-facing hits
+    fn choose<T>(
+        self,
+        front: Option<T>,
+        back: Option<T>,
+        distance: impl Fn(&T) -> f32,
+    ) -> Option<T> {
+        match (self, front, back) {
+            (PickFacing::PreferFront, Some(front), _) => Some(front),
+            (_, Some(front), Some(back)) if distance(&back) < distance(&front) => Some(back),
+            (_, front, back) => front.or(back),
+        }
+    }
+}
+
+/// CPU-side copy of part geometry used for ray casting.
+#[derive(Debug, Clone)]
+pub struct PickGeometry {
+    /// Vertex positions in part-local space.
+    pub vertices: Vec<Vec3>,
+    /// Triangle indices.
+    pub indices: Vec<u32>,
+    /// Part-local bounding box.
+    pub bounds: BoundingBox,
+}
+
+impl PickGeometry {
+    /// Creates pick geometry from a part's mesh.
+    pub fn from_part(part: &Part) -> Self {
+        Self {
+            vertices: part.vertices.iter().map(|v| Vec3::from(*v)).collect(),
+            indices: part.indices.clone(),
+            bounds: BoundingBox::new(Vec3::from(part.bbox_min), Vec3::from(part.bbox_max)),
+        }
+    }
+
+    /// Casts a ray against this geometry placed at `transform`.
+    ///
+    /// Returns the distance and the world-space normal of the closest hit.
+    pub fn raycast(&self, ray: &Ray, transform: &Mat4) -> Option<(f32, Vec3)> {
+        self.raycast_with(ray, transform, PickFacing::Nearest)
+    }
+
+    /// Casts a ray against this geometry, choosing the hit according to `facing`.
+    pub fn raycast_with(
+        &self,
+        ray: &Ray,
+        transform: &Mat4,
+        facing: PickFacing,
+    ) -> Option<(f32, Vec3)> {
+        let hits = self.facing_hits(ray, transform)?;
+        facing.choose(hits.front, hits.back, |(t, _)| *t)
+    }
+
+    fn facing_hits(&self, ray: &Ray, transform: &Mat4) -> Option<FacingHits> {
+        // Early rejection against the world-space AABB
+        let world_bounds = self.bounds.transform(transform);
+        ray_aabb_intersection(ray, world_bounds.min, world_bounds.max)?;
+
+        let mut hits = FacingHits::default();
+        for chunk in self.indices.chunks_exact(3) {
+            let [Some(v0), Some(v1), Some(v2)] =
+                [chunk[0], chunk[1], chunk[2]].map(|i| self.vertices.get(i as usize))
+            else {
+                continue;
+            };
+            let v0 = transform.transform_point3(*v0);
+            let v1 = transform.transform_point3(*v1);
+            let v2 = transform.transform_point3(*v2);
+
+            let Some(t) = ray_triangle_intersection(ray, v0, v1, v2) else {
+                continue;
+            };
+            let normal = (v1 - v0).cross(v2 - v0).normalize_or_zero();
+            // Report the normal facing the ray origin either way
+            let (closest, normal) = if normal.dot(ray.direction) > 0.0 {
+                (&mut hits.back, -normal)
+            } else {
+                (&mut hits.front, normal)
+            };
+            if closest.is_none_or(|(current, _)| t < current) {
+                *closest = Some((t, normal));
+            }
+        }
+        Some(hits)
+    }
+}
+
+/// Casts a ray against a set of placed geometries and returns the closest hit.
+pub fn raycast_closest<'a>(
+    ray: &Ray,
+    objects: impl IntoIterator<Item = (Uuid, &'a PickGeometry, Mat4)>,
+) -> Option<RayHit> {
+    raycast_closest_with(ray, objects, PickFacing::Nearest)
+}
+
+/// Casts a ray against a set of placed geometries, choosing the hit
+/// according to `facing` across all of them.
+pub fn raycast_closest_with<'a>(
+    ray: &Ray,
+    objects: impl IntoIterator<Item = (Uuid, &'a PickGeometry, Mat4)>,
+    facing: PickFacing,
+) -> Option<RayHit> {
+    let mut front: Option<(Uuid, f32, Vec3)> = None;
+    let mut back: Option<(Uuid, f32, Vec3)> = None;
+    for (part_id, geometry, transform) in objects {
+        let Some(hits) = geometry.facing_hits(ray, &transform) else {
+            continue;
+        };
+        for (closest, hit) in [(&mut front, hits.front), (&mut back, hits.back)] {
+            if let Some((distance, normal)) = hit
+                && closest.is_none_or(|(_, current, _)| distance < current)
+            {
+                *closest = Some((part_id, distance, normal));
+            }
+        }
+    }
+
+    let (part_id, distance, normal) = facing.choose(front, back, |(_, t, _)| *t)?;
+    Some(RayHit {
+        part_id,
+        position: ray.at(distance),
+        normal,
+        distance,
+    })
+}
+
+/// Ray-AABB intersection test.
+/// Returns the distance to intersection if hit, None otherwise.
+pub fn ray_aabb_intersection(ray: &Ray, bbox_min: Vec3, bbox_max: Vec3) -> Option<f32> {
+    let inv_dir = ray.direction.recip();
+
+    let t1 = (bbox_min - ray.origin) * inv_dir;
+    let t2 = (bbox_max - ray.origin) * inv_dir;
+
+    let tmin = t1.min(t2).max_element();
+    let tmax = t1.max(t2).min_element();
+
+    if tmax < 0.0 || tmin > tmax {
+        return None;
+    }
+
+    Some(if tmin < 0.0 { tmax } else { tmin })
+}
+
+/// Ray-triangle intersection using the Möller–Trumbore algorithm.
+/// Returns the distance to intersection if hit, None otherwise.
+pub fn ray_triangle_intersection(ray: &Ray, v0: Vec3, v1: Vec3, v2: Vec3) -> Option<f32> {
+    const EPSILON: f32 = 1e-6;
+
+    let edge1 = v1 - v0;
+    let edge2 = v2 - v0;
+    let h = ray.direction.cross(edge2);
+    let a = edge1.dot(h);
+
+    if a.abs() < EPSILON {
+        return None; // Ray is parallel to triangle
+    }
+
+    let f = 1.0 / a;
+    let s = ray.origin - v0;
+    let u = f * s.dot(h);
+
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = s.cross(edge1);
+    let v = f * ray.direction.dot(q);
+
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = f * edge2.dot(q);
+
+    if t > EPSILON { Some(t) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unit right triangle in the XY plane at z = 0
+    fn triangle() -> PickGeometry {
+        let vertices = vec![Vec3::ZERO, Vec3::X, Vec3::Y];
+        PickGeometry {
+            bounds: BoundingBox::from_points(vertices.iter().copied()),
+            vertices,
+            indices: vec![0, 1, 2],
+        }
+    }
+
+    #[test]
+    fn test_hit_position_and_normal() {
+        let geometry = triangle();
+        let ray = Ray::new(Vec3::new(0.25, 0.25, 5.0), Vec3::NEG_Z);
+        let id = Uuid::new_v4();
+
+        let hit = raycast_closest(&ray, [(id, &geometry, Mat4::IDENTITY)]).unwrap();
+        assert_eq!(hit.part_id, id);
+        assert!((hit.distance - 5.0).abs() < 1e-5);
+        assert!(hit.position.distance(Vec3::new(0.25, 0.25, 0.0)) < 1e-5);
+        assert!(hit.normal.distance(Vec3::Z) < 1e-5);
+    }
+
+    #[test]
+    fn test_normal_faces_ray_from_behind() {
+        let geometry = triangle();
+        let ray = Ray::new(Vec3::new(0.25, 0.25, -2.0), Vec3::Z);
+        let (t, normal) = geometry.raycast(&ray, &Mat4::IDENTITY).unwrap();
+        assert!((t - 2.0).abs() < 1e-5);
+        assert!(normal.distance(Vec3::NEG_Z) < 1e-5);
+    }
+
+    #[test]
+    fn test_transformed_hit() {
+        let geometry = triangle();
+        let transform = Mat4::from_translation(Vec3::new(10.0, 0.0, 1.0));
+        let ray = Ray::new(Vec3::new(10.25, 0.25, 5.0), Vec3::NEG_Z);
+        let (t, _) = geometry.raycast(&ray, &transform).unwrap();
+        assert!((t - 4.0).abs() < 1e-5);
+
+        // Untransformed location is now empty
+        let miss = Ray::new(Vec3::new(0.25, 0.25, 5.0), Vec3::NEG_Z);
+        assert!(geometry.raycast(&miss, &transform).is_none());
+    }
+
+    #[test]
+    fn test_miss_outside_triangle() {
+        let geometry = triangle();
+        let ray = Ray::new(Vec3::new(0.75, 0.75, 5.0), Vec3::NEG_Z);
+        assert!(geometry.raycast(&ray, &Mat4::IDENTITY).is_none());
+    }
+
+    #[test]
+    fn test_closest_of_two() {
+        let geometry = triangle();
+        let near = Uuid::new_v4();
+        let far = Uuid::new_v4();
+        let ray = Ray::new(Vec3::new(0.25, 0.25, 5.0), Vec3::NEG_Z);
+        let objects = [
+            (far, &geometry, Mat4::IDENTITY),
+            (near, &geometry, Mat4::from_translation(Vec3::Z)),
+        ];
+        assert_eq!(raycast_closest(&ray, objects).unwrap().part_id, near);
+    }
+
+    #[test]
+    fn test_hits_inward_facing_triangle() {
+        // Wound clockwise when seen from +Z, as in an inside-out mesh
+        let vertices = vec![Vec3::ZERO, Vec3::Y, Vec3::X];
+        let geometry = PickGeometry {
+            bounds: BoundingBox::from_points(vertices.iter().copied()),
+            vertices,
+            indices: vec![0, 1, 2],
+        };
+        let ray = Ray::new(Vec3::new(0.25, 0.25, 5.0), Vec3::NEG_Z);
+
+        for facing in [PickFacing::Nearest, PickFacing::PreferFront] {
+            let (t, normal) = geometry
+                .raycast_with(&ray, &Mat4::IDENTITY, facing)
+                .unwrap();
+            assert!((t - 5.0).abs() < 1e-5);
+            assert!(normal.distance(Vec3::Z) < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_prefer_front_skips_nearer_back_face() {
+        let front = triangle();
+        let inverted = PickGeometry {
+            indices: vec![0, 2, 1],
+            ..triangle()
+        };
+        let (front_id, back_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let ray = Ray::new(Vec3::new(0.25, 0.25, 5.0), Vec3::NEG_Z);
+        let objects = [
+            (front_id, &front, Mat4::IDENTITY),
+            (back_id, &inverted, Mat4::from_translation(Vec3::Z)),
+        ];
+
+        let nearest = raycast_closest_with(&ray, objects, PickFacing::Nearest).unwrap();
+        assert_eq!(nearest.part_id, back_id);
+        let preferred = raycast_closest_with(&ray, objects, PickFacing::PreferFront).unwrap();
+        assert_eq!(preferred.part_id, front_id);
+        assert!((preferred.distance - 5.0).abs() < 1e-5);
+
+        // Back faces are still picked when nothing else is hit
+        let only_back = [(back_id, &inverted, Mat4::IDENTITY)];
+        assert_eq!(
+            raycast_closest_with(&ray, only_back, PickFacing::PreferFront)
+                .unwrap()
+                .part_id,
+            back_id
+        );
+    }
+}
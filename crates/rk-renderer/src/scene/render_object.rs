@@ -83,30 +83,63 @@ impl RenderObject {
 }
 
 /// Render layer for sorting and filtering.
+///
+/// Layers are shown or hidden through a visibility bitmask built from
+/// [`RenderLayer::mask`]; see [`RenderLayer::is_visible_in`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum RenderLayer {
-    /// Default opaque geometry layer.
+    /// Default opaque geometry layer (visual meshes and the ground shadow).
     #[default]
     Default,
     /// Transparent geometry (rendered back-to-front).
     Transparent,
     /// Overlay elements (rendered on top).
     Overlay,
+    /// Collision shapes.
+    Collision,
+    /// Reference aids: grid, part axes and joint markers.
+    Annotations,
+    /// Diagnostic overlays from plugins.
+    Debug,
     /// Custom layer with user-defined ID.
     Custom(u32),
 }
 
 impl RenderLayer {
+    /// Visibility mask with every layer shown.
+    pub const ALL_MASK: u32 = u32::MAX;
+
     /// Returns the sort order for this layer (lower = rendered first).
     pub fn sort_order(&self) -> i32 {
         match self {
             RenderLayer::Default => 0,
-            RenderLayer::Transparent => 100,
-            RenderLayer::Overlay => 200,
+            RenderLayer::Transparent | RenderLayer::Collision => 100,
+            RenderLayer::Overlay | RenderLayer::Annotations => 200,
+            RenderLayer::Debug => 300,
             RenderLayer::Custom(id) => 1000 + (*id as i32),
         }
     }
 
+    /// Returns this layer's bit in a visibility mask.
+    ///
+    /// Opaque and transparent geometry share a bit, so hiding geometry hides
+    /// both. Custom layers map onto the upper 24 bits.
+    pub fn mask(&self) -> u32 {
+        match self {
+            RenderLayer::Default | RenderLayer::Transparent => 1 << 0,
+            RenderLayer::Collision => 1 << 1,
+            RenderLayer::Annotations => 1 << 2,
+            RenderLayer::Debug => 1 << 3,
+            RenderLayer::Overlay => 1 << 4,
+            RenderLayer::Custom(id) => 1 << (8 + id % 24),
+        }
+    }
+
+    /// Returns true if this layer is shown by the visibility `mask`.
+    pub fn is_visible_in(&self, mask: u32) -> bool {
+        mask & self.mask() != 0
+    }
+
     /// Returns true if this layer uses alpha blending.
     pub fn uses_blending(&self) -> bool {
         matches!(self, RenderLayer::Transparent | RenderLayer::Overlay)
@@ -0,0 +1,25 @@
+// Copy a depth texture into an 8-bit color target for CPU readback
+
+// Bound as unfilterable float, since GLSL has no texel fetch for depth textures
+@group(0) @binding(0)
+var depth_texture: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // Fullscreen triangle
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let depth = textureLoad(depth_texture, vec2<i32>(position.xy), 0).r;
+    // Little-endian bytes of the f32, exact through an 8-bit unorm target
+    let bits = bitcast<u32>(depth);
+    return vec4<f32>(
+        f32(bits & 0xffu),
+        f32((bits >> 8u) & 0xffu),
+        f32((bits >> 16u) & 0xffu),
+        f32(bits >> 24u),
+    ) / 255.0;
+}
@@ -10,6 +10,20 @@ struct CameraUniform {
 @group(0) @binding(0)
 var<uniform> camera: CameraUniform;
 
+struct GridUniform {
+    // Distance from the camera at which the grid has faded out (0 = no fade)
+    fade_radius: f32,
+    // Grid extent (half-size in each direction)
+    half_size: f32,
+    // Fraction of the half-size over which lines fade towards the edge
+    edge_fraction: f32,
+    // Line opacity before fading
+    alpha: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> grid: GridUniform;
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) color: vec3<f32>,
@@ -32,8 +46,18 @@ fn vs_main(in: VertexInput) -> VertexOutput {
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    // Fade out based on distance from camera
+    if (grid.fade_radius <= 0.0) {
+        return vec4<f32>(in.color, grid.alpha);
+    }
+
+    // Fade out with distance from the camera
     let dist = length(in.world_pos - camera.eye.xyz);
-    let fade = 1.0 - smoothstep(5.0, 15.0, dist);
-    return vec4<f32>(in.color, fade * 0.8);
+    let camera_fade = 1.0 - smoothstep(grid.fade_radius / 3.0, grid.fade_radius, dist);
+
+    // Fade out towards the edges so the grid blends into the background
+    let band = max(grid.half_size * grid.edge_fraction, 1e-4);
+    let to_edge = grid.half_size - max(abs(in.world_pos.x), abs(in.world_pos.y));
+    let edge_fade = smoothstep(0.0, band, to_edge);
+
+    return vec4<f32>(in.color, grid.alpha * camera_fade * edge_fade);
 }
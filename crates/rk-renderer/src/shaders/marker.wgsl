@@ -1,4 +1,4 @@
-// Marker (sphere) shader
+// Marker shader (sphere, cross and cube)
 
 struct CameraUniform {
     view_proj: mat4x4<f32>,
@@ -12,11 +12,12 @@ var<uniform> camera: CameraUniform;
 
 struct VertexInput {
     @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
 };
 
 struct InstanceInput {
-    @location(1) position_radius: vec4<f32>,  // xyz = position, w = radius
-    @location(2) color: vec4<f32>,
+    @location(2) position_size: vec4<f32>,  // xyz = position, w = size
+    @location(3) color: vec4<f32>,
 };
 
 struct VertexOutput {
@@ -33,13 +34,13 @@ fn vs_main(
 ) -> VertexOutput {
     var out: VertexOutput;
 
-    let instance_pos = instance.position_radius.xyz;
-    let radius = instance.position_radius.w;
-    let world_pos = in.position * radius + instance_pos;
+    let instance_pos = instance.position_size.xyz;
+    let size = instance.position_size.w;
+    let world_pos = in.position * size + instance_pos;
 
     out.clip_position = camera.view_proj * vec4<f32>(world_pos, 1.0);
     out.world_pos = world_pos;
-    out.world_normal = normalize(in.position); // Unit sphere, so position is normal
+    out.world_normal = normalize(in.normal);
     out.color = instance.color;
 
     return out;
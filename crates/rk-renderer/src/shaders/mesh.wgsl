@@ -11,14 +11,15 @@ struct InstanceUniform {
     model: mat4x4<f32>,
     color: vec4<f32>,
     selected: u32,
-    _padding1: u32,
-    _padding2: u32,
-    _padding3: u32,
+    highlighted: u32,
+    receives_shadow: u32,
+    vertex_colors: u32,
+    selection_color: vec4<f32>,
 };
 
 struct LightUniform {
     light_view_proj: mat4x4<f32>,
-    direction: vec4<f32>,      // xyz = direction (toward light), w = unused
+    direction: vec4<f32>,      // xyz = direction (toward light), w = two-sided (1.0 or 0.0)
     color_intensity: vec4<f32>, // rgb = color, a = intensity
     ambient: vec4<f32>,         // rgb = color, a = strength
     shadow_params: vec4<f32>,   // x = bias, y = normal_bias, z = softness, w = enabled
@@ -69,8 +70,8 @@ fn vs_main(in: VertexInput) -> VertexOutput {
     );
     out.world_normal = normalize(normal_matrix * in.normal);
 
-    // Use instance color if set, otherwise vertex color
-    out.color = instance.color;
+    // Imported multi-material meshes carry their colors per vertex
+    out.color = select(instance.color, in.color, instance.vertex_colors == 1u);
 
     // Transform position to light space for shadow mapping
     out.light_space_pos = light.light_view_proj * world_pos;
@@ -108,7 +109,7 @@ fn calculate_shadow(light_space_pos: vec4<f32>, normal: vec3<f32>, light_dir: ve
 
     // PCF filtering (3x3 kernel for soft shadows)
     // Always sample to maintain uniform control flow
-    let texel_size = 1.0 / 2048.0;  // Shadow map size
+    let texel_size = 1.0 / f32(textureDimensions(shadow_map).x);
     var shadow = 0.0;
 
     for (var x = -1; x <= 1; x++) {
@@ -129,14 +130,23 @@ fn calculate_shadow(light_space_pos: vec4<f32>, normal: vec3<f32>, light_dir: ve
     return select(1.0, shadow, shadows_enabled && in_bounds);
 }
 
-@fragment
-fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+// Lit, shadowed and selection-tinted surface color
+fn shade(in: VertexOutput) -> vec4<f32> {
     let light_dir = normalize(light.direction.xyz);
     let view_dir = normalize(camera.eye.xyz - in.world_pos);
-    let normal = normalize(in.world_normal);
+    var normal = normalize(in.world_normal);
+
+    // Two-sided lighting: light back faces of thin meshes as seen from the camera
+    if (light.direction.w >= 0.5 && dot(normal, view_dir) < 0.0) {
+        normal = -normal;
+    }
 
-    // Calculate shadow factor
-    let shadow = calculate_shadow(in.light_space_pos, normal, light_dir);
+    // Calculate shadow factor (always sampled for uniform control flow)
+    let shadow = select(
+        1.0,
+        calculate_shadow(in.light_space_pos, normal, light_dir),
+        instance.receives_shadow == 1u,
+    );
 
     // Ambient lighting (always visible, not affected by shadow)
     let ambient = light.ambient.rgb * light.ambient.a;
@@ -157,9 +167,48 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
     // Selection highlight
     if (instance.selected == 1u) {
-        // Add orange tint for selected objects
-        color = mix(color, vec3<f32>(1.0, 0.6, 0.2), 0.3);
+        // Tint selected objects with the configured selection color
+        color = mix(color, instance.selection_color.rgb, 0.3);
+    } else if (instance.highlighted == 1u) {
+        // Add cyan tint for links on the selected kinematic chain
+        color = mix(color, vec3<f32>(0.2, 0.8, 1.0), 0.3);
     }
 
     return vec4<f32>(color, in.color.a);
 }
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return shade(in);
+}
+
+struct GBufferOutput {
+    @location(0) color: vec4<f32>,
+    @location(1) normal: vec4<f32>,
+};
+
+// Color plus world-space normal for buffer capture; w marks coverage
+@fragment
+fn fs_gbuffer(in: VertexOutput) -> GBufferOutput {
+    var out: GBufferOutput;
+    out.color = shade(in);
+    out.normal = vec4<f32>(normalize(in.world_normal), 1.0);
+    return out;
+}
+
+// Selection rim drawn without depth test, so a hidden selection stays visible;
+// faces seen edge-on are nearly opaque, faces seen head-on nearly clear
+@fragment
+fn fs_selection_outline(in: VertexOutput) -> @location(0) vec4<f32> {
+    let view_dir = normalize(camera.eye.xyz - in.world_pos);
+    let rim = 1.0 - abs(dot(normalize(in.world_normal), view_dir));
+    return vec4<f32>(instance.selection_color.rgb, mix(0.15, 0.9, rim * rim));
+}
+
+// Ground shadow catcher: transparent except where the ground is in shadow
+@fragment
+fn fs_ground_shadow(in: VertexOutput) -> @location(0) vec4<f32> {
+    let light_dir = normalize(light.direction.xyz);
+    let shadow = calculate_shadow(in.light_space_pos, vec3<f32>(0.0, 0.0, 1.0), light_dir);
+    return vec4<f32>(0.0, 0.0, 0.0, (1.0 - shadow) * 0.4);
+}
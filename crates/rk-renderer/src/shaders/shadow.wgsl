@@ -12,9 +12,10 @@ struct InstanceUniform {
     model: mat4x4<f32>,
     color: vec4<f32>,
     selected: u32,
-    _padding1: u32,
-    _padding2: u32,
-    _padding3: u32,
+    highlighted: u32,
+    receives_shadow: u32,
+    vertex_colors: u32,
+    selection_color: vec4<f32>,
 };
 
 @group(0) @binding(0)
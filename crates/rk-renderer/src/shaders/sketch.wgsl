@@ -14,6 +14,8 @@ var<uniform> camera: CameraUniform;
 struct SketchUniform {
     transform: mat4x4<f32>,
     plane_color: vec4<f32>,
+    // Viewport size in pixels (xy), depth offset towards the camera (z)
+    viewport: vec4<f32>,
 };
 
 @group(1) @binding(0)
@@ -37,6 +39,16 @@ const FLAG_SELECTED: u32 = 1u;
 const FLAG_HOVERED: u32 = 2u;
 const FLAG_CONSTRUCTION: u32 = 4u;
 const FLAG_CONSTRAINED: u32 = 8u;
+const FLAG_FIXED: u32 = 16u;
+const FLAG_GRID: u32 = 32u;
+const FLAG_ORIGIN: u32 = 64u;
+const FLAG_CANDIDATE: u32 = 128u;
+
+// Pull a clip-space position towards the camera by a constant NDC depth so
+// sketches lying on a face win the depth test against it
+fn apply_depth_offset(clip: vec4<f32>) -> vec4<f32> {
+    return vec4<f32>(clip.xy, clip.z - sketch.viewport.z * clip.w, clip.w);
+}
 
 @vertex
 fn vs_main(in: VertexInput) -> VertexOutput {
@@ -44,7 +56,7 @@ fn vs_main(in: VertexInput) -> VertexOutput {
 
     // Transform from sketch space to world space
     let world_pos = (sketch.transform * vec4<f32>(in.position, 1.0)).xyz;
-    out.clip_position = camera.view_proj * vec4<f32>(world_pos, 1.0);
+    out.clip_position = apply_depth_offset(camera.view_proj * vec4<f32>(world_pos, 1.0));
     out.color = in.color;
     out.flags = in.flags;
     out.world_pos = world_pos;
@@ -63,6 +75,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     } else if ((in.flags & FLAG_HOVERED) != 0u) {
         // Hovered: slight highlight
         color = mix(color, vec4<f32>(1.0, 1.0, 0.0, 1.0), 0.3);
+    } else if ((in.flags & FLAG_CANDIDATE) != 0u) {
+        // Can complete the active constraint: faint blue tint
+        color = mix(color, vec4<f32>(0.3, 0.7, 1.0, 1.0), 0.3);
     }
 
     // Construction geometry: more transparent
@@ -70,8 +85,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         color.a *= 0.5;
     }
 
-    // Constrained geometry: fully saturated
-    if ((in.flags & FLAG_CONSTRAINED) != 0u) {
+    // Constrained geometry: fully saturated (glyphs and grid keep their own color)
+    if ((in.flags & FLAG_CONSTRAINED) != 0u && (in.flags & (FLAG_FIXED | FLAG_GRID | FLAG_ORIGIN)) == 0u) {
         color = vec4<f32>(0.0, 0.8, 0.0, color.a);
     }
 
@@ -83,22 +98,44 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     return color;
 }
 
-// Point rendering (separate entry points for point primitives)
+// Point rendering: each point is a camera-facing quad cut into a disc
+struct PointInput {
+    @location(0) position: vec3<f32>,
+    @location(1) corner: vec2<f32>,
+    @location(2) size: f32,
+    @location(3) color: vec4<f32>,
+    @location(4) flags: u32,
+};
+
+struct PointOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) @interpolate(flat) flags: u32,
+    @location(2) corner: vec2<f32>,
+};
+
 @vertex
-fn vs_point(in: VertexInput) -> VertexOutput {
-    var out: VertexOutput;
+fn vs_point(in: PointInput) -> PointOutput {
+    var out: PointOutput;
 
     let world_pos = (sketch.transform * vec4<f32>(in.position, 1.0)).xyz;
-    out.clip_position = camera.view_proj * vec4<f32>(world_pos, 1.0);
+    let center = apply_depth_offset(camera.view_proj * vec4<f32>(world_pos, 1.0));
+
+    // Offset in NDC so the quad is `size` pixels wide regardless of distance;
+    // scaling by w keeps the offset constant after the perspective divide and
+    // leaves depth at the point center for depth testing.
+    let viewport = max(sketch.viewport.xy, vec2<f32>(1.0, 1.0));
+    let offset = in.corner * in.size / viewport;
+    out.clip_position = center + vec4<f32>(offset * center.w, 0.0, 0.0);
     out.color = in.color;
     out.flags = in.flags;
-    out.world_pos = world_pos;
+    out.corner = in.corner;
 
     return out;
 }
 
 @fragment
-fn fs_point(in: VertexOutput) -> @location(0) vec4<f32> {
+fn fs_point(in: PointOutput) -> @location(0) vec4<f32> {
     var color = in.color;
 
     // Points are always more visible
@@ -106,7 +143,18 @@ fn fs_point(in: VertexOutput) -> @location(0) vec4<f32> {
         color = vec4<f32>(1.0, 0.4, 0.0, 1.0);
     } else if ((in.flags & FLAG_HOVERED) != 0u) {
         color = vec4<f32>(1.0, 0.8, 0.0, 1.0);
+    } else if ((in.flags & FLAG_CANDIDATE) != 0u) {
+        color = mix(color, vec4<f32>(0.3, 0.7, 1.0, 1.0), 0.3);
+    }
+
+    // Cut the quad into a disc with a one-pixel anti-aliased edge
+    let r = length(in.corner);
+    let edge = max(fwidth(r), 1e-4);
+    let coverage = 1.0 - smoothstep(1.0 - edge, 1.0, r);
+    if (coverage <= 0.0) {
+        discard;
     }
+    color.a *= coverage;
 
     return color;
 }
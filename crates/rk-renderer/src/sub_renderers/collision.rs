@@ -257,18 +257,26 @@ impl CollisionRenderer {
         self.capsule_instances.clear();
     }
 
+    /// Check if there are no collision instances
+    pub fn is_empty(&self) -> bool {
+        self.box_instances.is_empty()
+            && self.sphere_instances.is_empty()
+            && self.cylinder_instances.is_empty()
+            && self.capsule_instances.is_empty()
+    }
+
     /// Add a box collision instance
     pub fn add_box(&mut self, transform: Mat4, size: [f32; 3], color: [f32; 4]) {
         let scale = Mat4::from_scale(Vec3::from_array(size));
         let instance = CollisionInstance::new(transform * scale, color);
-        self.box_instances.push(instance);
+        push_capped(&mut self.box_instances, instance);
     }
 
     /// Add a sphere collision instance
     pub fn add_sphere(&mut self, transform: Mat4, radius: f32, color: [f32; 4]) {
         let scale = Mat4::from_scale(Vec3::splat(radius));
         let instance = CollisionInstance::new(transform * scale, color);
-        self.sphere_instances.push(instance);
+        push_capped(&mut self.sphere_instances, instance);
     }
 
     /// Add a cylinder collision instance
@@ -276,7 +284,7 @@ impl CollisionRenderer {
         // Cylinder is along Z axis, scale appropriately
         let scale = Mat4::from_scale(Vec3::new(radius, radius, length));
         let instance = CollisionInstance::new(transform * scale, color);
-        self.cylinder_instances.push(instance);
+        push_capped(&mut self.cylinder_instances, instance);
     }
 
     /// Add a capsule collision instance
@@ -284,7 +292,7 @@ impl CollisionRenderer {
         // Capsule is along Z axis
         let scale = Mat4::from_scale(Vec3::new(radius, radius, length + 2.0 * radius));
         let instance = CollisionInstance::new(transform * scale, color);
-        self.capsule_instances.push(instance);
+        push_capped(&mut self.capsule_instances, instance);
     }
 
     /// Upload instances to GPU
@@ -388,6 +396,13 @@ impl CollisionRenderer {
     }
 }
 
+/// Push an instance unless the GPU instance buffer is already full
+fn push_capped(list: &mut Vec<CollisionInstance>, instance: CollisionInstance) {
+    if list.len() < instances::MAX_COLLISIONS as usize {
+        list.push(instance);
+    }
+}
+
 fn create_vertex_buffer(
     device: &wgpu::Device,
     name: &str,
@@ -17,17 +17,7 @@ use crate::constants::gizmo as constants;
 use crate::constants::viewport::SAMPLE_COUNT;
 use geometry::{generate_rotation_gizmo, generate_scale_gizmo, generate_translation_gizmo};
 
-/// Gizmo mode
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum GizmoMode {
-    /// Translation mode (move along axes).
-    #[default]
-    Translate,
-    /// Rotation mode (rotate around axes).
-    Rotate,
-    /// Scale mode (scale along axes).
-    Scale,
-}
+pub use rk_core::GizmoMode;
 
 /// Gizmo coordinate space
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
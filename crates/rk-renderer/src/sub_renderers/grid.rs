@@ -3,10 +3,69 @@
 use crate::constants::grid as constants;
 use crate::context::RenderContext;
 use crate::pipeline::PipelineConfig;
-use crate::scene::Scene;
+use crate::scene::{RenderLayer, Scene};
 use crate::traits::SubRenderer;
 use crate::vertex::PositionColorVertex;
 
+/// Uniform data controlling how grid lines fade out.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub(crate) struct GridUniform {
+    /// Distance from the camera at which the grid has faded out (0 = no fade).
+    pub fade_radius: f32,
+    /// Grid extent (half-size in each direction).
+    pub half_size: f32,
+    /// Fraction of the half-size over which lines fade towards the edge.
+    pub edge_fraction: f32,
+    /// Line opacity before fading.
+    pub alpha: f32,
+}
+
+impl GridUniform {
+    /// Uniform for a grid of the given half-size.
+    pub(crate) fn new(fade_radius: f32, half_size: f32) -> Self {
+        Self {
+            fade_radius: fade_radius.max(0.0),
+            half_size,
+            edge_fraction: constants::EDGE_FADE_FRACTION,
+            alpha: constants::LINE_ALPHA,
+        }
+    }
+}
+
+/// Bind group layout for the grid uniform (group 1 of the grid shader).
+pub(crate) fn create_grid_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Grid Bind Group Layout"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    })
+}
+
+/// Bind group exposing a grid uniform buffer to the grid shader.
+pub(crate) fn create_grid_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Grid Bind Group"),
+        layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: buffer.as_entire_binding(),
+        }],
+    })
+}
+
 /// Grid sub-renderer for ground reference plane.
 pub struct GridSubRenderer {
     enabled: bool,
@@ -14,7 +73,10 @@ pub struct GridSubRenderer {
     pipeline: Option<wgpu::RenderPipeline>,
     vertex_buffer: Option<wgpu::Buffer>,
     camera_bind_group: Option<wgpu::BindGroup>,
+    uniform_buffer: Option<wgpu::Buffer>,
+    grid_bind_group: Option<wgpu::BindGroup>,
     vertex_count: u32,
+    fade_radius: f32,
 }
 
 impl GridSubRenderer {
@@ -26,9 +88,26 @@ impl GridSubRenderer {
             pipeline: None,
             vertex_buffer: None,
             camera_bind_group: None,
+            uniform_buffer: None,
+            grid_bind_group: None,
             vertex_count: 0,
+            fade_radius: constants::DEFAULT_FADE_RADIUS,
         }
     }
+
+    /// Distance from the camera at which the grid has faded out.
+    pub fn fade_radius(&self) -> f32 {
+        self.fade_radius
+    }
+
+    /// Set the fade distance; 0 disables fading.
+    pub fn set_fade_radius(&mut self, radius: f32) {
+        self.fade_radius = radius.max(0.0);
+    }
+
+    fn uniform(&self) -> GridUniform {
+        GridUniform::new(self.fade_radius, constants::DEFAULT_SIZE)
+    }
 }
 
 impl Default for GridSubRenderer {
@@ -54,13 +133,18 @@ impl SubRenderer for GridSubRenderer {
         self.enabled = enabled;
     }
 
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Annotations
+    }
+
     fn on_init(&mut self, ctx: &RenderContext) {
+        let grid_layout = create_grid_bind_group_layout(ctx.device());
         let pipeline = PipelineConfig::new(
             "Grid",
             include_str!("../shaders/grid.wgsl"),
             ctx.surface_format(),
             ctx.depth_format(),
-            &[ctx.camera_bind_group_layout()],
+            &[ctx.camera_bind_group_layout(), &grid_layout],
         )
         .with_vertex_layouts(vec![PositionColorVertex::layout()])
         .with_topology(wgpu::PrimitiveTopology::LineList)
@@ -86,9 +170,18 @@ impl SubRenderer for GridSubRenderer {
             }],
         });
 
+        let uniform_buffer = ctx.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Grid Uniform Buffer"),
+            contents: bytemuck::bytes_of(&self.uniform()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let grid_bind_group = create_grid_bind_group(ctx.device(), &grid_layout, &uniform_buffer);
+
         self.pipeline = Some(pipeline);
         self.vertex_buffer = Some(vertex_buffer);
         self.camera_bind_group = Some(camera_bind_group);
+        self.uniform_buffer = Some(uniform_buffer);
+        self.grid_bind_group = Some(grid_bind_group);
         self.initialized = true;
     }
 
@@ -96,8 +189,10 @@ impl SubRenderer for GridSubRenderer {
         // Grid doesn't need to respond to resize
     }
 
-    fn prepare(&mut self, _ctx: &RenderContext, _scene: &Scene) {
-        // Grid doesn't need per-frame preparation
+    fn prepare(&mut self, ctx: &RenderContext, _scene: &Scene) {
+        if let Some(buffer) = &self.uniform_buffer {
+            ctx.write_buffer(buffer, 0, bytemuck::bytes_of(&self.uniform()));
+        }
     }
 
     fn render<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>, _scene: &Scene) {
@@ -108,9 +203,11 @@ impl SubRenderer for GridSubRenderer {
         let pipeline = self.pipeline.as_ref().unwrap();
         let vertex_buffer = self.vertex_buffer.as_ref().unwrap();
         let camera_bind_group = self.camera_bind_group.as_ref().unwrap();
+        let grid_bind_group = self.grid_bind_group.as_ref().unwrap();
 
         pass.set_pipeline(pipeline);
         pass.set_bind_group(0, camera_bind_group, &[]);
+        pass.set_bind_group(1, grid_bind_group, &[]);
         pass.set_vertex_buffer(0, vertex_buffer.slice(..));
         pass.draw(0..self.vertex_count, 0..1);
     }
@@ -166,3 +263,22 @@ fn generate_grid_vertices(size: f32, spacing: f32) -> Vec<PositionColorVertex> {
 
     vertices
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_fade_radius_uniform() {
+        let shader = include_str!("../shaders/grid.wgsl");
+        assert!(shader.contains("var<uniform> grid: GridUniform"));
+        assert!(shader.contains("fade_radius"));
+        assert_eq!(std::mem::size_of::<GridUniform>(), 16);
+
+        let mut renderer = GridSubRenderer::new();
+        renderer.set_fade_radius(12.0);
+        assert_eq!(renderer.uniform().fade_radius, 12.0);
+        renderer.set_fade_radius(-1.0);
+        assert_eq!(renderer.fade_radius(), 0.0);
+    }
+}
@@ -2,6 +2,7 @@
 
 use wgpu::util::DeviceExt;
 
+use super::grid::{GridUniform, create_grid_bind_group, create_grid_bind_group_layout};
 use crate::constants::grid as constants;
 use crate::pipeline::{PipelineConfig, create_camera_bind_group};
 use crate::vertex::PositionColorVertex;
@@ -12,6 +13,9 @@ pub struct GridRenderer {
     vertex_buffer: wgpu::Buffer,
     vertex_count: u32,
     bind_group: wgpu::BindGroup,
+    grid_layout: wgpu::BindGroupLayout,
+    grid_bind_group: wgpu::BindGroup,
+    uniform: GridUniform,
 }
 
 impl GridRenderer {
@@ -25,13 +29,14 @@ impl GridRenderer {
     ) -> Self {
         let bind_group =
             create_camera_bind_group(device, camera_bind_group_layout, camera_buffer, "Grid");
+        let grid_layout = create_grid_bind_group_layout(device);
 
         let pipeline = PipelineConfig::new(
             "Grid",
             include_str!("../shaders/grid.wgsl"),
             format,
             depth_format,
-            &[camera_bind_group_layout],
+            &[camera_bind_group_layout, &grid_layout],
         )
         .with_vertex_layouts(vec![PositionColorVertex::layout()])
         .with_topology(wgpu::PrimitiveTopology::LineList)
@@ -47,18 +52,50 @@ impl GridRenderer {
             usage: wgpu::BufferUsages::VERTEX,
         });
 
+        let uniform = GridUniform::new(constants::DEFAULT_FADE_RADIUS, constants::DEFAULT_SIZE);
+        let grid_bind_group = Self::create_uniform_bind_group(device, &grid_layout, &uniform);
+
         Self {
             pipeline,
             vertex_buffer,
             vertex_count,
             bind_group,
+            grid_layout,
+            grid_bind_group,
+            uniform,
         }
     }
 
+    fn create_uniform_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        uniform: &GridUniform,
+    ) -> wgpu::BindGroup {
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Grid Uniform Buffer"),
+            contents: bytemuck::bytes_of(uniform),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        create_grid_bind_group(device, layout, &buffer)
+    }
+
+    /// Distance from the camera at which the grid has faded out.
+    pub fn fade_radius(&self) -> f32 {
+        self.uniform.fade_radius
+    }
+
+    /// Set the fade distance; 0 disables fading.
+    pub fn set_fade_radius(&mut self, device: &wgpu::Device, radius: f32) {
+        self.uniform = GridUniform::new(radius, self.uniform.half_size);
+        self.grid_bind_group =
+            Self::create_uniform_bind_group(device, &self.grid_layout, &self.uniform);
+    }
+
     /// Renders the grid.
     pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_bind_group(1, &self.grid_bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
         render_pass.draw(0..self.vertex_count, 0..1);
     }
@@ -87,6 +124,11 @@ impl GridRenderer {
             contents: bytemuck::cast_slice(&vertices),
             usage: wgpu::BufferUsages::VERTEX,
         });
+
+        // Edge fading follows the grid extent
+        self.uniform = GridUniform::new(self.uniform.fade_radius, size);
+        self.grid_bind_group =
+            Self::create_uniform_bind_group(device, &self.grid_layout, &self.uniform);
     }
 }
 
@@ -1,5 +1,7 @@
 //! Joint point marker renderer
 
+use std::ops::Range;
+
 use bytemuck::{Pod, Zeroable};
 use glam::Vec3;
 use wgpu::util::DeviceExt;
@@ -7,7 +9,33 @@ use wgpu::util::DeviceExt;
 use crate::constants::{instances, marker as constants};
 use crate::instanced::InstanceBuffer;
 use crate::pipeline::{PipelineConfig, create_camera_bind_group};
-use crate::vertex::PositionVertex;
+
+/// Shape drawn for a marker
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MarkerStyle {
+    /// Shaded sphere.
+    #[default]
+    Sphere = 0,
+    /// Three orthogonal bars.
+    Cross = 1,
+    /// Axis-aligned cube.
+    Cube = 2,
+}
+
+impl MarkerStyle {
+    /// All styles in draw order.
+    pub const ALL: [MarkerStyle; 3] = [MarkerStyle::Sphere, MarkerStyle::Cross, MarkerStyle::Cube];
+
+    /// Style for a raw instance value, falling back to a sphere.
+    pub fn from_raw(value: u32) -> Self {
+        match value {
+            1 => MarkerStyle::Cross,
+            2 => MarkerStyle::Cube,
+            _ => MarkerStyle::Sphere,
+        }
+    }
+}
 
 /// Marker instance data - passed as vertex instance
 #[repr(C)]
@@ -15,31 +43,143 @@ use crate::vertex::PositionVertex;
 pub struct MarkerInstance {
     /// Marker center position in world space.
     pub position: [f32; 3],
-    /// Marker sphere radius.
-    pub radius: f32,
+    /// Marker half-size (sphere radius, cube/cross half-extent).
+    pub size: f32,
     /// Marker color (RGBA).
     pub color: [f32; 4],
+    /// Marker shape, a [`MarkerStyle`] value.
+    pub style: u32,
+    /// Padding to 16-byte alignment.
+    pub _pad: [u32; 3],
 }
 
 impl MarkerInstance {
-    /// Creates a new marker instance.
-    pub fn new(position: Vec3, radius: f32, color: [f32; 4]) -> Self {
+    /// Creates a new sphere marker instance.
+    pub fn new(position: Vec3, size: f32, color: [f32; 4]) -> Self {
         Self {
             position: position.to_array(),
-            radius,
+            size,
             color,
+            style: MarkerStyle::Sphere as u32,
+            _pad: [0; 3],
         }
     }
+
+    /// Set the marker shape.
+    pub fn with_style(mut self, style: MarkerStyle) -> Self {
+        self.style = style as u32;
+        self
+    }
+
+    /// Marker shape.
+    pub fn marker_style(&self) -> MarkerStyle {
+        MarkerStyle::from_raw(self.style)
+    }
 }
 
 impl Default for MarkerInstance {
     fn default() -> Self {
+        Self::new(Vec3::ZERO, 0.02, [1.0, 1.0, 1.0, 1.0])
+    }
+}
+
+/// Marker mesh vertex with a flat or smooth normal
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct MarkerVertex {
+    position: [f32; 3],
+    normal: [f32; 3],
+}
+
+impl MarkerVertex {
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+
+    fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as u64,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// Global marker scale that keeps markers readable in a scene of `radius`
+///
+/// Markers keep their own size around [`constants::REFERENCE_SCENE_RADIUS`]
+/// and grow or shrink in proportion to the scene, within sane bounds.
+pub fn scene_marker_scale(radius: f32) -> f32 {
+    if radius.is_nan() || radius <= 0.0 {
+        return 1.0;
+    }
+    (radius / constants::REFERENCE_SCENE_RADIUS)
+        .clamp(constants::MIN_SCENE_SCALE, constants::MAX_SCENE_SCALE)
+}
+
+/// Index range of one style's geometry in the shared marker buffers
+#[derive(Debug, Clone, Copy, Default)]
+struct StyleMesh {
+    first_index: u32,
+    index_count: u32,
+    base_vertex: i32,
+}
+
+/// Sort instances by style and apply the global scale for upload
+///
+/// Returns the packed instances and, per [`MarkerStyle::ALL`] entry, the
+/// range of instances that use that style.
+pub fn pack_instances(
+    instances: &[MarkerInstance],
+    scale: f32,
+) -> (Vec<MarkerInstance>, [Range<u32>; 3]) {
+    let mut packed: Vec<MarkerInstance> = instances
+        .iter()
+        .map(|instance| MarkerInstance {
+            size: instance.size * scale,
+            ..*instance
+        })
+        .collect();
+    packed.sort_by_key(|instance| instance.marker_style() as u32);
+
+    let mut ranges: [Range<u32>; 3] = Default::default();
+    let mut start = 0;
+    for (range, style) in ranges.iter_mut().zip(MarkerStyle::ALL) {
+        let count = packed[start as usize..]
+            .iter()
+            .take_while(|instance| instance.marker_style() == style)
+            .count() as u32;
+        *range = start..start + count;
+        start += count;
+    }
+    (packed, ranges)
+}
+
+/// Instances of one pass and where each style starts
+struct MarkerBatch {
+    instances: InstanceBuffer<MarkerInstance>,
+    ranges: [Range<u32>; 3],
+}
+
+impl MarkerBatch {
+    fn new(device: &wgpu::Device, label: &str) -> Self {
         Self {
-            position: [0.0; 3],
-            radius: 0.02,
-            color: [1.0, 1.0, 1.0, 1.0],
+            instances: InstanceBuffer::new(device, label, instances::MAX_MARKERS),
+            ranges: Default::default(),
         }
     }
+
+    fn update(&mut self, queue: &wgpu::Queue, instances: &[MarkerInstance], scale: f32) {
+        let (packed, ranges) = pack_instances(instances, scale);
+        self.instances.update(queue, &packed);
+        // Drop anything the buffer truncated
+        let count = self.instances.count();
+        self.ranges = ranges.map(|r| r.start.min(count)..r.end.min(count));
+    }
+
+    fn clear(&mut self) {
+        self.instances.clear();
+        self.ranges = Default::default();
+    }
 }
 
 /// Marker renderer for joint points
@@ -49,10 +189,13 @@ pub struct MarkerRenderer {
     selected_pipeline: wgpu::RenderPipeline,
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
-    index_count: u32,
-    instances: InstanceBuffer<MarkerInstance>,
+    /// Geometry per style, indexed like [`MarkerStyle::ALL`]
+    meshes: [StyleMesh; 3],
+    batch: MarkerBatch,
     /// Selected marker instances (rendered on top)
-    selected_instances: InstanceBuffer<MarkerInstance>,
+    selected_batch: MarkerBatch,
+    /// Multiplier applied to every marker size on upload
+    scale: f32,
     bind_group: wgpu::BindGroup,
 }
 
@@ -68,36 +211,20 @@ impl MarkerRenderer {
         let bind_group =
             create_camera_bind_group(device, camera_bind_group_layout, camera_buffer, "Marker");
 
-        // Instance buffer layout: position+radius (Float32x4) + color (Float32x4)
-        let instance_layout = wgpu::VertexBufferLayout {
+        // Instance buffer layout: position+size (Float32x4) + color (Float32x4);
+        // the style only selects which draw an instance lands in
+        let instance_layout = || wgpu::VertexBufferLayout {
             array_stride: std::mem::size_of::<MarkerInstance>() as u64,
             step_mode: wgpu::VertexStepMode::Instance,
             attributes: &[
                 wgpu::VertexAttribute {
                     offset: 0,
-                    shader_location: 1,
-                    format: wgpu::VertexFormat::Float32x4,
-                },
-                wgpu::VertexAttribute {
-                    offset: 16,
                     shader_location: 2,
                     format: wgpu::VertexFormat::Float32x4,
                 },
-            ],
-        };
-
-        let instance_layout_clone = wgpu::VertexBufferLayout {
-            array_stride: std::mem::size_of::<MarkerInstance>() as u64,
-            step_mode: wgpu::VertexStepMode::Instance,
-            attributes: &[
-                wgpu::VertexAttribute {
-                    offset: 0,
-                    shader_location: 1,
-                    format: wgpu::VertexFormat::Float32x4,
-                },
                 wgpu::VertexAttribute {
                     offset: 16,
-                    shader_location: 2,
+                    shader_location: 3,
                     format: wgpu::VertexFormat::Float32x4,
                 },
             ],
@@ -110,7 +237,7 @@ impl MarkerRenderer {
             depth_format,
             &[camera_bind_group_layout],
         )
-        .with_vertex_layouts(vec![PositionVertex::layout(), instance_layout])
+        .with_vertex_layouts(vec![MarkerVertex::layout(), instance_layout()])
         .with_cull_mode(Some(wgpu::Face::Back))
         .build(device);
 
@@ -122,14 +249,25 @@ impl MarkerRenderer {
             depth_format,
             &[camera_bind_group_layout],
         )
-        .with_vertex_layouts(vec![PositionVertex::layout(), instance_layout_clone])
+        .with_vertex_layouts(vec![MarkerVertex::layout(), instance_layout()])
         .with_cull_mode(Some(wgpu::Face::Back))
         .without_depth_test()
         .build(device);
 
-        // Generate sphere mesh
-        let (vertices, indices) = generate_sphere(constants::SEGMENTS, constants::RINGS);
-        let index_count = indices.len() as u32;
+        // Concatenate the geometry of every style into one pair of buffers
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let meshes = MarkerStyle::ALL.map(|style| {
+            let (style_vertices, style_indices) = generate_style_mesh(style);
+            let mesh = StyleMesh {
+                first_index: indices.len() as u32,
+                index_count: style_indices.len() as u32,
+                base_vertex: vertices.len() as i32,
+            };
+            vertices.extend(style_vertices);
+            indices.extend(style_indices);
+            mesh
+        });
 
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Marker Vertex Buffer"),
@@ -143,64 +281,126 @@ impl MarkerRenderer {
             usage: wgpu::BufferUsages::INDEX,
         });
 
-        let instances = InstanceBuffer::new(device, "Marker", instances::MAX_MARKERS);
-        let selected_instances =
-            InstanceBuffer::new(device, "Selected Marker", instances::MAX_MARKERS);
-
         Self {
             pipeline,
             selected_pipeline,
             vertex_buffer,
             index_buffer,
-            index_count,
-            instances,
-            selected_instances,
+            meshes,
+            batch: MarkerBatch::new(device, "Marker"),
+            selected_batch: MarkerBatch::new(device, "Selected Marker"),
+            scale: 1.0,
             bind_group,
         }
     }
 
+    /// Global marker size multiplier
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Set the global marker size multiplier, applied on the next update
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.max(0.0);
+    }
+
     /// Update marker instances
     pub fn update_instances(&mut self, queue: &wgpu::Queue, instances: &[MarkerInstance]) {
-        self.instances.update(queue, instances);
+        self.batch.update(queue, instances, self.scale);
     }
 
     /// Update selected marker instances (rendered on top)
     pub fn update_selected_instances(&mut self, queue: &wgpu::Queue, instances: &[MarkerInstance]) {
-        self.selected_instances.update(queue, instances);
+        self.selected_batch.update(queue, instances, self.scale);
     }
 
     /// Clear all markers
     pub fn clear(&mut self) {
-        self.instances.clear();
-        self.selected_instances.clear();
+        self.batch.clear();
+        self.selected_batch.clear();
     }
 
     /// Renders all marker instances.
     pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-        // Render normal markers first (with depth test)
-        if !self.instances.is_empty() {
-            render_pass.set_pipeline(&self.pipeline);
+        // Render normal markers first (with depth test), then selected
+        // markers on top (no depth test)
+        for (pipeline, batch) in [
+            (&self.pipeline, &self.batch),
+            (&self.selected_pipeline, &self.selected_batch),
+        ] {
+            if batch.instances.is_empty() {
+                continue;
+            }
+            render_pass.set_pipeline(pipeline);
             render_pass.set_bind_group(0, &self.bind_group, &[]);
             render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.set_vertex_buffer(1, self.instances.slice());
+            render_pass.set_vertex_buffer(1, batch.instances.slice());
             render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-            render_pass.draw_indexed(0..self.index_count, 0, 0..self.instances.count());
+            for (mesh, range) in self.meshes.iter().zip(&batch.ranges) {
+                if range.is_empty() {
+                    continue;
+                }
+                render_pass.draw_indexed(
+                    mesh.first_index..mesh.first_index + mesh.index_count,
+                    mesh.base_vertex,
+                    range.clone(),
+                );
+            }
         }
+    }
+}
 
-        // Render selected markers on top (no depth test)
-        if !self.selected_instances.is_empty() {
-            render_pass.set_pipeline(&self.selected_pipeline);
-            render_pass.set_bind_group(0, &self.bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.set_vertex_buffer(1, self.selected_instances.slice());
-            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-            render_pass.draw_indexed(0..self.index_count, 0, 0..self.selected_instances.count());
+/// Unit-sized geometry for a marker style
+fn generate_style_mesh(style: MarkerStyle) -> (Vec<MarkerVertex>, Vec<u32>) {
+    match style {
+        MarkerStyle::Sphere => generate_sphere(constants::SEGMENTS, constants::RINGS),
+        MarkerStyle::Cube => {
+            let mut mesh = (Vec::new(), Vec::new());
+            push_box(&mut mesh, Vec3::ONE);
+            mesh
+        }
+        MarkerStyle::Cross => {
+            let t = constants::CROSS_THICKNESS;
+            let mut mesh = (Vec::new(), Vec::new());
+            for half_extents in [
+                Vec3::new(1.0, t, t),
+                Vec3::new(t, 1.0, t),
+                Vec3::new(t, t, 1.0),
+            ] {
+                push_box(&mut mesh, half_extents);
+            }
+            mesh
+        }
+    }
+}
+
+/// Append an origin-centered box with outward-facing, flat-shaded faces
+fn push_box((vertices, indices): &mut (Vec<MarkerVertex>, Vec<u32>), half_extents: Vec3) {
+    for normal in [
+        Vec3::X,
+        Vec3::NEG_X,
+        Vec3::Y,
+        Vec3::NEG_Y,
+        Vec3::Z,
+        Vec3::NEG_Z,
+    ] {
+        // u x v = normal, so corners run counter-clockwise seen from outside
+        let u = normal.any_orthonormal_vector();
+        let v = normal.cross(u);
+        let base = vertices.len() as u32;
+        for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let corner = normal + u * su + v * sv;
+            vertices.push(MarkerVertex {
+                position: (corner * half_extents).to_array(),
+                normal: normal.to_array(),
+            });
         }
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
     }
 }
 
 /// Generate a unit sphere mesh
-fn generate_sphere(segments: u32, rings: u32) -> (Vec<PositionVertex>, Vec<u32>) {
+fn generate_sphere(segments: u32, rings: u32) -> (Vec<MarkerVertex>, Vec<u32>) {
     let mut vertices = Vec::new();
     let mut indices = Vec::new();
 
@@ -214,27 +414,94 @@ fn generate_sphere(segments: u32, rings: u32) -> (Vec<PositionVertex>, Vec<u32>)
             let x = ring_radius * theta.cos();
             let z = ring_radius * theta.sin();
 
-            vertices.push(PositionVertex {
+            // Unit sphere, so position is normal
+            vertices.push(MarkerVertex {
                 position: [x, y, z],
+                normal: [x, y, z],
             });
         }
     }
 
-    // Generate indices
+    // Generate indices, counter-clockwise seen from outside
     for ring in 0..rings {
         for seg in 0..segments {
             let current = ring * (segments + 1) + seg;
             let next = current + segments + 1;
 
             indices.push(current);
-            indices.push(next);
             indices.push(current + 1);
+            indices.push(next);
 
             indices.push(current + 1);
-            indices.push(next);
             indices.push(next + 1);
+            indices.push(next);
         }
     }
 
     (vertices, indices)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_instances_keeps_size_and_style() {
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let instances = [
+            MarkerInstance::new(Vec3::X, 0.1, red).with_style(MarkerStyle::Cube),
+            MarkerInstance::new(Vec3::Y, 0.2, red),
+            MarkerInstance::new(Vec3::Z, 0.3, red).with_style(MarkerStyle::Cross),
+            MarkerInstance::new(Vec3::ZERO, 0.4, red).with_style(MarkerStyle::Cube),
+        ];
+
+        let (packed, ranges) = pack_instances(&instances, 2.0);
+        assert_eq!(ranges, [0..1, 1..2, 2..4]);
+        let summary: Vec<(MarkerStyle, f32)> =
+            packed.iter().map(|i| (i.marker_style(), i.size)).collect();
+        assert_eq!(
+            summary,
+            [
+                (MarkerStyle::Sphere, 0.4),
+                (MarkerStyle::Cross, 0.6),
+                (MarkerStyle::Cube, 0.2),
+                (MarkerStyle::Cube, 0.8),
+            ]
+        );
+
+        // Size and style sit where the shader and draw ranges expect them
+        let bytes: &[u8] = bytemuck::bytes_of(&packed[1]);
+        assert_eq!(std::mem::size_of::<MarkerInstance>(), 48);
+        assert_eq!(bytemuck::pod_read_unaligned::<f32>(&bytes[12..16]), 0.6);
+        assert_eq!(bytemuck::pod_read_unaligned::<u32>(&bytes[32..36]), 1);
+    }
+
+    #[test]
+    fn test_style_meshes_face_outward() {
+        for style in MarkerStyle::ALL {
+            let (vertices, indices) = generate_style_mesh(style);
+            assert!(!indices.is_empty());
+            for tri in indices.chunks_exact(3) {
+                let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(vertices[tri[i] as usize].position));
+                let face = (b - a).cross(c - a);
+                if face.length_squared() < 1e-12 {
+                    continue; // Sphere poles
+                }
+                let normal = Vec3::from(vertices[tri[0] as usize].normal);
+                assert!(face.dot(normal) > 0.0, "{style:?} has an inward face");
+            }
+        }
+    }
+
+    #[test]
+    fn test_scene_marker_scale_follows_scene_size() {
+        assert_eq!(scene_marker_scale(constants::REFERENCE_SCENE_RADIUS), 1.0);
+        assert!((scene_marker_scale(5.0) - 10.0).abs() < 1e-5);
+        assert!(scene_marker_scale(0.05) < 1.0);
+        assert_eq!(scene_marker_scale(1e-6), constants::MIN_SCENE_SCALE);
+        assert_eq!(scene_marker_scale(1e6), constants::MAX_SCENE_SCALE);
+        // Empty scenes keep markers at their own size
+        assert_eq!(scene_marker_scale(0.0), 1.0);
+        assert_eq!(scene_marker_scale(f32::NAN), 1.0);
+    }
+}
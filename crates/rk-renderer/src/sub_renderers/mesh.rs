@@ -6,6 +6,8 @@ use wgpu::util::DeviceExt;
 
 use rk_core::Part;
 
+use crate::capture::{CAPTURE_DEPTH_FORMAT, CAPTURE_NORMAL_FORMAT};
+use crate::constants::selection;
 use crate::constants::viewport::SAMPLE_COUNT;
 use crate::pipeline::create_camera_bind_group;
 
@@ -61,8 +63,14 @@ pub struct MeshInstance {
     pub color: [f32; 4],
     /// Selection state (0 = unselected, 1 = selected).
     pub selected: u32,
-    /// Padding for alignment.
-    pub _pad: [u32; 3],
+    /// Chain highlight state (0 = normal, 1 = highlighted).
+    pub highlighted: u32,
+    /// Shadow receiving (0 = ignore shadow map, 1 = shadowed).
+    pub receives_shadow: u32,
+    /// Color source (0 = instance color, 1 = per-vertex submesh colors).
+    pub vertex_colors: u32,
+    /// Selection highlight color (RGB, alpha unused).
+    pub selection_color: [f32; 4],
 }
 
 impl Default for MeshInstance {
@@ -71,11 +79,18 @@ impl Default for MeshInstance {
             model: Mat4::IDENTITY.to_cols_array_2d(),
             color: [0.7, 0.7, 0.7, 1.0],
             selected: 0,
-            _pad: [0; 3],
+            highlighted: 0,
+            receives_shadow: 1,
+            vertex_colors: 0,
+            selection_color: selection_color_uniform(selection::DEFAULT_COLOR),
         }
     }
 }
 
+fn selection_color_uniform(color: [f32; 3]) -> [f32; 4] {
+    [color[0], color[1], color[2], 1.0]
+}
+
 /// GPU mesh data
 pub struct MeshData {
     /// Vertex buffer containing mesh geometry.
@@ -93,6 +108,15 @@ pub struct MeshData {
 impl MeshData {
     /// Create mesh data from a Part
     pub fn from_part(device: &wgpu::Device, part: &Part) -> Self {
+        Self::from_part_with_selection_color(device, part, selection::DEFAULT_COLOR)
+    }
+
+    /// Create mesh data from a Part, highlighted in `selection_color` when selected
+    pub fn from_part_with_selection_color(
+        device: &wgpu::Device,
+        part: &Part,
+        selection_color: [f32; 3],
+    ) -> Self {
         tracing::info!(
             "Creating MeshData: {} vertices, {} normals, {} indices, bbox_min={:?}, bbox_max={:?}",
             part.vertices.len(),
@@ -102,8 +126,49 @@ impl MeshData {
             part.bbox_max
         );
 
-        // Build vertices with normals
+        let vertex_colors = part.has_submesh_colors();
+        let (vertex_buffer, index_buffer, index_count) = Self::create_geometry(device, part);
+
+        let instance = MeshInstance {
+            model: part.origin_transform.to_cols_array_2d(),
+            color: part.color,
+            selected: 0,
+            highlighted: 0,
+            receives_shadow: 1,
+            vertex_colors: u32::from(vertex_colors),
+            selection_color: selection_color_uniform(selection_color),
+        };
+
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mesh Instance Buffer"),
+            contents: bytemuck::cast_slice(&[instance]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        Self {
+            vertex_buffer,
+            index_buffer,
+            index_count,
+            instance,
+            instance_buffer,
+        }
+    }
+
+    /// Replace the vertex and index buffers with the part's current geometry
+    ///
+    /// The instance uniform (transform, color, selection) is kept as is.
+    pub fn set_geometry(&mut self, device: &wgpu::Device, part: &Part) {
+        let (vertex_buffer, index_buffer, index_count) = Self::create_geometry(device, part);
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.index_count = index_count;
+    }
+
+    /// Build the vertex and index buffers for a part
+    fn create_geometry(device: &wgpu::Device, part: &Part) -> (wgpu::Buffer, wgpu::Buffer, u32) {
+        // Build vertices with normals; imported submeshes color their own triangles
         let mut vertices = Vec::new();
+        let vertex_colors = part.has_submesh_colors();
 
         for (i, chunk) in part.indices.chunks(3).enumerate() {
             if chunk.len() != 3 {
@@ -116,12 +181,17 @@ impl MeshData {
                 [0.0, 0.0, 1.0]
             };
 
+            let color = part
+                .triangle_material(i as u32)
+                .filter(|_| vertex_colors)
+                .map_or(part.color, |m| m.color);
+
             for &idx in chunk {
                 let pos = part.vertices[idx as usize];
                 vertices.push(MeshVertex {
                     position: pos,
                     normal,
-                    color: part.color,
+                    color,
                 });
             }
         }
@@ -146,26 +216,7 @@ impl MeshData {
             usage: wgpu::BufferUsages::INDEX,
         });
 
-        let instance = MeshInstance {
-            model: part.origin_transform.to_cols_array_2d(),
-            color: part.color,
-            selected: 0,
-            _pad: [0; 3],
-        };
-
-        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Mesh Instance Buffer"),
-            contents: bytemuck::cast_slice(&[instance]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-
-        Self {
-            vertex_buffer,
-            index_buffer,
-            index_count: indices.len() as u32,
-            instance,
-            instance_buffer,
-        }
+        (vertex_buffer, index_buffer, indices.len() as u32)
     }
 
     /// Update instance transform
@@ -179,8 +230,11 @@ impl MeshData {
     }
 
     /// Update instance color
+    ///
+    /// An explicit color replaces any imported per-submesh colors.
     pub fn update_color(&mut self, queue: &wgpu::Queue, color: [f32; 4]) {
         self.instance.color = color;
+        self.instance.vertex_colors = 0;
         queue.write_buffer(
             &self.instance_buffer,
             0,
@@ -197,12 +251,51 @@ impl MeshData {
             bytemuck::cast_slice(&[self.instance]),
         );
     }
+
+    /// Set the selection highlight color
+    pub fn set_selection_color(&mut self, queue: &wgpu::Queue, color: [f32; 3]) {
+        self.instance.selection_color = selection_color_uniform(color);
+        queue.write_buffer(
+            &self.instance_buffer,
+            0,
+            bytemuck::cast_slice(&[self.instance]),
+        );
+    }
+
+    /// Set whether the mesh is shaded with shadows
+    pub fn set_receives_shadow(&mut self, queue: &wgpu::Queue, receives: bool) {
+        self.instance.receives_shadow = if receives { 1 } else { 0 };
+        queue.write_buffer(
+            &self.instance_buffer,
+            0,
+            bytemuck::cast_slice(&[self.instance]),
+        );
+    }
+
+    /// Whether the mesh is shaded with shadows
+    pub fn receives_shadow(&self) -> bool {
+        self.instance.receives_shadow != 0
+    }
+
+    /// Set chain highlight state
+    pub fn set_highlighted(&mut self, queue: &wgpu::Queue, highlighted: bool) {
+        self.instance.highlighted = if highlighted { 1 } else { 0 };
+        queue.write_buffer(
+            &self.instance_buffer,
+            0,
+            bytemuck::cast_slice(&[self.instance]),
+        );
+    }
 }
 
 /// Mesh renderer with shadow mapping support
 pub struct MeshRenderer {
     pipeline: wgpu::RenderPipeline,
+    /// Single-sampled color + normal pipeline for buffer capture
+    gbuffer_pipeline: wgpu::RenderPipeline,
     shadow_pipeline: wgpu::RenderPipeline,
+    ground_shadow_pipeline: wgpu::RenderPipeline,
+    selection_outline_pipeline: wgpu::RenderPipeline,
     camera_bind_group: wgpu::BindGroup,
     instance_bind_group_layout: wgpu::BindGroupLayout,
     light_bind_group_layout: wgpu::BindGroupLayout,
@@ -334,6 +427,137 @@ impl MeshRenderer {
             cache: None,
         });
 
+        // Ground shadow catcher - same bindings, darkens only where shadowed and
+        // leaves depth untouched so it never occludes geometry
+        let ground_shadow_pipeline =
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Ground Shadow Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[MeshVertex::layout()],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_ground_shadow"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: depth_format,
+                    depth_write_enabled: false,
+                    depth_compare: wgpu::CompareFunction::LessEqual,
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+                cache: None,
+            });
+
+        // Selection outline - drawn before the selected mesh itself, so the
+        // depth test only passes where other geometry hides the selection
+        let selection_outline_pipeline =
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Selection Outline Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[MeshVertex::layout()],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_selection_outline"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: depth_format,
+                    depth_write_enabled: false,
+                    depth_compare: wgpu::CompareFunction::Greater,
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+                cache: None,
+            });
+
+        // G-buffer pipeline - same shading plus a world-normal target, without
+        // MSAA so the captured textures can be copied and sampled directly
+        let gbuffer_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Mesh G-Buffer Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[MeshVertex::layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_gbuffer"),
+                targets: &[
+                    Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    }),
+                    Some(wgpu::ColorTargetState {
+                        format: CAPTURE_NORMAL_FORMAT,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    }),
+                ],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: CAPTURE_DEPTH_FORMAT,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
         // Shadow pipeline - uses light uniform at group 0, instance at group 1
         // (different from main pipeline which has camera at group 0)
         let shadow_light_bind_group_layout =
@@ -396,7 +620,10 @@ impl MeshRenderer {
 
         Self {
             pipeline,
+            gbuffer_pipeline,
             shadow_pipeline,
+            ground_shadow_pipeline,
+            selection_outline_pipeline,
             camera_bind_group,
             instance_bind_group_layout,
             light_bind_group_layout,
@@ -461,4 +688,55 @@ impl MeshRenderer {
         render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
         render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
     }
+
+    /// Render mesh color and world normals into a capture pass
+    pub fn render_gbuffer<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        mesh: &'a MeshData,
+        instance_bind_group: &'a wgpu::BindGroup,
+        light_bind_group: &'a wgpu::BindGroup,
+    ) {
+        render_pass.set_pipeline(&self.gbuffer_pipeline);
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        render_pass.set_bind_group(1, instance_bind_group, &[]);
+        render_pass.set_bind_group(2, light_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+    }
+
+    /// Render the selection outline of a mesh where other geometry hides it
+    pub fn render_selection_outline<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        mesh: &'a MeshData,
+        instance_bind_group: &'a wgpu::BindGroup,
+        light_bind_group: &'a wgpu::BindGroup,
+    ) {
+        render_pass.set_pipeline(&self.selection_outline_pipeline);
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        render_pass.set_bind_group(1, instance_bind_group, &[]);
+        render_pass.set_bind_group(2, light_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+    }
+
+    /// Render a ground plane that only shows shadows cast onto it
+    pub fn render_ground_shadow<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        mesh: &'a MeshData,
+        instance_bind_group: &'a wgpu::BindGroup,
+        light_bind_group: &'a wgpu::BindGroup,
+    ) {
+        render_pass.set_pipeline(&self.ground_shadow_pipeline);
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        render_pass.set_bind_group(1, instance_bind_group, &[]);
+        render_pass.set_bind_group(2, light_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+    }
 }
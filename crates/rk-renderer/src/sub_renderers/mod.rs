@@ -28,14 +28,14 @@ pub mod mesh;
 
 // Re-exports for new architecture
 pub use grid::GridSubRenderer;
-pub use sketch::{SketchRenderData, SketchRenderer, SketchVertex};
+pub use sketch::{PointStyle, SketchPointVertex, SketchRenderData, SketchRenderer, SketchVertex};
 
 // Re-exports for legacy code
 pub use axis::{AxisInstance, AxisRenderer};
 pub use collision::{CollisionInstance, CollisionRenderer};
 pub use gizmo::{GizmoAxis, GizmoMode, GizmoRenderer, GizmoSpace};
 pub use grid_legacy::GridRenderer;
-pub use marker::{MarkerInstance, MarkerRenderer};
+pub use marker::{MarkerInstance, MarkerRenderer, MarkerStyle, scene_marker_scale};
 pub use mesh::{MeshData, MeshRenderer, MeshVertex};
 
 /// Render priorities for sub-renderers.
@@ -7,9 +7,11 @@ use glam::{Mat4, Vec2, Vec3, Vec4};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::config::SketchConfig;
+use crate::constants::sketch::DEFAULT_DEPTH_OFFSET;
 use crate::context::RenderContext;
 use crate::pipeline::PipelineConfig;
-use crate::scene::Scene;
+use crate::scene::{RenderLayer, Scene};
 use crate::traits::SubRenderer;
 
 /// Vertex for sketch rendering.
@@ -20,7 +22,8 @@ pub struct SketchVertex {
     pub position: [f32; 3],
     /// Vertex color (RGBA).
     pub color: [f32; 4],
-    /// Flags: bit 0 = selected, bit 1 = hovered, bit 2 = construction, bit 3 = constrained.
+    /// Flags: bit 0 = selected, bit 1 = hovered, bit 2 = construction, bit 3 = constrained,
+    /// bit 4 = fixed glyph, bit 5 = grid line.
     pub flags: u32,
 }
 
@@ -63,6 +66,104 @@ impl SketchVertex {
     }
 }
 
+/// Vertex for sketch points drawn as screen-space discs.
+///
+/// Each point expands to a camera-facing quad of two triangles; `corner`
+/// holds the quad corner in [-1, 1] which the shader scales by `size` pixels
+/// and uses to cut out an anti-aliased disc.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SketchPointVertex {
+    /// Point center in sketch space.
+    pub position: [f32; 3],
+    /// Quad corner in [-1, 1].
+    pub corner: [f32; 2],
+    /// Disc diameter in pixels.
+    pub size: f32,
+    /// Vertex color (RGBA).
+    pub color: [f32; 4],
+    /// Same flags as [`SketchVertex::flags`].
+    pub flags: u32,
+}
+
+impl SketchPointVertex {
+    /// Vertex attributes for the shader.
+    pub const ATTRIBUTES: &'static [wgpu::VertexAttribute] = &wgpu::vertex_attr_array![
+        0 => Float32x3,
+        1 => Float32x2,
+        2 => Float32,
+        3 => Float32x4,
+        4 => Uint32,
+    ];
+
+    /// Returns the vertex buffer layout.
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as u64,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// Quad corners of a point billboard, as two counter-clockwise triangles.
+pub const BILLBOARD_CORNERS: [[f32; 2]; 6] = [
+    [-1.0, -1.0],
+    [1.0, -1.0],
+    [1.0, 1.0],
+    [-1.0, -1.0],
+    [1.0, 1.0],
+    [-1.0, 1.0],
+];
+
+/// Screen-space sizes of sketch point discs, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointStyle {
+    /// Diameter of regular points.
+    pub size: f32,
+    /// Diameter of selected points.
+    pub selected_size: f32,
+    /// Diameter of fixed points.
+    pub fixed_size: f32,
+}
+
+impl Default for PointStyle {
+    fn default() -> Self {
+        Self {
+            size: 8.0,
+            selected_size: 11.0,
+            fixed_size: 10.0,
+        }
+    }
+}
+
+impl PointStyle {
+    /// Disc diameter for a point with the given flags.
+    ///
+    /// Selection takes precedence over the fixed size.
+    pub fn size_for(&self, flags: u32) -> f32 {
+        if flags & flags::SELECTED != 0 {
+            self.selected_size
+        } else if flags & flags::FIXED != 0 {
+            self.fixed_size
+        } else {
+            self.size
+        }
+    }
+}
+
+/// Expand a point into the six vertices of its billboard quad.
+pub fn billboard_quad(point: &SketchVertex, style: &PointStyle) -> [SketchPointVertex; 6] {
+    let size = style.size_for(point.flags);
+    BILLBOARD_CORNERS.map(|corner| SketchPointVertex {
+        position: point.position,
+        corner,
+        size,
+        color: point.color,
+        flags: point.flags,
+    })
+}
+
 /// Vertex flag constants.
 pub mod flags {
     /// Entity is selected.
@@ -73,6 +174,14 @@ pub mod flags {
     pub const CONSTRUCTION: u32 = 4;
     /// Entity is fully constrained.
     pub const CONSTRAINED: u32 = 8;
+    /// Geometry is a fixed-point glyph.
+    pub const FIXED: u32 = 16;
+    /// Geometry is a sketch grid line.
+    pub const GRID: u32 = 32;
+    /// Geometry is the sketch origin glyph (axes and origin point).
+    pub const ORIGIN: u32 = 64;
+    /// Entity can be picked to complete the active constraint.
+    pub const CANDIDATE: u32 = 128;
 }
 
 /// Uniform data for sketch rendering.
@@ -83,6 +192,9 @@ struct SketchUniform {
     transform: [[f32; 4]; 4],
     /// Sketch plane visualization color.
     plane_color: [f32; 4],
+    /// Viewport size in pixels (xy) used to size point discs, and the
+    /// depth offset towards the camera (z).
+    viewport: [f32; 4],
 }
 
 /// Data for a single sketch to be rendered.
@@ -189,6 +301,83 @@ impl SketchRenderData {
         }
     }
 
+    /// Add a 2D grid of lines at multiples of `spacing`.
+    ///
+    /// Lines cover the square of half-width `half_extent` around `center`,
+    /// snapped outwards to the spacing so they line up with grid snapping.
+    /// Every `major_every`-th line (counted from the sketch origin) uses
+    /// `major_color`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_grid(
+        &mut self,
+        center: Vec2,
+        half_extent: f32,
+        spacing: f32,
+        major_every: u32,
+        minor_color: Vec4,
+        major_color: Vec4,
+    ) {
+        if spacing <= 0.0 || half_extent <= 0.0 {
+            return;
+        }
+
+        let min = ((center - Vec2::splat(half_extent)) / spacing).floor();
+        let max = ((center + Vec2::splat(half_extent)) / spacing).ceil();
+        let (x0, y0) = (min.x * spacing, min.y * spacing);
+        let (x1, y1) = (max.x * spacing, max.y * spacing);
+        let major_every = major_every.max(1) as i64;
+        let color_for = |k: i64| {
+            if k.rem_euclid(major_every) == 0 {
+                major_color
+            } else {
+                minor_color
+            }
+        };
+
+        for k in min.x as i64..=max.x as i64 {
+            let x = k as f32 * spacing;
+            self.add_line(
+                Vec2::new(x, y0),
+                Vec2::new(x, y1),
+                color_for(k),
+                flags::GRID,
+            );
+        }
+        for k in min.y as i64..=max.y as i64 {
+            let y = k as f32 * spacing;
+            self.add_line(
+                Vec2::new(x0, y),
+                Vec2::new(x1, y),
+                color_for(k),
+                flags::GRID,
+            );
+        }
+    }
+
+    /// Add a square glyph marking a fixed (grounded) point.
+    ///
+    /// `half_size` is the half-width of the square in sketch units.
+    pub fn add_fixed_marker(&mut self, position: Vec2, half_size: f32, color: Vec4, flags: u32) {
+        let flags = flags | flags::FIXED;
+        let corners = [
+            position + Vec2::new(-half_size, -half_size),
+            position + Vec2::new(half_size, -half_size),
+            position + Vec2::new(half_size, half_size),
+            position + Vec2::new(-half_size, half_size),
+        ];
+        for i in 0..corners.len() {
+            self.add_line(corners[i], corners[(i + 1) % corners.len()], color, flags);
+        }
+    }
+
+    /// Billboard vertices for all points, six per point.
+    pub fn point_quad_vertices(&self, style: &PointStyle) -> Vec<SketchPointVertex> {
+        self.point_vertices
+            .iter()
+            .flat_map(|point| billboard_quad(point, style))
+            .collect()
+    }
+
     /// Clear all geometry.
     pub fn clear(&mut self) {
         self.line_vertices.clear();
@@ -204,6 +393,8 @@ pub struct SketchRenderer {
     point_pipeline: Option<wgpu::RenderPipeline>,
     camera_bind_group: Option<wgpu::BindGroup>,
     sketch_bind_group_layout: Option<wgpu::BindGroupLayout>,
+    point_style: PointStyle,
+    depth_offset: f32,
 
     /// Per-sketch GPU resources.
     sketch_resources: HashMap<Uuid, SketchGpuResources>,
@@ -238,6 +429,8 @@ impl SketchRenderer {
             point_pipeline: None,
             camera_bind_group: None,
             sketch_bind_group_layout: None,
+            point_style: PointStyle::default(),
+            depth_offset: DEFAULT_DEPTH_OFFSET,
             sketch_resources: HashMap::new(),
             pending_sketches: Vec::new(),
         }
@@ -257,6 +450,39 @@ impl SketchRenderer {
     pub fn clear_sketches(&mut self) {
         self.pending_sketches.clear();
     }
+
+    /// Screen-space sizes of point discs.
+    pub fn point_style(&self) -> PointStyle {
+        self.point_style
+    }
+
+    /// Set the screen-space sizes of point discs.
+    pub fn set_point_style(&mut self, style: PointStyle) {
+        self.point_style = style;
+    }
+
+    /// Depth offset towards the camera, in NDC depth units.
+    pub fn depth_offset(&self) -> f32 {
+        self.depth_offset
+    }
+
+    /// Set the depth offset that keeps sketches on faces from z-fighting.
+    pub fn set_depth_offset(&mut self, offset: f32) {
+        self.depth_offset = offset.max(0.0);
+    }
+
+    /// Apply sketch configuration.
+    pub fn apply_config(&mut self, config: &SketchConfig) {
+        self.set_depth_offset(config.depth_offset);
+    }
+
+    fn uniform(&self, transform: Mat4, width: u32, height: u32) -> SketchUniform {
+        SketchUniform {
+            transform: transform.to_cols_array_2d(),
+            plane_color: [0.5, 0.5, 0.5, 0.2],
+            viewport: [width as f32, height as f32, self.depth_offset, 0.0],
+        }
+    }
 }
 
 impl SubRenderer for SketchRenderer {
@@ -276,6 +502,10 @@ impl SubRenderer for SketchRenderer {
         self.enabled = enabled;
     }
 
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Annotations
+    }
+
     fn on_init(&mut self, ctx: &RenderContext) {
         // Create sketch uniform bind group layout
         let sketch_bind_group_layout =
@@ -308,7 +538,7 @@ impl SubRenderer for SketchRenderer {
         .with_depth_write(false)
         .build(ctx.device());
 
-        // Create point pipeline (using same shader but different topology)
+        // Create point pipeline (billboard quads cut into discs by the fragment shader)
         let point_pipeline = PipelineConfig::new(
             "Sketch Points",
             include_str!("../shaders/sketch.wgsl"),
@@ -316,8 +546,8 @@ impl SubRenderer for SketchRenderer {
             ctx.depth_format(),
             &[ctx.camera_bind_group_layout(), &sketch_bind_group_layout],
         )
-        .with_vertex_layouts(vec![SketchVertex::layout()])
-        .with_topology(wgpu::PrimitiveTopology::PointList)
+        .with_vertex_layouts(vec![SketchPointVertex::layout()])
+        .with_topology(wgpu::PrimitiveTopology::TriangleList)
         .with_blend(wgpu::BlendState::ALPHA_BLENDING)
         .with_depth_write(false)
         .with_entry_point("vs_point", "fs_point")
@@ -353,15 +583,13 @@ impl SubRenderer for SketchRenderer {
 
         // Update GPU resources for each sketch
         for sketch_data in &self.pending_sketches {
-            let uniform = SketchUniform {
-                transform: sketch_data.transform.to_cols_array_2d(),
-                plane_color: [0.5, 0.5, 0.5, 0.2],
-            };
+            let uniform = self.uniform(sketch_data.transform, ctx.width(), ctx.height());
+            let point_quads = sketch_data.point_quad_vertices(&self.point_style);
 
             // Create or update resources
             let needs_update = !self.sketch_resources.contains_key(&sketch_data.id);
             let line_count = sketch_data.line_vertices.len() as u32;
-            let point_count = sketch_data.point_vertices.len() as u32;
+            let point_count = point_quads.len() as u32;
 
             if needs_update || line_count > 0 || point_count > 0 {
                 // Create buffers
@@ -377,10 +605,10 @@ impl SubRenderer for SketchRenderer {
 
                 let point_buffer = ctx.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                     label: Some("Sketch Point Buffer"),
-                    contents: if sketch_data.point_vertices.is_empty() {
-                        &[0u8; std::mem::size_of::<SketchVertex>()]
+                    contents: if point_quads.is_empty() {
+                        &[0u8; std::mem::size_of::<SketchPointVertex>()]
                     } else {
-                        bytemuck::cast_slice(&sketch_data.point_vertices)
+                        bytemuck::cast_slice(&point_quads)
                     },
                     usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
                 });
@@ -458,3 +686,66 @@ impl SubRenderer for SketchRenderer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sketch_pipeline_has_depth_offset() {
+        let mut renderer = SketchRenderer::new();
+        assert!(renderer.depth_offset() > 0.0);
+        let uniform = renderer.uniform(Mat4::IDENTITY, 800, 600);
+        assert_eq!(
+            uniform.viewport,
+            [800.0, 600.0, renderer.depth_offset(), 0.0]
+        );
+
+        renderer.apply_config(&SketchConfig { depth_offset: 2e-4 });
+        assert_eq!(renderer.uniform(Mat4::IDENTITY, 1, 1).viewport[2], 2e-4);
+        assert_eq!(SketchConfig::default().depth_offset, DEFAULT_DEPTH_OFFSET);
+    }
+
+    #[test]
+    fn test_billboard_quad_covers_unit_square() {
+        let point = SketchVertex::new(Vec3::new(1.0, 2.0, 0.0), Vec4::ONE, flags::HOVERED);
+        let quad = billboard_quad(&point, &PointStyle::default());
+
+        for v in &quad {
+            assert_eq!(v.position, point.position);
+            assert_eq!(v.color, point.color);
+            assert_eq!(v.flags, flags::HOVERED);
+            assert_eq!(v.size, PointStyle::default().size);
+        }
+        for corner in [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]] {
+            assert!(quad.iter().any(|v| v.corner == corner));
+        }
+
+        // Both triangles wind counter-clockwise and together span area 4
+        let mut area = 0.0;
+        for tri in quad.chunks(3) {
+            let [a, b, c] = [tri[0].corner, tri[1].corner, tri[2].corner].map(Vec2::from);
+            let signed = (b - a).perp_dot(c - a) * 0.5;
+            assert!(signed > 0.0);
+            area += signed;
+        }
+        assert!((area - 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_point_sizes_by_state() {
+        let style = PointStyle::default();
+        let mut data = SketchRenderData::default();
+        data.add_point(Vec2::ZERO, Vec4::ONE, 0);
+        data.add_point(Vec2::X, Vec4::ONE, flags::FIXED);
+        data.add_point(Vec2::Y, Vec4::ONE, flags::SELECTED | flags::FIXED);
+
+        let vertices = data.point_quad_vertices(&style);
+        assert_eq!(vertices.len(), 18);
+        let sizes: Vec<f32> = vertices.chunks(6).map(|q| q[0].size).collect();
+        assert_eq!(
+            sizes,
+            vec![style.size, style.fixed_size, style.selected_size]
+        );
+    }
+}
@@ -1,7 +1,10 @@
 //! SubRenderer trait definition.
 
+use std::any::Any;
+
 use crate::context::RenderContext;
-use crate::scene::Scene;
+use crate::scene::{RenderLayer, Scene};
+use crate::traits::PassType;
 
 /// A sub-renderer that handles a specific type of rendering.
 ///
@@ -17,7 +20,13 @@ use crate::scene::Scene;
 /// - 100-199: Main geometry (meshes)
 /// - 200-299: Overlays (axes, markers)
 /// - 1000+: Always-on-top elements (gizmos, UI)
-pub trait SubRenderer: Send + Sync {
+///
+/// # Typed access
+///
+/// Sub-renderers are `'static`, so a registered one can be looked up by its
+/// concrete type with [`RendererRegistry::find_mut`](crate::plugin::RendererRegistry::find_mut)
+/// to feed it data or configuration.
+pub trait SubRenderer: Any + Send + Sync {
     /// Returns the unique name of this sub-renderer.
     fn name(&self) -> &str;
 
@@ -30,6 +39,24 @@ pub trait SubRenderer: Send + Sync {
     /// Enables or disables this sub-renderer.
     fn set_enabled(&mut self, enabled: bool);
 
+    /// Returns the render layer this sub-renderer draws into.
+    ///
+    /// The sub-renderer is skipped while its layer is hidden.
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Default
+    }
+
+    /// Returns the pass this sub-renderer draws in.
+    ///
+    /// The main renderer draws [`PassType::Opaque`] sub-renderers after the
+    /// visual meshes, [`PassType::Transparent`] ones after the collision
+    /// shapes and [`PassType::Overlay`] ones just before the gizmo, all in
+    /// the main color pass. [`PassType::Shadow`] sub-renderers draw into the
+    /// shadow map. [`PassType::PostProcess`] is not dispatched yet.
+    fn pass_type(&self) -> PassType {
+        PassType::Opaque
+    }
+
     /// Called when the render context is initialized.
     ///
     /// Use this to create GPU resources (pipelines, buffers, etc.).
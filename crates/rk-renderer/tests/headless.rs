@@ -0,0 +1,163 @@
+//! Driving the renderer on a plain `wgpu` device, without egui
+
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use rk_core::{Part, PrimitiveShape};
+use rk_renderer::config::{GridConfig, SelectionConfig};
+use rk_renderer::constants::viewport::CLEAR_COLOR;
+use rk_renderer::{Renderer, SketchRenderData, SketchRenderer, read_texture};
+
+const SIZE: u32 = 64;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// The renderer's headless device, waited on
+fn headless_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    pollster::block_on(rk_renderer::headless_device())
+}
+
+/// Render one frame and return its RGBA8 pixels
+fn render_frame(device: &wgpu::Device, queue: &wgpu::Queue, renderer: &mut Renderer) -> Vec<u8> {
+    let ctx = renderer.create_context(device, queue);
+    renderer.registry_mut().init_all(&ctx);
+    renderer.prepare_plugins(&ctx);
+
+    let target = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Headless Target"),
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = target.create_view(&Default::default());
+    let mut encoder = device.create_command_encoder(&Default::default());
+    renderer.render(&mut encoder, &view, queue);
+    queue.submit([encoder.finish()]);
+    read_texture(device, queue, &target).expect("readback")
+}
+
+#[test]
+fn test_headless_frame_draws_part() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping");
+        return;
+    };
+
+    let mut renderer = Renderer::new(&device, FORMAT, SIZE, SIZE);
+    let ctx = renderer.create_context(&device, &queue);
+    assert_eq!(ctx.surface_format(), renderer.format());
+    assert_eq!((ctx.width(), ctx.height()), (SIZE, SIZE));
+
+    renderer.set_show_grid(false);
+    let part = Part::from_primitive("box", PrimitiveShape::Box { size: [0.5; 3] });
+    renderer.add_part(&device, &part);
+    renderer.camera_mut().fit_all(Vec3::ZERO, 0.5);
+
+    let pixels = render_frame(&device, &queue, &mut renderer);
+    let pixel = |x: u32, y: u32| {
+        let i = ((y * SIZE + x) * 4) as usize;
+        [pixels[i], pixels[i + 1], pixels[i + 2]]
+    };
+    let background =
+        [CLEAR_COLOR.r, CLEAR_COLOR.g, CLEAR_COLOR.b].map(|c| (c * 255.0).round() as u8);
+    let near = |a: [u8; 3], b: [u8; 3]| a.iter().zip(b).all(|(a, b)| a.abs_diff(b) <= 1);
+
+    assert!(near(pixel(0, 0), background), "corner {:?}", pixel(0, 0));
+    assert!(!near(pixel(SIZE / 2, SIZE / 2), background));
+}
+
+#[test]
+fn test_registered_sketch_renderer_draws_from_renderer_camera() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping");
+        return;
+    };
+
+    let mut renderer = Renderer::new(&device, FORMAT, SIZE, SIZE);
+    renderer.set_show_grid(false);
+    renderer.camera_mut().fit_all(Vec3::ZERO, 0.5);
+
+    let mut sketch = SketchRenderData::new(uuid::Uuid::new_v4(), Mat4::IDENTITY);
+    sketch.add_point(Vec2::ZERO, Vec4::new(1.0, 0.0, 0.0, 1.0), 0);
+    let mut sketches = SketchRenderer::new();
+    sketches.set_sketches(vec![sketch]);
+    renderer.registry_mut().register(sketches);
+
+    let pixels = render_frame(&device, &queue, &mut renderer);
+    let i = ((SIZE / 2 * SIZE + SIZE / 2) * 4) as usize;
+    let [r, g, b] = [pixels[i], pixels[i + 1], pixels[i + 2]];
+    assert!(r > 200 && g < 60 && b < 60, "center {:?}", [r, g, b]);
+}
+
+#[test]
+fn test_occluded_selection_outline_only_tints_hidden_parts() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping");
+        return;
+    };
+
+    let mut renderer = Renderer::new(&device, FORMAT, SIZE, SIZE);
+    renderer.set_show_grid(false);
+    renderer.camera_mut().fit_all(Vec3::ZERO, 0.5);
+    let selected = Part::from_primitive("selected", PrimitiveShape::Box { size: [0.5; 3] });
+    renderer.add_part(&device, &selected);
+    renderer.set_selected_part(&queue, Some(selected.id));
+
+    let frame_with_outline = |renderer: &mut Renderer, show_occluded| {
+        let config = SelectionConfig {
+            show_occluded,
+            ..Default::default()
+        };
+        renderer.apply_selection_config(&config, &queue);
+        render_frame(&device, &queue, renderer)
+    };
+
+    // A visible selection looks the same with or without the outline
+    assert!(frame_with_outline(&mut renderer, true) == frame_with_outline(&mut renderer, false));
+
+    // Once something sits between it and the camera, the outline shows through
+    let camera = renderer.camera();
+    let between = (camera.position + camera.target) / 2.0;
+    let occluder = Part::from_primitive("occluder", PrimitiveShape::Box { size: [0.5; 3] });
+    renderer.add_part(&device, &occluder);
+    renderer.update_part_transform(&queue, occluder.id, Mat4::from_translation(between));
+    assert!(frame_with_outline(&mut renderer, true) != frame_with_outline(&mut renderer, false));
+}
+
+#[test]
+fn test_grid_fade_radius_fades_rendered_lines() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping");
+        return;
+    };
+    let background =
+        [CLEAR_COLOR.r, CLEAR_COLOR.g, CLEAR_COLOR.b].map(|c| (c * 255.0).round() as u8);
+    let grid_pixels = |fade_radius: f32| {
+        let mut renderer = Renderer::new(&device, FORMAT, SIZE, SIZE);
+        let config = GridConfig {
+            fade_radius,
+            ..Default::default()
+        };
+        renderer.apply_grid_config(&config, &device);
+        render_frame(&device, &queue, &mut renderer)
+            .chunks_exact(4)
+            .filter(|px| {
+                px[..3]
+                    .iter()
+                    .zip(background)
+                    .any(|(a, b)| a.abs_diff(b) > 1)
+            })
+            .count()
+    };
+
+    // Radius 0 disables fading, so lines show at full alpha
+    let flat = grid_pixels(0.0);
+    assert!(flat > 0);
+    // Everything on the grid is further away than a tiny radius
+    assert_eq!(grid_pixels(0.01), 0, "flat grid drew {flat} pixels");
+}